@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use figment::Figment;
 use mimalloc::MiMalloc;
@@ -33,7 +33,12 @@ fn _ensure_modules_linked() {
 }
 
 // Bring runner types & our per-module DB factory
-use modkit::runtime::{run, DbOptions, RunOptions, ShutdownOptions};
+use modkit::registry::{GraphFormat, ModuleRegistry, ModuleSource};
+use modkit::runtime::{
+    run, run_until_rest, ConfigReload, DbOptions, PhaseHooks, RunOptions, ServiceManager,
+    ShutdownOptions,
+};
+use modkit::secrets::{Secrets, UnresolvedSecrets};
 
 #[allow(dead_code)]
 fn _ensure_drivers_linked() {
@@ -68,6 +73,15 @@ struct Cli {
     #[arg(long)]
     mock: bool,
 
+    /// Override any config value, e.g. `--set modules.api_ingress.config.cors_enabled=false`
+    /// (repeatable; wins over the file and the environment)
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Run as the named Windows service (systemd is detected automatically)
+    #[arg(long, value_name = "NAME")]
+    windows_service: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -76,8 +90,35 @@ struct Cli {
 enum Commands {
     /// Start the server
     Run,
-    /// Validate configuration and exit
+    /// Dry-run startup: validate module configs, DB connectivity and the REST phase, then exit
     Check,
+    /// Build the REST registry without serving and write the OpenAPI document
+    Openapi {
+        /// Output file
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Output format: json or yaml (defaults to the output file extension, else json)
+        #[arg(long)]
+        format: Option<api_ingress::OpenApiFormat>,
+    },
+    /// Inspect the compiled-in modules
+    Modules {
+        #[command(subcommand)]
+        command: ModulesCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ModulesCommand {
+    /// Print the module dependency graph (modules disabled in config are left out)
+    Graph {
+        /// Output format: dot or json
+        #[arg(long, default_value = "dot")]
+        format: GraphFormat,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -93,17 +134,38 @@ async fn main() -> Result<()> {
         print_config: cli.print_config,
         verbose: cli.verbose,
         mock: cli.mock,
+        set: cli.set.clone(),
     };
 
     // Layered config:
-    // 1) defaults -> 2) YAML (if provided) -> 3) env (APP__*) -> 4) CLI overrides
+    // 1) defaults -> 2) YAML (if provided) -> 3) env (APP__*) -> 4) CLI overrides (--set last)
     // Also normalizes + creates server.home_dir.
     let mut config = AppConfig::load_or_default(cli.config.as_deref())?;
-    config.apply_cli_overrides(&args);
-
-    // Init logging as early as possible.
+    config.apply_cli_overrides(&args)?;
+
+    // Init logging as early as possible, with the OTLP span exporter if configured.
+    let otlp = match config.telemetry.as_ref().and_then(|t| t.tracing.as_ref()) {
+        Some(tracing_cfg) => Some(Box::new(modkit::telemetry::init_tracing(&otlp_options(
+            tracing_cfg,
+        )?)?) as runtime::logging::ExtraLayer),
+        None => None,
+    };
     let logging_config = config.logging.as_ref().cloned().unwrap_or_default();
-    runtime::logging::init_logging_from_config(&logging_config, Path::new(&config.server.home_dir));
+    runtime::logging::init_logging_with_layer(
+        &logging_config,
+        Path::new(&config.server.home_dir),
+        otlp,
+    );
+
+    let metrics = config
+        .telemetry
+        .as_ref()
+        .map(|t| t.metrics.clone())
+        .unwrap_or_default();
+    modkit::telemetry::init(&modkit::telemetry::MetricsOptions {
+        prefix: metrics.prefix,
+        const_labels: metrics.labels,
+    })?;
 
     tracing::info!("HyperSpot Server starting");
 
@@ -114,12 +176,56 @@ async fn main() -> Result<()> {
 
     // Dispatch subcommands (default: run)
     match cli.command.unwrap_or(Commands::Run) {
-        Commands::Run => run_server(config, args).await,
-        Commands::Check => check_config(config).await,
+        Commands::Run => {
+            let service = match cli.windows_service {
+                Some(name) => ServiceManager::Windows { name },
+                None => ServiceManager::detect(),
+            };
+            run_server(config, args, cli.config, service).await
+        }
+        Commands::Check => check_config(config, args).await,
+        Commands::Openapi { output, format } => export_openapi(config, args, output, format).await,
+        Commands::Modules {
+            command: ModulesCommand::Graph { format, output },
+        } => export_module_graph(config, format, output),
     }
 }
 
-async fn run_server(config: AppConfig, args: CliArgs) -> Result<()> {
+/// Levels of the logging installed by `runtime::logging`, for `/__debug/telemetry`.
+struct RuntimeLogLevels;
+
+impl modkit::telemetry::LogLevels for RuntimeLogLevels {
+    fn directives(&self) -> String {
+        runtime::logging::filter_directives()
+    }
+
+    fn set_directives(&self, directives: &str) -> Result<()> {
+        runtime::logging::set_filter_directives(directives)
+    }
+}
+
+/// `telemetry.tracing` of the app config as exporter options.
+fn otlp_options(cfg: &runtime::TracingConfig) -> Result<modkit::telemetry::OtlpOptions> {
+    let mut resource = cfg.resource.clone();
+    resource
+        .entry("service.name".to_string())
+        .or_insert_with(|| "hyperspot".to_string());
+    Ok(modkit::telemetry::OtlpOptions {
+        endpoint: cfg.endpoint.clone(),
+        protocol: cfg.protocol.parse()?,
+        headers: cfg.headers.clone(),
+        resource,
+        sampler: modkit::telemetry::Sampler::from_name(&cfg.sampler, cfg.sampler_arg)?,
+        timeout: cfg.timeout,
+    })
+}
+
+async fn run_server(
+    config: AppConfig,
+    args: CliArgs,
+    config_path: Option<PathBuf>,
+    service: ServiceManager,
+) -> Result<()> {
     tracing::info!("Initializing modules…");
 
     // Bridge AppConfig into ModKit’s ConfigProvider (per-module JSON bag).
@@ -127,15 +233,63 @@ async fn run_server(config: AppConfig, args: CliArgs) -> Result<()> {
         config.clone(),
     ))));
 
-    // Base dir used by DB factory for file-based SQLite resolution
-    let _home_dir = PathBuf::from(&config.server.home_dir);
+    // Run the ModKit runtime (signals-driven shutdown).
+    let run_options = RunOptions {
+        modules_cfg: config_provider,
+        db: db_options(&config, &args)?,
+        shutdown: ShutdownOptions::Signals,
+        hooks: PhaseHooks::default(),
+        config_reload: config_path.map(|path| config_reload(path, args.clone())),
+        module_sources: module_sources(&config)?,
+        registry: None,
+        service,
+        client_hub: None,
+        resources: {
+            let mut resources = modkit::Resources::default();
+            resources.insert::<dyn modkit::telemetry::LogLevels>(Arc::new(RuntimeLogLevels));
+            resources
+        },
+        secrets: Default::default(),
+        home_dir: Some(PathBuf::from(&config.server.home_dir)),
+        profile: config.profile.clone(),
+    };
+
+    run(run_options).await
+}
 
-    // Configure DB options: DbManager or no-DB.
-    let db_options = if config.database.is_some() {
+/// Reload the YAML file (with the same env and CLI overrides) on SIGHUP or
+/// when it changes, so modules can pick up new `config` sections live.
+fn config_reload(path: PathBuf, args: CliArgs) -> ConfigReload {
+    ConfigReload::new({
+        let path = path.clone();
+        move || {
+            let mut config = AppConfig::load_or_default(Some(&path))?;
+            config.apply_cli_overrides(&args)?;
+            let provider: Arc<dyn modkit::ConfigProvider> = Arc::new(ModkitConfigAdapter(
+                Arc::new(AppConfigProvider::new(config)),
+            ));
+            Ok(provider)
+        }
+    })
+    .watch_file(path)
+}
+
+/// WASM plugins from `plugins_dir`, registered next to the compiled-in modules.
+fn module_sources(config: &AppConfig) -> Result<Vec<Arc<dyn ModuleSource>>> {
+    let Some(dir) = &config.plugins_dir else {
+        return Ok(Vec::new());
+    };
+    let plugins = modkit_wasm::PluginHost::load_dir(dir)?;
+    Ok(vec![Arc::new(plugins)])
+}
+
+/// Configure DB options: DbManager or no-DB.
+fn db_options(config: &AppConfig, args: &CliArgs) -> Result<DbOptions> {
+    Ok(if config.database.is_some() {
         if args.mock {
             tracing::info!("Mock mode enabled: using in-memory SQLite for all modules");
             // For mock mode, create a simple figment with mock database config
-            let mock_figment = create_mock_figment(&config);
+            let mock_figment = create_mock_figment(config);
             let home_dir = PathBuf::from(&config.server.home_dir);
             let db_manager = Arc::new(modkit_db::DbManager::from_figment(mock_figment, home_dir)?);
             DbOptions::Manager(db_manager)
@@ -143,7 +297,7 @@ async fn run_server(config: AppConfig, args: CliArgs) -> Result<()> {
             tracing::info!("Using DbManager with Figment-based configuration");
 
             // Create Figment from the current configuration
-            let figment = create_figment_from_config(&config)?;
+            let figment = create_figment_from_config(config)?;
             let home_dir = PathBuf::from(&config.server.home_dir);
             let db_manager = Arc::new(modkit_db::DbManager::from_figment(figment, home_dir)?);
 
@@ -152,26 +306,143 @@ async fn run_server(config: AppConfig, args: CliArgs) -> Result<()> {
     } else {
         tracing::warn!("No global database section found; running without databases");
         DbOptions::None
-    };
+    })
+}
 
-    // Run the ModKit runtime (signals-driven shutdown).
-    let run_options = RunOptions {
-        modules_cfg: config_provider,
-        db: db_options,
-        shutdown: ShutdownOptions::Signals,
-    };
+async fn export_openapi(
+    config: AppConfig,
+    args: CliArgs,
+    output: PathBuf,
+    format: Option<api_ingress::OpenApiFormat>,
+) -> Result<()> {
+    let format = format.unwrap_or_else(|| {
+        output
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(|ext| ext.parse().ok())
+            .unwrap_or_default()
+    });
 
-    run(run_options).await
+    let config_provider = Arc::new(ModkitConfigAdapter(Arc::new(AppConfigProvider::new(
+        config.clone(),
+    ))));
+    let registry = run_until_rest(
+        config_provider,
+        &module_sources(&config)?,
+        db_options(&config, &args)?,
+    )
+    .await?;
+
+    let ingress = registry
+        .modules()
+        .iter()
+        .find_map(|m| m.core.as_any().downcast_ref::<api_ingress::ApiIngress>())
+        .ok_or_else(|| anyhow::anyhow!("api_ingress module is not registered"))?;
+    let spec = ingress.export_openapi(format)?;
+
+    std::fs::write(&output, spec)?;
+    println!("OpenAPI document written to {}", output.display());
+    Ok(())
 }
 
-async fn check_config(config: AppConfig) -> Result<()> {
-    tracing::info!("Checking configuration…");
-    // If load_layered/load_or_default succeeded and home_dir normalized, we're good.
-    println!("Configuration is valid");
-    println!("{}", config.to_yaml()?);
+fn export_module_graph(
+    config: AppConfig,
+    format: GraphFormat,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let sources = module_sources(&config)?;
+    let config_provider = ModkitConfigAdapter(Arc::new(AppConfigProvider::new(config)));
+    let graph = ModuleRegistry::discover_and_build_with_sources(&config_provider, &sources)?
+        .export_graph(format);
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, graph)?;
+            println!("Module graph written to {}", path.display());
+        }
+        None => print!("{graph}"),
+    }
     Ok(())
 }
 
+/// Drive startup as far as it goes without serving, collecting every failure.
+async fn check_config(config: AppConfig, args: CliArgs) -> Result<()> {
+    tracing::info!("Checking configuration…");
+    let config_provider = Arc::new(ModkitConfigAdapter(Arc::new(AppConfigProvider::new(
+        config.clone(),
+    ))));
+    let mut failures: Vec<String> = Vec::new();
+
+    // Discovery problems (unknown/disabled deps, cycles) leave nothing else to check.
+    let sources = module_sources(&config).context("plugin loading failed")?;
+    let registry =
+        ModuleRegistry::discover_and_build_with_sources(config_provider.as_ref(), &sources)
+            .map_err(|e| anyhow::anyhow!("module discovery failed: {e}"))?;
+    let names: Vec<_> = registry.modules().iter().map(|m| m.name).collect();
+    println!("Modules: {}", names.join(", "));
+
+    // Validate with secrets resolved, as the runner would see the config.
+    let (config_provider, secrets_ok): (Arc<dyn modkit::ConfigProvider>, _) =
+        match Secrets::default().resolve_config(config_provider.clone(), &names) {
+            Ok(resolved) => (resolved, true),
+            Err(UnresolvedSecrets(errors)) => {
+                failures.extend(errors.iter().map(|e| format!("secret: {e}")));
+                (config_provider, false)
+            }
+        };
+
+    let config_errors = registry.validate_configs(config_provider.as_ref());
+    let configs_ok = secrets_ok && config_errors.is_empty();
+    // The config error already carries its serde cause, so print one level only.
+    failures.extend(
+        config_errors
+            .iter()
+            .map(|e| match std::error::Error::source(e) {
+                Some(source) => format!("{e}: {source}"),
+                None => e.to_string(),
+            }),
+    );
+
+    let db = match db_options(&config, &args) {
+        Ok(db) => Some(db),
+        Err(e) => {
+            failures.push(format!("database setup: {e:#}"));
+            None
+        }
+    };
+    if let Some(DbOptions::Manager(manager)) = &db {
+        for module in registry.modules().iter().filter(|m| m.db.is_some()) {
+            let result = match manager.get(module.name).await {
+                Ok(Some(handle)) => handle.ping().await.map_err(anyhow::Error::from),
+                Ok(None) => Err(anyhow::anyhow!("no database configured")),
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = result {
+                failures.push(format!("database for '{}': {e:#}", module.name));
+            }
+        }
+    }
+
+    // Init would only repeat the config failures above, so the REST dry run needs valid configs.
+    match db {
+        Some(db) if configs_ok => {
+            if let Err(e) = run_until_rest(config_provider, &sources, db).await {
+                failures.push(format!("REST dry run: {e:#}"));
+            }
+        }
+        _ => println!("REST dry run skipped: fix the problems above first"),
+    }
+
+    if failures.is_empty() {
+        println!("Configuration is valid");
+        return Ok(());
+    }
+    for failure in &failures {
+        println!("  - {failure}");
+    }
+    anyhow::bail!("configuration check found {} problem(s)", failures.len())
+}
+
 /// Create a Figment from the loaded AppConfig for use with DbManager.
 fn create_figment_from_config(config: &AppConfig) -> Result<Figment> {
     use figment::providers::Serialized;
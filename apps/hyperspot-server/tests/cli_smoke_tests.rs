@@ -98,17 +98,17 @@ fn test_cli_invalid_command() {
 fn test_cli_config_validation_missing_file() {
     let output = run_hyperspot_server(&["--config", "/nonexistent/config.yaml", "check"]);
 
-    // The application gracefully falls back to defaults when config file is missing
-    // This is actually good UX behavior, so the check should succeed
+    // A missing file falls back to defaults, which carry no module sections;
+    // the dry run reports that api_ingress could not be configured.
     assert!(
-        output.status.success(),
-        "Should succeed with default config fallback"
+        !output.status.success(),
+        "Defaults alone cannot boot api_ingress"
     );
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        stdout.contains("Configuration check passed") || stdout.contains("valid"),
-        "Should indicate successful validation with defaults: {}",
+        stdout.contains("config check failed for 'api_ingress'"),
+        "Should report the missing api_ingress config: {}",
         stdout
     );
 }
@@ -155,6 +155,11 @@ logging:
     max_age_days: 28
     max_backups: 3
     max_size_mb: 1000
+
+modules:
+  api_ingress:
+    config:
+      bind_addr: "127.0.0.1:8087"
 "#;
 
     std::fs::write(&config_path, config_content).expect("Failed to write config file");
@@ -316,6 +321,11 @@ logging:
     console_level: error
     file: "logs/hyperspot.log"
     file_level: error
+
+modules:
+  api_ingress:
+    config:
+      bind_addr: "127.0.0.1:8087"
 "#;
 
     std::fs::write(&config_path, config_content).expect("Failed to write config file");
@@ -353,19 +363,18 @@ fn test_cli_verbose_flag() {
 
 #[test]
 fn test_cli_config_flag_short_form() {
-    // Test short form of config flag with missing file (should gracefully fall back to defaults)
+    // Test short form of config flag with missing file (falls back to defaults)
     let output = run_hyperspot_server(&["-c", "/nonexistent/config.yaml", "check"]);
 
-    // The application gracefully falls back to defaults when config file is missing
+    // The defaults are loaded and checked just like with the long flag
     assert!(
-        output.status.success(),
-        "Should succeed with default config fallback using short flag"
+        !output.status.success(),
+        "Defaults alone cannot boot api_ingress"
     );
-
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
-        stdout.contains("Configuration check passed") || stdout.contains("valid"),
-        "Should indicate successful validation with defaults using short flag: {}",
+        stdout.contains("config check failed for 'api_ingress'"),
+        "Should report the missing api_ingress config using short flag: {}",
         stdout
     );
 }
@@ -418,6 +427,11 @@ logging:
     console_level: debug
     file: "logs/hyperspot.log"
     file_level: debug
+
+modules:
+  api_ingress:
+    config:
+      bind_addr: "127.0.0.1:8087"
 "#;
 
     std::fs::write(&config_path, config_content).expect("Failed to write config file");
@@ -455,3 +469,41 @@ async fn test_cli_no_arguments() {
         Err(other) => panic!("Unexpected failure: {other}"),
     }
 }
+
+#[test]
+fn test_cli_openapi_export() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("openapi.yaml");
+    let spec_path = temp_dir.path().join("spec.yaml");
+
+    let config_content = format!(
+        r#"
+server:
+  home_dir: "{}"
+modules:
+  api_ingress:
+    config:
+      bind_addr: "127.0.0.1:0"
+"#,
+        temp_dir.path().display()
+    );
+    std::fs::write(&config_path, config_content).expect("Failed to write config file");
+
+    let output = run_hyperspot_server(&[
+        "--config",
+        config_path.to_str().unwrap(),
+        "openapi",
+        "--output",
+        spec_path.to_str().unwrap(),
+    ]);
+    assert!(
+        output.status.success(),
+        "openapi export should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Format follows the file extension
+    let spec = std::fs::read_to_string(&spec_path).expect("spec file should be written");
+    assert!(spec.starts_with("openapi:"), "expected YAML output: {spec}");
+    assert!(spec.contains("paths:"));
+}
@@ -2,7 +2,7 @@ use crate::api::rest::{dto, handlers};
 use crate::domain::service::Service;
 use axum::{Extension, Router};
 use modkit::api::operation_builder::OperationBuilderODataExt;
-use modkit::api::{OpenApiRegistry, OperationBuilder};
+use modkit::api::{OpenApiRegistry, OperationBuilder, ResourceBuilder, ResourceOp};
 use std::sync::Arc;
 use std::time::Duration;
 use tower_http::timeout::TimeoutLayer;
@@ -14,79 +14,28 @@ pub fn register_routes(
 ) -> anyhow::Result<Router> {
     // Schemas should be auto-registered via ToSchema when used in operations
 
-    // GET /users - List users with cursor-based pagination
-    router = OperationBuilder::<modkit::api::Missing, modkit::api::Missing, ()>::get("/users")
-        .operation_id("users_info.list_users")
-        .summary("List users with cursor pagination")
-        .description("Retrieve a paginated list of users using cursor-based pagination")
-        .tag("users")
-        .query_param_typed("limit", false, "Maximum number of users to return", "integer")
-        .query_param("cursor", false, "Cursor for pagination")
-        .handler(handlers::list_users)
-        .json_response_with_schema::<odata_core::Page<dto::UserDto>>(openapi, 200, "Paginated list of users")
-        .with_odata_filter_doc("OData v4 filter. Examples: `email eq 'test@example.com'`, `contains(email,'@acme.com')`")
-        .query_param("$orderby", false, "OData orderby clause. Example: 'created_at desc, id desc'")
-        .problem_response(openapi, 400, "Bad Request")
-        .problem_response(openapi, 500, "Internal Server Error")
-        .register(router, openapi);
-
-    // GET /users/{id} - Get a specific user
-    router = OperationBuilder::<modkit::api::Missing, modkit::api::Missing, ()>::get("/users/{id}")
-        .operation_id("users_info.get_user")
-        .summary("Get user by ID")
-        .description("Retrieve a specific user by their UUID")
-        .tag("users")
-        .path_param("id", "User UUID")
-        .handler(handlers::get_user)
-        .json_response_with_schema::<dto::UserDto>(openapi, 200, "User found")
-        .problem_response(openapi, 404, "Not Found")
-        .problem_response(openapi, 500, "Internal Server Error")
-        .register(router, openapi);
-
-    // POST /users - Create a new user
-    router = OperationBuilder::<modkit::api::Missing, modkit::api::Missing, ()>::post("/users")
-        .operation_id("users_info.create_user")
-        .summary("Create a new user")
-        .description("Create a new user with the provided information")
-        .tag("users")
-        .json_request::<dto::CreateUserReq>(openapi, "User creation data")
-        .handler(handlers::create_user)
-        .json_response_with_schema::<dto::UserDto>(openapi, 201, "Created user")
-        .problem_response(openapi, 400, "Bad Request")
-        .problem_response(openapi, 409, "Conflict")
-        .problem_response(openapi, 500, "Internal Server Error")
-        .register(router, openapi);
-
-    // PUT /users/{id} - Update a user
-    router = OperationBuilder::<modkit::api::Missing, modkit::api::Missing, ()>::put("/users/{id}")
-        .operation_id("users_info.update_user")
-        .summary("Update user")
-        .description("Update a user with partial data")
-        .tag("users")
-        .path_param("id", "User UUID")
-        .json_request::<dto::UpdateUserReq>(openapi, "User update data")
-        .handler(handlers::update_user)
-        .json_response_with_schema::<dto::UserDto>(openapi, 200, "Updated user")
-        .problem_response(openapi, 400, "Bad Request")
-        .problem_response(openapi, 404, "Not Found")
-        .problem_response(openapi, 409, "Conflict")
-        .problem_response(openapi, 500, "Internal Server Error")
+    // GET/POST /users and GET/PUT/DELETE /users/{id}
+    router = ResourceBuilder::<dto::UserDto>::new("/users")
+        .operation_prefix("users_info")
+        .id_param_uuid("id", "User UUID")
+        .list(handlers::list_users)
+        .get(handlers::get_user)
+        .create::<dto::CreateUserReq, _, _>(handlers::create_user)
+        .update::<dto::UpdateUserReq, _, _>(handlers::update_user)
+        .delete(handlers::delete_user)
+        .customize(ResourceOp::List, |op| {
+            op.summary("List users with cursor pagination")
+                .with_odata_filter_doc("OData v4 filter. Examples: `email eq 'test@example.com'`, `contains(email,'@acme.com')`")
+                .query_param("$orderby", false, "OData orderby clause. Example: 'created_at desc, id desc'")
+        })
+        .customize(ResourceOp::Create, |op| {
+            op.request_example(serde_json::json!({
+                "email": "ada@example.com",
+                "display_name": "Ada Lovelace"
+            }))
+        })
         .register(router, openapi);
 
-    // DELETE /users/{id} - Delete a user
-    router =
-        OperationBuilder::<modkit::api::Missing, modkit::api::Missing, ()>::delete("/users/{id}")
-            .operation_id("users_info.delete_user")
-            .summary("Delete user")
-            .description("Delete a user by their UUID")
-            .tag("users")
-            .path_param("id", "User UUID")
-            .handler(handlers::delete_user)
-            .json_response(204, "User deleted successfully")
-            .problem_response(openapi, 404, "Not Found")
-            .problem_response(openapi, 500, "Internal Server Error")
-            .register(router, openapi);
-
     router = router.layer(Extension(service.clone()));
 
     Ok(router)
@@ -101,21 +50,17 @@ pub fn register_users_sse_route<S>(
 where
     S: Clone + Send + Sync + 'static,
 {
-    // First register the route, then add layers
-    let router =
-        OperationBuilder::<modkit::api::Missing, modkit::api::Missing, S>::get("/users/events")
-            .operation_id("users_info.events")
-            .summary("User events stream (SSE)")
-            .description("Real-time stream of user events as Server-Sent Events")
-            .tag("users")
-            .handler(handlers::users_events)
-            .sse_json::<dto::UserEvent>(openapi, "SSE stream of UserEvent")
-            .register(router, openapi);
-
-    // Apply layers to the specific route using Router::layer
-    router
+    OperationBuilder::<modkit::api::Missing, modkit::api::Missing, S>::get("/users/events")
+        .operation_id("users_info.events")
+        .summary("User events stream (SSE)")
+        .description("Real-time stream of user events as Server-Sent Events")
+        .tag("users")
+        .handler(handlers::users_events)
+        // Route-level layers: the broadcaster and a long timeout only apply to the stream
         .layer(axum::Extension(sse))
         .layer(TimeoutLayer::new(Duration::from_secs(60 * 60)))
+        .sse_json::<dto::UserEvent>(openapi, "SSE stream of UserEvent")
+        .register(router, openapi)
 }
 
 #[cfg(test)]
@@ -1,12 +1,14 @@
 use serde::{Deserialize, Serialize};
 
 /// Configuration for the users_info module
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, modkit::ConfigSchema)]
 #[serde(deny_unknown_fields)]
 pub struct UsersInfoConfig {
     #[serde(default = "default_page_size")]
+    #[config(min = 1)]
     pub default_page_size: u32,
     #[serde(default = "default_max_page_size")]
+    #[config(min = 1)]
     pub max_page_size: u32,
 }
 
@@ -22,7 +22,8 @@ use crate::infra::storage::sea_orm_repo::SeaOrmUsersRepository;
 #[modkit::module(
     name = "users_info",
     capabilities = [db, rest],
-    client = crate::contract::client::UsersInfoApi
+    client = crate::contract::client::UsersInfoApi,
+    config = crate::config::UsersInfoConfig
 )]
 pub struct UsersInfo {
     // Keep the domain service behind ArcSwap for cheap read-mostly access.
@@ -105,8 +106,9 @@ impl DbModule for UsersInfo {
     }
 }
 
+#[async_trait]
 impl RestfulModule for UsersInfo {
-    fn register_rest(
+    async fn register_rest(
         &self,
         _ctx: &ModuleCtx,
         router: axum::Router,
@@ -0,0 +1,745 @@
+//! Typed REST client generation from ModKit `OperationSpec`s.
+//!
+//! [`SpecCollector`] is an [`OpenApiRegistry`] that only records operations, so a
+//! module's route registration can run in a build script or test to obtain its
+//! specs. [`ClientGenerator`] turns them into Rust source for a `reqwest`-based
+//! client with one async method per operation. Bodies are typed with the
+//! module's own DTOs (looked up by component schema name), so client and server
+//! share a single definition instead of hand-written `reqwest` calls drifting
+//! from the routes.
+//!
+//! ```rust,ignore
+//! let collector = SpecCollector::new();
+//! let _ = users_info::api::rest::routes::register_routes(Router::new(), &collector, service)?;
+//! let code = ClientGenerator::new("UsersInfoClient")
+//!     .types_path("users_info::api::rest::dto")
+//!     .type_override("Page_UserDto", "odata_core::Page<types::UserDto>")
+//!     .generate(&collector.specs());
+//! std::fs::write(out_dir.join("users_info_client.rs"), code)?;
+//! ```
+//!
+//! The generated code depends on `reqwest` (with the `json` feature) and
+//! `serde_json`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, PoisonError};
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use modkit::api::{
+    CollectionFormat, OpenApiRegistry, OperationSpec, ParamLocation, ParamSpec, ResponseSpec,
+    ResponseStatus,
+};
+
+/// Registry that records operations and ignores schemas.
+#[derive(Default)]
+pub struct SpecCollector {
+    operations: Mutex<Vec<OperationSpec>>,
+}
+
+impl SpecCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Operations registered so far, in registration order.
+    pub fn specs(&self) -> Vec<OperationSpec> {
+        self.operations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl OpenApiRegistry for SpecCollector {
+    fn register_operation(&self, spec: &OperationSpec) {
+        self.operations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(spec.clone());
+    }
+
+    fn ensure_schema_raw(
+        &self,
+        name: &str,
+        _schemas: Vec<(
+            String,
+            utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+        )>,
+    ) -> String {
+        name.to_string()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Generates the source of a typed client for a set of operations.
+#[derive(Clone, Debug)]
+pub struct ClientGenerator {
+    client_name: String,
+    types_path: String,
+    type_overrides: BTreeMap<String, String>,
+}
+
+impl ClientGenerator {
+    /// Generator for a client struct named `client_name` (e.g. `UsersInfoClient`).
+    pub fn new(client_name: impl Into<String>) -> Self {
+        Self {
+            client_name: client_name.into(),
+            types_path: "super".to_string(),
+            type_overrides: BTreeMap::new(),
+        }
+    }
+
+    /// Module holding the DTOs named by the schemas, imported as `types`
+    /// (default: `super`).
+    pub fn types_path(mut self, path: impl Into<String>) -> Self {
+        self.types_path = path.into();
+        self
+    }
+
+    /// Rust type to use for a component schema whose name is not a Rust path,
+    /// e.g. `("Page_UserDto", "odata_core::Page<types::UserDto>")`.
+    pub fn type_override(
+        mut self,
+        schema: impl Into<String>,
+        rust_type: impl Into<String>,
+    ) -> Self {
+        self.type_overrides.insert(schema.into(), rust_type.into());
+        self
+    }
+
+    /// Rust source of the client, its error type and per-operation parameter structs.
+    pub fn generate(&self, specs: &[OperationSpec]) -> String {
+        let mut specs: Vec<&OperationSpec> = specs.iter().collect();
+        specs.sort_by(|a, b| (&a.path, a.method.as_str()).cmp(&(&b.path, b.method.as_str())));
+
+        let mut used = BTreeSet::new();
+        let mut ops = Vec::new();
+        let mut skipped = Vec::new();
+        for spec in specs {
+            if let Some(reason) = unsupported(spec) {
+                skipped.push(format!("{} {}: {reason}", spec.method, spec.path));
+                continue;
+            }
+            let name = unique_name(spec, &mut used);
+            ops.push((name, spec));
+        }
+
+        let client = &self.client_name;
+        let error = format!("{client}Error");
+        let mut code = Code::default();
+        code.line(
+            0,
+            "// @generated by modkit-client-gen from the registered OperationSpecs.",
+        );
+        code.line(
+            0,
+            "// Do not edit by hand; regenerate after changing the routes.",
+        );
+        for skip in &skipped {
+            code.line(0, format!("// skipped {skip}"));
+        }
+        code.blank();
+        code.line(0, "#[allow(unused_imports)]");
+        code.line(0, format!("use {} as types;", self.types_path));
+        code.blank();
+        self.emit_error(&mut code, &error);
+
+        for (name, spec) in &ops {
+            self.emit_params(&mut code, name, spec);
+        }
+
+        code.line(0, format!("/// Typed client for {} operations.", ops.len()));
+        code.line(0, "#[derive(Clone, Debug)]");
+        code.line(0, format!("pub struct {client} {{"));
+        code.line(1, "http: reqwest::Client,");
+        code.line(1, "base_url: String,");
+        code.line(0, "}");
+        code.blank();
+        code.line(0, format!("impl {client} {{"));
+        code.line(
+            1,
+            "/// Client for the service at `base_url`, e.g. `http://localhost:8087`.",
+        );
+        code.line(1, "pub fn new(base_url: impl Into<String>) -> Self {");
+        code.line(2, "Self::with_client(reqwest::Client::new(), base_url)");
+        code.line(1, "}");
+        code.blank();
+        code.line(
+            1,
+            "/// Client reusing a configured `reqwest::Client` (timeouts, TLS, ...).",
+        );
+        code.line(
+            1,
+            "pub fn with_client(http: reqwest::Client, base_url: impl Into<String>) -> Self {",
+        );
+        code.line(
+            2,
+            "let base_url = base_url.into().trim_end_matches('/').to_string();",
+        );
+        code.line(2, "Self { http, base_url }");
+        code.line(1, "}");
+        for (name, spec) in &ops {
+            code.blank();
+            self.emit_method(&mut code, name, spec, &error);
+        }
+        code.blank();
+        code.line(
+            1,
+            format!(
+                "async fn check(resp: reqwest::Response) -> Result<reqwest::Response, {error}> {{"
+            ),
+        );
+        code.line(2, "let status = resp.status();");
+        code.line(2, "if status.is_success() {");
+        code.line(3, "return Ok(resp);");
+        code.line(2, "}");
+        code.line(2, "let body = resp.text().await.unwrap_or_default();");
+        code.line(
+            2,
+            format!("Err({error}::Status {{ status: status.as_u16(), body }})"),
+        );
+        code.line(1, "}");
+        code.line(0, "}");
+
+        if ops.iter().any(|(_, spec)| {
+            url_format(&spec.path)
+                .1
+                .iter()
+                .any(|v| v.starts_with("encode_segment"))
+        }) {
+            code.blank();
+            code.line(
+                0,
+                "/// Percent-encode a path segment (RFC 3986 unreserved characters kept).",
+            );
+            code.line(0, "fn encode_segment(value: &str) -> String {");
+            code.line(1, "let mut out = String::with_capacity(value.len());");
+            code.line(1, "for b in value.bytes() {");
+            code.line(
+                2,
+                "if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {",
+            );
+            code.line(3, "out.push(b as char);");
+            code.line(2, "} else {");
+            code.line(3, "out.push_str(&format!(\"%{b:02X}\"));");
+            code.line(2, "}");
+            code.line(1, "}");
+            code.line(1, "out");
+            code.line(0, "}");
+        }
+        code.buf
+    }
+
+    fn emit_error(&self, code: &mut Code, error: &str) {
+        code.line(
+            0,
+            format!("/// Error returned by [`{}`].", self.client_name),
+        );
+        code.line(0, "#[derive(Debug)]");
+        code.line(0, format!("pub enum {error} {{"));
+        code.line(1, "/// Transport or decoding failure.");
+        code.line(1, "Http(reqwest::Error),");
+        code.line(
+            1,
+            "/// Non-2xx response; `body` is usually an RFC 9457 Problem.",
+        );
+        code.line(1, "Status { status: u16, body: String },");
+        code.line(0, "}");
+        code.blank();
+        code.line(0, format!("impl std::fmt::Display for {error} {{"));
+        code.line(
+            1,
+            "fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {",
+        );
+        code.line(2, "match self {");
+        code.line(3, "Self::Http(e) => write!(f, \"request failed: {e}\"),");
+        code.line(
+            3,
+            "Self::Status { status, body } => write!(f, \"unexpected status {status}: {body}\"),",
+        );
+        code.line(2, "}");
+        code.line(1, "}");
+        code.line(0, "}");
+        code.blank();
+        code.line(0, format!("impl std::error::Error for {error} {{}}"));
+        code.blank();
+        code.line(0, format!("impl From<reqwest::Error> for {error} {{"));
+        code.line(1, "fn from(e: reqwest::Error) -> Self {");
+        code.line(2, "Self::Http(e)");
+        code.line(1, "}");
+        code.line(0, "}");
+        code.blank();
+    }
+
+    /// `{Op}Params` struct for query and header parameters, if the operation has any.
+    fn emit_params(&self, code: &mut Code, name: &str, spec: &OperationSpec) {
+        let params: Vec<&ParamSpec> = extra_params(spec).collect();
+        if params.is_empty() {
+            return;
+        }
+        let has_query = params.iter().any(|p| p.location == ParamLocation::Query);
+        let has_header = params.iter().any(|p| p.location == ParamLocation::Header);
+        let ty = params_type(name);
+
+        code.line(
+            0,
+            format!(
+                "/// Query and header parameters of [`{}::{name}`].",
+                self.client_name
+            ),
+        );
+        code.line(0, "#[derive(Clone, Debug, Default)]");
+        code.line(0, format!("pub struct {ty} {{"));
+        for p in &params {
+            if let Some(desc) = &p.description {
+                code.doc(1, desc);
+            }
+            code.line(1, format!("pub {}: {},", ident(&p.name), field_type(p)));
+        }
+        code.line(0, "}");
+        code.blank();
+        code.line(0, format!("impl {ty} {{"));
+        // Headers are applied by rebinding `req`, so only then must it be mutable
+        let req = if has_header { "mut req" } else { "req" };
+        code.line(
+            1,
+            format!(
+                "fn apply(&self, {req}: reqwest::RequestBuilder) -> reqwest::RequestBuilder {{"
+            ),
+        );
+        if has_query {
+            code.line(2, "let mut query: Vec<(&str, String)> = Vec::new();");
+        }
+        for p in &params {
+            let field = format!("self.{}", ident(&p.name));
+            let target = match p.location {
+                ParamLocation::Query => format!("query.push(({:?}, ", p.name),
+                _ => format!("req = req.header({:?}, ", p.name),
+            };
+            match &p.items {
+                Some(items) if items.collection == CollectionFormat::Multi => {
+                    code.line(2, format!("for v in &{field} {{"));
+                    code.line(3, format!("{target}v.to_string()));"));
+                    code.line(2, "}");
+                }
+                Some(_) => {
+                    code.line(2, format!("if !{field}.is_empty() {{"));
+                    code.line(
+                        3,
+                        format!(
+                            "let joined: Vec<String> = {field}.iter().map(ToString::to_string).collect();"
+                        ),
+                    );
+                    code.line(3, format!("{target}joined.join(\",\")));"));
+                    code.line(2, "}");
+                }
+                None if p.required => code.line(2, format!("{target}{field}.to_string()));")),
+                None => {
+                    code.line(2, format!("if let Some(v) = &{field} {{"));
+                    code.line(3, format!("{target}v.to_string()));"));
+                    code.line(2, "}");
+                }
+            }
+        }
+        code.line(
+            2,
+            if has_query {
+                "req.query(&query)"
+            } else {
+                "req"
+            },
+        );
+        code.line(1, "}");
+        code.line(0, "}");
+        code.blank();
+    }
+
+    fn emit_method(&self, code: &mut Code, name: &str, spec: &OperationSpec, error: &str) {
+        let (template, values, path_args) = url_format(&spec.path);
+        let has_extra = extra_params(spec).next().is_some();
+        // A `204` never carries a body, whatever content type was declared
+        let response = success_response(spec).filter(|r| r.status != ResponseStatus::Code(204));
+
+        let mut args = vec!["&self".to_string()];
+        for arg in &path_args {
+            args.push(format!("{arg}: impl std::fmt::Display"));
+        }
+        if let Some(rb) = &spec.request_body {
+            args.push(match (rb.content_type, &rb.schema_name) {
+                ("application/json", Some(schema)) => format!("body: &{}", self.type_ref(schema)),
+                ("application/json", None) => "body: &serde_json::Value".to_string(),
+                _ => "body: impl Into<reqwest::Body>".to_string(),
+            });
+        }
+        if has_extra {
+            args.push(format!("params: &{}", params_type(name)));
+        }
+        let ret = match response {
+            Some(r) => self.response_type(r),
+            None => "()".to_string(),
+        };
+
+        if let Some(summary) = spec.summary.as_ref().or(spec.description.as_ref()) {
+            code.doc(1, summary);
+            code.line(1, "///");
+        }
+        code.line(1, format!("/// `{} {}`", spec.method, spec.path));
+        code.line(
+            1,
+            format!(
+                "pub async fn {name}({}) -> Result<{ret}, {error}> {{",
+                args.join(", ")
+            ),
+        );
+
+        if values.is_empty() {
+            code.line(
+                2,
+                format!("let url = format!(\"{{}}{template}\", self.base_url);"),
+            );
+        } else {
+            code.line(
+                2,
+                format!(
+                    "let url = format!(\"{{}}{template}\", self.base_url, {});",
+                    values.join(", ")
+                ),
+            );
+        }
+        let method = spec.method.as_str().to_ascii_uppercase();
+        code.line(
+            2,
+            format!("let req = self.http.request(reqwest::Method::{method}, url);"),
+        );
+        if has_extra {
+            code.line(2, "let req = params.apply(req);");
+        }
+        if let Some(rb) = &spec.request_body {
+            if rb.content_type == "application/json" {
+                code.line(2, "let req = req.json(body);");
+            } else {
+                code.line(
+                    2,
+                    format!(
+                        "let req = req.header(reqwest::header::CONTENT_TYPE, {:?}).body(body);",
+                        rb.content_type
+                    ),
+                );
+            }
+        }
+        code.line(2, "let resp = Self::check(req.send().await?).await?;");
+        match response.map(|r| r.content_type) {
+            None | Some("") => {
+                code.line(2, "drop(resp);");
+                code.line(2, "Ok(())");
+            }
+            Some("text/event-stream") => code.line(2, "Ok(resp)"),
+            Some(ct) if is_json(ct) => code.line(2, "Ok(resp.json().await?)"),
+            Some(ct) if ct.starts_with("text/") => code.line(2, "Ok(resp.text().await?)"),
+            Some(_) => code.line(2, "Ok(resp.bytes().await?.to_vec())"),
+        }
+        code.line(1, "}");
+    }
+
+    fn response_type(&self, r: &ResponseSpec) -> String {
+        match r.content_type {
+            "" => "()".to_string(),
+            "text/event-stream" => "reqwest::Response".to_string(),
+            ct if is_json(ct) => match &r.schema_name {
+                Some(schema) => self.type_ref(schema),
+                None => "serde_json::Value".to_string(),
+            },
+            ct if ct.starts_with("text/") => "String".to_string(),
+            _ => "Vec<u8>".to_string(),
+        }
+    }
+
+    fn type_ref(&self, schema: &str) -> String {
+        self.type_overrides
+            .get(schema)
+            .cloned()
+            .unwrap_or_else(|| format!("types::{schema}"))
+    }
+}
+
+#[derive(Default)]
+struct Code {
+    buf: String,
+}
+
+impl Code {
+    fn line(&mut self, depth: usize, text: impl AsRef<str>) {
+        for _ in 0..depth {
+            self.buf.push_str("    ");
+        }
+        self.buf.push_str(text.as_ref());
+        self.buf.push('\n');
+    }
+
+    fn doc(&mut self, depth: usize, text: &str) {
+        for line in text.lines() {
+            self.line(depth, format!("/// {line}").trim_end());
+        }
+    }
+
+    fn blank(&mut self) {
+        self.buf.push('\n');
+    }
+}
+
+/// Why an operation cannot be called through a plain HTTP client, if it can't.
+fn unsupported(spec: &OperationSpec) -> Option<&'static str> {
+    if spec.websocket {
+        return Some("WebSocket endpoint");
+    }
+    match &spec.request_body {
+        Some(rb) if !rb.multipart_fields.is_empty() => Some("multipart request body"),
+        _ => None,
+    }
+}
+
+fn is_json(content_type: &str) -> bool {
+    content_type == "application/json" || content_type.ends_with("+json")
+}
+
+/// First declared 2xx response; its body becomes the method's return type.
+fn success_response(spec: &OperationSpec) -> Option<&ResponseSpec> {
+    spec.responses.iter().find(|r| {
+        r.status
+            .code()
+            .is_some_and(|code| (200..300).contains(&code))
+    })
+}
+
+/// Query and header parameters; a name declared twice keeps its first declaration.
+fn extra_params(spec: &OperationSpec) -> impl Iterator<Item = &ParamSpec> {
+    let mut seen = BTreeSet::new();
+    spec.params.iter().filter(move |p| {
+        matches!(p.location, ParamLocation::Query | ParamLocation::Header)
+            && seen.insert(ident(&p.name))
+    })
+}
+
+fn params_type(method_name: &str) -> String {
+    format!("{}Params", method_name.to_upper_camel_case())
+}
+
+fn scalar_type(ty: &str) -> &'static str {
+    match ty {
+        "integer" => "i64",
+        "number" => "f64",
+        "boolean" => "bool",
+        _ => "String",
+    }
+}
+
+fn field_type(p: &ParamSpec) -> String {
+    match &p.items {
+        Some(items) => format!("Vec<{}>", scalar_type(&items.item_type)),
+        None if p.required => scalar_type(&p.param_type).to_string(),
+        None => format!("Option<{}>", scalar_type(&p.param_type)),
+    }
+}
+
+/// Method name from the last segment of the operation id (`users_info.get_user`
+/// → `get_user`), falling back to the full id and then the handler id.
+fn unique_name(spec: &OperationSpec, used: &mut BTreeSet<String>) -> String {
+    let candidates = match &spec.operation_id {
+        Some(id) => vec![
+            id.rsplit('.').next().unwrap_or(id).to_snake_case(),
+            id.to_snake_case(),
+        ],
+        None => vec![spec.handler_id.to_snake_case()],
+    };
+    let mut name = candidates
+        .iter()
+        .map(|c| ident(c))
+        .find(|c| !used.contains(c))
+        .unwrap_or_else(|| ident(&candidates[candidates.len() - 1]));
+    let base = name.clone();
+    let mut n = 2;
+    while used.contains(&name) {
+        name = format!("{base}_{n}");
+        n += 1;
+    }
+    used.insert(name.clone());
+    name
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Rust identifier for a parameter or operation name (`$filter` → `filter`).
+fn ident(raw: &str) -> String {
+    let mut out: String = raw
+        .trim_start_matches('$')
+        .to_snake_case()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    if matches!(out.as_str(), "self" | "super" | "crate") {
+        out.push('_');
+    } else if KEYWORDS.contains(&out.as_str()) {
+        out.insert_str(0, "r#");
+    }
+    out
+}
+
+/// `format!` template, its arguments and the parameter names of a route path;
+/// `{*rest}` wildcards are inserted verbatim, other parameters percent-encoded.
+fn url_format(path: &str) -> (String, Vec<String>, Vec<String>) {
+    let mut template = String::new();
+    let mut values = Vec::new();
+    let mut names = Vec::new();
+    for segment in path.split('/').skip(1) {
+        template.push('/');
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) => {
+                template.push_str("{}");
+                match name.strip_prefix('*') {
+                    Some(rest) => {
+                        names.push(ident(rest));
+                        values.push(ident(rest));
+                    }
+                    None => {
+                        names.push(ident(name));
+                        values.push(format!("encode_segment(&{}.to_string())", ident(name)));
+                    }
+                }
+            }
+            None => template.push_str(&segment.replace('{', "{{").replace('}', "}}")),
+        }
+    }
+    (template, values, names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+    #[allow(dead_code)]
+    struct UserDto {
+        id: String,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+    #[allow(dead_code)]
+    struct CreateUserReq {
+        email: String,
+    }
+
+    fn users_api() -> Vec<OperationSpec> {
+        let collector = SpecCollector::new();
+        let router = axum::Router::new();
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/users")
+            .operation_id("users_info.list_users")
+            .summary("List users")
+            .query_param_typed("limit", false, "Maximum number of users", "integer")
+            .query_param_array("status", false, "Statuses", "string", CollectionFormat::Csv)
+            .query_param("$filter", false, "OData filter")
+            .handler(|| async { "ok" })
+            .paginated::<UserDto>(&collector)
+            .register(router, &collector);
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/users/{id}")
+            .operation_id("users_info.get_user")
+            .path_param_uuid("id", "User id")
+            .handler(|| async { "ok" })
+            .json_response_with_schema::<UserDto>(&collector, 200, "User")
+            .register(router, &collector);
+        let router = OperationBuilder::<Missing, Missing, ()>::post("/users")
+            .operation_id("users_info.create_user")
+            .json_request::<CreateUserReq>(&collector, "New user")
+            .handler(|| async { "ok" })
+            .json_response_with_schema::<UserDto>(&collector, 201, "Created")
+            .problem_response(&collector, 409, "Conflict")
+            .register(router, &collector);
+        let _router = OperationBuilder::<Missing, Missing, ()>::delete("/users/{id}")
+            .operation_id("users_info.delete_user")
+            .path_param_uuid("id", "User id")
+            .handler(|| async { "ok" })
+            .json_response(204, "Deleted")
+            .register(router, &collector);
+        collector.specs()
+    }
+
+    fn generate() -> String {
+        ClientGenerator::new("UsersInfoClient")
+            .type_override("Page_UserDto", "odata_core::Page<types::UserDto>")
+            .generate(&users_api())
+    }
+
+    #[test]
+    fn generated_client_is_valid_rust() {
+        let code = generate();
+        syn::parse_file(&code).unwrap_or_else(|e| panic!("{e}\n{code}"));
+    }
+
+    #[test]
+    fn methods_follow_operation_ids_and_types() {
+        let code = generate();
+        assert!(code.contains(
+            "pub async fn list_users(&self, params: &ListUsersParams) -> Result<odata_core::Page<types::UserDto>, UsersInfoClientError>"
+        ));
+        assert!(code.contains(
+            "pub async fn get_user(&self, id: impl std::fmt::Display) -> Result<types::UserDto, UsersInfoClientError>"
+        ));
+        assert!(code.contains(
+            "pub async fn create_user(&self, body: &types::CreateUserReq) -> Result<types::UserDto, UsersInfoClientError>"
+        ));
+        assert!(code.contains(
+            "pub async fn delete_user(&self, id: impl std::fmt::Display) -> Result<(), UsersInfoClientError>"
+        ));
+        assert!(code.contains(
+            "let url = format!(\"{}/users/{}\", self.base_url, encode_segment(&id.to_string()));"
+        ));
+    }
+
+    #[test]
+    fn params_struct_serializes_query_styles() {
+        let code = generate();
+        assert_eq!(code.matches("pub limit: Option<i64>,").count(), 1);
+        assert!(code.contains("pub status: Vec<String>,"));
+        assert!(code.contains("pub filter: Option<String>,"));
+        assert!(code.contains("query.push((\"$filter\", v.to_string()));"));
+        assert!(code.contains("query.push((\"status\", joined.join(\",\")));"));
+    }
+
+    #[test]
+    fn path_templates_become_format_calls() {
+        let (template, values, names) = url_format("/v1/files/{file-id}/raw/{*rest}");
+        assert_eq!(template, "/v1/files/{}/raw/{}");
+        assert_eq!(values, ["encode_segment(&file_id.to_string())", "rest"]);
+        assert_eq!(names, ["file_id", "rest"]);
+    }
+
+    #[test]
+    fn names_are_deduplicated_and_sanitized() {
+        assert_eq!(ident("type"), "r#type");
+        assert_eq!(ident("$orderby"), "orderby");
+        assert_eq!(ident("X-Tenant-Id"), "x_tenant_id");
+        assert_eq!(ident("self"), "self_");
+
+        let spec = |id: &str| {
+            OperationBuilder::<Missing, Missing, ()>::get("/x")
+                .operation_id(id)
+                .spec()
+                .clone()
+        };
+        let mut used = BTreeSet::new();
+        assert_eq!(unique_name(&spec("a.list"), &mut used), "list");
+        assert_eq!(unique_name(&spec("b.list"), &mut used), "b_list");
+    }
+}
@@ -1,5 +1,7 @@
 //! Shared database error helpers (SQLSTATE categorization, etc.)
 
+use crate::DbError;
+
 /// Returns true if the given SQLSTATE code represents a unique constraint violation
 /// across popular backends (Postgres 23505, SQLite 2067, MySQL 1062).
 pub fn is_unique_violation_code(code: &str) -> bool {
@@ -7,7 +9,9 @@ pub fn is_unique_violation_code(code: &str) -> bool {
 }
 
 pub fn is_sqlx_unique_violation(db: &dyn sqlx::error::DatabaseError) -> bool {
-    db.code().map(|c| is_unique_violation_code(c.as_ref())).unwrap_or(false)
+    db.code()
+        .map(|c| is_unique_violation_code(c.as_ref()))
+        .unwrap_or(false)
 }
 
 #[cfg(feature = "sea-orm")]
@@ -16,3 +20,115 @@ pub fn is_seaorm_unique_violation(err: &sea_orm::RuntimeErr) -> bool {
     let msg = err.to_string().to_lowercase();
     msg.contains("unique") || msg.contains("duplicate") || msg.contains("constraint")
 }
+
+/// Returns true if the given code represents a foreign key violation
+/// (Postgres 23503, SQLite 787, MySQL 1451/1452).
+pub fn is_foreign_key_violation_code(code: &str) -> bool {
+    matches!(code, "23503" | "787" | "1451" | "1452")
+}
+
+/// Coarse classification of a [`DbError`], for mapping onto API responses
+/// without matching on driver-specific errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DbErrorKind {
+    /// A unique or primary key constraint was violated.
+    UniqueViolation,
+    /// A foreign key constraint was violated.
+    ForeignKeyViolation,
+    /// The requested row does not exist.
+    NotFound,
+    /// The database could not be reached (pool exhausted/closed, I/O).
+    Unavailable,
+    /// Configuration problems and everything else.
+    Other,
+}
+
+impl DbError {
+    /// Classify this error; see [`DbErrorKind`].
+    pub fn kind(&self) -> DbErrorKind {
+        match self {
+            DbError::Sqlx(e) => sqlx_error_kind(e),
+            #[cfg(feature = "sea-orm")]
+            DbError::Sea(e) => seaorm_error_kind(e),
+            DbError::Io(_) => DbErrorKind::Unavailable,
+            _ => DbErrorKind::Other,
+        }
+    }
+}
+
+fn sqlx_error_kind(err: &sqlx::Error) -> DbErrorKind {
+    match err {
+        sqlx::Error::RowNotFound => DbErrorKind::NotFound,
+        sqlx::Error::Database(db) => match db.code() {
+            Some(code) if is_unique_violation_code(&code) => DbErrorKind::UniqueViolation,
+            Some(code) if is_foreign_key_violation_code(&code) => DbErrorKind::ForeignKeyViolation,
+            _ => DbErrorKind::Other,
+        },
+        sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => {
+            DbErrorKind::Unavailable
+        }
+        _ => DbErrorKind::Other,
+    }
+}
+
+#[cfg(feature = "sea-orm")]
+fn seaorm_error_kind(err: &sea_orm::DbErr) -> DbErrorKind {
+    use sea_orm::{DbErr, RuntimeErr, SqlErr};
+
+    match err.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => return DbErrorKind::UniqueViolation,
+        Some(SqlErr::ForeignKeyConstraintViolation(_)) => return DbErrorKind::ForeignKeyViolation,
+        _ => {}
+    }
+    match err {
+        DbErr::RecordNotFound(_) | DbErr::RecordNotUpdated => DbErrorKind::NotFound,
+        DbErr::ConnectionAcquire(_) => DbErrorKind::Unavailable,
+        DbErr::Conn(RuntimeErr::SqlxError(e))
+        | DbErr::Exec(RuntimeErr::SqlxError(e))
+        | DbErr::Query(RuntimeErr::SqlxError(e)) => sqlx_error_kind(e),
+        _ => DbErrorKind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_common_errors() {
+        assert_eq!(
+            DbError::Sqlx(sqlx::Error::RowNotFound).kind(),
+            DbErrorKind::NotFound
+        );
+        assert_eq!(
+            DbError::Sqlx(sqlx::Error::PoolTimedOut).kind(),
+            DbErrorKind::Unavailable
+        );
+        assert_eq!(
+            DbError::InvalidConfig("x".into()).kind(),
+            DbErrorKind::Other
+        );
+        #[cfg(feature = "sea-orm")]
+        assert_eq!(
+            DbError::Sea(sea_orm::DbErr::RecordNotFound("user".into())).kind(),
+            DbErrorKind::NotFound
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn classifies_sqlite_unique_violation() -> crate::Result<()> {
+        let db = crate::DbHandle::connect("sqlite::memory:", crate::ConnectOpts::default()).await?;
+        let pool = db.sqlx_sqlite().unwrap();
+        sqlx::query("CREATE TABLE users (email TEXT UNIQUE)")
+            .execute(pool)
+            .await?;
+        let insert =
+            || sqlx::query("INSERT INTO users (email) VALUES ('a@example.com')").execute(pool);
+        insert().await?;
+
+        let err = DbError::from(insert().await.unwrap_err());
+        assert_eq!(err.kind(), DbErrorKind::UniqueViolation);
+        Ok(())
+    }
+}
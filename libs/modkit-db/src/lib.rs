@@ -60,7 +60,9 @@
 //! let home_dir = PathBuf::from("/app/data");
 //! let db_manager = Arc::new(DbManager::from_figment(figment, home_dir).unwrap());
 //!
-//! // Use in runtime with DbOptions::Manager(db_manager)
+//! // Use in runtime with DbOptions::Manager(db_manager), or with
+//! // DbOptions::ManagerWithFallback { manager, fallback } to give modules
+//! // without a `database` section one shared handle.
 //! // Modules can then use: ctx.db_required_async().await?
 //! ```
 
@@ -70,6 +72,7 @@ pub use advisory_locks::{DbLockGuard, LockConfig};
 // Core modules
 pub mod advisory_locks;
 pub mod config;
+pub mod errors;
 pub mod manager;
 pub mod odata;
 pub mod options;
@@ -81,7 +84,8 @@ mod sqlite;
 
 // Re-export important types from new modules
 pub use config::{DbConnConfig, GlobalDatabaseConfig, PoolCfg};
-pub use manager::DbManager;
+pub use errors::DbErrorKind;
+pub use manager::{module_data_dir, DbManager};
 pub use options::{
     build_db_handle, redact_credentials_in_dsn, ConnectionOptionsError, DbConnectOptions,
 };
@@ -427,6 +431,20 @@ impl DbHandle {
         }
     }
 
+    /// Cheap connectivity check: acquire a pooled connection and ping it.
+    pub async fn ping(&self) -> Result<()> {
+        use sqlx::Connection;
+        match &self.pool {
+            #[cfg(feature = "pg")]
+            DbPool::Postgres(p) => p.acquire().await?.ping().await?,
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(p) => p.acquire().await?.ping().await?,
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(p) => p.acquire().await?.ping().await?,
+        }
+        Ok(())
+    }
+
     /// Get the backend.
     pub fn engine(&self) -> DbEngine {
         self.engine
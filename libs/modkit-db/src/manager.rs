@@ -12,6 +12,21 @@ use dashmap::DashMap;
 use figment::Figment;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// `<home_dir>/<module>`, created (owner-only on Unix) if it does not exist yet.
+///
+/// Relative SQLite paths of the module live here; `ModuleCtx::data_dir` hands
+/// the same directory to the module itself.
+pub fn module_data_dir(home_dir: &Path, module: &str) -> std::io::Result<PathBuf> {
+    let dir = home_dir.join(module);
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(&dir)?;
+    Ok(dir)
+}
 
 /// Central database manager that handles per-module database connections.
 pub struct DbManager {
@@ -23,6 +38,8 @@ pub struct DbManager {
     home_dir: PathBuf,
     /// Cache of database handles per module
     cache: DashMap<String, Arc<DbHandle>>,
+    /// How long building each cached handle took
+    connect_times: DashMap<String, Duration>,
 }
 
 impl DbManager {
@@ -42,6 +59,7 @@ impl DbManager {
             figment,
             home_dir,
             cache: DashMap::new(),
+            connect_times: DashMap::new(),
         })
     }
 
@@ -54,7 +72,11 @@ impl DbManager {
         }
 
         // Build new handle
+        let started = Instant::now();
         if let Some(handle) = self.build_for_module(module).await? {
+            self.connect_times
+                .entry(module.to_string())
+                .or_insert_with(|| started.elapsed());
             // Use entry API to handle race conditions properly
             match self.cache.entry(module.to_string()) {
                 dashmap::mapref::entry::Entry::Occupied(entry) => {
@@ -72,6 +94,36 @@ impl DbManager {
         }
     }
 
+    /// Base directory of the per-module data directories.
+    pub fn home_dir(&self) -> &Path {
+        &self.home_dir
+    }
+
+    /// Time taken to open each connection so far, keyed by module name.
+    pub fn connect_times(&self) -> Vec<(String, Duration)> {
+        let mut out: Vec<_> = self
+            .connect_times
+            .iter()
+            .map(|e| (e.key().clone(), *e.value()))
+            .collect();
+        out.sort();
+        out
+    }
+
+    /// Ping every connection opened so far, keyed by module name.
+    pub async fn health(&self) -> Vec<(String, Result<()>)> {
+        let handles: Vec<(String, Arc<DbHandle>)> = self
+            .cache
+            .iter()
+            .map(|e| (e.key().clone(), e.value().clone()))
+            .collect();
+        let mut out = Vec::with_capacity(handles.len());
+        for (module, handle) in handles {
+            out.push((module, handle.ping().await));
+        }
+        out
+    }
+
     /// Build a database handle for the specified module.
     async fn build_for_module(&self, module: &str) -> Result<Option<Arc<DbHandle>>> {
         // Read module database configuration from Figment
@@ -114,8 +166,7 @@ impl DbManager {
         }
 
         // Finalize SQLite paths if needed
-        let module_home_dir = self.home_dir.join(module);
-        cfg = self.finalize_sqlite_paths(cfg, &module_home_dir)?;
+        cfg = self.finalize_sqlite_paths(cfg, module)?;
 
         // Build the database handle
         let handle = build_db_handle(cfg, self.global.as_ref()).await?;
@@ -185,12 +236,11 @@ impl DbManager {
         module_cfg
     }
 
-    /// Finalize SQLite paths by resolving relative file paths to absolute paths.
-    fn finalize_sqlite_paths(
-        &self,
-        mut cfg: DbConnConfig,
-        module_home: &Path,
-    ) -> Result<DbConnConfig> {
+    /// Finalize SQLite paths by resolving relative file paths against the
+    /// module's data directory.
+    fn finalize_sqlite_paths(&self, mut cfg: DbConnConfig, module: &str) -> Result<DbConnConfig> {
+        let module_home = self.home_dir.join(module);
+
         // If file is specified, convert to absolute path under module home
         if let Some(file) = &cfg.file {
             let absolute_path = module_home.join(file);
@@ -203,7 +253,8 @@ impl DbManager {
                 .unwrap_or(true); // Default to true for backward compatibility
 
             if auto_provision {
-                // Create all necessary directories
+                // Create the data directory, then any subdirectories the file names
+                module_data_dir(&self.home_dir, module).map_err(DbError::Io)?;
                 if let Some(parent) = absolute_path.parent() {
                     std::fs::create_dir_all(parent).map_err(DbError::Io)?;
                 }
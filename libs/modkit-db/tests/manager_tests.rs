@@ -1,7 +1,9 @@
 //! Tests for DbManager functionality.
 
 use figment::{providers::Serialized, Figment};
-use modkit_db::{DbConnConfig, DbEngine, DbManager, GlobalDatabaseConfig, PoolCfg};
+use modkit_db::{
+    module_data_dir, DbConnConfig, DbEngine, DbManager, GlobalDatabaseConfig, PoolCfg,
+};
 use std::collections::HashMap;
 use std::time::Duration;
 use tempfile::TempDir;
@@ -71,6 +73,26 @@ async fn test_dbmanager_sqlite_with_file() {
     assert_eq!(db_handle.engine(), DbEngine::Sqlite);
 }
 
+#[tokio::test]
+async fn test_dbmanager_sqlite_file_lives_in_module_data_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
+        "modules": { "notes": { "database": { "file": "db/notes.db" } } }
+    })));
+    let manager = DbManager::from_figment(figment, temp_dir.path().to_path_buf()).unwrap();
+
+    assert!(manager.get("notes").await.unwrap().is_some());
+    let data_dir = module_data_dir(manager.home_dir(), "notes").unwrap();
+    assert_eq!(data_dir, temp_dir.path().join("notes"));
+    assert!(data_dir.join("db/notes.db").exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&data_dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
+
 #[tokio::test]
 async fn test_dbmanager_sqlite_with_path() {
     let temp_dir = TempDir::new().unwrap();
@@ -308,3 +330,46 @@ async fn test_dbmanager_sqlite_server_without_dsn() {
         module_dir
     );
 }
+
+#[tokio::test]
+async fn test_dbmanager_health_pings_open_handles() {
+    let temp_dir = TempDir::new().unwrap();
+    let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
+        "modules": {
+            "health_module": {
+                "database": {
+                    "file": "health.db"
+                }
+            }
+        }
+    })));
+
+    let manager = DbManager::from_figment(figment, temp_dir.path().to_path_buf()).unwrap();
+    assert!(manager.health().await.is_empty());
+
+    manager.get("health_module").await.unwrap().unwrap();
+    let health = manager.health().await;
+    assert_eq!(health.len(), 1);
+    assert_eq!(health[0].0, "health_module");
+    assert!(health[0].1.is_ok());
+}
+
+#[tokio::test]
+async fn test_dbmanager_records_connect_times() {
+    let temp_dir = TempDir::new().unwrap();
+    let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
+        "modules": {
+            "timed_module": { "database": { "dsn": "sqlite::memory:" } },
+            "plain_module": { "config": {} }
+        }
+    })));
+
+    let manager = DbManager::from_figment(figment, temp_dir.path().to_path_buf()).unwrap();
+    manager.get("timed_module").await.unwrap().unwrap();
+    manager.get("timed_module").await.unwrap().unwrap();
+    assert!(manager.get("plain_module").await.unwrap().is_none());
+
+    let times = manager.connect_times();
+    assert_eq!(times.len(), 1);
+    assert_eq!(times[0].0, "timed_module");
+}
@@ -91,19 +91,20 @@ async fn test_precedence_module_dsn_override_server() {
         auto_provision: Some(false),
     };
 
+    let temp_dir = TempDir::new().unwrap();
+    let module_db = temp_dir.path().join("module_override.db");
     let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
         "database": global_config,
         "modules": {
             "test_module": {
                 "database": {
                     "server": "sqlite_server",
-                    "dsn": format!("sqlite:file:module_{}.db?synchronous=NORMAL", std::process::id())  // Should completely override server DSN
+                    "dsn": format!("sqlite:file:{}?synchronous=NORMAL", module_db.display())  // Should completely override server DSN
                 }
             }
         }
     })));
 
-    let temp_dir = TempDir::new().unwrap();
     let manager = DbManager::from_figment(figment, temp_dir.path().to_path_buf()).unwrap();
 
     let result = manager.get("test_module").await;
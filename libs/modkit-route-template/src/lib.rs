@@ -0,0 +1,87 @@
+//! Parsing of axum route templates (`/users/{id}/{*rest}`).
+//!
+//! Shared by `modkit::api::path_template_params` (runtime) and the
+//! `modkit_macros::route!` macro (compile time) so both apply the same rules.
+
+/// Malformed route template
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum TemplateError {
+    #[error("path must start with '/'")]
+    NotAbsolute,
+    #[error("segment '{0}' must be a single '{{name}}' parameter")]
+    MalformedSegment(String),
+    #[error("invalid parameter name '{0}'")]
+    InvalidName(String),
+    #[error("wildcard '{{*{0}}}' must be the last segment")]
+    WildcardNotLast(String),
+    #[error("duplicate parameter '{0}'")]
+    Duplicate(String),
+}
+
+/// Parameter names of a route template (`/users/{id}/{*rest}` → `id`, `rest`).
+pub fn params(path: &str) -> Result<Vec<String>, TemplateError> {
+    if !path.starts_with('/') {
+        return Err(TemplateError::NotAbsolute);
+    }
+    let segments: Vec<&str> = path.split('/').skip(1).collect();
+    let mut params: Vec<String> = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        if !segment.contains(['{', '}']) {
+            continue;
+        }
+        let Some(inner) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+            return Err(TemplateError::MalformedSegment(segment.to_string()));
+        };
+        let (name, wildcard) = match inner.strip_prefix('*') {
+            Some(name) => (name, true),
+            None => (inner, false),
+        };
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(TemplateError::InvalidName(name.to_string()));
+        }
+        if wildcard && i + 1 != segments.len() {
+            return Err(TemplateError::WildcardNotLast(name.to_string()));
+        }
+        if params.iter().any(|p| p == name) {
+            return Err(TemplateError::Duplicate(name.to_string()));
+        }
+        params.push(name.to_string());
+    }
+    Ok(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_params_and_rejects_malformed_templates() {
+        assert_eq!(params("/users").unwrap(), Vec::<String>::new());
+        assert_eq!(
+            params("/orgs/{org}/files/{*path}").unwrap(),
+            ["org", "path"]
+        );
+        assert_eq!(params("users/{id}"), Err(TemplateError::NotAbsolute));
+        assert_eq!(
+            params("/a/{id}x"),
+            Err(TemplateError::MalformedSegment("{id}x".into()))
+        );
+        assert_eq!(
+            params("/a/{1id}"),
+            Err(TemplateError::InvalidName("1id".into()))
+        );
+        assert_eq!(
+            params("/{*rest}/x"),
+            Err(TemplateError::WildcardNotLast("rest".into()))
+        );
+        assert_eq!(
+            params("/a/{id}/b/{id}"),
+            Err(TemplateError::Duplicate("id".into()))
+        );
+    }
+}
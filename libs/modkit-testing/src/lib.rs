@@ -0,0 +1,71 @@
+//! Test helpers for ModKit modules.
+//!
+//! [`HubFixture`] builds a [`ClientHub`] pre-filled with the clients a test
+//! needs, and [`module_ctx`] hands a module the context the registry would.
+//! To keep the real wiring and swap only one dependency, pass the hub to
+//! `RunOptions::client_hub` and shadow that client with
+//! [`ClientHub::scoped_override`]:
+//!
+//! ```rust,ignore
+//! let hub = HubFixture::new().build();
+//! let _mock = hub.scoped_override::<dyn BillingApi>(Arc::new(FakeBilling::default()));
+//! let opts = RunOptions::builder().client_hub(hub.clone()).build();
+//! ```
+
+use std::sync::Arc;
+
+use modkit::client_hub::ClientHub;
+use modkit::context::{ModuleCtx, ModuleCtxBuilder};
+use tokio_util::sync::CancellationToken;
+
+/// Builder for a [`ClientHub`] holding fixture clients.
+#[derive(Default)]
+pub struct HubFixture {
+    hub: ClientHub,
+}
+
+impl HubFixture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `client` as the global client of `T`.
+    pub fn with<T>(self, client: Arc<T>) -> Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.hub.register::<T>(client);
+        self
+    }
+
+    /// Register `client` for `T` in `scope`.
+    pub fn with_scoped<T>(self, scope: &str, client: Arc<T>) -> Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.hub.register_scoped::<T>(scope, client);
+        self
+    }
+
+    /// Register `client` for `T` under `name`.
+    pub fn with_named<T>(self, name: &str, client: Arc<T>) -> Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.hub.register_named::<T>(name, client);
+        self
+    }
+
+    pub fn build(self) -> Arc<ClientHub> {
+        Arc::new(self.hub)
+    }
+}
+
+/// Context for calling `init` of `module` by hand, backed by `hub`, with no
+/// config, no database and a fresh cancellation token.
+pub fn module_ctx(module: &str, hub: Arc<ClientHub>) -> ModuleCtx {
+    ModuleCtxBuilder::new(CancellationToken::new())
+        .with_client_hub(hub)
+        .for_module(module)
+        .build()
+}
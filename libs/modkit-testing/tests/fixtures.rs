@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use modkit::client_hub::ClientHubError;
+use modkit_testing::{module_ctx, HubFixture};
+
+#[async_trait::async_trait]
+trait Greeter: Send + Sync {
+    async fn greet(&self) -> String;
+}
+
+struct Fixed(&'static str);
+
+#[async_trait::async_trait]
+impl Greeter for Fixed {
+    async fn greet(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[tokio::test]
+async fn fixture_hub_serves_registered_clients() -> anyhow::Result<()> {
+    let hub = HubFixture::new()
+        .with::<dyn Greeter>(Arc::new(Fixed("hello")))
+        .with_scoped::<dyn Greeter>("tenant-1", Arc::new(Fixed("tenant")))
+        .with_named::<dyn Greeter>("loud", Arc::new(Fixed("HELLO")))
+        .build();
+    let ctx = module_ctx("consumer", hub);
+
+    assert_eq!(ctx.client::<dyn Greeter>()?.greet().await, "hello");
+    assert_eq!(
+        ctx.client_scoped::<dyn Greeter>("tenant-1")?.greet().await,
+        "tenant"
+    );
+    assert_eq!(
+        ctx.client_named::<dyn Greeter>("loud")?.greet().await,
+        "HELLO"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn override_replaces_one_client_for_the_test() -> anyhow::Result<()> {
+    let hub = HubFixture::new()
+        .with::<dyn Greeter>(Arc::new(Fixed("real")))
+        .build();
+    let ctx = module_ctx("consumer", hub.clone());
+    {
+        let _mock = hub.scoped_override::<dyn Greeter>(Arc::new(Fixed("mock")));
+        assert_eq!(ctx.client::<dyn Greeter>()?.greet().await, "mock");
+    }
+    assert_eq!(ctx.client::<dyn Greeter>()?.greet().await, "real");
+    Ok(())
+}
+
+#[test]
+fn misses_name_the_module_under_test() {
+    let ctx = module_ctx("consumer", HubFixture::new().build());
+    let Err(ClientHubError::NotFound(missing)) = ctx.client::<dyn Greeter>() else {
+        panic!("expected a miss");
+    };
+    assert_eq!(missing.requested_by.as_deref(), Some("consumer"));
+}
@@ -0,0 +1,156 @@
+//! Host for ModKit modules shipped as WASM components.
+//!
+//! A plugin is a component implementing the `module` world of
+//! `wit/plugin.wit`, next to a YAML [`PluginManifest`] naming it and listing
+//! its deps, capabilities and routes. [`PluginHost`] compiles and instantiates
+//! every plugin of a directory at startup and, as a
+//! [`ModuleSource`](modkit::registry::ModuleSource), registers each one as a
+//! regular module: `init` receives the module's `config` section as JSON, and
+//! every manifest route is mounted through `OperationBuilder` (so it shows up
+//! in OpenAPI) and forwarded to the component's `handle` export.
+//!
+//! ```rust,ignore
+//! let plugins = PluginHost::load_dir("plugins")?;
+//! let opts = RunOptions {
+//!     module_sources: vec![Arc::new(plugins)],
+//!     ..
+//! };
+//! ```
+//!
+//! Plugins get no host imports (no WASI, no DB handles): each call sees only
+//! its arguments. Calls into one plugin are serialized and bounded by
+//! [`PluginLimits`]; a call that traps or runs out of fuel fails with `500`,
+//! and the plugin is re-instantiated (and re-initialized) for the next one.
+
+pub mod manifest;
+mod module;
+
+pub use manifest::{PluginManifest, RouteDecl};
+pub use module::{WasmModule, MAX_BODY_BYTES};
+
+use modkit::registry::{ModuleSource, RegistryBuilder};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use wasmtime::Engine;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    #[error("cannot set up the WASM engine")]
+    Engine(#[source] anyhow::Error),
+    #[error("cannot read plugin directory {}", path.display())]
+    Dir {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("invalid plugin manifest {}: {message}", path.display())]
+    Manifest { path: PathBuf, message: String },
+    #[error("cannot load component of plugin '{plugin}'")]
+    Component {
+        plugin: String,
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+/// Resources a single plugin instance may use.
+#[derive(Debug, Clone)]
+pub struct PluginLimits {
+    /// Fuel for each `init`/`handle` call; roughly one unit per executed
+    /// instruction. A call that runs out traps.
+    pub fuel_per_call: u64,
+    /// Cap on the instance's linear memory; `memory.grow` beyond it fails.
+    pub max_memory_bytes: usize,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self {
+            fuel_per_call: 1_000_000_000,
+            max_memory_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+/// Loaded plugins, sharing one compilation engine.
+pub struct PluginHost {
+    engine: Engine,
+    limits: PluginLimits,
+    plugins: Vec<Arc<WasmModule>>,
+}
+
+impl PluginHost {
+    pub fn new() -> Result<Self, PluginError> {
+        Self::with_limits(PluginLimits::default())
+    }
+
+    pub fn with_limits(limits: PluginLimits) -> Result<Self, PluginError> {
+        let mut config = wasmtime::Config::new();
+        config.wasm_component_model(true);
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(PluginError::Engine)?;
+        Ok(Self {
+            engine,
+            limits,
+            plugins: Vec::new(),
+        })
+    }
+
+    /// Load every `*.yaml`/`*.yml` manifest in `dir`, in file name order.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, PluginError> {
+        Self::load_dir_with_limits(dir, PluginLimits::default())
+    }
+
+    /// [`load_dir`](Self::load_dir) with non-default [`PluginLimits`].
+    pub fn load_dir_with_limits(
+        dir: impl AsRef<Path>,
+        limits: PluginLimits,
+    ) -> Result<Self, PluginError> {
+        let dir = dir.as_ref();
+        let dir_err = |source| PluginError::Dir {
+            path: dir.to_path_buf(),
+            source,
+        };
+        let mut manifests = Vec::new();
+        for entry in std::fs::read_dir(dir).map_err(dir_err)? {
+            let path = entry.map_err(dir_err)?.path();
+            if matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("yaml" | "yml")
+            ) {
+                manifests.push(path);
+            }
+        }
+        manifests.sort();
+
+        let mut host = Self::with_limits(limits)?;
+        for path in manifests {
+            host.load(&path)?;
+        }
+        Ok(host)
+    }
+
+    /// Load one plugin from its manifest.
+    pub fn load(&mut self, manifest_path: &Path) -> Result<&Arc<WasmModule>, PluginError> {
+        let manifest = PluginManifest::load(manifest_path)?;
+        let module = WasmModule::instantiate(&self.engine, manifest, &self.limits)?;
+        tracing::info!(plugin = module.name(), "Loaded WASM plugin");
+        self.plugins.push(Arc::new(module));
+        Ok(self.plugins.last().expect("just pushed"))
+    }
+
+    pub fn plugins(&self) -> &[Arc<WasmModule>] {
+        &self.plugins
+    }
+}
+
+impl ModuleSource for PluginHost {
+    fn register(&self, b: &mut RegistryBuilder) {
+        for plugin in &self.plugins {
+            b.register_core_with_meta(plugin.name(), plugin.deps(), plugin.clone());
+            if plugin.manifest().has_rest() {
+                b.register_rest_with_meta(plugin.name(), plugin.clone());
+            }
+        }
+    }
+}
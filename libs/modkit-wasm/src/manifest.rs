@@ -0,0 +1,170 @@
+//! Plugin manifests: what a component is called, what it depends on and
+//! which routes it serves.
+
+use crate::PluginError;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Capabilities a plugin may declare. Other module capabilities need host
+/// access (DB handles, lifecycle tasks) that the component boundary does not offer.
+pub const SUPPORTED_CAPABILITIES: &[&str] = &["rest"];
+
+/// A `<name>.yaml` manifest describing one plugin.
+///
+/// ```yaml
+/// name: greeter
+/// component: greeter.wasm   # relative to the manifest
+/// deps: [users_info]
+/// capabilities: [rest]
+/// routes:
+///   - method: GET
+///     path: /greeter/hello/{name}
+///     operation_id: greeter.hello
+///     summary: Greet someone
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginManifest {
+    pub name: String,
+    pub component: PathBuf,
+    /// Same syntax as `#[module(deps = ...)]`, including optional `name?` deps.
+    #[serde(default)]
+    pub deps: Vec<String>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub routes: Vec<RouteDecl>,
+}
+
+/// One REST operation served by the plugin's `handle` export.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteDecl {
+    pub method: String,
+    pub path: String,
+    /// Passed to `handle` so the plugin knows which route was hit.
+    pub operation_id: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl PluginManifest {
+    /// Read and validate a manifest; `component` is resolved against its directory.
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let manifest_err = |message: String| PluginError::Manifest {
+            path: path.to_path_buf(),
+            message,
+        };
+        let text = std::fs::read_to_string(path).map_err(|e| manifest_err(e.to_string()))?;
+        let mut manifest: Self =
+            serde_yaml::from_str(&text).map_err(|e| manifest_err(e.to_string()))?;
+        manifest.validate().map_err(manifest_err)?;
+        if let Some(dir) = path.parent() {
+            manifest.component = dir.join(&manifest.component);
+        }
+        Ok(manifest)
+    }
+
+    pub fn has_rest(&self) -> bool {
+        self.capabilities.iter().any(|c| c == "rest")
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err("name must not be empty".into());
+        }
+        if let Some(other) = self
+            .capabilities
+            .iter()
+            .find(|c| !SUPPORTED_CAPABILITIES.contains(&c.as_str()))
+        {
+            return Err(format!(
+                "capability '{other}' is not supported for plugins, expected one of: {}",
+                SUPPORTED_CAPABILITIES.join(", ")
+            ));
+        }
+        match (self.has_rest(), self.routes.is_empty()) {
+            (true, true) => return Err("capability 'rest' requires at least one route".into()),
+            (false, false) => return Err("routes require the 'rest' capability".into()),
+            _ => {}
+        }
+        for route in &self.routes {
+            route.method()?;
+            modkit::api::path_template_params(&route.path)
+                .map_err(|e| format!("route '{}': {e}", route.path))?;
+        }
+        Ok(())
+    }
+}
+
+impl RouteDecl {
+    pub fn method(&self) -> Result<http::Method, String> {
+        match self.method.to_ascii_uppercase().as_str() {
+            "GET" => Ok(http::Method::GET),
+            "POST" => Ok(http::Method::POST),
+            "PUT" => Ok(http::Method::PUT),
+            "DELETE" => Ok(http::Method::DELETE),
+            "PATCH" => Ok(http::Method::PATCH),
+            other => Err(format!(
+                "route '{}': unsupported method '{other}'",
+                self.path
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> Result<PluginManifest, String> {
+        let manifest: PluginManifest = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+        manifest.validate().map(|_| manifest)
+    }
+
+    #[test]
+    fn rest_plugin_manifest_is_accepted() {
+        let m = parse(
+            r#"
+name: greeter
+component: greeter.wasm
+deps: ["cache?"]
+capabilities: [rest]
+routes:
+  - { method: get, path: "/greeter/{name}", operation_id: greeter.hello }
+"#,
+        )
+        .unwrap();
+        assert!(m.has_rest());
+        assert_eq!(m.routes[0].method(), Ok(http::Method::GET));
+    }
+
+    #[test]
+    fn unsupported_capability_is_rejected() {
+        let err = parse("{ name: x, component: x.wasm, capabilities: [db] }").unwrap_err();
+        assert!(err.contains("capability 'db' is not supported"), "{err}");
+    }
+
+    #[test]
+    fn routes_and_rest_capability_go_together() {
+        let err = parse("{ name: x, component: x.wasm, capabilities: [rest] }").unwrap_err();
+        assert!(err.contains("requires at least one route"), "{err}");
+
+        let err = parse(
+            "{ name: x, component: x.wasm, routes: [{ method: GET, path: /x, operation_id: x }] }",
+        )
+        .unwrap_err();
+        assert!(err.contains("require the 'rest' capability"), "{err}");
+    }
+
+    #[test]
+    fn malformed_route_path_is_rejected() {
+        let err = parse(
+            "{ name: x, component: x.wasm, capabilities: [rest], routes: [{ method: GET, path: '/x/{id', operation_id: x }] }",
+        )
+        .unwrap_err();
+        assert!(err.contains("route '/x/{id'"), "{err}");
+    }
+}
@@ -0,0 +1,303 @@
+//! A loaded plugin, exposed to the registry as an ordinary module.
+
+use crate::manifest::PluginManifest;
+use crate::{PluginError, PluginLimits};
+use async_trait::async_trait;
+use axum::response::{IntoResponse, Response as HttpResponse};
+use axum::Router;
+use http::{HeaderName, HeaderValue, StatusCode};
+use modkit::api::OperationBuilder;
+use modkit::context::ModuleCtx;
+use modkit::contracts::{Module, OpenApiRegistry, RestfulModule};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use wasmtime::component::{Component, ComponentType, InstancePre, Lift, Linker, Lower, TypedFunc};
+use wasmtime::{Engine, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Largest request body forwarded to a plugin.
+pub const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// `request` record of `wit/plugin.wit`.
+#[derive(ComponentType, Lower, Debug)]
+#[component(record)]
+struct Request {
+    method: String,
+    path: String,
+    query: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+/// `response` record of `wit/plugin.wit`.
+#[derive(ComponentType, Lift, Debug)]
+#[component(record)]
+struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+/// A plugin's component instance. Calls are serialized and each one runs on
+/// a fresh fuel budget. A trap (including running out of fuel) leaves an
+/// instance unusable, so it is dropped and the next call gets a new one,
+/// initialized again with the last accepted config.
+struct Instance {
+    pre: InstancePre<StoreLimits>,
+    limits: PluginLimits,
+    /// Config of the last successful `init`.
+    config: Option<String>,
+    live: Option<Live>,
+}
+
+struct Live {
+    store: Store<StoreLimits>,
+    init: TypedFunc<(String,), (Result<(), String>,)>,
+    handle: TypedFunc<(String, Request), (Response,)>,
+}
+
+impl Live {
+    fn new(pre: &InstancePre<StoreLimits>, limits: &PluginLimits) -> anyhow::Result<Self> {
+        let store_limits = StoreLimitsBuilder::new()
+            .memory_size(limits.max_memory_bytes)
+            .build();
+        let mut store = Store::new(pre.engine(), store_limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(limits.fuel_per_call)?;
+        let instance = pre.instantiate(&mut store)?;
+        let init = instance.get_typed_func(&mut store, "init")?;
+        let handle = instance.get_typed_func(&mut store, "handle")?;
+        Ok(Self {
+            store,
+            init,
+            handle,
+        })
+    }
+
+    fn init(&mut self, config: String) -> anyhow::Result<Result<(), String>> {
+        let (result,) = self.init.call(&mut self.store, (config,))?;
+        self.init.post_return(&mut self.store)?;
+        Ok(result)
+    }
+
+    fn handle(&mut self, operation_id: String, request: Request) -> anyhow::Result<Response> {
+        let (response,) = self.handle.call(&mut self.store, (operation_id, request))?;
+        self.handle.post_return(&mut self.store)?;
+        Ok(response)
+    }
+}
+
+impl Instance {
+    fn init(&mut self, config: String) -> anyhow::Result<Result<(), String>> {
+        let result = self.call(|live| live.init(config.clone()))?;
+        if result.is_ok() {
+            self.config = Some(config);
+        }
+        Ok(result)
+    }
+
+    fn handle(&mut self, operation_id: String, request: Request) -> anyhow::Result<Response> {
+        self.call(|live| live.handle(operation_id, request))
+    }
+
+    fn call<R>(&mut self, f: impl FnOnce(&mut Live) -> anyhow::Result<R>) -> anyhow::Result<R> {
+        let fuel = self.limits.fuel_per_call;
+        let result = self.live().and_then(|live| {
+            live.store.set_fuel(fuel)?;
+            f(live)
+        });
+        if result.is_err() {
+            self.live = None;
+        }
+        result
+    }
+
+    fn live(&mut self) -> anyhow::Result<&mut Live> {
+        if self.live.is_none() {
+            tracing::warn!("Re-instantiating WASM plugin after a failed call");
+            let mut live = Live::new(&self.pre, &self.limits)?;
+            if let Some(config) = &self.config {
+                live.init(config.clone())?
+                    .map_err(|message| anyhow::anyhow!("plugin init failed: {message}"))?;
+            }
+            self.live = Some(live);
+        }
+        Ok(self.live.as_mut().expect("just instantiated"))
+    }
+}
+
+/// Bridges `Module` (and `RestfulModule` for `rest` plugins) to the
+/// component's `init` and `handle` exports.
+pub struct WasmModule {
+    manifest: PluginManifest,
+    name: &'static str,
+    deps: &'static [&'static str],
+    instance: Arc<Mutex<Instance>>,
+}
+
+impl WasmModule {
+    pub(crate) fn instantiate(
+        engine: &Engine,
+        manifest: PluginManifest,
+        limits: &PluginLimits,
+    ) -> Result<Self, PluginError> {
+        let component_err = |source| PluginError::Component {
+            plugin: manifest.name.clone(),
+            source,
+        };
+        let component = Component::from_file(engine, &manifest.component).map_err(component_err)?;
+        // Plugins get no host imports: everything they need arrives as arguments.
+        let pre = Linker::<StoreLimits>::new(engine)
+            .instantiate_pre(&component)
+            .map_err(component_err)?;
+        let live = Live::new(&pre, limits).map_err(component_err)?;
+
+        // The registry keys modules by `&'static str`; plugins are loaded once per process.
+        let name: &'static str = Box::leak(manifest.name.clone().into_boxed_str());
+        let deps: Vec<&'static str> = manifest
+            .deps
+            .iter()
+            .map(|d| &*Box::leak(d.clone().into_boxed_str()))
+            .collect();
+
+        Ok(Self {
+            name,
+            deps: Box::leak(deps.into_boxed_slice()),
+            manifest,
+            instance: Arc::new(Mutex::new(Instance {
+                pre,
+                limits: limits.clone(),
+                config: None,
+                live: Some(live),
+            })),
+        })
+    }
+
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn deps(&self) -> &'static [&'static str] {
+        self.deps
+    }
+}
+
+#[async_trait]
+impl Module for WasmModule {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        let config = serde_json::to_string(ctx.raw_config())?;
+        let instance = self.instance.clone();
+        tokio::task::spawn_blocking(move || instance.lock().init(config))
+            .await??
+            .map_err(|message| anyhow::anyhow!("plugin init failed: {message}"))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[async_trait]
+impl RestfulModule for WasmModule {
+    async fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        mut router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> anyhow::Result<Router> {
+        for route in &self.manifest.routes {
+            let method = route.method().map_err(anyhow::Error::msg)?;
+            let mut op = OperationBuilder::<_, _, ()>::new(method, &route.path)
+                .operation_id(&route.operation_id)
+                .tag(self.name);
+            if let Some(summary) = &route.summary {
+                op = op.summary(summary);
+            }
+            if let Some(description) = &route.description {
+                op = op.description(description);
+            }
+            for param in modkit::api::path_template_params(&route.path)? {
+                op = op.path_param(param, "Path parameter");
+            }
+
+            let instance = self.instance.clone();
+            let operation_id = route.operation_id.clone();
+            router = op
+                .handler(move |req: axum::extract::Request| {
+                    serve(instance.clone(), operation_id.clone(), req)
+                })
+                .json_response(200, "Plugin response")
+                .register(router, openapi);
+        }
+        Ok(router)
+    }
+}
+
+async fn serve(
+    instance: Arc<Mutex<Instance>>,
+    operation_id: String,
+    req: axum::extract::Request,
+) -> HttpResponse {
+    let (parts, body) = req.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => return modkit::bad_request(e.to_string()).into_response(),
+    };
+    // Nesting under a REST prefix strips it from `uri`; plugins see the full path.
+    let uri = parts
+        .extensions
+        .get::<axum::extract::OriginalUri>()
+        .map_or(&parts.uri, |original| &original.0);
+    let request = Request {
+        method: parts.method.to_string(),
+        path: uri.path().to_string(),
+        query: uri.query().unwrap_or_default().to_string(),
+        headers: parts
+            .headers
+            .iter()
+            .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+            .collect(),
+        body: body.to_vec(),
+    };
+
+    let result = tokio::task::spawn_blocking({
+        let operation_id = operation_id.clone();
+        move || instance.lock().handle(operation_id, request)
+    })
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|r| r);
+    match result {
+        Ok(response) => into_http(response),
+        Err(e) => {
+            tracing::error!(operation_id, error = %format!("{e:#}"), "Plugin call failed");
+            modkit::internal_error("plugin call failed").into_response()
+        }
+    }
+}
+
+fn into_http(response: Response) -> HttpResponse {
+    let mut out = HttpResponse::new(axum::body::Body::from(response.body));
+    *out.status_mut() = StatusCode::from_u16(response.status).unwrap_or_else(|_| {
+        tracing::warn!(
+            status = response.status,
+            "Plugin returned an invalid status"
+        );
+        StatusCode::INTERNAL_SERVER_ERROR
+    });
+    for (name, value) in response.headers {
+        match (
+            HeaderName::try_from(name.as_str()),
+            HeaderValue::try_from(value.as_str()),
+        ) {
+            (Ok(name), Ok(value)) => {
+                out.headers_mut().append(name, value);
+            }
+            _ => tracing::warn!(header = name, "Plugin returned an invalid header"),
+        }
+    }
+    out
+}
@@ -0,0 +1,315 @@
+//! Loads a hand-written echo component (component text format) and drives it
+//! through the registry's init and REST phases.
+
+use axum::body::Body;
+use axum::Router;
+use http::{Request, StatusCode};
+use modkit::api::OperationSpec;
+use modkit::context::{ConfigProvider, ModuleCtx, ModuleCtxBuilder};
+use modkit::contracts::{Module, OpenApiRegistry, RestHostModule};
+use modkit::registry::{ModuleSource, RegistryBuilder, RegistryError};
+use modkit_wasm::{PluginError, PluginHost, PluginLimits};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+
+/// `init` fails on an empty config; `handle` answers 201 with the request
+/// path in `x-plugin-path` and the request body echoed back.
+const ECHO_COMPONENT: &str = r#"
+(component
+  (core module $m
+    (memory (export "memory") 1)
+    (global $heap (mut i32) (i32.const 4096))
+    (data (i32.const 64) "missing config")
+    (data (i32.const 96) "x-plugin-path")
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $p i32)
+      (local.set $p (i32.and (i32.add (global.get $heap) (i32.const 7)) (i32.const -8)))
+      (global.set $heap (i32.add (local.get $p) (local.get 3)))
+      (local.get $p))
+    (func (export "init") (param $ptr i32) (param $len i32) (result i32)
+      (if (i32.eq (local.get $len) (i32.const 2))
+        (then
+          (i32.store8 (i32.const 0) (i32.const 1))
+          (i32.store (i32.const 4) (i32.const 64))
+          (i32.store (i32.const 8) (i32.const 14)))
+        (else (i32.store8 (i32.const 0) (i32.const 0))))
+      (i32.const 0))
+    ;; operation-id, method, path, query, headers, body: (ptr, len) each
+    (func (export "handle") (param i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)
+      (i32.store (i32.const 128) (i32.const 96))
+      (i32.store (i32.const 132) (i32.const 13))
+      (i32.store (i32.const 136) (local.get 4))
+      (i32.store (i32.const 140) (local.get 5))
+      (i32.store16 (i32.const 32) (i32.const 201))
+      (i32.store (i32.const 36) (i32.const 128))
+      (i32.store (i32.const 40) (i32.const 1))
+      (i32.store (i32.const 44) (local.get 10))
+      (i32.store (i32.const 48) (local.get 11))
+      (i32.const 32)))
+  (core instance $i (instantiate $m))
+  (type $request' (record
+    (field "method" string)
+    (field "path" string)
+    (field "query" string)
+    (field "headers" (list (tuple string string)))
+    (field "body" (list u8))))
+  (export $request "request" (type $request'))
+  (type $response' (record
+    (field "status" u16)
+    (field "headers" (list (tuple string string)))
+    (field "body" (list u8))))
+  (export $response "response" (type $response'))
+  (func $init (param "config" string) (result (result (error string)))
+    (canon lift (core func $i "init") (memory $i "memory") (realloc (func $i "realloc"))))
+  (export "init" (func $init))
+  (func $handle (param "operation-id" string) (param "request" $request) (result $response)
+    (canon lift (core func $i "handle") (memory $i "memory") (realloc (func $i "realloc"))))
+  (export "handle" (func $handle)))
+"#;
+
+const ECHO_MANIFEST: &str = r#"
+name: echo
+component: echo.wat
+capabilities: [rest]
+routes:
+  - method: POST
+    path: /echo/{channel}
+    operation_id: echo.post
+    summary: Echo the body
+"#;
+
+/// The echo plugin, except that `/faulty/trap` hits `unreachable` and
+/// `/faulty/forever` never returns.
+fn faulty_component() -> String {
+    let handle = "(param i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)\n";
+    assert!(ECHO_COMPONENT.contains(handle));
+    ECHO_COMPONENT.replace(
+        handle,
+        &format!(
+            "{handle}      (if (i32.eq (local.get 5) (i32.const 12)) (then unreachable))
+      (if (i32.eq (local.get 5) (i32.const 15)) (then (loop $spin (br $spin))))\n"
+        ),
+    )
+}
+
+const FAULTY_MANIFEST: &str = r#"
+name: faulty
+component: faulty.wat
+capabilities: [rest]
+routes:
+  - method: POST
+    path: /faulty/{mode}
+    operation_id: faulty.post
+"#;
+
+#[derive(Default)]
+struct Specs(Mutex<Vec<OperationSpec>>);
+
+impl OpenApiRegistry for Specs {
+    fn register_operation(&self, spec: &OperationSpec) {
+        self.0.lock().unwrap().push(spec.clone());
+    }
+    fn ensure_schema_raw(
+        &self,
+        name: &str,
+        _schemas: Vec<(
+            String,
+            utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+        )>,
+    ) -> String {
+        name.to_string()
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[derive(Default)]
+struct Host(Specs);
+
+#[async_trait::async_trait]
+impl Module for Host {
+    async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+        Ok(())
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl RestHostModule for Host {
+    async fn rest_prepare(&self, _ctx: &ModuleCtx, router: Router) -> anyhow::Result<Router> {
+        Ok(router)
+    }
+    async fn rest_finalize(&self, _ctx: &ModuleCtx, router: Router) -> anyhow::Result<Router> {
+        Ok(router)
+    }
+    fn as_registry(&self) -> &dyn OpenApiRegistry {
+        &self.0
+    }
+}
+
+struct Config(serde_json::Value);
+
+impl ConfigProvider for Config {
+    fn get_module_config(&self, module_name: &str) -> Option<&serde_json::Value> {
+        self.0.get(module_name)
+    }
+}
+
+fn plugin_dir() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("echo.wat"), ECHO_COMPONENT).unwrap();
+    std::fs::write(dir.path().join("echo.yaml"), ECHO_MANIFEST).unwrap();
+    dir
+}
+
+fn registry(plugins: PluginHost) -> (modkit::ModuleRegistry, Arc<Host>) {
+    let host = Arc::new(Host::default());
+    let mut b = RegistryBuilder::default();
+    b.register_core_with_meta("host", &[], host.clone());
+    b.register_rest_host_with_meta("host", host.clone());
+    plugins.register(&mut b);
+    (b.build_topo_sorted().unwrap(), host)
+}
+
+fn ctx(config: serde_json::Value) -> ModuleCtx {
+    ModuleCtxBuilder::new(CancellationToken::new())
+        .with_config_provider(Arc::new(Config(config)))
+        .build()
+}
+
+#[tokio::test]
+async fn plugin_serves_its_manifest_routes() {
+    let dir = plugin_dir();
+    let plugins = PluginHost::load_dir(dir.path()).unwrap();
+    assert_eq!(plugins.plugins().len(), 1);
+    let (registry, host) = registry(plugins);
+
+    let ctx = ctx(serde_json::json!({ "echo": { "config": { "greeting": "hi" } } }));
+    registry.run_init_phase(&ctx).await.unwrap();
+    let router = registry.run_rest_phase(&ctx, Router::new()).await.unwrap();
+
+    let specs = host.0 .0.lock().unwrap().clone();
+    assert_eq!(specs.len(), 1);
+    assert_eq!(specs[0].operation_id.as_deref(), Some("echo.post"));
+    assert_eq!(specs[0].params[0].name, "channel");
+
+    let response = router
+        .oneshot(
+            Request::post("/echo/news")
+                .body(Body::from("ping"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()["x-plugin-path"], "/echo/news");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"ping");
+}
+
+#[tokio::test]
+async fn plugin_init_error_fails_the_phase() {
+    let dir = plugin_dir();
+    let (registry, _) = registry(PluginHost::load_dir(dir.path()).unwrap());
+
+    let err = registry
+        .run_init_phase(&ctx(serde_json::json!({})))
+        .await
+        .unwrap_err();
+    let RegistryError::Init { module, source } = err else {
+        panic!("unexpected error: {err}");
+    };
+    assert_eq!(module, "echo");
+    assert_eq!(source.to_string(), "plugin init failed: missing config");
+}
+
+#[test]
+fn component_without_the_plugin_exports_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("empty.wat"), "(component)").unwrap();
+    std::fs::write(
+        dir.path().join("empty.yaml"),
+        "{ name: empty, component: empty.wat }",
+    )
+    .unwrap();
+
+    let err = PluginHost::load_dir(dir.path()).err().unwrap();
+    assert!(
+        matches!(err, PluginError::Component { ref plugin, .. } if plugin == "empty"),
+        "{err}"
+    );
+}
+
+fn small_limits() -> PluginLimits {
+    PluginLimits {
+        fuel_per_call: 1_000_000,
+        ..PluginLimits::default()
+    }
+}
+
+async fn faulty_router() -> (Router, tempfile::TempDir) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("faulty.wat"), faulty_component()).unwrap();
+    std::fs::write(dir.path().join("faulty.yaml"), FAULTY_MANIFEST).unwrap();
+    let plugins = PluginHost::load_dir_with_limits(dir.path(), small_limits()).unwrap();
+    let (registry, _) = registry(plugins);
+
+    let ctx = ctx(serde_json::json!({ "faulty": { "config": { "greeting": "hi" } } }));
+    registry.run_init_phase(&ctx).await.unwrap();
+    let router = registry.run_rest_phase(&ctx, Router::new()).await.unwrap();
+    (router, dir)
+}
+
+async fn post(router: &Router, path: &str) -> StatusCode {
+    router
+        .clone()
+        .oneshot(Request::post(path).body(Body::from("ping")).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn looping_plugin_runs_out_of_fuel_and_recovers() {
+    let (router, _dir) = faulty_router().await;
+
+    assert_eq!(
+        post(&router, "/faulty/forever").await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+    assert_eq!(post(&router, "/faulty/ok").await, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn trapping_plugin_is_reinstantiated() {
+    let (router, _dir) = faulty_router().await;
+
+    assert_eq!(post(&router, "/faulty/ok").await, StatusCode::CREATED);
+    assert_eq!(
+        post(&router, "/faulty/trap").await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+    assert_eq!(post(&router, "/faulty/ok").await, StatusCode::CREATED);
+}
+
+#[test]
+fn memory_above_the_limit_is_rejected() {
+    let limits = PluginLimits {
+        // The echo component starts with one 64 KiB page.
+        max_memory_bytes: 1024,
+        ..PluginLimits::default()
+    };
+    let err = PluginHost::load_dir_with_limits(plugin_dir().path(), limits)
+        .err()
+        .unwrap();
+    assert!(
+        matches!(err, PluginError::Component { ref plugin, .. } if plugin == "echo"),
+        "{err}"
+    );
+}
@@ -15,7 +15,20 @@ struct ModuleConfig {
     caps: Vec<Capability>,
     ctor: Option<Expr>,             // arbitrary constructor expression
     client: Option<Path>,           // trait path for client DX helpers
+    remote: Option<Path>,           // fn building the client of a remote instance
+    config: Option<Path>,           // `ConfigSchema` struct, checked by `validate_configs`
+    host: Option<LitStr>,           // REST host the module's routes bind to
+    version: Option<LitStr>,        // semver version, checked against `name@req` deps
     lifecycle: Option<LcModuleCfg>, // optional lifecycle config (on type)
+    timeouts: Vec<(Ident, proc_macro2::TokenStream)>, // phase variant + Duration expr
+    jobs: Vec<JobCfg>,              // periodic jobs backed by methods
+}
+
+/// One entry of `jobs(...)`: the method to run, its schedule and jitter.
+struct JobCfg {
+    method: Ident,
+    spec: LitStr,
+    jitter: Option<proc_macro2::TokenStream>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -24,6 +37,8 @@ enum Capability {
     Rest,
     RestHost,
     Stateful,
+    Health,
+    ConfigReload,
 }
 
 impl Capability {
@@ -33,10 +48,12 @@ impl Capability {
             "rest" => Ok(Capability::Rest),
             "rest_host" => Ok(Capability::RestHost),
             "stateful" => Ok(Capability::Stateful),
+            "health" => Ok(Capability::Health),
+            "config_reload" => Ok(Capability::ConfigReload),
             other => Err(syn::Error::new_spanned(
                 ident,
                 format!(
-                    "unknown capability '{other}', expected one of: db, rest, rest_host, stateful"
+                    "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, health, config_reload"
                 ),
             )),
         }
@@ -48,10 +65,12 @@ impl Capability {
             "rest" => Ok(Capability::Rest),
             "rest_host" => Ok(Capability::RestHost),
             "stateful" => Ok(Capability::Stateful),
+            "health" => Ok(Capability::Health),
+            "config_reload" => Ok(Capability::ConfigReload),
             other => Err(syn::Error::new_spanned(
                 lit,
                 format!(
-                    "unknown capability '{other}', expected one of: db, rest, rest_host, stateful"
+                    "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, health, config_reload"
                 ),
             )),
         }
@@ -60,9 +79,11 @@ impl Capability {
 
 #[derive(Debug, Clone)]
 struct LcModuleCfg {
-    entry: String,        // entry method name (e.g., "serve")
-    stop_timeout: String, // human duration (e.g., "30s")
-    await_ready: bool,    // require ReadySignal gating
+    entry: String,                 // entry method name (e.g., "serve")
+    stop_timeout: String,          // human duration (e.g., "30s")
+    await_ready: bool,             // require ReadySignal gating
+    tasks: Vec<Ident>,             // extra named task methods
+    worker_threads: Option<usize>, // dedicated runtime size
 }
 
 impl Default for LcModuleCfg {
@@ -71,6 +92,8 @@ impl Default for LcModuleCfg {
             entry: "serve".to_string(),
             stop_timeout: "30s".to_string(),
             await_ready: false,
+            tasks: Vec::new(),
+            worker_threads: None,
         }
     }
 }
@@ -82,14 +105,26 @@ impl Parse for ModuleConfig {
         let mut caps: Vec<Capability> = Vec::new();
         let mut ctor: Option<Expr> = None;
         let mut client: Option<Path> = None;
+        let mut remote: Option<Path> = None;
+        let mut config: Option<Path> = None;
+        let mut host: Option<LitStr> = None;
+        let mut version: Option<LitStr> = None;
         let mut lifecycle: Option<LcModuleCfg> = None;
+        let mut timeouts = Vec::new();
+        let mut jobs = Vec::new();
 
         let mut seen_name = false;
         let mut seen_deps = false;
         let mut seen_caps = false;
         let mut seen_ctor = false;
         let mut seen_client = false;
+        let mut seen_remote = false;
+        let mut seen_config = false;
+        let mut seen_host = false;
+        let mut seen_version = false;
         let mut seen_lifecycle = false;
+        let mut seen_timeouts = false;
+        let mut seen_jobs = false;
 
         let punctuated: Punctuated<Meta, Token![,]> =
             input.parse_terminated(Meta::parse, Token![,])?;
@@ -165,6 +200,90 @@ impl Parse for ModuleConfig {
                         }
                     }
                 }
+                Meta::NameValue(nv) if nv.path.is_ident("remote") => {
+                    if seen_remote {
+                        return Err(syn::Error::new_spanned(
+                            nv.path,
+                            "duplicate `remote` parameter",
+                        ));
+                    }
+                    seen_remote = true;
+                    match nv.value {
+                        Expr::Path(ep) => {
+                            remote = Some(ep.path);
+                        }
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                other,
+                                "remote must be a function path, e.g. remote = crate::gateways::remote::connect",
+                            ));
+                        }
+                    }
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("config") => {
+                    if seen_config {
+                        return Err(syn::Error::new_spanned(
+                            nv.path,
+                            "duplicate `config` parameter",
+                        ));
+                    }
+                    seen_config = true;
+                    match nv.value {
+                        Expr::Path(ep) => {
+                            config = Some(ep.path);
+                        }
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                other,
+                                "config must be a type path, e.g. config = crate::config::MyConfig",
+                            ));
+                        }
+                    }
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("version") => {
+                    if seen_version {
+                        return Err(syn::Error::new_spanned(
+                            nv.path,
+                            "duplicate `version` parameter",
+                        ));
+                    }
+                    seen_version = true;
+                    match nv.value {
+                        Expr::Lit(syn::ExprLit {
+                            lit: Lit::Str(s), ..
+                        }) => {
+                            version = Some(s);
+                        }
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                other,
+                                "version must be a string literal, e.g. version = \"1.2.0\"",
+                            ));
+                        }
+                    }
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("host") => {
+                    if seen_host {
+                        return Err(syn::Error::new_spanned(
+                            nv.path,
+                            "duplicate `host` parameter",
+                        ));
+                    }
+                    seen_host = true;
+                    match nv.value {
+                        Expr::Lit(syn::ExprLit {
+                            lit: Lit::Str(s), ..
+                        }) => {
+                            host = Some(s);
+                        }
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                other,
+                                "host must be the name of a rest_host module, e.g. host = \"admin_ingress\"",
+                            ));
+                        }
+                    }
+                }
                 Meta::NameValue(nv) if nv.path.is_ident("deps") => {
                     if seen_deps {
                         return Err(syn::Error::new_spanned(
@@ -181,7 +300,24 @@ impl Parse for ModuleConfig {
                                     Expr::Lit(syn::ExprLit {
                                         lit: Lit::Str(s), ..
                                     }) => {
-                                        deps.push(s.value());
+                                        let dep = s.value();
+                                        let dep_spec = dep.strip_suffix('?').unwrap_or(&dep);
+                                        let (name, req) = match dep_spec.split_once('@') {
+                                            Some((name, req)) => (name, Some(req)),
+                                            None => (dep_spec, None),
+                                        };
+                                        if name.is_empty()
+                                            || name.contains('?')
+                                            || req.is_some_and(|r| {
+                                                r.trim().is_empty() || r.contains('?')
+                                            })
+                                        {
+                                            return Err(syn::Error::new_spanned(
+                                                s,
+                                                "dep must be a module name, optionally with a version requirement and suffixed with `?` for an optional dep, e.g. \"users_info@^1\" or \"cache?\"",
+                                            ));
+                                        }
+                                        deps.push(dep);
                                     }
                                     other => {
                                         return Err(syn::Error::new_spanned(
@@ -219,7 +355,7 @@ impl Parse for ModuleConfig {
                                         } else {
                                             return Err(syn::Error::new_spanned(
                                                 path,
-                                                "capability must be a simple identifier (db, rest, rest_host, stateful)",
+                                                "capability must be a simple identifier (db, rest, rest_host, stateful, health, config_reload)",
                                             ));
                                         }
                                     }
@@ -231,7 +367,7 @@ impl Parse for ModuleConfig {
                                     other => {
                                         return Err(syn::Error::new_spanned(
                                             other,
-                                            "capability must be an identifier or string literal (\"db\", \"rest\", \"rest_host\", \"stateful\")",
+                                            "capability must be an identifier or string literal (\"db\", \"rest\", \"rest_host\", \"stateful\", \"health\", \"config_reload\")",
                                         ));
                                     }
                                 }
@@ -256,6 +392,26 @@ impl Parse for ModuleConfig {
                     seen_lifecycle = true;
                     lifecycle = Some(parse_lifecycle_list(&list)?);
                 }
+                Meta::List(list) if list.path.is_ident("timeouts") => {
+                    if seen_timeouts {
+                        return Err(syn::Error::new_spanned(
+                            list.path,
+                            "duplicate `timeouts(...)` parameter",
+                        ));
+                    }
+                    seen_timeouts = true;
+                    timeouts = parse_timeouts_list(&list)?;
+                }
+                Meta::List(list) if list.path.is_ident("jobs") => {
+                    if seen_jobs {
+                        return Err(syn::Error::new_spanned(
+                            list.path,
+                            "duplicate `jobs(...)` parameter",
+                        ));
+                    }
+                    seen_jobs = true;
+                    jobs = parse_jobs_list(&list)?;
+                }
                 other => {
                     return Err(syn::Error::new_spanned(
                         other,
@@ -271,6 +427,12 @@ impl Parse for ModuleConfig {
                 "name parameter is required, e.g. #[module(name = \"my-module\", ...)]",
             )
         })?;
+        if let (Some(remote), None) = (&remote, &client) {
+            return Err(syn::Error::new_spanned(
+                remote,
+                "`remote` needs `client = Trait`: the remote client is published under that trait",
+            ));
+        }
 
         Ok(ModuleConfig {
             name,
@@ -278,11 +440,136 @@ impl Parse for ModuleConfig {
             caps,
             ctor,
             client,
+            remote,
+            config,
+            host,
+            version,
             lifecycle,
+            timeouts,
+            jobs,
         })
     }
 }
 
+/// `timeouts(init = "30s", migrate = "5m", start = "10s", stop = "15s")` → `(Phase variant, Duration)`.
+fn parse_timeouts_list(list: &MetaList) -> syn::Result<Vec<(Ident, proc_macro2::TokenStream)>> {
+    const EXPECTED: &str =
+        "expected timeouts args: init=\"...\", migrate=\"...\", start=\"...\", stop=\"...\"";
+
+    let inner: Punctuated<Meta, Token![,]> =
+        list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+    let mut out: Vec<(Ident, proc_macro2::TokenStream)> = Vec::new();
+    for m in inner {
+        let Meta::NameValue(MetaNameValue { path, value, .. }) = m else {
+            return Err(syn::Error::new_spanned(m, EXPECTED));
+        };
+        let variant = match path.get_ident().map(|i| i.to_string()).as_deref() {
+            Some("init") => "Init",
+            Some("migrate") => "DbMigrate",
+            Some("start") => "Start",
+            Some("stop") => "Stop",
+            _ => return Err(syn::Error::new_spanned(path, EXPECTED)),
+        };
+        let variant = Ident::new(variant, Span::call_site());
+        if out.iter().any(|(v, _)| *v == variant) {
+            return Err(syn::Error::new_spanned(
+                path,
+                "duplicate phase in `timeouts(...)`",
+            ));
+        }
+        let Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(s), ..
+        }) = value
+        else {
+            return Err(syn::Error::new_spanned(
+                value,
+                "timeout must be a string literal like \"30s\"",
+            ));
+        };
+        let duration =
+            parse_duration_tokens(&s.value()).map_err(|e| syn::Error::new_spanned(&s, e))?;
+        out.push((variant, duration));
+    }
+    Ok(out)
+}
+
+/// `jobs(cleanup = "every 5m", report(spec = "0 0 * * * *", jitter = "30s"))`.
+/// Each key names an `async fn(&self, CancellationToken) -> anyhow::Result<()>`;
+/// specs are parsed when the registry is built.
+fn parse_jobs_list(list: &MetaList) -> syn::Result<Vec<JobCfg>> {
+    const EXPECTED: &str =
+        "expected jobs args: method = \"<spec>\" or method(spec = \"<spec>\", jitter = \"...\")";
+
+    let str_lit = |value: Expr, what: &str| match value {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(s), ..
+        }) => Ok(s),
+        other => Err(syn::Error::new_spanned(
+            other,
+            format!("{what} must be a string literal"),
+        )),
+    };
+
+    let inner: Punctuated<Meta, Token![,]> =
+        list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+    let mut out: Vec<JobCfg> = Vec::new();
+    for m in inner {
+        let job = match m {
+            Meta::NameValue(MetaNameValue { path, value, .. }) => JobCfg {
+                method: path
+                    .get_ident()
+                    .cloned()
+                    .ok_or_else(|| syn::Error::new_spanned(&path, EXPECTED))?,
+                spec: str_lit(value, "job spec")?,
+                jitter: None,
+            },
+            Meta::List(job_list) => {
+                let method = job_list
+                    .path
+                    .get_ident()
+                    .cloned()
+                    .ok_or_else(|| syn::Error::new_spanned(&job_list.path, EXPECTED))?;
+                let args: Punctuated<MetaNameValue, Token![,]> = job_list
+                    .parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
+                let mut spec = None;
+                let mut jitter = None;
+                for arg in args {
+                    if arg.path.is_ident("spec") {
+                        spec = Some(str_lit(arg.value, "job spec")?);
+                    } else if arg.path.is_ident("jitter") {
+                        let s = str_lit(arg.value, "jitter")?;
+                        jitter = Some(
+                            parse_duration_tokens(&s.value())
+                                .map_err(|e| syn::Error::new_spanned(&s, e))?,
+                        );
+                    } else {
+                        return Err(syn::Error::new_spanned(arg.path, EXPECTED));
+                    }
+                }
+                let spec = spec.ok_or_else(|| {
+                    syn::Error::new_spanned(&job_list.path, "job is missing `spec = \"...\"`")
+                })?;
+                JobCfg {
+                    method,
+                    spec,
+                    jitter,
+                }
+            }
+            other => return Err(syn::Error::new_spanned(other, EXPECTED)),
+        };
+        if out.iter().any(|j| j.method == job.method) {
+            return Err(syn::Error::new_spanned(
+                &job.method,
+                "duplicate job in `jobs(...)`",
+            ));
+        }
+        out.push(job);
+    }
+    Ok(out)
+}
+
 fn parse_lifecycle_list(list: &MetaList) -> syn::Result<LcModuleCfg> {
     let mut cfg = LcModuleCfg::default();
 
@@ -334,10 +621,39 @@ fn parse_lifecycle_list(list: &MetaList) -> syn::Result<LcModuleCfg> {
                     ));
                 }
             }
+            Meta::List(tasks) if tasks.path.is_ident("tasks") => {
+                let names: Punctuated<Ident, Token![,]> =
+                    tasks.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?;
+                for name in names {
+                    if cfg.tasks.contains(&name) {
+                        return Err(syn::Error::new_spanned(
+                            &name,
+                            "duplicate task in `tasks(...)`",
+                        ));
+                    }
+                    cfg.tasks.push(name);
+                }
+            }
+            Meta::NameValue(MetaNameValue { path, value, .. })
+                if path.is_ident("worker_threads") =>
+            {
+                let workers = match &value {
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Int(n), ..
+                    }) => n.base10_parse::<usize>().ok().filter(|&n| n > 0),
+                    _ => None,
+                };
+                cfg.worker_threads = Some(workers.ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &value,
+                        "worker_threads must be a positive integer literal, e.g. worker_threads = 2",
+                    )
+                })?);
+            }
             other => {
                 return Err(syn::Error::new_spanned(
                     other,
-                    "expected lifecycle args: entry=\"...\", stop_timeout=\"...\", await_ready[=true|false]",
+                    "expected lifecycle args: entry=\"...\", stop_timeout=\"...\", await_ready[=true|false], tasks(...), worker_threads=N",
                 ));
             }
         }
@@ -366,6 +682,7 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
     let caps_for_regs: Vec<Capability> = config.caps.clone();
     let ctor_expr_opt: Option<Expr> = config.ctor.clone();
     let client_trait_opt: Option<Path> = config.client.clone();
+    let config_ty_opt: Option<Path> = config.config.clone();
     let lifecycle_cfg_opt: Option<LcModuleCfg> = config.lifecycle.clone();
 
     // Prepare string literals for name/deps
@@ -375,6 +692,67 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
         .map(|s| LitStr::new(s, Span::call_site()))
         .collect();
 
+    let timeout_regs: Vec<_> = config
+        .timeouts
+        .iter()
+        .map(|(phase, duration)| {
+            quote! {
+                b.register_timeout_with_meta(#name_lit, ::modkit::registry::Phase::#phase, #duration);
+            }
+        })
+        .collect();
+
+    let job_regs: Vec<_> = config
+        .jobs
+        .iter()
+        .map(|job| {
+            let method = &job.method;
+            let job_name = LitStr::new(&method.to_string(), method.span());
+            let spec = &job.spec;
+            let jitter = match &job.jitter {
+                Some(d) => quote! { ::core::option::Option::Some(#d) },
+                None => quote! { ::core::option::Option::None },
+            };
+            quote! {
+                {
+                    let m = module.clone();
+                    b.register_job_with_meta(
+                        #name_lit,
+                        #job_name,
+                        #spec,
+                        #jitter,
+                        Arc::new(move |cancel: ::tokio_util::sync::CancellationToken|
+                            -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::anyhow::Result<()>> + Send>>
+                        {
+                            let m = m.clone();
+                            Box::pin(async move { m.#method(cancel).await })
+                        }),
+                    );
+                }
+            }
+        })
+        .collect();
+
+    let version_reg = config.version.as_ref().map(|version| {
+        quote! {
+            b.register_version_with_meta(#name_lit, #version);
+        }
+    });
+
+    let host_binding = config.host.as_ref().map(|host| {
+        quote! {
+            b.register_rest_binding_with_meta(#name_lit, #host);
+        }
+    });
+
+    let config_check = config_ty_opt.as_ref().map(|ty| {
+        quote! {
+            b.register_config_with_meta(#name_lit, |provider| {
+                ::modkit::config_schema::check_typed::<#ty>(provider, #name_lit)
+            });
+        }
+    });
+
     // Constructor expression (provided or Default::default())
     let constructor = if let Some(expr) = &ctor_expr_opt {
         quote! { #expr }
@@ -426,6 +804,24 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                     {}
                 };
             },
+            Capability::Health => quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn __modkit_require_HealthProbe_impl()
+                    where
+                        #struct_ident #ty_generics: ::modkit::contracts::HealthProbe,
+                    {}
+                };
+            },
+            Capability::ConfigReload => quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn __modkit_require_ConfigReloadable_impl()
+                    where
+                        #struct_ident #ty_generics: ::modkit::contracts::ConfigReloadable,
+                    {}
+                };
+            },
             Capability::Stateful => {
                 if lifecycle_cfg_opt.is_none() {
                     // Only require direct StatefulModule impl when lifecycle(...) is NOT used.
@@ -453,6 +849,27 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
     // === Top-level extras (impl Runnable + optional ready shim) ===
     let mut extra_top_level = proc_macro2::TokenStream::new();
 
+    // Named lifecycle tasks: one shim per method plus the `.with_task(...)` chain.
+    let mut lc_tasks = proc_macro2::TokenStream::new();
+    for task in lifecycle_cfg_opt.iter().flat_map(|lc| &lc.tasks) {
+        let shim_ident = format_ident!("__modkit_task_shim_for_{}_{}", struct_name_snake, task);
+        let task_name = LitStr::new(&task.to_string(), task.span());
+        extra_top_level.extend(quote! {
+            #[doc(hidden)]
+            #[allow(dead_code, non_snake_case)]
+            fn #shim_ident(
+                this: ::std::sync::Arc<#struct_ident #ty_generics>,
+                cancel: ::tokio_util::sync::CancellationToken,
+            ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = ::anyhow::Result<()>> + Send>> {
+                Box::pin(async move { this.#task(cancel).await })
+            }
+        });
+        lc_tasks.extend(quote! { .with_task(#task_name, #shim_ident) });
+    }
+    if let Some(workers) = lifecycle_cfg_opt.as_ref().and_then(|lc| lc.worker_threads) {
+        lc_tasks.extend(quote! { .with_dedicated_runtime(#name_lit, #workers) });
+    }
+
     if let Some(lc) = &lifecycle_cfg_opt {
         // If the type declares lifecycle(...), we generate Runnable at top-level.
         let entry_ident = format_ident!("{}", lc.entry);
@@ -494,6 +911,7 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                         ::modkit::lifecycle::WithLifecycle::new(self)
                             .with_stop_timeout(#timeout_ts)
                             .with_ready_mode(true, true, Some(#ready_shim_ident))
+                            #lc_tasks
                     }
                 }
             });
@@ -513,6 +931,7 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                         ::modkit::lifecycle::WithLifecycle::new(self)
                             .with_stop_timeout(#timeout_ts)
                             .with_ready_mode(false, false, None)
+                            #lc_tasks
                     }
                 }
             });
@@ -534,6 +953,14 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                 b.register_rest_host_with_meta(#name_lit,
                     module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::RestHostModule>);
             },
+            Capability::Health => quote! {
+                b.register_health_with_meta(#name_lit,
+                    module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::HealthProbe>);
+            },
+            Capability::ConfigReload => quote! {
+                b.register_reloadable_with_meta(#name_lit,
+                    module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::ConfigReloadable>);
+            },
             Capability::Stateful => {
                 if let Some(lc) = &lifecycle_cfg_opt {
                     let timeout_ts = parse_duration_tokens(&lc.stop_timeout)
@@ -546,7 +973,8 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                         quote! {
                             let wl = ::modkit::lifecycle::WithLifecycle::from_arc(module.clone())
                                 .with_stop_timeout(#timeout_ts)
-                                .with_ready_mode(true, true, Some(#ready_shim_ident));
+                                .with_ready_mode(true, true, Some(#ready_shim_ident))
+                                #lc_tasks;
 
                             b.register_stateful_with_meta(
                                 #name_lit,
@@ -557,7 +985,8 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                         quote! {
                             let wl = ::modkit::lifecycle::WithLifecycle::from_arc(module.clone())
                                 .with_stop_timeout(#timeout_ts)
-                                .with_ready_mode(false, false, None);
+                                .with_ready_mode(false, false, None)
+                                #lc_tasks;
 
                             b.register_stateful_with_meta(
                                 #name_lit,
@@ -585,6 +1014,32 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
         let accessor_in_fn = format_ident!("{}_client_in", snake);
         let publish_mock_fn = format_ident!("publish_mock_{}_client", snake);
 
+        // Client of an instance served by another process (`modules.<name>.remote`).
+        let remote_code = match &config.remote {
+            Some(connect) => {
+                let register_fn = format_ident!("__modkit_register_remote_{}_client", snake);
+                quote! {
+                    #[doc(hidden)]
+                    fn #register_fn(
+                        hub: &::modkit::client_hub::ClientHub,
+                        endpoint: &::modkit::remote::RemoteEndpoint,
+                    ) -> ::anyhow::Result<()> {
+                        let client: ::std::sync::Arc<dyn #client_trait_path> = #connect(endpoint)?;
+                        hub.register::<dyn #client_trait_path>(client);
+                        Ok(())
+                    }
+
+                    ::modkit::inventory::submit! {
+                        ::modkit::remote::RemoteClient {
+                            module: #name_lit,
+                            register: #register_fn,
+                        }
+                    }
+                }
+            }
+            None => quote! {},
+        };
+
         quote! {
             // Compile-time trait checks: object-safe + Send + Sync + 'static
             const _: () = {
@@ -623,9 +1078,10 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
             pub fn #accessor_fn(
                 hub: &::modkit::client_hub::ClientHub
             ) -> ::std::sync::Arc<dyn #client_trait_path> {
-                hub.get::<dyn #client_trait_path>()
-                    .expect(concat!(#name_lit, " client not registered; call ",
-                                    stringify!(#expose_fn), "(ctx, &client) in provider init()"))
+                hub.get::<dyn #client_trait_path>().unwrap_or_else(|e| {
+                    panic!(concat!(#name_lit, " client not registered ({}); call ",
+                                   stringify!(#expose_fn), "(ctx, &client) in provider init()"), e)
+                })
             }
 
             /// Fetch typed client in custom scope (panics if missing).
@@ -634,9 +1090,10 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                 hub: &::modkit::client_hub::ClientHub,
                 scope: &str
             ) -> ::std::sync::Arc<dyn #client_trait_path> {
-                hub.get_scoped::<dyn #client_trait_path>(scope)
-                    .expect(concat!(#name_lit, " client (scoped) not registered; call ",
-                                    stringify!(#expose_in_fn), "(ctx, scope, &client) in provider init()"))
+                hub.get_scoped::<dyn #client_trait_path>(scope).unwrap_or_else(|e| {
+                    panic!(concat!(#name_lit, " client (scoped) not registered ({}); call ",
+                                   stringify!(#expose_in_fn), "(ctx, scope, &client) in provider init()"), e)
+                })
             }
 
             /// Dev-only helper to inject mocks quickly.
@@ -647,6 +1104,8 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
             ) {
                 hub.register::<dyn #client_trait_path>(client);
             }
+
+            #remote_code
         }
     } else {
         // Even without a client trait, expose MODULE_NAME for ergonomics.
@@ -664,22 +1123,52 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
         // Compile-time capability assertions (better errors if trait impls are missing)
         #(#cap_asserts)*
 
-        // Registrator that targets the *builder*, not the final registry
-        #[doc(hidden)]
-        fn #registrator_name(b: &mut ::modkit::registry::RegistryBuilder) {
-            use ::std::sync::Arc;
+        // Registration of a given instance; also used for explicit (inventory-free) setups
+        impl #impl_generics ::modkit::registry::ModuleRegistration for #struct_ident #ty_generics #where_clause {
+            const NAME: &'static str = #name_lit;
+
+            fn register_into(
+                module: ::std::sync::Arc<Self>,
+                b: &mut ::modkit::registry::RegistryBuilder,
+            ) {
+                use ::std::sync::Arc;
 
-            let module: Arc<#struct_ident #ty_generics> = Arc::new(#constructor);
+                // register core with metadata (name + deps)
+                b.register_core_with_meta(
+                    #name_lit,
+                    &[#(#deps_lits),*],
+                    module.clone() as Arc<dyn ::modkit::contracts::Module>
+                );
+                b.register_typed_with_meta(
+                    #name_lit,
+                    module.clone() as Arc<dyn ::core::any::Any + Send + Sync>
+                );
 
-            // register core with metadata (name + deps)
-            b.register_core_with_meta(
-                #name_lit,
-                &[#(#deps_lits),*],
-                module.clone() as Arc<dyn ::modkit::contracts::Module>
-            );
+                #version_reg
 
-            // capabilities
-            #(#capability_registrations)*
+                // capabilities
+                #(#capability_registrations)*
+
+                // per-module phase timeouts
+                #(#timeout_regs)*
+
+                // periodic jobs
+                #(#job_regs)*
+
+                // REST host binding
+                #host_binding
+
+                // typed config check
+                #config_check
+            }
+        }
+
+        // Registrator that targets the *builder*, not the final registry
+        #[doc(hidden)]
+        fn #registrator_name(b: &mut ::modkit::registry::RegistryBuilder) {
+            let module: ::std::sync::Arc<#struct_ident #ty_generics> =
+                ::std::sync::Arc::new(#constructor);
+            <#struct_ident #ty_generics as ::modkit::registry::ModuleRegistration>::register_into(module, b);
         }
 
         ::inventory::submit! {
@@ -980,3 +1469,550 @@ fn path_last_is(path: &syn::Path, want: &str) -> bool {
         .map(|s| s.ident == want)
         .unwrap_or(false)
 }
+
+/// Compile-time checked route template: `route!("/users/{id}")` expands to the
+/// string literal once it passes the same checks the ingress applies at
+/// startup — a leading `/`, balanced braces, whole-segment parameters with
+/// identifier names, no duplicates, and `{*wildcard}` only as the last segment.
+#[proc_macro]
+pub fn route(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    match modkit_route_template::params(&lit.value()) {
+        Ok(_) => quote! { #lit }.into(),
+        Err(e) => syn::Error::new_spanned(&lit, format!("invalid route template: {e}"))
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Per-variant mapping parsed from `#[api_error(...)]`
+enum ApiErrorMapping {
+    Problem {
+        status: u16,
+        title: Option<LitStr>,
+        code: Option<LitStr>,
+    },
+    Transparent,
+}
+
+/// Derive `From<YourError> for modkit::api::problem::ProblemResponse`, so the
+/// error works with `ApiError<YourError>` and `?` without a hand-written match.
+///
+/// ```ignore
+/// #[derive(Debug, thiserror::Error, modkit::IntoApiError)]
+/// #[api_error(type_base = "https://errors.example.com/")]
+/// enum UsersError {
+///     #[error("User {0} not found")]
+///     #[api_error(status = 404, code = "USERS_NOT_FOUND")]
+///     NotFound(Uuid),
+///     #[error(transparent)]
+///     #[api_error(transparent)] // DbError: unique violation → 409, not found → 404
+///     Db(#[from] modkit_db::DbError),
+/// }
+/// ```
+///
+/// The detail is the variant's `Display` output (logged instead for 5xx);
+/// `title` defaults to the status reason phrase and `code` to none.
+#[proc_macro_derive(IntoApiError, attributes(api_error))]
+pub fn derive_into_api_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_into_api_error(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_into_api_error(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let syn::Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "IntoApiError can only be derived for enums",
+        ));
+    };
+
+    let mut type_base: Option<LitStr> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("api_error"))
+    {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            match meta {
+                Meta::NameValue(nv) if nv.path.is_ident("type_base") => {
+                    type_base = Some(expect_lit_str(&nv.value, "type_base")?);
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "expected `type_base = \"...\"` on the enum",
+                    ))
+                }
+            }
+        }
+    }
+    let type_base = match type_base {
+        Some(lit) => quote! { ::core::option::Option::Some(#lit) },
+        None => quote! { ::core::option::Option::None },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut arms = Vec::new();
+    let mut needs_detail = false;
+    let mut errors: Option<syn::Error> = None;
+    for variant in &data.variants {
+        let name = &variant.ident;
+        let mapping = match parse_api_error_mapping(variant) {
+            Ok(mapping) => mapping,
+            Err(e) => {
+                match &mut errors {
+                    Some(errors) => errors.combine(e),
+                    None => errors = Some(e),
+                }
+                continue;
+            }
+        };
+        let arm = match mapping {
+            ApiErrorMapping::Transparent => {
+                if variant.fields.len() != 1 {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "#[api_error(transparent)] requires exactly one field",
+                    ));
+                }
+                match &variant.fields {
+                    syn::Fields::Named(fields) => {
+                        let field = fields.named.first().and_then(|f| f.ident.as_ref());
+                        quote! { #ident::#name { #field: inner } => ::core::convert::Into::into(inner), }
+                    }
+                    _ => quote! { #ident::#name(inner) => ::core::convert::Into::into(inner), },
+                }
+            }
+            ApiErrorMapping::Problem {
+                status,
+                title,
+                code,
+            } => {
+                let pattern = match &variant.fields {
+                    syn::Fields::Named(_) => quote! { #ident::#name { .. } },
+                    syn::Fields::Unnamed(_) => quote! { #ident::#name(..) },
+                    syn::Fields::Unit => quote! { #ident::#name },
+                };
+                let title = match title {
+                    Some(lit) => quote! { ::core::option::Option::Some(#lit) },
+                    None => quote! { ::core::option::Option::None },
+                };
+                let code = code.map(|c| c.value()).unwrap_or_default();
+                needs_detail = true;
+                quote! {
+                    #pattern => ::modkit::api::error::derived_problem(
+                        #status, #title, #code, #type_base, detail,
+                    ),
+                }
+            }
+        };
+        arms.push(arm);
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let detail =
+        needs_detail.then(|| quote! { let detail = ::std::string::ToString::to_string(&err); });
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#ident #ty_generics>
+            for ::modkit::api::problem::ProblemResponse #where_clause
+        {
+            fn from(err: #ident #ty_generics) -> Self {
+                #detail
+                match err {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+fn parse_api_error_mapping(variant: &syn::Variant) -> syn::Result<ApiErrorMapping> {
+    let Some(attr) = variant
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident("api_error"))
+    else {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            "missing #[api_error(status = ...)] or #[api_error(transparent)]",
+        ));
+    };
+
+    let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+    let mut status: Option<u16> = None;
+    let mut title: Option<LitStr> = None;
+    let mut code: Option<LitStr> = None;
+    let mut transparent = false;
+    for meta in metas {
+        match meta {
+            Meta::Path(p) if p.is_ident("transparent") => transparent = true,
+            Meta::NameValue(nv) if nv.path.is_ident("status") => {
+                let lit = match &nv.value {
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Int(lit), ..
+                    }) => lit.clone(),
+                    other => {
+                        return Err(syn::Error::new_spanned(
+                            other,
+                            "status must be an integer literal",
+                        ))
+                    }
+                };
+                let value = lit.base10_parse::<u16>()?;
+                if !(400..=599).contains(&value) {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "status must be an error status (400..=599)",
+                    ));
+                }
+                status = Some(value);
+            }
+            Meta::NameValue(nv) if nv.path.is_ident("title") => {
+                title = Some(expect_lit_str(&nv.value, "title")?);
+            }
+            Meta::NameValue(nv) if nv.path.is_ident("code") => {
+                code = Some(expect_lit_str(&nv.value, "code")?);
+            }
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "unknown api_error argument, expected one of: status, title, code, transparent",
+                ))
+            }
+        }
+    }
+
+    match (transparent, status) {
+        (true, None) if title.is_none() && code.is_none() => Ok(ApiErrorMapping::Transparent),
+        (true, _) => Err(syn::Error::new_spanned(
+            attr,
+            "#[api_error(transparent)] takes no other arguments",
+        )),
+        (false, Some(status)) => Ok(ApiErrorMapping::Problem {
+            status,
+            title,
+            code,
+        }),
+        (false, None) => Err(syn::Error::new_spanned(attr, "missing `status = ...`")),
+    }
+}
+
+fn expect_lit_str(value: &Expr, what: &str) -> syn::Result<LitStr> {
+    match value {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(s), ..
+        }) => Ok(s.clone()),
+        other => Err(syn::Error::new_spanned(
+            other,
+            format!("{what} must be a string literal"),
+        )),
+    }
+}
+
+// ============================================================================
+// Instrumented clients
+// ============================================================================
+
+/// Let a `ClientHub::instrumented()` hub wrap clients of this trait, so that
+/// every `async` method call gets a `client_call` span and is counted and
+/// timed with `contract` (the trait name) and `method` labels:
+///
+/// ```ignore
+/// #[modkit::instrumented_client]
+/// #[async_trait::async_trait]
+/// pub trait UsersApi: Send + Sync {
+///     async fn get(&self, id: Uuid) -> Result<User, UsersError>;
+/// }
+/// ```
+///
+/// Methods returning a `Result` are recorded as `error` when they return
+/// `Err`. Plain methods are forwarded untouched. Place it above
+/// `#[async_trait]`; every method must take `&self` and have no generics.
+#[proc_macro_attribute]
+pub fn instrumented_client(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(Span::call_site(), "instrumented_client takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+    let item_trait = parse_macro_input!(item as syn::ItemTrait);
+    match expand_instrumented_client(&item_trait) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_instrumented_client(item: &syn::ItemTrait) -> syn::Result<proc_macro2::TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "instrumented_client traits cannot be generic",
+        ));
+    }
+    let trait_ident = &item.ident;
+    let vis = &item.vis;
+    let contract = LitStr::new(&trait_ident.to_string(), trait_ident.span());
+    let wrapper = format_ident!("__Instrumented{}", trait_ident);
+    let type_name_fn = format_ident!(
+        "__instrumented_{}_type_name",
+        trait_ident.to_string().to_snake_case()
+    );
+    let wrap_fn = format_ident!(
+        "__instrumented_{}_wrap",
+        trait_ident.to_string().to_snake_case()
+    );
+
+    let mut methods = Vec::new();
+    for trait_item in &item.items {
+        let syn::TraitItem::Fn(f) = trait_item else {
+            return Err(syn::Error::new_spanned(
+                trait_item,
+                "instrumented_client traits may only contain methods",
+            ));
+        };
+        let sig = &f.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.generics,
+                "instrumented_client methods cannot be generic",
+            ));
+        }
+        match sig.inputs.first() {
+            Some(syn::FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_none() => {}
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    sig,
+                    "instrumented_client methods must take `&self`",
+                ))
+            }
+        }
+
+        let method = &sig.ident;
+        let method_lit = LitStr::new(&method.to_string(), method.span());
+        let mut args = Vec::new();
+        let mut params = Vec::new();
+        for (i, input) in sig.inputs.iter().skip(1).enumerate() {
+            let syn::FnArg::Typed(pt) = input else {
+                unreachable!("only the first input can be a receiver")
+            };
+            let arg = format_ident!("__arg{}", i);
+            let ty = &pt.ty;
+            params.push(quote! { #arg: #ty });
+            args.push(arg);
+        }
+        let output = &sig.output;
+        let asyncness = &sig.asyncness;
+        let body = if sig.asyncness.is_none() {
+            quote! { self.0.#method(#(#args),*) }
+        } else if returns_result(output) {
+            quote! {
+                ::modkit::client_hub::observe_call(#contract, #method_lit, self.0.#method(#(#args),*)).await
+            }
+        } else {
+            quote! {
+                ::modkit::client_hub::observe_call_infallible(#contract, #method_lit, self.0.#method(#(#args),*)).await
+            }
+        };
+        methods.push(quote! {
+            #asyncness fn #method(&self, #(#params),*) #output {
+                #body
+            }
+        });
+    }
+
+    Ok(quote! {
+        #item
+
+        #[doc(hidden)]
+        #vis struct #wrapper(::std::sync::Arc<dyn #trait_ident>);
+
+        #[::async_trait::async_trait]
+        impl #trait_ident for #wrapper {
+            #(#methods)*
+        }
+
+        #[doc(hidden)]
+        fn #type_name_fn() -> &'static str {
+            ::std::any::type_name::<dyn #trait_ident>()
+        }
+
+        #[doc(hidden)]
+        fn #wrap_fn(
+            client: ::std::boxed::Box<dyn ::std::any::Any + Send + Sync>,
+        ) -> ::std::boxed::Box<dyn ::std::any::Any + Send + Sync> {
+            match client.downcast::<::std::sync::Arc<dyn #trait_ident>>() {
+                Ok(inner) => {
+                    let wrapped: ::std::sync::Arc<dyn #trait_ident> =
+                        ::std::sync::Arc::new(#wrapper(*inner));
+                    ::std::boxed::Box::new(wrapped)
+                }
+                Err(other) => other,
+            }
+        }
+
+        ::modkit::inventory::submit! {
+            ::modkit::client_hub::ClientDecorator {
+                type_name: #type_name_fn,
+                wrap: #wrap_fn,
+            }
+        }
+    })
+}
+
+/// Whether a method's return type is spelled `...Result<..>`.
+fn returns_result(output: &syn::ReturnType) -> bool {
+    match output {
+        syn::ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(TypePath { path, .. }) => path_last_is(path, "Result"),
+            _ => false,
+        },
+        syn::ReturnType::Default => false,
+    }
+}
+
+// ============================================================================
+// Config schema
+// ============================================================================
+
+/// Derive `modkit::ConfigSchema` from `#[config(...)]` field attributes.
+///
+/// ```ignore
+/// #[derive(serde::Deserialize, modkit::ConfigSchema)]
+/// struct CacheConfig {
+///     #[config(min = 1, max = 10_000)]
+///     capacity: u32,
+///     #[config(one_of = ["lru", "lfu"])]
+///     policy: String,
+///     #[config(required_with = "tls_cert")]
+///     tls_key: Option<String>,
+///     tls_cert: Option<String>,
+///     #[config(nested)] // RetryConfig: ConfigSchema
+///     retry: RetryConfig,
+/// }
+/// ```
+///
+/// `min`/`max` apply to numbers, `one_of` to strings; unset `Option`s pass.
+#[proc_macro_derive(ConfigSchema, attributes(config))]
+pub fn derive_config_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_config_schema(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_config_schema(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "ConfigSchema can only be derived for structs with named fields",
+        ));
+    };
+
+    let names: Vec<&Ident> = fields
+        .named
+        .iter()
+        .filter_map(|f| f.ident.as_ref())
+        .collect();
+    let mut checks = Vec::new();
+    for field in &fields.named {
+        let Some(ident) = field.ident.as_ref() else {
+            continue;
+        };
+        let key = ident.to_string().trim_start_matches("r#").to_string();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("config")) {
+            let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            let (mut min, mut max) = (None, None);
+            for meta in metas {
+                match meta {
+                    Meta::NameValue(nv) if nv.path.is_ident("min") => min = Some(nv.value),
+                    Meta::NameValue(nv) if nv.path.is_ident("max") => max = Some(nv.value),
+                    Meta::NameValue(nv) if nv.path.is_ident("one_of") => {
+                        let Expr::Array(arr) = &nv.value else {
+                            return Err(syn::Error::new_spanned(
+                                &nv.value,
+                                "one_of must be an array of string literals",
+                            ));
+                        };
+                        let allowed = arr
+                            .elems
+                            .iter()
+                            .map(|e| expect_lit_str(e, "one_of entry"))
+                            .collect::<syn::Result<Vec<_>>>()?;
+                        checks.push(quote! {
+                            ::modkit::config_schema::check_one_of(
+                                &mut out, #key, &self.#ident, &[#(#allowed),*],
+                            );
+                        });
+                    }
+                    Meta::NameValue(nv) if nv.path.is_ident("required_with") => {
+                        let lit = expect_lit_str(&nv.value, "required_with")?;
+                        let Some(other) = names.iter().find(|n| **n == lit.value().as_str())
+                        else {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "required_with must name another field of this struct",
+                            ));
+                        };
+                        checks.push(quote! {
+                            ::modkit::config_schema::check_required_with(
+                                &mut out, #key, &self.#ident, #lit, &self.#other,
+                            );
+                        });
+                    }
+                    Meta::Path(p) if p.is_ident("nested") => {
+                        checks.push(quote! {
+                            ::modkit::config_schema::check_nested(&mut out, #key, &self.#ident);
+                        });
+                    }
+                    other => {
+                        return Err(syn::Error::new_spanned(
+                            other,
+                            "expected `min = N`, `max = N`, `one_of = [..]`, `required_with = \"field\"` or `nested`",
+                        ))
+                    }
+                }
+            }
+            if min.is_some() || max.is_some() {
+                let bound = |b: Option<Expr>| match b {
+                    Some(e) => quote! { ::core::option::Option::Some((#e) as f64) },
+                    None => quote! { ::core::option::Option::None },
+                };
+                let (min, max) = (bound(min), bound(max));
+                checks.push(quote! {
+                    ::modkit::config_schema::check_range(&mut out, #key, &self.#ident, #min, #max);
+                });
+            }
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let out_binding = if checks.is_empty() {
+        quote! { let out = ::std::vec::Vec::new(); }
+    } else {
+        quote! { let mut out = ::std::vec::Vec::new(); }
+    };
+    Ok(quote! {
+        impl #impl_generics ::modkit::config_schema::ConfigSchema for #ident #ty_generics #where_clause {
+            fn violations(&self) -> ::std::vec::Vec<::modkit::config_schema::ConfigViolation> {
+                #out_binding
+                #(#checks)*
+                out
+            }
+        }
+    })
+}
@@ -0,0 +1,10 @@
+use modkit_macros::ConfigSchema;
+
+#[derive(ConfigSchema)]
+struct Tls {
+    #[config(required_with = "cert")]
+    key: Option<String>,
+    certificate: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use modkit_macros::IntoApiError;
+
+#[derive(Debug, IntoApiError)]
+enum UsersError {
+    #[api_error(status = 404)]
+    NotFound,
+    #[api_error(code = "USERS_CONFLICT")]
+    Conflict,
+    #[api_error(status = 200)]
+    NotAnError,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use modkit_macros::module;
+
+#[module(name="x", capabilities=[stateful], remote=connect)]
+pub struct X;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use modkit_macros::route;
+
+const DUPLICATE: &str = route!("/users/{id}/posts/{id}");
+const UNBALANCED: &str = route!("/users/{id");
+const PARTIAL: &str = route!("/files/{name}.json");
+const WILDCARD: &str = route!("/static/{*path}/meta");
+const RELATIVE: &str = route!("users/{id}");
+
+fn main() {}
@@ -0,0 +1,6 @@
+use modkit_macros::module;
+
+#[module(name = "x", timeouts(init = "soon"))]
+pub struct X;
+
+fn main() {}
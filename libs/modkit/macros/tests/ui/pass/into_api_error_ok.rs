@@ -0,0 +1,44 @@
+use modkit::api::problem::{conflict, ProblemResponse};
+use modkit_macros::IntoApiError;
+
+#[derive(Debug, IntoApiError)]
+#[api_error(type_base = "https://errors.example.com/")]
+enum UsersError {
+    #[api_error(status = 404, code = "USERS_NOT_FOUND", title = "User not found")]
+    NotFound { id: u64 },
+    #[api_error(status = 400)]
+    Invalid(String),
+    #[api_error(status = 500, code = "INTERNAL")]
+    Internal,
+    #[api_error(transparent)]
+    Upstream(ProblemResponse),
+}
+
+impl std::fmt::Display for UsersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsersError::NotFound { id } => write!(f, "user {id} not found"),
+            UsersError::Invalid(msg) => write!(f, "invalid: {msg}"),
+            UsersError::Internal => write!(f, "connection string leaked"),
+            UsersError::Upstream(p) => write!(f, "{}", p.0.detail),
+        }
+    }
+}
+
+fn main() {
+    let p = ProblemResponse::from(UsersError::NotFound { id: 7 }).0;
+    assert_eq!(p.status, 404);
+    assert_eq!(p.title, "User not found");
+    assert_eq!(p.detail, "user 7 not found");
+    assert_eq!(p.type_url, "https://errors.example.com/USERS_NOT_FOUND");
+
+    let p = ProblemResponse::from(UsersError::Invalid("email".into())).0;
+    assert_eq!((p.status, p.title.as_str()), (400, "Bad Request"));
+
+    let p = ProblemResponse::from(UsersError::Internal).0;
+    assert_eq!(p.status, 500);
+    assert!(!p.detail.contains("leaked"));
+
+    let p = ProblemResponse::from(UsersError::Upstream(conflict("taken"))).0;
+    assert_eq!(p.status, 409);
+}
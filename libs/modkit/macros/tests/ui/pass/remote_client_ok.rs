@@ -0,0 +1,37 @@
+// Module whose client can be served from another process
+use modkit_macros::module;
+use std::sync::Arc;
+
+pub trait Directory: Send + Sync {
+    fn lookup(&self, id: u64) -> Option<String>;
+}
+
+struct HttpDirectory {
+    base: String,
+}
+
+impl Directory for HttpDirectory {
+    fn lookup(&self, id: u64) -> Option<String> {
+        Some(format!("{}/{id}", self.base))
+    }
+}
+
+fn connect(endpoint: &modkit::remote::RemoteEndpoint) -> anyhow::Result<Arc<dyn Directory>> {
+    Ok(Arc::new(HttpDirectory { base: endpoint.url.clone() }))
+}
+
+#[derive(Default)]
+#[module(name = "directory", client = Directory, remote = connect)]
+pub struct DirectoryModule;
+
+#[async_trait::async_trait]
+impl modkit::Module for DirectoryModule {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> anyhow::Result<()> {
+        Ok(())
+    }
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+fn main() {}
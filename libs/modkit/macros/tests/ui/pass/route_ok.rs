@@ -0,0 +1,9 @@
+use modkit_macros::route;
+
+const USER: &str = route!("/users/{id}");
+const FILES: &str = route!("/v1/files/{owner_id}/{*path}");
+
+fn main() {
+    assert_eq!(USER, "/users/{id}");
+    assert_eq!(FILES, "/v1/files/{owner_id}/{*path}");
+}
@@ -0,0 +1,18 @@
+// Per-module phase timeouts override the registry-wide defaults
+use modkit_macros::module;
+
+#[derive(Default)]
+#[module(name = "slow_boot", timeouts(init = "45s", migrate = "5m", start = "500ms"))]
+pub struct SlowBoot;
+
+#[async_trait::async_trait]
+impl modkit::Module for SlowBoot {
+    async fn init(&self, _ctx: &modkit::ModuleCtx) -> anyhow::Result<()> {
+        Ok(())
+    }
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+fn main() {}
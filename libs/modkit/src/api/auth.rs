@@ -0,0 +1,112 @@
+//! Authentication primitives shared between the ingress and module handlers.
+//!
+//! The ingress validates credentials and injects an [`AuthContext`] into request
+//! extensions; handlers consume it as an extractor. Operations declare what they
+//! need through [`SecurityRequirement`]s on the `OperationSpec`, which drives
+//! both enforcement and the OpenAPI `security` section.
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+
+use crate::api::problem::{unauthorized, ProblemResponse};
+
+/// Name of the bearer (JWT) security scheme in the OpenAPI document.
+pub const BEARER_AUTH_SCHEME: &str = "bearerAuth";
+
+/// Name of the API key (header) security scheme in the OpenAPI document.
+pub const API_KEY_AUTH_SCHEME: &str = "apiKeyAuth";
+
+/// Security requirement attached to an operation (one OpenAPI `security` entry).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecurityRequirement {
+    /// Name of the security scheme, e.g. [`BEARER_AUTH_SCHEME`].
+    pub scheme: String,
+    /// Scopes that must all be granted to the caller.
+    pub scopes: Vec<String>,
+}
+
+impl SecurityRequirement {
+    pub fn new(scheme: impl Into<String>, scopes: Vec<String>) -> Self {
+        Self {
+            scheme: scheme.into(),
+            scopes,
+        }
+    }
+}
+
+/// Authenticated caller, injected by the ingress auth layer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthContext {
+    /// Token subject (`sub` claim).
+    pub subject: String,
+    /// Granted scopes.
+    pub scopes: Vec<String>,
+    /// Tenant the caller acts on behalf of, if the token carries one.
+    pub tenant: Option<String>,
+}
+
+impl AuthContext {
+    /// Check whether a single scope was granted.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+
+    /// Check whether every scope in `required` was granted.
+    pub fn has_all_scopes<S: AsRef<str>>(&self, required: &[S]) -> bool {
+        required.iter().all(|r| self.has_scope(r.as_ref()))
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthContext
+where
+    S: Send + Sync,
+{
+    type Rejection = ProblemResponse;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthContext>()
+            .cloned()
+            .ok_or_else(|| unauthorized("Request is not authenticated"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    #[test]
+    fn scope_checks() {
+        let ctx = AuthContext {
+            subject: "user-1".into(),
+            scopes: vec!["users:read".into(), "users:write".into()],
+            tenant: None,
+        };
+        assert!(ctx.has_scope("users:read"));
+        assert!(!ctx.has_scope("admin"));
+        assert!(ctx.has_all_scopes(&["users:read", "users:write"]));
+        assert!(!ctx.has_all_scopes(&["users:read", "admin"]));
+        assert!(ctx.has_all_scopes::<&str>(&[]));
+    }
+
+    #[tokio::test]
+    async fn extractor_reads_extension_or_rejects() {
+        let (mut parts, _) = Request::new(()).into_parts();
+        let err = AuthContext::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.0.status, 401);
+
+        let ctx = AuthContext {
+            subject: "svc".into(),
+            scopes: vec![],
+            tenant: Some("t1".into()),
+        };
+        parts.extensions.insert(ctx.clone());
+        let got = AuthContext::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+        assert_eq!(got, ctx);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::api::problem::ProblemResponse;
-use axum::response::IntoResponse;
+use crate::api::problem::{Problem, ProblemResponse};
+use axum::{http::StatusCode, response::IntoResponse};
+use modkit_db::{DbError, DbErrorKind};
 use odata_core::Error as ODataError;
 
 /// Unified API error type that handles all errors at the API boundary
@@ -15,6 +16,10 @@ pub enum ApiError<D> {
     #[error(transparent)]
     OData(ODataError),
 
+    /// Database errors, mapped by [`DbErrorKind`] (unique violation → 409, not found → 404)
+    #[error(transparent)]
+    Db(DbError),
+
     /// Domain business logic errors
     #[error(transparent)]
     Domain(D),
@@ -39,6 +44,94 @@ impl<D> From<ODataError> for ApiError<D> {
     }
 }
 
+impl<D> From<DbError> for ApiError<D> {
+    fn from(e: DbError) -> Self {
+        ApiError::Db(e)
+    }
+}
+
+/// Map a database error to a Problem by its [`DbErrorKind`].
+///
+/// Driver messages may reveal schema details, so only the classification reaches
+/// the client; unexpected errors are logged.
+impl From<DbError> for ProblemResponse {
+    fn from(e: DbError) -> Self {
+        let problem = match e.kind() {
+            DbErrorKind::UniqueViolation => Problem::new(
+                StatusCode::CONFLICT,
+                "Conflict",
+                "A resource with the same unique value already exists",
+            )
+            .with_code("DB_UNIQUE_VIOLATION"),
+            DbErrorKind::ForeignKeyViolation => Problem::new(
+                StatusCode::CONFLICT,
+                "Conflict",
+                "The operation conflicts with a related resource",
+            )
+            .with_code("DB_FOREIGN_KEY_VIOLATION"),
+            DbErrorKind::NotFound => Problem::new(
+                StatusCode::NOT_FOUND,
+                "Not Found",
+                "The requested resource was not found",
+            )
+            .with_code("DB_NOT_FOUND"),
+            DbErrorKind::Unavailable => {
+                tracing::error!(error = ?e, "Database unavailable");
+                Problem::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Service Unavailable",
+                    "The database is temporarily unavailable",
+                )
+                .with_code("DB_UNAVAILABLE")
+            }
+            DbErrorKind::Other => {
+                tracing::error!(error = ?e, "Database error occurred");
+                Problem::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal Database Error",
+                    "An internal database error occurred",
+                )
+                .with_code("INTERNAL_DB")
+            }
+        };
+        problem.into()
+    }
+}
+
+/// Build the Problem for one variant of a `#[derive(IntoApiError)]` enum.
+///
+/// The type URL is `type_base` + `code`; for 5xx statuses the detail is logged
+/// instead of returned.
+#[doc(hidden)]
+pub fn derived_problem(
+    status: u16,
+    title: Option<&str>,
+    code: &str,
+    type_base: Option<&str>,
+    detail: String,
+) -> ProblemResponse {
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let title = title
+        .or_else(|| status.canonical_reason())
+        .unwrap_or("Error");
+    let detail = if status.is_server_error() {
+        tracing::error!(code, error = %detail, "Internal error occurred");
+        "An internal error occurred".to_string()
+    } else {
+        detail
+    };
+
+    let mut problem = Problem::new(status, title, detail).with_code(code);
+    if let (Some(base), false) = (type_base, code.is_empty()) {
+        problem = problem.with_type(format!("{base}{code}"));
+    }
+    // Add request ID from current tracing span if available
+    if let Some(id) = tracing::Span::current().id() {
+        problem = problem.with_trace_id(id.into_u64().to_string());
+    }
+    problem.into()
+}
+
 impl<D> IntoResponse for ApiError<D>
 where
     D: Into<ProblemResponse>,
@@ -50,6 +143,7 @@ where
                 // In real apps, this could be improved to get actual request path
                 crate::api::odata::odata_error_to_problem(&e, "/").into_response()
             }
+            ApiError::Db(e) => ProblemResponse::from(e).into_response(),
             ApiError::Domain(e) => {
                 // Convert the domain error to a ProblemResponse
                 e.into().into_response()
@@ -57,3 +151,19 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn db_errors_map_by_kind() {
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+        let resp = ApiError::<ProblemResponse>::from(DbError::Io(io)).into_response();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let resp = ApiError::<ProblemResponse>::from(DbError::InvalidConfig("secret".into()))
+            .into_response();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -3,49 +3,130 @@
 //! This module provides utilities for automatically converting all framework
 //! and module errors into consistent RFC 9457 Problem+JSON responses, eliminating
 //! per-route boilerplate.
+//!
+//! Modules can teach the mapping about foreign error types (third-party SDKs,
+//! clients) with [`register_error_mapper`]; the REST host collects them into
+//! [`ErrorMappers`], and handlers returning [`AnyhowResponse`] get those mappers
+//! applied with the request path and trace id filled in by
+//! [`error_mapping_middleware`].
 
 use axum::{
-    extract::Request,
-    http::{HeaderMap, StatusCode},
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use std::any::Any;
+use std::sync::Arc;
 
-use crate::api::problem::{Problem, ProblemResponse};
+use crate::api::problem::{InstancelessProblem, Problem, ProblemResponse};
+use crate::api::OpenApiRegistry;
 use crate::context::ConfigError;
 use odata_core::Error as ODataError;
 
-/// Middleware function that provides centralized error mapping
-///
-/// This middleware can be applied to routes to automatically extract request context
-/// and provide it to error handlers. The actual error conversion happens in the
-/// `IntoProblemResponse` trait implementations and `map_error_to_problem` function.
-pub async fn error_mapping_middleware(request: Request, next: Next) -> Response {
-    let _uri = request.uri().clone();
-    let _headers = request.headers().clone();
+/// A module-provided mapping from an error to a Problem; `None` means "not mine".
+pub type ErrorMapper = dyn Fn(&anyhow::Error) -> Option<Problem> + Send + Sync;
 
-    let response = next.run(request).await;
+/// The error mappers of one REST host, consulted in registration order.
+#[derive(Clone, Default)]
+pub struct ErrorMappers(Arc<[Arc<ErrorMapper>]>);
 
-    // If the response is already successful or is already a Problem response, pass it through
-    if response.status().is_success() || is_problem_response(&response) {
-        return response;
+impl ErrorMappers {
+    pub fn new(mappers: Vec<Arc<ErrorMapper>>) -> Self {
+        Self(mappers.into())
     }
 
-    // For error responses, the actual error conversion should happen in the handlers
-    // using the IntoProblemResponse trait or map_error_to_problem function
-    // This middleware provides the infrastructure for extracting request context
-    response
+    /// Run the mappers against an error; the first `Some` wins.
+    pub fn map(&self, error: &anyhow::Error) -> Option<Problem> {
+        self.0.iter().find_map(|mapper| mapper(error))
+    }
 }
 
-/// Check if a response is already a Problem+JSON response
-fn is_problem_response(response: &Response) -> bool {
-    response
-        .headers()
-        .get(axum::http::header::CONTENT_TYPE)
-        .and_then(|v| v.to_str().ok())
-        .map(|ct| ct.contains("application/problem+json"))
-        .unwrap_or(false)
+/// Register an error mapper with the REST host behind `openapi`, typically
+/// from a module's `register_rest`.
+///
+/// Mappers are consulted in registration order for `anyhow::Error`s before the
+/// generic 500; the first `Some` wins. Empty `instance`/`trace_id` fields of
+/// the returned Problem are filled in from the request.
+///
+/// ```ignore
+/// modkit::api::register_error_mapper(openapi, |e| {
+///     e.downcast_ref::<aws_sdk_s3::Error>()
+///         .map(|_| Problem::new(StatusCode::BAD_GATEWAY, "Bad Gateway", "Storage unavailable"))
+/// });
+/// ```
+pub fn register_error_mapper<F>(openapi: &dyn OpenApiRegistry, mapper: F)
+where
+    F: Fn(&anyhow::Error) -> Option<Problem> + Send + Sync + 'static,
+{
+    openapi.add_error_mapper(Arc::new(mapper));
+}
+
+/// Handler error carrying an `anyhow::Error` to [`error_mapping_middleware`].
+///
+/// Converts from any error via `?`. On its own it renders through
+/// [`map_error_to_problem`]; behind the middleware the Problem also gets the
+/// request path as `instance` and the request's trace id.
+#[derive(Debug)]
+pub struct AnyhowResponse(pub anyhow::Error);
+
+impl<E> From<E> for AnyhowResponse
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(e: E) -> Self {
+        Self(e.into())
+    }
+}
+
+/// Response extension through which [`AnyhowResponse`] hands its error to the middleware
+#[derive(Clone)]
+struct UnmappedError(Arc<anyhow::Error>);
+
+impl IntoResponse for AnyhowResponse {
+    fn into_response(self) -> Response {
+        let mut response = map_error_to_problem(&self.0 as &dyn Any, "", None).into_response();
+        response
+            .extensions_mut()
+            .insert(UnmappedError(Arc::new(self.0)));
+        response
+    }
+}
+
+/// Middleware function that provides centralized error mapping
+///
+/// Responses produced from an [`AnyhowResponse`] are re-rendered with the request
+/// path as `instance` and the request's trace id, consulting `mappers` first.
+/// Problem responses without an `instance` get the same two fields filled in.
+/// Everything else passes through untouched. Install it with
+/// `from_fn_with_state(mappers, error_mapping_middleware)`.
+pub async fn error_mapping_middleware(
+    State(mappers): State<ErrorMappers>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let instance = request.uri().path().to_string();
+    let trace_id = extract_trace_id(request.headers());
+
+    let mut response = next.run(request).await;
+
+    if let Some(UnmappedError(error)) = response.extensions_mut().remove::<UnmappedError>() {
+        return map_error(&mappers, error.as_ref() as &dyn Any, &instance, trace_id)
+            .into_response();
+    }
+    match response.extensions_mut().remove::<InstancelessProblem>() {
+        Some(InstancelessProblem(mut problem)) => {
+            problem.instance = instance;
+            if problem.trace_id.is_none() {
+                problem.trace_id = trace_id;
+            }
+            // Keep the handler's status and headers; only the body changes
+            let (mut parts, _) = response.into_parts();
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, axum::Json(problem).into_response().into_body())
+        }
+        None => response,
+    }
 }
 
 /// Extract trace ID from headers or generate one
@@ -73,6 +154,15 @@ pub fn map_error_to_problem(
     error: &dyn Any,
     instance: &str,
     trace_id: Option<String>,
+) -> ProblemResponse {
+    map_error(&ErrorMappers::default(), error, instance, trace_id)
+}
+
+fn map_error(
+    mappers: &ErrorMappers,
+    error: &dyn Any,
+    instance: &str,
+    trace_id: Option<String>,
 ) -> ProblemResponse {
     // Try to downcast to known error types
     if let Some(odata_err) = error.downcast_ref::<ODataError>() {
@@ -109,7 +199,9 @@ pub fn map_error_to_problem(
             .with_code("CONFIG_MISSING_SECTION")
             .with_type("https://errors.example.com/CONFIG_MISSING_SECTION"),
 
-            ConfigError::InvalidConfig { module, .. } => Problem::new(
+            ConfigError::InvalidConfig { module, .. }
+            | ConfigError::Violations { module, .. }
+            | ConfigError::UnknownKeys { module, .. } => Problem::new(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Configuration Error",
                 format!("Module '{}' has invalid configuration", module),
@@ -125,8 +217,18 @@ pub fn map_error_to_problem(
         return problem.into();
     }
 
-    // Handle anyhow::Error
+    // Handle anyhow::Error, letting registered mappers claim it first
     if let Some(anyhow_err) = error.downcast_ref::<anyhow::Error>() {
+        if let Some(mut problem) = mappers.map(anyhow_err) {
+            if problem.instance.is_empty() {
+                problem = problem.with_instance(instance);
+            }
+            if let (None, Some(tid)) = (&problem.trace_id, trace_id) {
+                problem = problem.with_trace_id(tid);
+            }
+            return problem.into();
+        }
+
         let mut problem = Problem::new(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Internal Server Error",
@@ -237,4 +339,104 @@ mod tests {
         let trace_id = extract_trace_id(&headers);
         assert_eq!(trace_id, Some("test-trace-123".to_string()));
     }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("quota exceeded for bucket {0}")]
+    struct QuotaError(String);
+
+    #[tokio::test]
+    async fn test_registered_mapper_applies_through_middleware() {
+        use axum::{body::Body, middleware::from_fn_with_state, routing::get, Router};
+        use tower::ServiceExt;
+
+        let quota: Arc<ErrorMapper> = Arc::new(|e| {
+            e.downcast_ref::<QuotaError>().map(|q| {
+                Problem::new(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    "Quota Exceeded",
+                    q.to_string(),
+                )
+                .with_code("QUOTA_EXCEEDED")
+            })
+        });
+
+        async fn upload() -> Result<&'static str, AnyhowResponse> {
+            Err(QuotaError("reports".into()))?
+        }
+        async fn crash() -> Result<&'static str, AnyhowResponse> {
+            Err(anyhow::anyhow!("boom"))?
+        }
+        let app = Router::new()
+            .route("/upload", get(upload))
+            .route("/crash", get(crash))
+            .layer(from_fn_with_state(
+                ErrorMappers::new(vec![quota]),
+                error_mapping_middleware,
+            ));
+
+        let call = |path: &'static str| {
+            let app = app.clone();
+            async move {
+                let request = Request::get(path)
+                    .header("x-request-id", "req-1")
+                    .body(Body::empty())
+                    .unwrap();
+                let resp = app.oneshot(request).await.unwrap();
+                let status = resp.status();
+                let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let problem: Problem = serde_json::from_slice(&bytes).unwrap();
+                (status, problem)
+            }
+        };
+
+        let (status, problem) = call("/upload").await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(problem.code, "QUOTA_EXCEEDED");
+        assert_eq!(problem.instance, "/upload");
+        assert_eq!(problem.trace_id.as_deref(), Some("req-1"));
+
+        let (status, problem) = call("/crash").await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(problem.code, "INTERNAL_ERROR");
+        assert_eq!(problem.instance, "/crash");
+    }
+
+    #[tokio::test]
+    async fn test_problem_without_instance_gets_request_path() {
+        use axum::{body::Body, middleware::from_fn_with_state, routing::get, Router};
+        use tower::ServiceExt;
+
+        async fn lookup() -> axum::response::Response {
+            let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+            let problem = ProblemResponse::from(modkit_db::DbError::Io(io));
+            ([("retry-after", "5")], problem).into_response()
+        }
+        let app = Router::new()
+            .route("/users/{id}", get(lookup))
+            .layer(from_fn_with_state(
+                ErrorMappers::default(),
+                error_mapping_middleware,
+            ));
+
+        let request = Request::get("/users/7")
+            .header("x-request-id", "req-2")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(request).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()["retry-after"], "5");
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            crate::api::APPLICATION_PROBLEM_JSON
+        );
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: Problem = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(problem.code, "DB_UNAVAILABLE");
+        assert_eq!(problem.instance, "/users/7");
+        assert_eq!(problem.trace_id.as_deref(), Some("req-2"));
+    }
 }
@@ -4,6 +4,7 @@
 //! that API operations cannot be registered unless both a handler and at least one
 //! response are specified.
 
+pub mod auth;
 pub mod error;
 pub mod error_layer;
 pub mod odata;
@@ -11,17 +12,30 @@ pub mod odata_policy_tests;
 pub mod operation_builder;
 pub mod pagination;
 pub mod problem;
+pub mod resource_builder;
+pub mod response;
+pub mod validation;
 
+pub use auth::{AuthContext, SecurityRequirement, API_KEY_AUTH_SCHEME, BEARER_AUTH_SCHEME};
 pub use error::ApiError;
 pub use error_layer::{
-    error_mapping_middleware, extract_trace_id, map_error_to_problem, IntoProblemResponse,
+    error_mapping_middleware, extract_trace_id, map_error_to_problem, register_error_mapper,
+    AnyhowResponse, ErrorMapper, ErrorMappers, IntoProblemResponse,
 };
 pub use operation_builder::{
-    ensure_schema, state, Missing, OpenApiRegistry, OperationBuilder, OperationSpec, ParamLocation,
-    ParamSpec, Present, ResponseSpec,
+    ensure_generic_schema, ensure_schema, ensure_schema_named, ensure_schema_with_extensions,
+    generic_schema_name, path_template_params, schema_extensions_mut, schema_name, state,
+    ArrayParam, CallbackOperation, CallbackSpec, CollectionFormat, ExternalDocsSpec, GenericSchema,
+    Missing, ModuleOpenApiRegistry, MultipartField, OpenApiRegistry, OperationBuilder,
+    OperationSpec, ParamLocation, ParamSpec, PathTemplateError, Present, RateLimitSpec,
+    ResponseExampleSpec, ResponseHeaderSpec, ResponseSpec, ResponseStatus,
 };
 pub use pagination::{normalize_filter_for_hash, short_filter_hash};
 pub use problem::{
-    bad_request, conflict, internal_error, not_found, Problem, ProblemResponse, ValidationError,
-    APPLICATION_PROBLEM_JSON,
+    bad_request, conflict, forbidden, internal_error, not_found, unauthorized, Problem,
+    ProblemResponse, ValidationError, APPLICATION_PROBLEM_JSON,
+};
+pub use resource_builder::{ResourceBuilder, ResourceOp};
+pub use validation::{
+    from_json_path_error, from_validator_errors, validation_problem, ValidatedJson,
 };
@@ -9,12 +9,24 @@
 //! - Schema-aware responses (`json_response_with_schema`)
 //! - Typed Router state `S` usage pattern: pass a state type once via `Router::with_state`,
 //!   then use plain function handlers (no per-route closures that capture/clones).
-//! - Optional `method_router(...)` for advanced use (layers/middleware on route level).
-
-use axum::{handler::Handler, routing::MethodRouter, Router};
+//! - Route-level middleware via `layer(...)`, or a hand-built `method_router(...)`.
+//! - WebSocket endpoints (`websocket`) registered and documented like any other operation.
+
+use axum::{
+    extract::Request,
+    handler::Handler,
+    response::IntoResponse,
+    routing::{MethodRouter, Route},
+    Router,
+};
 use http::Method;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
 use std::marker::PhantomData;
+use tower::{Layer, Service};
+use utoipa::openapi::extensions::Extensions;
 
+use crate::api::auth::{SecurityRequirement, API_KEY_AUTH_SCHEME, BEARER_AUTH_SCHEME};
 use crate::api::problem;
 
 /// Type alias for schema collections used in API operations.
@@ -65,6 +77,30 @@ pub struct ParamSpec {
     pub required: bool,
     pub description: Option<String>,
     pub param_type: String, // JSON Schema type (string, integer, etc.)
+    /// OpenAPI `format` refining `param_type` (e.g. `uuid`, `int64`).
+    pub format: Option<String>,
+    /// Allowed values, rendered as `enum` (on the items for array parameters).
+    pub enum_values: Vec<String>,
+    /// Item type and serialization when `param_type` is `array`.
+    pub items: Option<ArrayParam>,
+}
+
+/// Items of an array parameter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArrayParam {
+    /// JSON Schema type of each item.
+    pub item_type: String,
+    pub collection: CollectionFormat,
+}
+
+/// How an array parameter is serialized in the query string
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollectionFormat {
+    /// `status=active,archived` (`style: form`, `explode: false`)
+    #[default]
+    Csv,
+    /// `status=active&status=archived` (`style: form`, `explode: true`)
+    Multi,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -85,18 +121,215 @@ pub struct RequestBodySpec {
     pub schema_name: Option<String>,
     /// Whether request body is required (OpenAPI default is `false`).
     pub required: bool,
+    /// Fields of a `multipart/form-data` body, rendered as an inline schema.
+    pub multipart_fields: Vec<MultipartField>,
+}
+
+/// Field of a `multipart/form-data` request body
+#[derive(Clone, Debug)]
+pub struct MultipartField {
+    pub name: String,
+    /// `true` for file uploads (`format: binary`), `false` for text fields.
+    pub file: bool,
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+impl MultipartField {
+    /// Required text field.
+    pub fn text(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            file: false,
+            required: true,
+            description: None,
+        }
+    }
+
+    /// Required file field.
+    pub fn file(name: impl Into<String>) -> Self {
+        Self {
+            file: true,
+            ..Self::text(name)
+        }
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.required = false;
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
 }
 
 /// Response specification for API operations
 #[derive(Clone, Debug)]
 pub struct ResponseSpec {
-    pub status: u16,
+    pub status: ResponseStatus,
     pub content_type: &'static str,
     pub description: String,
     /// Name of a registered component schema (if any).
     pub schema_name: Option<String>,
 }
 
+impl ResponseSpec {
+    /// Whether this is the `default` response rather than a concrete status.
+    pub fn is_default(&self) -> bool {
+        self.status == ResponseStatus::Default
+    }
+}
+
+/// Status a documented response applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResponseStatus {
+    /// A concrete HTTP status code.
+    Code(u16),
+    /// The OpenAPI `default` response, covering every status not listed.
+    Default,
+}
+
+impl ResponseStatus {
+    /// The HTTP status code, `None` for the `default` response.
+    pub fn code(self) -> Option<u16> {
+        match self {
+            Self::Code(code) => Some(code),
+            Self::Default => None,
+        }
+    }
+}
+
+/// Serialized like the OpenAPI key: a number, or `"default"`.
+impl serde::Serialize for ResponseStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Code(code) => serializer.serialize_u16(*code),
+            Self::Default => serializer.serialize_str("default"),
+        }
+    }
+}
+
+/// Key of the response in the OpenAPI `responses` map: `"200"` or `"default"`.
+impl std::fmt::Display for ResponseStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Code(code) => write!(f, "{code}"),
+            Self::Default => f.write_str("default"),
+        }
+    }
+}
+
+/// Response header documented on the successful (2xx) responses of an operation
+#[derive(Clone, Debug)]
+pub struct ResponseHeaderSpec {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Link to documentation outside the OpenAPI document
+#[derive(Clone, Debug)]
+pub struct ExternalDocsSpec {
+    pub url: String,
+    pub description: Option<String>,
+}
+
+/// Per-route request rate, enforced by the ingress with a token bucket
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitSpec {
+    /// Sustained requests per second.
+    pub rps: u32,
+    /// Requests allowed in a burst above the sustained rate.
+    pub burst: u32,
+}
+
+/// Outbound request the module makes to a consumer-supplied URL, documented as
+/// an OpenAPI callback (e.g. a webhook delivery)
+#[derive(Clone, Debug)]
+pub struct CallbackSpec {
+    /// Callback name, e.g. `orderShipped`.
+    pub name: String,
+    /// Runtime expression for the target URL, e.g. `{$request.body#/callback_url}`.
+    pub url_expression: String,
+    pub operation: CallbackOperation,
+}
+
+/// Request sent to a callback URL and the responses the module expects back
+#[derive(Clone, Debug)]
+pub struct CallbackOperation {
+    pub method: Method,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub request_body: Option<RequestBodySpec>,
+    pub responses: Vec<ResponseSpec>,
+}
+
+impl CallbackOperation {
+    pub fn new(method: Method) -> Self {
+        Self {
+            method,
+            summary: None,
+            description: None,
+            request_body: None,
+            responses: Vec::new(),
+        }
+    }
+
+    /// `POST` callback, the usual webhook shape.
+    pub fn post() -> Self {
+        Self::new(Method::POST)
+    }
+
+    pub fn summary(mut self, text: impl Into<String>) -> Self {
+        self.summary = Some(text.into());
+        self
+    }
+
+    pub fn description(mut self, text: impl Into<String>) -> Self {
+        self.description = Some(text.into());
+        self
+    }
+
+    /// JSON payload of type `T` sent to the consumer (auto-registers the schema).
+    pub fn json_payload<T>(
+        mut self,
+        registry: &dyn OpenApiRegistry,
+        description: impl Into<String>,
+    ) -> Self
+    where
+        T: utoipa::ToSchema + utoipa::PartialSchema + 'static,
+    {
+        self.request_body = Some(RequestBodySpec {
+            content_type: "application/json",
+            description: Some(description.into()),
+            schema_name: Some(ensure_schema::<T>(registry)),
+            required: true,
+            multipart_fields: Vec::new(),
+        });
+        self
+    }
+
+    /// Body-less response the consumer is expected to return, e.g. `204`.
+    pub fn response(mut self, status: u16, description: impl Into<String>) -> Self {
+        self.responses.push(ResponseSpec {
+            status: ResponseStatus::Code(status),
+            content_type: "",
+            description: description.into(),
+            schema_name: None,
+        });
+        self
+    }
+}
+
+/// Named example of a response body
+#[derive(Clone, Debug)]
+pub struct ResponseExampleSpec {
+    pub status: u16,
+    pub name: String,
+    pub value: serde_json::Value,
+}
+
 /// Simplified operation specification for the type-safe builder
 #[derive(Clone, Debug)]
 pub struct OperationSpec {
@@ -109,10 +342,96 @@ pub struct OperationSpec {
     pub params: Vec<ParamSpec>,
     pub request_body: Option<RequestBodySpec>,
     pub responses: Vec<ResponseSpec>,
+    /// Security requirements; enforced by the ingress and rendered as OpenAPI `security`.
+    pub security: Vec<SecurityRequirement>,
+    /// API version (e.g. `v1`); the path is mounted under `/{version}`.
+    pub version: Option<String>,
+    /// Headers returned with successful responses (e.g. `ETag`).
+    pub response_headers: Vec<ResponseHeaderSpec>,
+    /// Example request body shown in the docs.
+    pub request_example: Option<serde_json::Value>,
+    /// Named response body examples, matched to responses by status.
+    pub response_examples: Vec<ResponseExampleSpec>,
+    /// WebSocket endpoint (`GET` + upgrade); rendered as `x-websocket: true`.
+    pub websocket: bool,
+    /// Marked deprecated in OpenAPI; the ingress adds a `Deprecation` header.
+    pub deprecated: bool,
+    /// Planned removal date (RFC 3339, `YYYY-MM-DD` or HTTP-date), sent as `Sunset`.
+    pub sunset: Option<String>,
+    /// Long-form documentation linked from the operation.
+    pub external_docs: Option<ExternalDocsSpec>,
+    /// Outbound requests triggered by this operation (webhooks), as OpenAPI `callbacks`.
+    pub callbacks: Vec<CallbackSpec>,
+    /// Route-wide rate limit; rendered as `x-rate-limit`.
+    pub rate_limit: Option<RateLimitSpec>,
+    /// Vendor extensions (`x-*`) emitted on the OpenAPI operation.
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Owning module; filled in by the runtime during the REST phase.
+    pub module: Option<String>,
     /// Internal handler id; can be used by registry/generator to map a handler identity
     pub handler_id: String,
 }
 
+impl OperationSpec {
+    /// Check the path template and that its parameters match the declared
+    /// `path_param`s exactly. The ingress refuses to start on a mismatch.
+    pub fn validate_path(&self) -> Result<(), PathTemplateError> {
+        let in_path = path_template_params(&self.path)?;
+        let declared: Vec<&str> = self
+            .params
+            .iter()
+            .filter(|p| p.location == ParamLocation::Path)
+            .map(|p| p.name.as_str())
+            .collect();
+        if let Some(missing) = in_path.iter().find(|p| !declared.contains(&p.as_str())) {
+            return Err(PathTemplateError::Undeclared(missing.clone()));
+        }
+        if let Some(extra) = declared.iter().find(|d| !in_path.iter().any(|p| p == *d)) {
+            return Err(PathTemplateError::NotInPath(extra.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Malformed route template or path parameters not matching it
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum PathTemplateError {
+    #[error("path must start with '/'")]
+    NotAbsolute,
+    #[error("segment '{0}' must be a single '{{name}}' parameter")]
+    MalformedSegment(String),
+    #[error("invalid parameter name '{0}'")]
+    InvalidName(String),
+    #[error("wildcard '{{*{0}}}' must be the last segment")]
+    WildcardNotLast(String),
+    #[error("duplicate parameter '{0}'")]
+    Duplicate(String),
+    #[error("path parameter '{0}' is not declared with path_param")]
+    Undeclared(String),
+    #[error("declared path parameter '{0}' does not appear in the path")]
+    NotInPath(String),
+}
+
+impl From<modkit_route_template::TemplateError> for PathTemplateError {
+    fn from(e: modkit_route_template::TemplateError) -> Self {
+        use modkit_route_template::TemplateError;
+        match e {
+            TemplateError::NotAbsolute => Self::NotAbsolute,
+            TemplateError::MalformedSegment(s) => Self::MalformedSegment(s),
+            TemplateError::InvalidName(s) => Self::InvalidName(s),
+            TemplateError::WildcardNotLast(s) => Self::WildcardNotLast(s),
+            TemplateError::Duplicate(s) => Self::Duplicate(s),
+        }
+    }
+}
+
+/// Parameter names of an axum route template (`/users/{id}/{*rest}` → `id`, `rest`).
+///
+/// The `route!` macro applies the same rules at compile time.
+pub fn path_template_params(path: &str) -> Result<Vec<String>, PathTemplateError> {
+    Ok(modkit_route_template::params(path)?)
+}
+
 //
 pub trait OperationBuilderODataExt<S, H, R> {
     /// Adds optional `$filter` query parameter to OpenAPI.
@@ -133,6 +452,9 @@ where
             required: false,
             description: Some("OData v4 filter expression".to_string()),
             param_type: "string".to_string(),
+            format: None,
+            enum_values: Vec::new(),
+            items: None,
         });
         self
     }
@@ -144,13 +466,16 @@ where
             required: false,
             description: Some(description.into()),
             param_type: "string".to_string(),
+            format: None,
+            enum_values: Vec::new(),
+            items: None,
         });
         self
     }
 }
 
 /// Registry trait for OpenAPI operations and schemas
-pub trait OpenApiRegistry {
+pub trait OpenApiRegistry: Send + Sync {
     /// Register an API operation specification
     fn register_operation(&self, spec: &OperationSpec);
 
@@ -161,16 +486,31 @@ pub trait OpenApiRegistry {
 
     /// Downcast support for accessing the concrete implementation if needed.
     fn as_any(&self) -> &dyn std::any::Any;
+
+    /// Add a mapper for errors returned through
+    /// [`AnyhowResponse`](crate::api::AnyhowResponse); see
+    /// [`register_error_mapper`](crate::api::register_error_mapper). Registries
+    /// that don't serve requests ignore it.
+    fn add_error_mapper(&self, _mapper: std::sync::Arc<crate::api::ErrorMapper>) {}
 }
 
 /// Helper function to call ensure_schema with proper type information
 pub fn ensure_schema<T: utoipa::ToSchema + utoipa::PartialSchema + 'static>(
     registry: &dyn OpenApiRegistry,
+) -> String {
+    ensure_schema_named::<T>(registry, schema_name::<T>())
+}
+
+/// Like [`ensure_schema`], but registers `T` under an explicit component name,
+/// e.g. to give a generic instantiation a domain-specific name.
+pub fn ensure_schema_named<T: utoipa::ToSchema + utoipa::PartialSchema + 'static>(
+    registry: &dyn OpenApiRegistry,
+    name: impl Into<String>,
 ) -> String {
     use utoipa::PartialSchema;
 
-    // 1) Canonical component name for T as seen by utoipa
-    let root_name = T::name().to_string();
+    // 1) Component name for T
+    let root_name = name.into();
 
     // 2) Always insert T's own schema first (actual object, not a ref)
     //    This avoids self-referential components.
@@ -183,6 +523,183 @@ pub fn ensure_schema<T: utoipa::ToSchema + utoipa::PartialSchema + 'static>(
     registry.ensure_schema_raw(&root_name, collected)
 }
 
+/// Component name of `T`, i.e. [`utoipa::ToSchema::name`].
+///
+/// Generic wrappers should be registered through [`ensure_generic_schema`]:
+/// utoipa names every instantiation after the bare type, so `Page<UserDto>`
+/// and `Page<OrderDto>` would overwrite each other.
+pub fn schema_name<T: utoipa::ToSchema + ?Sized>() -> String {
+    T::name().into_owned()
+}
+
+/// A generic schema type whose component name includes its type arguments.
+///
+/// Implemented for [`odata_core::Page`]; implement it for other generic DTO
+/// wrappers that are returned with different item types.
+pub trait GenericSchema: utoipa::ToSchema {
+    /// Component names of the type arguments, in declaration order.
+    fn type_arg_names() -> Vec<String>;
+}
+
+impl<T: utoipa::ToSchema> GenericSchema for odata_core::Page<T> {
+    fn type_arg_names() -> Vec<String> {
+        vec![T::name().into_owned()]
+    }
+}
+
+/// Component name of a generic instantiation: the base name followed by the
+/// type argument names, e.g. `Page_UserDto`.
+pub fn generic_schema_name<T: GenericSchema + ?Sized>() -> String {
+    let mut name = T::name().into_owned();
+    for arg in T::type_arg_names() {
+        name.push('_');
+        name.push_str(&arg);
+    }
+    name
+}
+
+/// Like [`ensure_schema`], but registers `T` under [`generic_schema_name`].
+pub fn ensure_generic_schema<T>(registry: &dyn OpenApiRegistry) -> String
+where
+    T: GenericSchema + utoipa::PartialSchema + 'static,
+{
+    ensure_schema_named::<T>(registry, generic_schema_name::<T>())
+}
+
+/// Like [`ensure_schema`], but attaches vendor extensions (`x-*`) to `T`'s own
+/// component schema, e.g. `[("x-internal", json!(true))]`.
+pub fn ensure_schema_with_extensions<T, I, K>(
+    registry: &dyn OpenApiRegistry,
+    extensions: I,
+) -> String
+where
+    T: utoipa::ToSchema + utoipa::PartialSchema + 'static,
+    I: IntoIterator<Item = (K, serde_json::Value)>,
+    K: Into<String>,
+{
+    use utoipa::PartialSchema;
+
+    let root_name = schema_name::<T>();
+    let mut root = <T as PartialSchema>::schema();
+    let added: Extensions = extensions
+        .into_iter()
+        .map(|(k, v)| (vendor_key(k.into()), v))
+        .collect();
+    if let Some(slot) = schema_extensions_mut(&mut root) {
+        slot.get_or_insert_with(Extensions::default).merge(added);
+    }
+    let mut collected: SchemaCollection = vec![(root_name.clone(), root)];
+    T::schemas(&mut collected);
+    registry.ensure_schema_raw(&root_name, collected)
+}
+
+/// Vendor extensions slot of an inline schema (`None` for `$ref`s).
+pub fn schema_extensions_mut(
+    schema: &mut utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+) -> Option<&mut Option<Extensions>> {
+    use utoipa::openapi::{schema::Schema, RefOr};
+
+    match schema {
+        RefOr::T(Schema::Object(o)) => Some(&mut o.extensions),
+        RefOr::T(Schema::Array(a)) => Some(&mut a.extensions),
+        RefOr::T(Schema::OneOf(o)) => Some(&mut o.extensions),
+        RefOr::T(Schema::AllOf(a)) => Some(&mut a.extensions),
+        RefOr::T(Schema::AnyOf(a)) => Some(&mut a.extensions),
+        _ => None,
+    }
+}
+
+/// OpenAPI only allows `x-` prefixed extension keys; add the prefix when missing.
+fn vendor_key(key: String) -> String {
+    if key.starts_with("x-") {
+        key
+    } else {
+        format!("x-{key}")
+    }
+}
+
+/// Registry wrapper the runtime hands to each module during the REST phase.
+///
+/// Tags every registered operation with its owning module and, when the module
+/// is mounted under a `rest_prefix`, prefixes its path so the OpenAPI paths
+/// match the routes actually served.
+pub struct ModuleOpenApiRegistry<'a> {
+    inner: &'a dyn OpenApiRegistry,
+    module: String,
+    prefix: Option<String>,
+}
+
+impl<'a> ModuleOpenApiRegistry<'a> {
+    pub fn new(inner: &'a dyn OpenApiRegistry, module: impl Into<String>) -> Self {
+        Self {
+            inner,
+            module: module.into(),
+            prefix: None,
+        }
+    }
+
+    /// Prefix every registered path (e.g. `/api/users-info`).
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+}
+
+impl OpenApiRegistry for ModuleOpenApiRegistry<'_> {
+    fn register_operation(&self, spec: &OperationSpec) {
+        let mut spec = spec.clone();
+        spec.module = Some(self.module.clone());
+        if let Some(prefix) = &self.prefix {
+            spec.path = format!("{prefix}{}", spec.path);
+            spec.handler_id = handler_id_for(&spec.method, &spec.path);
+        }
+        self.inner.register_operation(&spec);
+    }
+
+    fn ensure_schema_raw(&self, name: &str, schemas: SchemaCollection) -> String {
+        self.inner.ensure_schema_raw(name, schemas)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+
+    fn add_error_mapper(&self, mapper: std::sync::Arc<crate::api::ErrorMapper>) {
+        self.inner.add_error_mapper(mapper);
+    }
+}
+
+/// Primitive JSON Schema type of a property (`string` when it cannot be told).
+fn json_schema_type(prop: &serde_json::Value) -> String {
+    let ty = match prop.get("type") {
+        Some(serde_json::Value::String(t)) => Some(t.as_str()),
+        // `Option<T>` renders as `[T, "null"]`
+        Some(serde_json::Value::Array(types)) => types
+            .iter()
+            .filter_map(|t| t.as_str())
+            .find(|t| *t != "null"),
+        _ => None,
+    };
+    ty.unwrap_or("string").to_string()
+}
+
+/// String values of a property's `enum`, if any.
+fn json_enum_values(prop: &serde_json::Value) -> Vec<String> {
+    prop.get("enum")
+        .and_then(|e| e.as_array())
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_owned))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+const PAGE_LIMIT_DESCRIPTION: &str = "Maximum number of items to return";
+const PAGE_CURSOR_DESCRIPTION: &str =
+    "Opaque cursor from `page_info.next_cursor` or `page_info.prev_cursor`";
+
 /// Type-safe operation builder with compile-time guarantees.
 ///
 /// Generic parameters:
@@ -204,15 +721,19 @@ where
 // -------------------------------------------------------------------------------------------------
 // Constructors — starts with both handler and response missing
 // -------------------------------------------------------------------------------------------------
+fn handler_id_for(method: &Method, path: &str) -> String {
+    format!(
+        "{}:{}",
+        method.as_str().to_lowercase(),
+        path.replace(['/', '{', '}'], "_")
+    )
+}
+
 impl<S> OperationBuilder<Missing, Missing, S> {
     /// Create a new operation builder with an HTTP method and path
     pub fn new(method: Method, path: impl Into<String>) -> Self {
         let path_str = path.into();
-        let handler_id = format!(
-            "{}:{}",
-            method.as_str().to_lowercase(),
-            path_str.replace(['/', '{', '}'], "_")
-        );
+        let handler_id = handler_id_for(&method, &path_str);
 
         Self {
             spec: OperationSpec {
@@ -225,6 +746,19 @@ impl<S> OperationBuilder<Missing, Missing, S> {
                 params: Vec::new(),
                 request_body: None,
                 responses: Vec::new(),
+                security: Vec::new(),
+                version: None,
+                response_headers: Vec::new(),
+                request_example: None,
+                response_examples: Vec::new(),
+                websocket: false,
+                deprecated: false,
+                sunset: None,
+                external_docs: None,
+                callbacks: Vec::new(),
+                rate_limit: None,
+                extensions: BTreeMap::new(),
+                module: None,
                 handler_id,
             },
             method_router: (), // no router in Missing state
@@ -290,6 +824,86 @@ where
         self
     }
 
+    /// Register the operation under an API version: the route is mounted at
+    /// `/{version}{path}` and the operation appears in `/openapi-{version}.json`.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        let version = version.into();
+        let path = match &self.spec.version {
+            Some(old) => self
+                .spec
+                .path
+                .strip_prefix(&format!("/{old}"))
+                .unwrap_or(&self.spec.path)
+                .to_string(),
+            None => self.spec.path.clone(),
+        };
+        self.spec.path = format!("/{version}{path}");
+        self.spec.handler_id = handler_id_for(&self.spec.method, &self.spec.path);
+        self.spec.version = Some(version);
+        self
+    }
+
+    /// Document a header returned with successful responses.
+    pub fn response_header(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.spec.response_headers.push(ResponseHeaderSpec {
+            name: name.into(),
+            description: Some(description.into()),
+        });
+        self
+    }
+
+    /// Document conditional GET support: the `ETag` response header, the
+    /// `If-None-Match` request header and the `304 Not Modified` response.
+    /// Pair with `modkit::api::response::ETagged` in the handler.
+    pub fn with_etag(mut self) -> Self {
+        self = self.response_header("ETag", "Entity tag of the returned representation");
+        self.spec.params.push(ParamSpec {
+            name: "If-None-Match".to_string(),
+            location: ParamLocation::Header,
+            required: false,
+            description: Some("Return 304 if the representation still matches this ETag".into()),
+            param_type: "string".to_string(),
+            format: None,
+            enum_values: Vec::new(),
+            items: None,
+        });
+        self.spec.responses.push(ResponseSpec {
+            status: ResponseStatus::Code(304),
+            content_type: "",
+            description: "Not Modified".to_string(),
+            schema_name: None,
+        });
+        self
+    }
+
+    /// Document optimistic concurrency: the `If-Match` request header and the
+    /// `412 Precondition Failed` Problem. Pair with
+    /// `modkit::api::response::{if_match, precondition_failed}` in the handler.
+    pub fn with_if_match(mut self, registry: &dyn OpenApiRegistry) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: "If-Match".to_string(),
+            location: ParamLocation::Header,
+            required: false,
+            description: Some("Apply only if the resource still matches this ETag".into()),
+            param_type: "string".to_string(),
+            format: None,
+            enum_values: Vec::new(),
+            items: None,
+        });
+        let problem_name = ensure_schema::<crate::api::problem::Problem>(registry);
+        self.spec.responses.push(ResponseSpec {
+            status: ResponseStatus::Code(412),
+            content_type: problem::APPLICATION_PROBLEM_JSON,
+            description: "Precondition Failed".to_string(),
+            schema_name: Some(problem_name),
+        });
+        self
+    }
+
     /// Add a tag to the operation
     pub fn tag(mut self, tag: impl Into<String>) -> Self {
         self.spec.tags.push(tag.into());
@@ -310,10 +924,45 @@ where
             required: true,
             description: Some(description.into()),
             param_type: "string".to_string(),
+            format: None,
+            enum_values: Vec::new(),
+            items: None,
+        });
+        self
+    }
+
+    /// Add a path parameter with an explicit OpenAPI type and optional format,
+    /// e.g. `path_param_typed("id", "integer", Some("int32"), "Item id")`.
+    pub fn path_param_typed(
+        mut self,
+        name: impl Into<String>,
+        param_type: impl Into<String>,
+        format: Option<&str>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: name.into(),
+            location: ParamLocation::Path,
+            required: true,
+            description: Some(description.into()),
+            param_type: param_type.into(),
+            format: format.map(str::to_owned),
+            enum_values: Vec::new(),
+            items: None,
         });
         self
     }
 
+    /// Add a path parameter documented as a UUID string.
+    pub fn path_param_uuid(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.path_param_typed(name, "string", Some("uuid"), description)
+    }
+
+    /// Add a path parameter documented as a 64-bit integer.
+    pub fn path_param_i64(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.path_param_typed(name, "integer", Some("int64"), description)
+    }
+
     /// Add a query parameter (defaults to string)
     pub fn query_param(
         mut self,
@@ -327,6 +976,9 @@ where
             required,
             description: Some(description.into()),
             param_type: "string".to_string(),
+            format: None,
+            enum_values: Vec::new(),
+            items: None,
         });
         self
     }
@@ -345,10 +997,121 @@ where
             required,
             description: Some(description.into()),
             param_type: param_type.into(),
+            format: None,
+            enum_values: Vec::new(),
+            items: None,
         });
         self
     }
 
+    /// Add a string query parameter restricted to `values`, e.g. `sort=asc|desc`.
+    pub fn query_param_enum<I, V>(
+        mut self,
+        name: impl Into<String>,
+        required: bool,
+        description: impl Into<String>,
+        values: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        self.spec.params.push(ParamSpec {
+            name: name.into(),
+            location: ParamLocation::Query,
+            required,
+            description: Some(description.into()),
+            param_type: "string".to_string(),
+            format: None,
+            enum_values: values.into_iter().map(Into::into).collect(),
+            items: None,
+        });
+        self
+    }
+
+    /// Add an array query parameter of `item_type` items, e.g.
+    /// `status=active,archived` with [`CollectionFormat::Csv`]. Restrict the
+    /// items with [`allowed_values`](Self::allowed_values).
+    pub fn query_param_array(
+        mut self,
+        name: impl Into<String>,
+        required: bool,
+        description: impl Into<String>,
+        item_type: impl Into<String>,
+        collection: CollectionFormat,
+    ) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: name.into(),
+            location: ParamLocation::Query,
+            required,
+            description: Some(description.into()),
+            param_type: "array".to_string(),
+            format: None,
+            enum_values: Vec::new(),
+            items: Some(ArrayParam {
+                item_type: item_type.into(),
+                collection,
+            }),
+        });
+        self
+    }
+
+    /// Restrict the values of the most recently added parameter (the items, for
+    /// arrays), e.g. `.query_param_array(...).allowed_values(["active", "archived"])`.
+    pub fn allowed_values<I, V>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        if let Some(param) = self.spec.params.last_mut() {
+            param.enum_values = values.into_iter().map(Into::into).collect();
+        }
+        self
+    }
+
+    /// Declare one query parameter per field of `T` (the type you extract with
+    /// `Query<T>`): type from the field schema, required unless optional, and
+    /// description from the field's doc comment.
+    pub fn query_params<T>(mut self) -> Self
+    where
+        T: utoipa::ToSchema + serde::de::DeserializeOwned,
+    {
+        let schema =
+            serde_json::to_value(<T as utoipa::PartialSchema>::schema()).unwrap_or_default();
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (name, prop) in props {
+                self.spec.params.push(ParamSpec {
+                    name: name.clone(),
+                    location: ParamLocation::Query,
+                    required: required.contains(&name.as_str()),
+                    description: prop
+                        .get("description")
+                        .and_then(|d| d.as_str())
+                        .map(str::to_owned),
+                    param_type: json_schema_type(prop),
+                    format: prop
+                        .get("format")
+                        .and_then(|f| f.as_str())
+                        .map(str::to_owned),
+                    enum_values: json_enum_values(prop.get("items").unwrap_or(prop)),
+                    items: (json_schema_type(prop) == "array").then(|| ArrayParam {
+                        item_type: prop
+                            .get("items")
+                            .map(json_schema_type)
+                            .unwrap_or_else(|| "string".to_string()),
+                        collection: CollectionFormat::Multi,
+                    }),
+                });
+            }
+        }
+        self
+    }
+
     /// Attach a JSON request body by *schema name* that you've already registered.
     /// This variant sets a description (`Some(desc)`) and marks the body as **required**.
     pub fn json_request_schema(
@@ -361,6 +1124,7 @@ where
             description: Some(desc.into()),
             schema_name: Some(schema_name.into()),
             required: true,
+            multipart_fields: Vec::new(),
         });
         self
     }
@@ -373,6 +1137,7 @@ where
             description: None,
             schema_name: Some(schema_name.into()),
             required: true,
+            multipart_fields: Vec::new(),
         });
         self
     }
@@ -393,6 +1158,7 @@ where
             description: Some(desc.into()),
             schema_name: Some(name),
             required: true,
+            multipart_fields: Vec::new(),
         });
         self
     }
@@ -409,10 +1175,160 @@ where
             description: None,
             schema_name: Some(name),
             required: true,
+            multipart_fields: Vec::new(),
+        });
+        self
+    }
+
+    /// Require a bearer token granting all of `scopes` (an empty list requires
+    /// authentication only). Repeated calls accumulate scopes.
+    pub fn bearer_auth<I, T>(self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.security(BEARER_AUTH_SCHEME, scopes)
+    }
+
+    /// Same as [`bearer_auth`](Self::bearer_auth).
+    pub fn require_scopes<I, T>(self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.bearer_auth(scopes)
+    }
+
+    /// Accept an API key (the `apiKeyAuth` scheme). Combined with
+    /// [`bearer_auth`](Self::bearer_auth), either credential is accepted.
+    pub fn api_key_auth(self) -> Self {
+        self.security(API_KEY_AUTH_SCHEME, Vec::<String>::new())
+    }
+
+    /// Declare a security requirement against the named scheme (one OpenAPI
+    /// `security` entry). Calls for the same scheme accumulate scopes; calls for
+    /// different schemes are alternatives.
+    pub fn security<I, T>(mut self, scheme: impl Into<String>, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let scheme = scheme.into();
+        let scopes = scopes.into_iter().map(Into::into);
+        match self.spec.security.iter_mut().find(|r| r.scheme == scheme) {
+            Some(req) => {
+                for s in scopes {
+                    if !req.scopes.contains(&s) {
+                        req.scopes.push(s);
+                    }
+                }
+            }
+            None => self
+                .spec
+                .security
+                .push(SecurityRequirement::new(scheme, scopes.collect())),
+        }
+        self
+    }
+
+    /// Attach a `multipart/form-data` request body made of `fields`, e.g.
+    /// `[MultipartField::file("file"), MultipartField::text("title").optional()]`.
+    /// Extract it with `modkit::http::LimitedMultipart`.
+    pub fn multipart_request(
+        mut self,
+        fields: impl IntoIterator<Item = MultipartField>,
+        desc: impl Into<String>,
+    ) -> Self {
+        self.spec.request_body = Some(RequestBodySpec {
+            content_type: "multipart/form-data",
+            description: Some(desc.into()),
+            schema_name: None,
+            required: true,
+            multipart_fields: fields.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Attach an example request body, e.g. `.request_example(json!({"name": "Ada"}))`.
+    pub fn request_example(mut self, example: serde_json::Value) -> Self {
+        self.spec.request_example = Some(example);
+        self
+    }
+
+    /// Attach a named example body to the JSON response declared for `status`.
+    pub fn response_example(
+        mut self,
+        status: u16,
+        name: impl Into<String>,
+        example: serde_json::Value,
+    ) -> Self {
+        self.spec.response_examples.push(ResponseExampleSpec {
+            status,
+            name: name.into(),
+            value: example,
+        });
+        self
+    }
+
+    /// Link the operation to external documentation, e.g. a handbook guide.
+    pub fn external_docs(mut self, url: impl Into<String>, description: impl Into<String>) -> Self {
+        self.spec.external_docs = Some(ExternalDocsSpec {
+            url: url.into(),
+            description: Some(description.into()),
+        });
+        self
+    }
+
+    /// Document a callback this operation triggers, e.g.
+    /// `.callback("orderShipped", "{$request.body#/callback_url}", CallbackOperation::post()...)`.
+    pub fn callback(
+        mut self,
+        name: impl Into<String>,
+        url_expression: impl Into<String>,
+        operation: CallbackOperation,
+    ) -> Self {
+        self.spec.callbacks.push(CallbackSpec {
+            name: name.into(),
+            url_expression: url_expression.into(),
+            operation,
         });
         self
     }
 
+    /// Limit the route to `rps` requests per second with bursts of `burst`,
+    /// shared by all callers. Excess requests get `429` with `Retry-After`.
+    pub fn rate_limit(mut self, rps: u32, burst: u32) -> Self {
+        self.spec.rate_limit = Some(RateLimitSpec { rps, burst });
+        self
+    }
+
+    /// Mark the operation as deprecated.
+    pub fn deprecated(mut self) -> Self {
+        self.spec.deprecated = true;
+        self
+    }
+
+    /// Mark the operation as deprecated and announce its removal date, e.g.
+    /// `"2026-12-31"`. The date is validated by the ingress at startup.
+    pub fn deprecated_with_sunset(mut self, sunset: impl Into<String>) -> Self {
+        self.spec.deprecated = true;
+        self.spec.sunset = Some(sunset.into());
+        self
+    }
+
+    /// Attach a vendor extension to the operation, e.g.
+    /// `.extension("x-internal", true)`. Keys without the `x-` prefix get it added.
+    pub fn extension(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        self.spec
+            .extensions
+            .insert(vendor_key(key.into()), value.into());
+        self
+    }
+
     /// Make the previously attached request body **optional** (if any).
     pub fn request_optional(mut self) -> Self {
         if let Some(rb) = &mut self.spec.request_body {
@@ -468,6 +1384,61 @@ where
     }
 }
 
+// -------------------------------------------------------------------------------------------------
+// Route-level middleware — only once a handler is present
+// -------------------------------------------------------------------------------------------------
+impl<R, S> OperationBuilder<Present, R, S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Wrap this operation's handler in a tower layer (timeout, cache, scope
+    /// check, ...). Unlike `Router::layer`, it only affects this route; repeated
+    /// calls stack, the last one outermost.
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + Sync + 'static,
+        L::Service: Service<Request, Error = Infallible> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.method_router = self.method_router.layer(layer);
+        self
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// WebSocket — sets handler and response at once
+// -------------------------------------------------------------------------------------------------
+impl<S> OperationBuilder<Missing, Missing, S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Register a WebSocket endpoint.
+    ///
+    /// `h` is a regular handler that extracts
+    /// [`WebSocketUpgrade`](axum::extract::ws::WebSocketUpgrade) (plus any other
+    /// extractors, e.g. `AuthContext`) and returns `ws.on_upgrade(...)`. Upgrades
+    /// are always `GET`; the operation is documented with a
+    /// `101 Switching Protocols` response.
+    pub fn websocket<F, T>(mut self, h: F) -> OperationBuilder<Present, Present, S>
+    where
+        F: Handler<T, S> + Clone + Send + 'static,
+        T: 'static,
+    {
+        if self.spec.method != Method::GET {
+            self.spec.method = Method::GET;
+            self.spec.handler_id = handler_id_for(&self.spec.method, &self.spec.path);
+        }
+        self.spec.websocket = true;
+        self.handler(h).response(ResponseSpec {
+            status: ResponseStatus::Code(101),
+            content_type: "",
+            description: "Switching Protocols".to_string(),
+            schema_name: None,
+        })
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // Response setting — transitions Missing -> Present for response (first response)
 // -------------------------------------------------------------------------------------------------
@@ -494,7 +1465,7 @@ where
         description: impl Into<String>,
     ) -> OperationBuilder<H, Present, S> {
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: "application/json",
             description: description.into(),
             schema_name: None,
@@ -520,7 +1491,7 @@ where
     {
         let name = ensure_schema::<T>(registry);
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: "application/json",
             description: description.into(),
             schema_name: Some(name),
@@ -534,6 +1505,27 @@ where
         }
     }
 
+    /// Document a cursor-paginated list endpoint: adds the `limit` and `cursor`
+    /// query parameters (as read by the `OData` extractor) and registers
+    /// `Page<T>` as the `200` response.
+    pub fn paginated<T>(self, registry: &dyn OpenApiRegistry) -> OperationBuilder<H, Present, S>
+    where
+        T: utoipa::ToSchema + utoipa::PartialSchema + 'static,
+    {
+        // `Page<T>` only references `T`, so make sure it is registered too
+        let item = ensure_schema::<T>(registry);
+        let description = format!("Page of {item} with cursors for adjacent pages");
+        let page = ensure_generic_schema::<odata_core::Page<T>>(registry);
+        self.query_param_typed("limit", false, PAGE_LIMIT_DESCRIPTION, "integer")
+            .query_param("cursor", false, PAGE_CURSOR_DESCRIPTION)
+            .response(ResponseSpec {
+                status: ResponseStatus::Code(200),
+                content_type: "application/json",
+                description,
+                schema_name: Some(page),
+            })
+    }
+
     /// Add a text response (transitions from Missing to Present).
     pub fn text_response(
         mut self,
@@ -541,7 +1533,7 @@ where
         description: impl Into<String>,
     ) -> OperationBuilder<H, Present, S> {
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: "text/plain",
             description: description.into(),
             schema_name: None,
@@ -555,6 +1547,29 @@ where
         }
     }
 
+    /// Add a binary response such as a file download (transitions from Missing
+    /// to Present). Pair with `modkit::api::response::{binary_download, stream_download}`.
+    pub fn binary_response(
+        mut self,
+        status: u16,
+        content_type: &'static str,
+        description: impl Into<String>,
+    ) -> OperationBuilder<H, Present, S> {
+        self.spec.responses.push(ResponseSpec {
+            status: ResponseStatus::Code(status),
+            content_type,
+            description: description.into(),
+            schema_name: None,
+        });
+        OperationBuilder {
+            spec: self.spec,
+            method_router: self.method_router,
+            _has_handler: self._has_handler,
+            _has_response: PhantomData::<Present>,
+            _state: self._state,
+        }
+    }
+
     /// Add an HTML response (transitions from Missing to Present).
     pub fn html_response(
         mut self,
@@ -562,7 +1577,7 @@ where
         description: impl Into<String>,
     ) -> OperationBuilder<H, Present, S> {
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: "text/html",
             description: description.into(),
             schema_name: None,
@@ -586,7 +1601,7 @@ where
         // Ensure `Problem` schema is registered in components
         let problem_name = ensure_schema::<crate::api::problem::Problem>(registry);
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: problem::APPLICATION_PROBLEM_JSON,
             description: description.into(),
             schema_name: Some(problem_name),
@@ -611,7 +1626,7 @@ where
     {
         let name = ensure_schema::<T>(openapi);
         self.spec.responses.push(ResponseSpec {
-            status: 200,
+            status: ResponseStatus::Code(200),
             content_type: "text/event-stream",
             description: description.into(),
             schema_name: Some(name),
@@ -636,7 +1651,7 @@ where
     /// Add a JSON response (additional).
     pub fn json_response(mut self, status: u16, description: impl Into<String>) -> Self {
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: "application/json",
             description: description.into(),
             schema_name: None,
@@ -656,7 +1671,7 @@ where
     {
         let name = ensure_schema::<T>(registry);
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: "application/json",
             description: description.into(),
             schema_name: Some(name),
@@ -667,7 +1682,7 @@ where
     /// Add a text response (additional).
     pub fn text_response(mut self, status: u16, description: impl Into<String>) -> Self {
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: "text/plain",
             description: description.into(),
             schema_name: None,
@@ -675,10 +1690,26 @@ where
         self
     }
 
+    /// Add a binary response (additional).
+    pub fn binary_response(
+        mut self,
+        status: u16,
+        content_type: &'static str,
+        description: impl Into<String>,
+    ) -> Self {
+        self.spec.responses.push(ResponseSpec {
+            status: ResponseStatus::Code(status),
+            content_type,
+            description: description.into(),
+            schema_name: None,
+        });
+        self
+    }
+
     /// Add an HTML response (additional).
     pub fn html_response(mut self, status: u16, description: impl Into<String>) -> Self {
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
             content_type: "text/html",
             description: description.into(),
             schema_name: None,
@@ -695,7 +1726,24 @@ where
     ) -> Self {
         let problem_name = ensure_schema::<crate::api::problem::Problem>(registry);
         self.spec.responses.push(ResponseSpec {
-            status,
+            status: ResponseStatus::Code(status),
+            content_type: problem::APPLICATION_PROBLEM_JSON,
+            description: description.into(),
+            schema_name: Some(problem_name),
+        });
+        self
+    }
+
+    /// Declare the `default` response as a Problem, covering every status not
+    /// listed explicitly (strict linters require one).
+    pub fn default_problem_response(
+        mut self,
+        registry: &dyn OpenApiRegistry,
+        description: impl Into<String>,
+    ) -> Self {
+        let problem_name = ensure_schema::<crate::api::problem::Problem>(registry);
+        self.spec.responses.push(ResponseSpec {
+            status: ResponseStatus::Default,
             content_type: problem::APPLICATION_PROBLEM_JSON,
             description: description.into(),
             schema_name: Some(problem_name),
@@ -714,7 +1762,7 @@ where
     {
         let name = ensure_schema::<T>(openapi);
         self.spec.responses.push(ResponseSpec {
-            status: 200,
+            status: ResponseStatus::Code(200),
             content_type: "text/event-stream",
             description: description.into(),
             schema_name: Some(name),
@@ -750,6 +1798,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::auth::API_KEY_AUTH_SCHEME;
     use axum::Json;
 
     // Mock registry for testing: stores operations; records schema names
@@ -840,13 +1889,350 @@ mod tests {
         assert!(op.request_body.is_some());
         assert!(op.request_body.as_ref().unwrap().required);
         assert_eq!(op.responses.len(), 1);
-        assert_eq!(op.responses[0].status, 200);
+        assert_eq!(op.responses[0].status, ResponseStatus::Code(200));
 
         // Verify schemas recorded
         let schemas = registry.schemas.lock().unwrap();
         assert!(!schemas.is_empty());
     }
 
+    #[test]
+    fn test_require_scopes_accumulates() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/secure")
+            .require_scopes(["users:read"])
+            .require_scopes(["users:read", "users:write"]);
+
+        assert_eq!(builder.spec.security.len(), 1);
+        let req = &builder.spec.security[0];
+        assert_eq!(req.scheme, BEARER_AUTH_SCHEME);
+        assert_eq!(req.scopes, vec!["users:read", "users:write"]);
+    }
+
+    #[test]
+    fn test_security_with_multiple_schemes() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/secure")
+            .security(API_KEY_AUTH_SCHEME, Vec::<String>::new())
+            .require_scopes(["users:read"]);
+
+        let schemes: Vec<_> = builder
+            .spec
+            .security
+            .iter()
+            .map(|r| r.scheme.as_str())
+            .collect();
+        assert_eq!(schemes, [API_KEY_AUTH_SCHEME, BEARER_AUTH_SCHEME]);
+        assert!(builder.spec.security[0].scopes.is_empty());
+    }
+
+    #[test]
+    fn test_bearer_and_api_key_auth() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/secure")
+            .bearer_auth(["ops"])
+            .api_key_auth();
+
+        assert_eq!(
+            builder.spec.security,
+            vec![
+                SecurityRequirement::new(BEARER_AUTH_SCHEME, vec!["ops".into()]),
+                SecurityRequirement::new(API_KEY_AUTH_SCHEME, Vec::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extensions_are_prefixed() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/internal")
+            .extension("x-internal", true)
+            .extension("rate-limit", serde_json::json!({ "rps": 10 }));
+
+        assert_eq!(builder.spec.extensions["x-internal"], true);
+        assert_eq!(builder.spec.extensions["x-rate-limit"]["rps"], 10);
+    }
+
+    #[test]
+    fn test_default_problem_response() {
+        let registry = MockRegistry::new();
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/items")
+            .json_response(200, "OK")
+            .default_problem_response(&registry, "Unexpected error");
+
+        let default = builder.spec.responses.last().unwrap();
+        assert!(default.is_default());
+        assert_eq!(default.content_type, problem::APPLICATION_PROBLEM_JSON);
+        assert!(!builder.spec.responses[0].is_default());
+    }
+
+    #[test]
+    fn test_version_prefixes_path() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/users/{id}")
+            .version("v1")
+            .version("v2");
+
+        assert_eq!(builder.spec.path, "/v2/users/{id}");
+        assert_eq!(builder.spec.version.as_deref(), Some("v2"));
+        assert_eq!(builder.spec.handler_id, "get:_v2_users__id_");
+    }
+
+    #[test]
+    fn test_with_etag_documents_conditional_get() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/items/{id}").with_etag();
+
+        assert_eq!(builder.spec.response_headers[0].name, "ETag");
+        assert!(builder
+            .spec
+            .params
+            .iter()
+            .any(|p| p.name == "If-None-Match" && p.location == ParamLocation::Header));
+        assert!(builder
+            .spec
+            .responses
+            .iter()
+            .any(|r| r.status == ResponseStatus::Code(304)));
+    }
+
+    #[test]
+    fn test_with_if_match_documents_precondition() {
+        let registry = MockRegistry::new();
+        let builder =
+            OperationBuilder::<Missing, Missing, ()>::put("/items/{id}").with_if_match(&registry);
+
+        assert!(builder
+            .spec
+            .params
+            .iter()
+            .any(|p| p.name == "If-Match" && p.location == ParamLocation::Header));
+        let precondition = &builder.spec.responses[0];
+        assert_eq!(precondition.status, ResponseStatus::Code(412));
+        assert_eq!(precondition.content_type, problem::APPLICATION_PROBLEM_JSON);
+        assert_eq!(precondition.schema_name.as_deref(), Some("Problem"));
+    }
+
+    #[test]
+    fn test_websocket_is_a_documented_get() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::post("/events/ws").websocket(
+            |ws: axum::extract::ws::WebSocketUpgrade| async move { ws.on_upgrade(|_| async {}) },
+        );
+
+        assert_eq!(builder.spec.method, Method::GET);
+        assert_eq!(builder.spec.handler_id, "get:_events_ws");
+        assert!(builder.spec.websocket);
+        assert_eq!(builder.spec.responses[0].status, ResponseStatus::Code(101));
+        assert!(builder.spec.responses[0].content_type.is_empty());
+    }
+
+    #[test]
+    fn test_module_registry_tags_and_prefixes_operations() {
+        let registry = MockRegistry::new();
+        let scoped =
+            ModuleOpenApiRegistry::new(&registry, "users_info").with_prefix("/api/users-info");
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/users/{id}")
+            .handler(|| async { "ok" })
+            .json_response(200, "OK")
+            .register(Router::new(), &scoped);
+
+        let ops = registry.operations.lock().unwrap();
+        assert_eq!(ops[0].path, "/api/users-info/users/{id}");
+        assert_eq!(ops[0].handler_id, "get:_api_users-info_users__id_");
+        assert_eq!(ops[0].module.as_deref(), Some("users_info"));
+    }
+
+    #[test]
+    fn test_query_params_from_struct() {
+        /// Filters for listing users.
+        #[derive(serde::Deserialize, utoipa::ToSchema)]
+        #[allow(dead_code)]
+        struct ListQuery {
+            /// Max items to return
+            limit: Option<u32>,
+            /// Case-insensitive name filter
+            name: String,
+            active: Option<bool>,
+        }
+
+        let builder =
+            OperationBuilder::<Missing, Missing, ()>::get("/users").query_params::<ListQuery>();
+        let params = &builder.spec.params;
+        let find = |n: &str| params.iter().find(|p| p.name == n).unwrap();
+
+        assert_eq!(params.len(), 3);
+        assert!(params.iter().all(|p| p.location == ParamLocation::Query));
+        assert_eq!(find("limit").param_type, "integer");
+        assert!(!find("limit").required);
+        assert_eq!(
+            find("limit").description.as_deref(),
+            Some("Max items to return")
+        );
+        assert_eq!(find("name").param_type, "string");
+        assert!(find("name").required);
+        assert_eq!(find("active").param_type, "boolean");
+        assert!(find("active").description.is_none());
+    }
+
+    #[test]
+    fn test_typed_path_params() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/items/{org}/{seq}/{code}")
+            .path_param_uuid("org", "Organization")
+            .path_param_i64("seq", "Sequence")
+            .path_param_typed("code", "integer", Some("int32"), "Code");
+        let params = &builder.spec.params;
+
+        assert!(params
+            .iter()
+            .all(|p| p.location == ParamLocation::Path && p.required));
+        assert_eq!(params[0].param_type, "string");
+        assert_eq!(params[0].format.as_deref(), Some("uuid"));
+        assert_eq!(params[1].param_type, "integer");
+        assert_eq!(params[1].format.as_deref(), Some("int64"));
+        assert_eq!(params[2].format.as_deref(), Some("int32"));
+    }
+
+    #[test]
+    fn test_array_and_enum_query_params() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/items")
+            .query_param_enum("sort", false, "Sort order", ["asc", "desc"])
+            .query_param_array("status", false, "Statuses", "string", CollectionFormat::Csv)
+            .allowed_values(["active", "archived"]);
+        let params = &builder.spec.params;
+
+        assert_eq!(params[0].param_type, "string");
+        assert_eq!(params[0].enum_values, ["asc", "desc"]);
+        assert!(params[0].items.is_none());
+        assert_eq!(params[1].param_type, "array");
+        assert_eq!(params[1].enum_values, ["active", "archived"]);
+        assert_eq!(
+            params[1].items,
+            Some(ArrayParam {
+                item_type: "string".to_owned(),
+                collection: CollectionFormat::Csv,
+            })
+        );
+    }
+
+    #[test]
+    fn test_paginated_adds_params_and_page_schema() {
+        #[derive(utoipa::ToSchema)]
+        #[allow(dead_code)]
+        struct ItemDto {
+            id: String,
+        }
+
+        let registry = MockRegistry::new();
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/items")
+            .handler(|| async { "ok" })
+            .paginated::<ItemDto>(&registry);
+
+        let names: Vec<_> = builder
+            .spec
+            .params
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, ["limit", "cursor"]);
+        assert_eq!(builder.spec.params[0].param_type, "integer");
+        assert!(builder.spec.params.iter().all(|p| !p.required));
+        let response = &builder.spec.responses[0];
+        assert_eq!(response.status, ResponseStatus::Code(200));
+        assert_eq!(response.schema_name.as_deref(), Some("Page_ItemDto"));
+        assert!(response.description.contains("ItemDto"));
+    }
+
+    #[test]
+    fn test_schema_name_for_generic_instantiations() {
+        #[derive(utoipa::ToSchema)]
+        #[allow(dead_code)]
+        struct UserDto {
+            id: String,
+        }
+
+        assert_eq!(schema_name::<UserDto>(), "UserDto");
+        assert_eq!(
+            generic_schema_name::<odata_core::Page<UserDto>>(),
+            "Page_UserDto"
+        );
+        let registry = MockRegistry::new();
+        assert_eq!(
+            ensure_generic_schema::<odata_core::Page<UserDto>>(&registry),
+            "Page_UserDto"
+        );
+
+        assert_eq!(
+            ensure_schema_named::<odata_core::Page<UserDto>>(&registry, "UserPage"),
+            "UserPage"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_layer_applies_to_its_route_only() {
+        use tower::ServiceExt;
+
+        let registry = MockRegistry::new();
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/layered")
+            .handler(|| async { "ok" })
+            .layer(axum::middleware::map_response(
+                |mut res: axum::response::Response| async move {
+                    res.headers_mut().insert(
+                        http::header::CACHE_CONTROL,
+                        http::HeaderValue::from_static("max-age=60"),
+                    );
+                    res
+                },
+            ))
+            .text_response(200, "OK")
+            .register(Router::new(), &registry);
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/plain")
+            .handler(|| async { "ok" })
+            .text_response(200, "OK")
+            .register(router, &registry);
+
+        let call = |path: &'static str| {
+            router.clone().oneshot(
+                http::Request::get(path)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+        };
+        let layered = call("/layered").await.unwrap();
+        assert_eq!(layered.headers()[http::header::CACHE_CONTROL], "max-age=60");
+        let plain = call("/plain").await.unwrap();
+        assert!(plain.headers().get(http::header::CACHE_CONTROL).is_none());
+    }
+
+    #[test]
+    fn test_path_template_validation() {
+        assert_eq!(
+            path_template_params("/orgs/{org}/files/{*path}").unwrap(),
+            ["org", "path"]
+        );
+        assert_eq!(
+            path_template_params("/a/{id}/b/{id}"),
+            Err(PathTemplateError::Duplicate("id".into()))
+        );
+        assert_eq!(
+            path_template_params("/a/{id"),
+            Err(PathTemplateError::MalformedSegment("{id".into()))
+        );
+        assert_eq!(
+            path_template_params("/{*rest}/x"),
+            Err(PathTemplateError::WildcardNotLast("rest".into()))
+        );
+
+        let ok = OperationBuilder::<Missing, Missing, ()>::get("/users/{id}")
+            .path_param("id", "User id");
+        assert_eq!(ok.spec.validate_path(), Ok(()));
+        let typo = OperationBuilder::<Missing, Missing, ()>::get("/users/{id}")
+            .path_param("user_id", "User id");
+        assert_eq!(
+            typo.spec.validate_path(),
+            Err(PathTemplateError::Undeclared("id".into()))
+        );
+        let extra =
+            OperationBuilder::<Missing, Missing, ()>::get("/users").path_param("id", "User id");
+        assert_eq!(
+            extra.spec.validate_path(),
+            Err(PathTemplateError::NotInPath("id".into()))
+        );
+    }
+
     #[test]
     fn test_convenience_constructors() {
         let get_builder = OperationBuilder::<Missing, Missing, ()>::get("/get");
@@ -94,11 +94,24 @@ impl From<Problem> for ProblemResponse {
     }
 }
 
+/// Response extension carrying a Problem rendered without `instance`, for
+/// `error_mapping_middleware` to fill in from the request.
+#[derive(Clone)]
+pub(crate) struct InstancelessProblem(pub(crate) Problem);
+
 impl IntoResponse for ProblemResponse {
     fn into_response(self) -> Response {
         let status =
             StatusCode::from_u16(self.0.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let pending = self
+            .0
+            .instance
+            .is_empty()
+            .then(|| InstancelessProblem(self.0.clone()));
         let mut resp = axum::Json(self.0).into_response();
+        if let Some(pending) = pending {
+            resp.extensions_mut().insert(pending);
+        }
         *resp.status_mut() = status;
         resp.headers_mut().insert(
             axum::http::header::CONTENT_TYPE,
@@ -113,6 +126,14 @@ pub fn bad_request(detail: impl Into<String>) -> ProblemResponse {
     Problem::new(StatusCode::BAD_REQUEST, "Bad Request", detail).into()
 }
 
+pub fn unauthorized(detail: impl Into<String>) -> ProblemResponse {
+    Problem::new(StatusCode::UNAUTHORIZED, "Unauthorized", detail).into()
+}
+
+pub fn forbidden(detail: impl Into<String>) -> ProblemResponse {
+    Problem::new(StatusCode::FORBIDDEN, "Forbidden", detail).into()
+}
+
 pub fn not_found(detail: impl Into<String>) -> ProblemResponse {
     Problem::new(StatusCode::NOT_FOUND, "Not Found", detail).into()
 }
@@ -185,6 +206,14 @@ mod tests {
         assert_eq!(conflict_resp.0.status, 409);
         assert_eq!(conflict_resp.0.title, "Conflict");
 
+        let unauthorized_resp = unauthorized("Missing bearer token");
+        assert_eq!(unauthorized_resp.0.status, 401);
+        assert_eq!(unauthorized_resp.0.title, "Unauthorized");
+
+        let forbidden_resp = forbidden("Insufficient scope");
+        assert_eq!(forbidden_resp.0.status, 403);
+        assert_eq!(forbidden_resp.0.title, "Forbidden");
+
         let internal_resp = internal_error("Database connection failed");
         assert_eq!(internal_resp.0.status, 500);
         assert_eq!(internal_resp.0.title, "Internal Server Error");
@@ -0,0 +1,456 @@
+//! CRUD scaffold on top of [`OperationBuilder`]
+//!
+//! One declaration wires the usual list/get/create/update/delete operations of a
+//! collection with consistent operation ids, tags, item path parameter,
+//! pagination and Problem responses:
+//!
+//! ```ignore
+//! router = ResourceBuilder::<dto::UserDto>::new("/users")
+//!     .operation_prefix("users_info")
+//!     .id_param_uuid("id", "User UUID")
+//!     .list(handlers::list_users)
+//!     .get(handlers::get_user)
+//!     .create::<dto::CreateUserReq, _, _>(handlers::create_user)
+//!     .update::<dto::UpdateUserReq, _, _>(handlers::update_user)
+//!     .delete(handlers::delete_user)
+//!     .customize(ResourceOp::List, |op| op.with_odata_filter())
+//!     .register(router, openapi);
+//! ```
+//!
+//! This yields `users_info.list_users`, `users_info.get_user`, ... on `/users`
+//! and `/users/{id}`. Anything beyond the defaults goes through `customize`.
+
+use axum::{handler::Handler, Router};
+use http::Method;
+use std::marker::PhantomData;
+
+use crate::api::operation_builder::{
+    Missing, OpenApiRegistry, OperationBuilder, ParamLocation, ParamSpec, Present,
+};
+
+/// The standard operations of a resource
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceOp {
+    /// `GET /collection` — a `Page<T>` of items
+    List,
+    /// `GET /collection/{id}`
+    Get,
+    /// `POST /collection` — `201` with the created item
+    Create,
+    /// `PUT /collection/{id}`
+    Update,
+    /// `DELETE /collection/{id}` — `204`
+    Delete,
+}
+
+impl ResourceOp {
+    fn method(self) -> Method {
+        match self {
+            Self::List | Self::Get => Method::GET,
+            Self::Create => Method::POST,
+            Self::Update => Method::PUT,
+            Self::Delete => Method::DELETE,
+        }
+    }
+
+    fn on_item(self) -> bool {
+        matches!(self, Self::Get | Self::Update | Self::Delete)
+    }
+}
+
+type Registered<S> = OperationBuilder<Present, Present, S>;
+type BuildFn<S> = Box<dyn FnOnce(&ResourceNaming, &dyn OpenApiRegistry) -> Registered<S>>;
+type CustomizeFn<S> = Box<dyn FnOnce(Registered<S>) -> Registered<S>>;
+
+/// Paths, names and the item parameter shared by all operations of a resource
+struct ResourceNaming {
+    collection: String,
+    singular: String,
+    plural: String,
+    prefix: Option<String>,
+    tag: Option<String>,
+    id: ParamSpec,
+}
+
+impl ResourceNaming {
+    fn operation<S>(&self, op: ResourceOp) -> OperationBuilder<Missing, Missing, S> {
+        let (verb, noun) = match op {
+            ResourceOp::List => ("list", &self.plural),
+            ResourceOp::Get => ("get", &self.singular),
+            ResourceOp::Create => ("create", &self.singular),
+            ResourceOp::Update => ("update", &self.singular),
+            ResourceOp::Delete => ("delete", &self.singular),
+        };
+        let mut summary = format!("{}{} {noun}", verb[..1].to_uppercase(), &verb[1..]);
+        if op == ResourceOp::Get {
+            summary.push_str(" by ID");
+        }
+        let operation_id = match &self.prefix {
+            Some(prefix) => format!("{prefix}.{verb}_{noun}"),
+            None => format!("{verb}_{noun}"),
+        };
+        let path = if op.on_item() {
+            format!("{}/{{{}}}", self.collection, self.id.name)
+        } else {
+            self.collection.clone()
+        };
+
+        let builder = OperationBuilder::new(op.method(), path)
+            .operation_id(operation_id)
+            .summary(summary)
+            .tag(self.tag.as_deref().unwrap_or(&self.plural));
+        if op.on_item() {
+            builder.param(self.id.clone())
+        } else {
+            builder
+        }
+    }
+}
+
+/// Declares the CRUD operations of one resource collection in a single chain.
+///
+/// Generic parameters:
+/// - `T`: the item DTO returned by list/get/create/update
+/// - `S`: Router state type, as for [`OperationBuilder`]
+///
+/// Names default to the last segment of the collection path (`/users` →
+/// `users` / `user`) and operations are registered in declaration order.
+pub struct ResourceBuilder<T, S = ()> {
+    naming: ResourceNaming,
+    ops: Vec<(ResourceOp, BuildFn<S>)>,
+    customizers: Vec<(ResourceOp, CustomizeFn<S>)>,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T, S> ResourceBuilder<T, S>
+where
+    T: utoipa::ToSchema + utoipa::PartialSchema + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    /// Start a resource rooted at a collection path such as `/users`
+    pub fn new(collection: impl Into<String>) -> Self {
+        let collection = collection.into().trim_end_matches('/').to_string();
+        let plural = collection
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let singular = plural.strip_suffix('s').unwrap_or(&plural).to_string();
+        let id = ParamSpec {
+            name: "id".to_string(),
+            location: ParamLocation::Path,
+            required: true,
+            description: Some(format!("{singular} identifier")),
+            param_type: "string".to_string(),
+            format: None,
+            enum_values: Vec::new(),
+            items: None,
+        };
+
+        Self {
+            naming: ResourceNaming {
+                collection,
+                singular,
+                plural,
+                prefix: None,
+                tag: None,
+                id,
+            },
+            ops: Vec::new(),
+            customizers: Vec::new(),
+            _item: PhantomData,
+        }
+    }
+
+    /// Override the singular/plural names used in operation ids and summaries
+    pub fn names(mut self, singular: impl Into<String>, plural: impl Into<String>) -> Self {
+        self.naming.singular = singular.into();
+        self.naming.plural = plural.into();
+        self
+    }
+
+    /// Prefix operation ids, usually with the module name (`users_info.get_user`)
+    pub fn operation_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.naming.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Tag for all operations (defaults to the plural name)
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.naming.tag = Some(tag.into());
+        self
+    }
+
+    /// Name and describe the item path parameter (defaults to a string `id`)
+    pub fn id_param(mut self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.naming.id.name = name.into();
+        self.naming.id.description = Some(description.into());
+        self.naming.id.param_type = "string".to_string();
+        self.naming.id.format = None;
+        self
+    }
+
+    /// Item path parameter documented as a UUID string
+    pub fn id_param_uuid(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        let mut this = self.id_param(name, description);
+        this.naming.id.format = Some("uuid".to_string());
+        this
+    }
+
+    /// Item path parameter documented as a 64-bit integer
+    pub fn id_param_i64(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        let mut this = self.id_param(name, description);
+        this.naming.id.param_type = "integer".to_string();
+        this.naming.id.format = Some("int64".to_string());
+        this
+    }
+
+    /// `GET /collection`: cursor-paginated `Page<T>`, `400` and `500` Problems
+    pub fn list<F, X>(self, handler: F) -> Self
+    where
+        F: Handler<X, S> + Clone + Send + 'static,
+        X: 'static,
+    {
+        self.push(ResourceOp::List, move |naming, openapi| {
+            naming
+                .operation(ResourceOp::List)
+                .handler(handler)
+                .paginated::<T>(openapi)
+                .problem_response(openapi, 400, "Bad Request")
+                .problem_response(openapi, 500, "Internal Server Error")
+        })
+    }
+
+    /// `GET /collection/{id}`: `200` with `T`, `404` and `500` Problems
+    pub fn get<F, X>(self, handler: F) -> Self
+    where
+        F: Handler<X, S> + Clone + Send + 'static,
+        X: 'static,
+    {
+        self.push(ResourceOp::Get, move |naming, openapi| {
+            let description = format!("{} found", naming.singular);
+            naming
+                .operation(ResourceOp::Get)
+                .handler(handler)
+                .json_response_with_schema::<T>(openapi, 200, description)
+                .problem_response(openapi, 404, "Not Found")
+                .problem_response(openapi, 500, "Internal Server Error")
+        })
+    }
+
+    /// `POST /collection` with a `C` body: `201` with `T`, `400`, `409` and `500` Problems
+    pub fn create<C, F, X>(self, handler: F) -> Self
+    where
+        C: utoipa::ToSchema + utoipa::PartialSchema + 'static,
+        F: Handler<X, S> + Clone + Send + 'static,
+        X: 'static,
+    {
+        self.push(ResourceOp::Create, move |naming, openapi| {
+            let request = format!("{} creation data", naming.singular);
+            let response = format!("Created {}", naming.singular);
+            naming
+                .operation(ResourceOp::Create)
+                .json_request::<C>(openapi, request)
+                .handler(handler)
+                .json_response_with_schema::<T>(openapi, 201, response)
+                .problem_response(openapi, 400, "Bad Request")
+                .problem_response(openapi, 409, "Conflict")
+                .problem_response(openapi, 500, "Internal Server Error")
+        })
+    }
+
+    /// `PUT /collection/{id}` with a `U` body: `200` with `T`, `400`, `404`, `409`
+    /// and `500` Problems
+    pub fn update<U, F, X>(self, handler: F) -> Self
+    where
+        U: utoipa::ToSchema + utoipa::PartialSchema + 'static,
+        F: Handler<X, S> + Clone + Send + 'static,
+        X: 'static,
+    {
+        self.push(ResourceOp::Update, move |naming, openapi| {
+            let request = format!("{} update data", naming.singular);
+            let response = format!("Updated {}", naming.singular);
+            naming
+                .operation(ResourceOp::Update)
+                .json_request::<U>(openapi, request)
+                .handler(handler)
+                .json_response_with_schema::<T>(openapi, 200, response)
+                .problem_response(openapi, 400, "Bad Request")
+                .problem_response(openapi, 404, "Not Found")
+                .problem_response(openapi, 409, "Conflict")
+                .problem_response(openapi, 500, "Internal Server Error")
+        })
+    }
+
+    /// `DELETE /collection/{id}`: `204`, `404` and `500` Problems
+    pub fn delete<F, X>(self, handler: F) -> Self
+    where
+        F: Handler<X, S> + Clone + Send + 'static,
+        X: 'static,
+    {
+        self.push(ResourceOp::Delete, move |naming, openapi| {
+            let description = format!("{} deleted", naming.singular);
+            naming
+                .operation(ResourceOp::Delete)
+                .handler(handler)
+                .json_response(204, description)
+                .problem_response(openapi, 404, "Not Found")
+                .problem_response(openapi, 500, "Internal Server Error")
+        })
+    }
+
+    /// Adjust one operation beyond the defaults (descriptions, extra params,
+    /// examples, auth, layers, ...). Applied in order just before registration.
+    pub fn customize<F>(mut self, op: ResourceOp, f: F) -> Self
+    where
+        F: FnOnce(Registered<S>) -> Registered<S> + 'static,
+    {
+        self.customizers.push((op, Box::new(f)));
+        self
+    }
+
+    /// Register every declared operation with the router and OpenAPI registry.
+    pub fn register(self, mut router: Router<S>, openapi: &dyn OpenApiRegistry) -> Router<S> {
+        let Self {
+            naming,
+            ops,
+            mut customizers,
+            ..
+        } = self;
+        for (op, build) in ops {
+            let (mine, rest): (Vec<_>, Vec<_>) = customizers
+                .into_iter()
+                .partition(|(target, _)| *target == op);
+            customizers = rest;
+            let builder = mine
+                .into_iter()
+                .fold(build(&naming, openapi), |builder, (_, f)| f(builder));
+            router = builder.register(router, openapi);
+        }
+        router
+    }
+
+    // Declaring the same operation twice keeps the last one
+    fn push<F>(mut self, op: ResourceOp, build: F) -> Self
+    where
+        F: FnOnce(&ResourceNaming, &dyn OpenApiRegistry) -> Registered<S> + 'static,
+    {
+        self.ops.retain(|(existing, _)| *existing != op);
+        self.ops.push((op, Box::new(build)));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::operation_builder::OperationSpec;
+    use axum::Json;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingRegistry {
+        operations: Mutex<Vec<OperationSpec>>,
+    }
+
+    impl OpenApiRegistry for RecordingRegistry {
+        fn register_operation(&self, spec: &OperationSpec) {
+            self.operations.lock().unwrap().push(spec.clone());
+        }
+
+        fn ensure_schema_raw(
+            &self,
+            name: &str,
+            _schemas: Vec<(
+                String,
+                utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+            )>,
+        ) -> String {
+            name.to_string()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+    struct Widget {
+        id: i64,
+    }
+
+    async fn ok() -> Json<serde_json::Value> {
+        Json(serde_json::json!({}))
+    }
+
+    fn statuses(spec: &OperationSpec) -> Vec<u16> {
+        spec.responses
+            .iter()
+            .filter_map(|r| r.status.code())
+            .collect()
+    }
+
+    #[test]
+    fn test_resource_wires_crud_operations() {
+        let registry = RecordingRegistry::default();
+        let _router = ResourceBuilder::<Widget>::new("/v1/widgets")
+            .operation_prefix("shop")
+            .id_param_i64("widget_id", "Widget id")
+            .list(ok)
+            .get(ok)
+            .create::<Widget, _, _>(ok)
+            .update::<Widget, _, _>(ok)
+            .delete(ok)
+            .register(Router::new(), &registry);
+
+        let ops = registry.operations.lock().unwrap();
+        let summary: Vec<_> = ops
+            .iter()
+            .map(|s| {
+                (
+                    s.method.as_str(),
+                    s.path.as_str(),
+                    s.operation_id.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("GET", "/v1/widgets", "shop.list_widgets"),
+                ("GET", "/v1/widgets/{widget_id}", "shop.get_widget"),
+                ("POST", "/v1/widgets", "shop.create_widget"),
+                ("PUT", "/v1/widgets/{widget_id}", "shop.update_widget"),
+                ("DELETE", "/v1/widgets/{widget_id}", "shop.delete_widget"),
+            ]
+        );
+        for spec in ops.iter() {
+            assert_eq!(spec.tags, ["widgets"]);
+            assert_eq!(spec.validate_path(), Ok(()));
+        }
+        assert_eq!(ops[1].params[0].format.as_deref(), Some("int64"));
+        assert!(ops[0].params.iter().any(|p| p.name == "cursor"));
+        assert_eq!(statuses(&ops[2]), [201, 400, 409, 500]);
+        assert_eq!(statuses(&ops[4]), [204, 404, 500]);
+    }
+
+    #[test]
+    fn test_resource_customize_and_names() {
+        let registry = RecordingRegistry::default();
+        let _router = ResourceBuilder::<Widget>::new("/people")
+            .names("person", "people")
+            .tag("directory")
+            .get(ok)
+            .delete(ok)
+            .customize(ResourceOp::Delete, |op| op.description("Soft delete"))
+            .register(Router::new(), &registry);
+
+        let ops = registry.operations.lock().unwrap();
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[0].operation_id.as_deref(), Some("get_person"));
+        assert_eq!(ops[0].summary.as_deref(), Some("Get person by ID"));
+        assert_eq!(ops[0].tags, ["directory"]);
+        assert_eq!(ops[0].description, None);
+        assert_eq!(ops[1].description.as_deref(), Some("Soft delete"));
+    }
+}
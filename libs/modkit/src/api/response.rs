@@ -0,0 +1,456 @@
+//! Response helpers: ETags, conditional requests and file downloads.
+//!
+//! Handlers attach an `ETag` with [`json_with_etag`] (or compute one with
+//! [`strong_etag`] / [`weak_etag`]); [`conditional_get_middleware`] then turns
+//! a matching `If-None-Match` into `304 Not Modified` without the body.
+//! Optimistic-concurrency writes check [`if_match`] against the current ETag
+//! and answer [`precondition_failed`] on a mismatch.
+//! Download endpoints return [`binary_download`] or [`stream_download`], which
+//! set `Content-Type` and an attachment `Content-Disposition`. Endpoints that
+//! document several media types for one status pick one with [`negotiate`].
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderMap, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::api::problem::{internal_error, Problem, ProblemResponse};
+
+fn digest(bytes: &[u8]) -> String {
+    // 128 bits of SHA-256 are plenty to tell representations apart
+    hex::encode(&Sha256::digest(bytes)[..16])
+}
+
+/// Strong ETag (`"…"`) for a byte-exact representation.
+pub fn strong_etag(bytes: &[u8]) -> String {
+    format!("\"{}\"", digest(bytes))
+}
+
+/// Weak ETag (`W/"…"`) for a semantically equivalent representation.
+pub fn weak_etag(bytes: &[u8]) -> String {
+    format!("W/\"{}\"", digest(bytes))
+}
+
+/// Strong ETag of a value's JSON serialization.
+pub fn json_etag<T: Serialize>(value: &T) -> serde_json::Result<String> {
+    Ok(strong_etag(&serde_json::to_vec(value)?))
+}
+
+/// JSON response carrying a strong ETag of its body.
+pub fn json_with_etag<T: Serialize>(value: &T) -> Response {
+    let body = match serde_json::to_vec(value) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(error = %e, "failed to serialize response body");
+            return internal_error("Failed to serialize response").into_response();
+        }
+    };
+    let etag = strong_etag(&body);
+    let mut resp = (
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )],
+        body,
+    )
+        .into_response();
+    if let Ok(v) = HeaderValue::from_str(&etag) {
+        resp.headers_mut().insert(header::ETAG, v);
+    }
+    resp
+}
+
+/// `200` JSON response carrying a caller-supplied ETag (e.g. derived from a
+/// row version), for representations whose bytes aren't the version.
+pub fn ok_json_with_etag<T: Serialize>(value: &T, etag: &str) -> Response {
+    let mut resp = axum::Json(value).into_response();
+    if resp.status().is_success() {
+        if let Ok(v) = HeaderValue::from_str(etag) {
+            resp.headers_mut().insert(header::ETAG, v);
+        }
+    }
+    resp
+}
+
+/// `304 Not Modified` carrying the current ETag, for handlers that evaluate
+/// `If-None-Match` themselves (see [`if_none_match`]).
+pub fn not_modified(etag: &str) -> Response {
+    let mut resp = StatusCode::NOT_MODIFIED.into_response();
+    if let Ok(v) = HeaderValue::from_str(etag) {
+        resp.headers_mut().insert(header::ETAG, v);
+    }
+    resp
+}
+
+/// `412 Precondition Failed` Problem for a stale `If-Match`.
+pub fn precondition_failed(detail: impl Into<String>) -> Response {
+    ProblemResponse::from(
+        Problem::new(
+            StatusCode::PRECONDITION_FAILED,
+            "Precondition Failed",
+            detail,
+        )
+        .with_code("PRECONDITION_FAILED"),
+    )
+    .into_response()
+}
+
+/// `Content-Disposition: attachment` for `filename`, with an ASCII fallback and
+/// the RFC 8187 `filename*` form for non-ASCII names.
+pub fn attachment_disposition(filename: &str) -> HeaderValue {
+    let fallback: String = filename
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => '_',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '_',
+        })
+        .collect();
+    let value = if fallback == filename {
+        format!("attachment; filename=\"{fallback}\"")
+    } else {
+        format!(
+            "attachment; filename=\"{fallback}\"; filename*=UTF-8''{}",
+            urlencoding::encode(filename)
+        )
+    };
+    HeaderValue::from_str(&value).unwrap_or_else(|_| HeaderValue::from_static("attachment"))
+}
+
+fn download(body: Body, content_type: &str, filename: Option<&str>) -> Response {
+    let content_type = HeaderValue::from_str(content_type)
+        .unwrap_or_else(|_| HeaderValue::from_static("application/octet-stream"));
+    let mut resp = ([(header::CONTENT_TYPE, content_type)], body).into_response();
+    if let Some(name) = filename {
+        resp.headers_mut()
+            .insert(header::CONTENT_DISPOSITION, attachment_disposition(name));
+    }
+    resp
+}
+
+/// In-memory binary response, offered as a download when `filename` is set.
+pub fn binary_download(
+    bytes: impl Into<Bytes>,
+    content_type: &str,
+    filename: Option<&str>,
+) -> Response {
+    download(Body::from(bytes.into()), content_type, filename)
+}
+
+/// Streamed binary response (e.g. a file read with `ReaderStream`), offered as
+/// a download when `filename` is set. Chunks are sent as they are produced.
+pub fn stream_download<St>(stream: St, content_type: &str, filename: Option<&str>) -> Response
+where
+    St: futures::TryStream + Send + 'static,
+    St::Ok: Into<Bytes>,
+    St::Error: Into<axum::BoxError>,
+{
+    download(Body::from_stream(stream), content_type, filename)
+}
+
+/// Quality the `Accept` range gives `media_type`, with the specificity of the
+/// matching range (2 exact, 1 `type/*`, 0 `*/*`); the most specific range wins.
+fn accept_quality(accept: &str, media_type: &str) -> Option<(u8, f32)> {
+    let (ty, subtype) = media_type.split_once('/')?;
+    accept
+        .split(',')
+        .filter_map(|range| {
+            let mut params = range.split(';');
+            let (r_ty, r_sub) = params.next()?.trim().split_once('/')?;
+            let specificity = match (r_ty, r_sub) {
+                ("*", "*") => 0,
+                (t, "*") if t.eq_ignore_ascii_case(ty) => 1,
+                (t, s) if t.eq_ignore_ascii_case(ty) && s.eq_ignore_ascii_case(subtype) => 2,
+                _ => return None,
+            };
+            let q = params
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            Some((specificity, q))
+        })
+        .max_by_key(|(specificity, _)| *specificity)
+}
+
+/// Media type from `offered` the client prefers according to `Accept`
+/// (RFC 9110 §12.5.1). Without an `Accept` header the first offer wins; ties
+/// keep the order of `offered`. `None` means nothing is acceptable (406).
+pub fn negotiate(headers: &HeaderMap, offered: &[&'static str]) -> Option<&'static str> {
+    let accept: Vec<&str> = headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect();
+    if accept.is_empty() {
+        return offered.first().copied();
+    }
+    let accept = accept.join(",");
+    let mut best: Option<(&'static str, f32)> = None;
+    for &media_type in offered {
+        let Some((_, q)) = accept_quality(&accept, media_type) else {
+            continue;
+        };
+        if q > 0.0 && best.is_none_or(|(_, best_q)| q > best_q) {
+            best = Some((media_type, q));
+        }
+    }
+    best.map(|(media_type, _)| media_type)
+}
+
+/// `406 Not Acceptable` Problem listing the media types the endpoint can produce.
+pub fn not_acceptable(offered: &[&str]) -> Response {
+    ProblemResponse::from(Problem::new(
+        StatusCode::NOT_ACCEPTABLE,
+        "Not Acceptable",
+        format!("Supported media types: {}", offered.join(", ")),
+    ))
+    .into_response()
+}
+
+/// Weak comparison (RFC 9110 §8.8.3.2): opaque tags equal, `W/` ignored.
+fn weak_match(a: &str, b: &str) -> bool {
+    a.trim_start_matches("W/") == b.trim_start_matches("W/")
+}
+
+/// Whether an `If-None-Match` header value matches `etag`.
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || weak_match(candidate, etag))
+}
+
+/// Whether the `If-Match` precondition holds for the current `etag`
+/// (RFC 9110 §13.1.1): strong comparison, `*` matches anything, and a request
+/// without the header passes.
+pub fn if_match(headers: &HeaderMap, etag: &str) -> bool {
+    let mut candidates = headers
+        .get_all(header::IF_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .peekable();
+    if candidates.peek().is_none() {
+        return true;
+    }
+    // Weak tags never match strongly
+    candidates.any(|candidate| candidate == "*" || (candidate == etag && !etag.starts_with("W/")))
+}
+
+/// Middleware answering `If-None-Match` with `304 Not Modified` when the
+/// handler's response carries a matching `ETag` (GET/HEAD, 2xx only).
+pub async fn conditional_get_middleware(req: Request<Body>, next: Next) -> Response {
+    let conditional = matches!(*req.method(), Method::GET | Method::HEAD)
+        && req.headers().contains_key(header::IF_NONE_MATCH);
+    if !conditional {
+        return next.run(req).await;
+    }
+    let request_headers = req.headers().clone();
+    let resp = next.run(req).await;
+
+    let etag = resp
+        .headers()
+        .get(header::ETAG)
+        .and_then(|v| v.to_str().ok());
+    match etag {
+        Some(etag) if resp.status().is_success() && if_none_match(&request_headers, etag) => {
+            let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+            for name in [
+                header::ETAG,
+                header::CACHE_CONTROL,
+                header::VARY,
+                header::EXPIRES,
+                header::CONTENT_LOCATION,
+            ] {
+                if let Some(v) = resp.headers().get(&name) {
+                    not_modified.headers_mut().insert(name, v.clone());
+                }
+            }
+            not_modified
+        }
+        _ => resp,
+    }
+}
+
+/// JSON response with a strong ETag computed from the body; a drop-in
+/// replacement for `Json<T>` as a handler return type.
+pub struct ETagged<T>(pub T);
+
+impl<T: Serialize> IntoResponse for ETagged<T> {
+    fn into_response(self) -> Response {
+        json_with_etag(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[test]
+    fn etags_are_stable_and_quoted() {
+        let a = json_etag(&serde_json::json!({"id": 1})).unwrap();
+        let b = json_etag(&serde_json::json!({"id": 1})).unwrap();
+        assert_eq!(a, b);
+        assert!(a.starts_with('"') && a.ends_with('"'));
+        assert!(weak_etag(b"x").starts_with("W/\""));
+        assert_ne!(a, json_etag(&serde_json::json!({"id": 2})).unwrap());
+    }
+
+    #[test]
+    fn attachment_disposition_encodes_non_ascii_names() {
+        assert_eq!(
+            attachment_disposition("report.csv"),
+            "attachment; filename=\"report.csv\""
+        );
+        assert_eq!(
+            attachment_disposition("отчёт \"1\".pdf"),
+            "attachment; filename=\"_____ _1_.pdf\"; \
+             filename*=UTF-8''%D0%BE%D1%82%D1%87%D1%91%D1%82%20%221%22.pdf"
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_download_sets_headers_and_streams_body() {
+        let chunks = futures::stream::iter([
+            Ok::<_, std::io::Error>(Bytes::from_static(b"ab")),
+            Ok(Bytes::from_static(b"cd")),
+        ]);
+        let resp = stream_download(chunks, "application/octet-stream", Some("data.bin"));
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "application/octet-stream"
+        );
+        assert_eq!(
+            resp.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"data.bin\""
+        );
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"abcd");
+    }
+
+    #[test]
+    fn negotiate_honours_quality_and_specificity() {
+        let offered = ["application/json", "text/csv"];
+        let accept = |v: &'static str| {
+            let mut h = HeaderMap::new();
+            h.insert(header::ACCEPT, HeaderValue::from_static(v));
+            h
+        };
+        assert_eq!(
+            negotiate(&HeaderMap::new(), &offered),
+            Some("application/json")
+        );
+        assert_eq!(negotiate(&accept("text/csv"), &offered), Some("text/csv"));
+        assert_eq!(
+            negotiate(&accept("application/json;q=0.5, text/*"), &offered),
+            Some("text/csv")
+        );
+        assert_eq!(
+            negotiate(&accept("*/*, application/json;q=0"), &offered),
+            Some("text/csv")
+        );
+        assert_eq!(negotiate(&accept("image/png"), &offered), None);
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let etag = strong_etag(b"body");
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("\"other\", W/{etag}")).unwrap(),
+        );
+        assert!(if_none_match(&headers, &etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"other\""));
+        assert!(!if_none_match(&headers, &etag));
+    }
+
+    #[tokio::test]
+    async fn middleware_returns_304_for_matching_etag() {
+        let app = Router::new()
+            .route(
+                "/item",
+                get(|| async { ETagged(serde_json::json!({"id": 1})) }),
+            )
+            .layer(from_fn(conditional_get_middleware));
+
+        let first = app
+            .clone()
+            .oneshot(Request::get("/item").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers().get(header::ETAG).unwrap().clone();
+
+        let second = app
+            .clone()
+            .oneshot(
+                Request::get("/item")
+                    .header(header::IF_NONE_MATCH, etag.clone())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(second.headers().get(header::ETAG), Some(&etag));
+
+        let stale = app
+            .oneshot(
+                Request::get("/item")
+                    .header(header::IF_NONE_MATCH, "\"stale\"")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(stale.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn if_match_uses_strong_comparison() {
+        let etag = strong_etag(b"v1");
+        let with = |v: String| {
+            let mut h = HeaderMap::new();
+            h.insert(header::IF_MATCH, HeaderValue::from_str(&v).unwrap());
+            h
+        };
+        assert!(if_match(&HeaderMap::new(), &etag));
+        assert!(if_match(&with(format!("\"other\", {etag}")), &etag));
+        assert!(if_match(&with("*".into()), &etag));
+        assert!(!if_match(&with(format!("W/{etag}")), &etag));
+        assert!(!if_match(&with("\"stale\"".into()), &etag));
+    }
+
+    #[tokio::test]
+    async fn conditional_helpers_set_status_and_etag() {
+        let ok = ok_json_with_etag(&serde_json::json!({"id": 1}), "\"v7\"");
+        assert_eq!(ok.status(), StatusCode::OK);
+        assert_eq!(ok.headers()[header::ETAG], "\"v7\"");
+
+        let nm = not_modified("\"v7\"");
+        assert_eq!(nm.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(nm.headers()[header::ETAG], "\"v7\"");
+
+        let pf = precondition_failed("Item changed since it was read");
+        assert_eq!(pf.status(), StatusCode::PRECONDITION_FAILED);
+        let body = axum::body::to_bytes(pf.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: Problem = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code, "PRECONDITION_FAILED");
+    }
+}
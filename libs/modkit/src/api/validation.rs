@@ -0,0 +1,247 @@
+//! Input validation as RFC 9457 Problems.
+//!
+//! [`ValidatedJson`] deserializes a JSON body and runs its `validator::Validate`
+//! rules; any failure becomes a `422` Problem whose `errors` carry JSON
+//! pointers to the offending fields. The converters are public so handlers
+//! validating by hand produce the same shape.
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header, HeaderMap, StatusCode},
+};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
+
+use crate::api::problem::{Problem, ProblemResponse, ValidationError};
+
+/// A `422 Unprocessable Entity` Problem listing the given field errors.
+pub fn validation_problem(errors: Vec<ValidationError>) -> ProblemResponse {
+    Problem::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "Unprocessable Entity",
+        "Request validation failed",
+    )
+    .with_code("VALIDATION_ERROR")
+    .with_errors(errors)
+    .into()
+}
+
+/// Flatten `validator` errors into field errors with JSON pointers
+/// (`/address/street`, `/items/0/name`), sorted by pointer.
+pub fn from_validator_errors(errors: &ValidationErrors) -> Vec<ValidationError> {
+    let mut out = Vec::new();
+    collect(errors, "", &mut out);
+    out.sort_by(|a, b| a.pointer.cmp(&b.pointer));
+    out
+}
+
+fn collect(errors: &ValidationErrors, prefix: &str, out: &mut Vec<ValidationError>) {
+    for (field, kind) in errors.errors() {
+        // Struct-level (`#[validate(schema(...))]`) errors are reported on the parent
+        let pointer = if field == "__all__" {
+            prefix.to_string()
+        } else {
+            format!("{prefix}/{}", escape_pointer(field))
+        };
+        match kind {
+            ValidationErrorsKind::Field(errs) => out.extend(errs.iter().map(|e| ValidationError {
+                detail: match &e.message {
+                    Some(message) => message.to_string(),
+                    None => format!("failed '{}' validation", e.code),
+                },
+                pointer: pointer.clone(),
+            })),
+            ValidationErrorsKind::Struct(nested) => collect(nested, &pointer, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect(nested, &format!("{pointer}/{index}"), out);
+                }
+            }
+        }
+    }
+}
+
+/// The field error for a JSON body that failed to deserialize into the target type.
+pub fn from_json_path_error(
+    err: &serde_path_to_error::Error<serde_json::Error>,
+) -> ValidationError {
+    use serde_path_to_error::Segment;
+
+    let pointer = err
+        .path()
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(format!("/{index}")),
+            Segment::Map { key } => Some(format!("/{}", escape_pointer(key))),
+            Segment::Enum { .. } | Segment::Unknown => None,
+        })
+        .collect();
+    ValidationError {
+        detail: err.inner().to_string(),
+        pointer,
+    }
+}
+
+impl From<ValidationErrors> for ProblemResponse {
+    fn from(errors: ValidationErrors) -> Self {
+        validation_problem(from_validator_errors(&errors))
+    }
+}
+
+/// RFC 6901 escaping of one reference token.
+fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// JSON body extractor that also runs `validator::Validate`.
+///
+/// Rejections are Problems: `415` for a non-JSON content type, `400` for
+/// malformed JSON, and `422` (with field pointers) when the body does not fit
+/// `T` or fails validation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ValidatedJson<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = ProblemResponse;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_json(req.headers()) {
+            return Err(Problem::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Unsupported Media Type",
+                "Expected request with `Content-Type: application/json`",
+            )
+            .with_code("UNSUPPORTED_MEDIA_TYPE")
+            .into());
+        }
+        let body = Bytes::from_request(req, state).await.map_err(|e| {
+            let title = e.status().canonical_reason().unwrap_or("Bad Request");
+            ProblemResponse::from(Problem::new(e.status(), title, e.body_text()))
+        })?;
+
+        let de = &mut serde_json::Deserializer::from_slice(&body);
+        let value: T = serde_path_to_error::deserialize(de).map_err(|e| {
+            if e.inner().is_data() {
+                validation_problem(vec![from_json_path_error(&e)])
+            } else {
+                Problem::new(
+                    StatusCode::BAD_REQUEST,
+                    "Bad Request",
+                    e.inner().to_string(),
+                )
+                .with_code("MALFORMED_JSON")
+                .into()
+            }
+        })?;
+        value.validate()?;
+        Ok(Self(value))
+    }
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    mime.eq_ignore_ascii_case("application/json")
+        || (mime.starts_with("application/") && mime.ends_with("+json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, response::IntoResponse, routing::post, Router};
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct Item {
+        #[validate(length(min = 1, message = "name must not be empty"))]
+        name: String,
+    }
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct Order {
+        #[validate(email)]
+        email: String,
+        #[validate(range(min = 1))]
+        quantity: u32,
+        #[validate(nested)]
+        items: Vec<Item>,
+    }
+
+    #[test]
+    fn flattens_nested_errors_into_pointers() {
+        let order = Order {
+            email: "nope".into(),
+            quantity: 0,
+            items: vec![
+                Item { name: "ok".into() },
+                Item {
+                    name: String::new(),
+                },
+            ],
+        };
+        let errors = from_validator_errors(&order.validate().unwrap_err());
+        let pointers: Vec<_> = errors.iter().map(|e| e.pointer.as_str()).collect();
+        assert_eq!(pointers, ["/email", "/items/1/name", "/quantity"]);
+        assert_eq!(errors[1].detail, "name must not be empty");
+        assert_eq!(errors[0].detail, "failed 'email' validation");
+    }
+
+    async fn post_order(body: &'static str, content_type: &str) -> (StatusCode, serde_json::Value) {
+        async fn create(ValidatedJson(order): ValidatedJson<Order>) -> impl IntoResponse {
+            order.items.len().to_string()
+        }
+        let app = Router::new().route("/orders", post(create));
+        let resp = app
+            .oneshot(
+                Request::post("/orders")
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn extractor_rejects_with_problems() {
+        let valid = r#"{"email":"a@example.com","quantity":1,"items":[{"name":"x"}]}"#;
+        assert_eq!(
+            post_order(valid, "application/json").await.0,
+            StatusCode::OK
+        );
+
+        let (status, _) = post_order(valid, "text/plain").await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let (status, body) = post_order(r#"{"email":"#, "application/json").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "MALFORMED_JSON");
+
+        let wrong_type = r#"{"email":"a@example.com","quantity":1,"items":[{"name":7}]}"#;
+        let (status, body) = post_order(wrong_type, "application/json").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["errors"][0]["pointer"], "/items/0/name");
+
+        let invalid = r#"{"email":"a@example.com","quantity":0,"items":[]}"#;
+        let (status, body) = post_order(invalid, "application/json").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "VALIDATION_ERROR");
+        assert_eq!(body["errors"][0]["pointer"], "/quantity");
+    }
+}
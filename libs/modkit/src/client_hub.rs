@@ -4,15 +4,22 @@
 //! - Providers register an implementation once (local or remote).
 //! - Consumers fetch by *interface type* (trait object): `get::<dyn my::Api>()`.
 //! - Optional scopes (e.g., multi-tenant): `register_scoped / get_scoped`.
+//! - Several providers of one interface side by side: `register_named / get_named`.
+//! - Diagnostics: `list()` reports every registered client with the module that provided it.
+//! - Optional instrumentation: a hub built with `ClientHub::instrumented()` wraps clients of
+//!   `#[modkit::instrumented_client]` traits in a decorator that records a tracing span, a call
+//!   counter and a latency histogram per call, labeled by contract and method.
 //!
 //! Implementation details:
-//! - Key = (type name, scope). We use `type_name::<T>()`, which works for `T = dyn Trait`.
+//! - Key = (type name, scope, name). We use `type_name::<T>()`, which works for `T = dyn Trait`.
+//!   Named clients live in the global scope, apart from the unnamed one.
 //! - Value = `Arc<T>` stored as `Box<dyn Any + Send + Sync>` (downcast on read).
 //! - Sync hot path: `get()` is non-async; no hidden per-entry cells or lazy slots.
 //!
 //! Notes:
 //! - Re-registering overwrites the previous value atomically; existing Arcs held by consumers remain valid.
-//! - For testing, just register a mock under the same trait type.
+//! - For testing, register a mock under the same trait type, or use `scoped_override`
+//!   to shadow the client until the returned guard drops, whatever providers register.
 
 use parking_lot::RwLock;
 use std::{any::Any, collections::HashMap, fmt, sync::Arc};
@@ -64,21 +71,138 @@ impl fmt::Debug for ScopeKey {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ClientHubError {
-    #[error("client not found: type={type_key:?}, scope={scope:?}")]
-    NotFound { type_key: TypeKey, scope: ScopeKey },
+    #[error("{0}")]
+    NotFound(Box<MissingClient>),
 
     #[error("type mismatch in hub for type={type_key:?}, scope={scope:?}")]
     TypeMismatch { type_key: TypeKey, scope: ScopeKey },
 }
 
+/// What was asked for when a lookup failed, and what the hub had instead.
+#[derive(Debug, Clone)]
+pub struct MissingClient {
+    pub type_key: TypeKey,
+    pub scope: ScopeKey,
+    pub name: Option<Arc<str>>,
+    /// Module that did the lookup, when it went through its `ModuleCtx`.
+    pub requested_by: Option<String>,
+    /// Every registered client, sorted (`type`, plus scope or name when set).
+    pub registered: Vec<String>,
+    /// Registered clients that look like what was meant: the same type under
+    /// another scope or name, or a type with a similar name.
+    pub suggestions: Vec<String>,
+}
+
+impl fmt::Display for MissingClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "client not found: type={:?}", self.type_key)?;
+        match &self.name {
+            Some(name) => write!(f, ", name={name}")?,
+            None => write!(f, ", scope={:?}", self.scope)?,
+        }
+        if let Some(module) = &self.requested_by {
+            write!(f, ", requested by module '{module}'")?;
+        }
+        if self.registered.is_empty() {
+            f.write_str("; no clients are registered")?;
+        } else {
+            write!(f, "; registered: [{}]", self.registered.join(", "))?;
+        }
+        if !self.suggestions.is_empty() {
+            write!(f, "; did you mean: {}?", self.suggestions.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Most suggestions a [`MissingClient`] carries.
+const MAX_SUGGESTIONS: usize = 5;
+
+fn describe(key: &ClientKey) -> String {
+    let (type_key, scope, name) = key;
+    match (name, &scope.0) {
+        (Some(name), _) => format!("{type_key:?} (name={name})"),
+        (None, Some(s)) if &**s != GLOBAL_SCOPE => format!("{type_key:?} (scope={s})"),
+        _ => format!("{type_key:?}"),
+    }
+}
+
+/// Last path segment of a type name, e.g. `MyApi` for `dyn my::contract::MyApi`.
+fn short_type_name(type_name: &str) -> &str {
+    let base = type_name.split('<').next().unwrap_or(type_name);
+    base.rsplit("::")
+        .next()
+        .unwrap_or(base)
+        .trim_start_matches("dyn ")
+}
+
+/// Levenshtein distance over chars.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let subst = prev[j] + usize::from(ca != cb);
+            cur[j + 1] = subst.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
 type Boxed = Box<dyn Any + Send + Sync>;
 
+/// Internal map key: interface type, scope and, for named clients, the name.
+type ClientKey = (TypeKey, ScopeKey, Option<Arc<str>>);
+
 /// Internal map type for the client hub.
-type ClientMap = HashMap<(TypeKey, ScopeKey), Boxed>;
+type ClientMap = HashMap<ClientKey, Boxed>;
+
+/// Providing module and time of one registration.
+type Registration = (Option<&'static str>, chrono::DateTime<chrono::Utc>);
 
-/// Type-safe registry of clients keyed by (interface type, scope).
+tokio::task_local! {
+    /// Module whose `init` is running; recorded as the provider of the
+    /// clients it registers.
+    static PROVIDER: &'static str;
+}
+
+/// Run `f` as `module`, so clients it registers are listed as provided by it.
+pub(crate) async fn providing<F: std::future::Future>(module: &'static str, f: F) -> F::Output {
+    PROVIDER.scope(module, f).await
+}
+
+/// Like [`providing`], for synchronous registration.
+pub(crate) fn providing_sync<R>(module: &'static str, f: impl FnOnce() -> R) -> R {
+    PROVIDER.sync_scope(module, f)
+}
+
+/// One registered client, as reported by [`ClientHub::list`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ClientInfo {
+    /// Interface type, e.g. `dyn users_info::contract::client::UsersInfoApi`.
+    pub contract: &'static str,
+    /// Set for clients outside the global scope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Module whose `init` registered the client (`None` when registered
+    /// outside one, e.g. by the embedder).
+    pub provider: Option<&'static str>,
+    pub registered_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Type-safe registry of clients keyed by (interface type, scope, name).
 pub struct ClientHub {
     map: RwLock<ClientMap>,
+    /// Provider and time of each registration in `map`.
+    registrations: RwLock<HashMap<ClientKey, Registration>>,
+    /// Clients installed by `scoped_override*`; looked up before `map`.
+    overrides: RwLock<ClientMap>,
+    /// Wrap registered clients in their `ClientDecorator`, if any.
+    instrumented: bool,
 }
 
 impl ClientHub {
@@ -86,8 +210,105 @@ impl ClientHub {
     pub fn new() -> Self {
         Self {
             map: RwLock::new(HashMap::new()),
+            registrations: RwLock::new(HashMap::new()),
+            overrides: RwLock::new(HashMap::new()),
+            instrumented: false,
         }
     }
+
+    /// A hub that instruments clients of `#[modkit::instrumented_client]`
+    /// traits as they are registered. Overrides are stored as given.
+    pub fn instrumented() -> Self {
+        Self {
+            instrumented: true,
+            ..Self::new()
+        }
+    }
+
+    fn decorate(&self, type_key: &TypeKey, client: Boxed) -> Boxed {
+        if !self.instrumented {
+            return client;
+        }
+        match inventory::iter::<ClientDecorator>
+            .into_iter()
+            .find(|d| (d.type_name)() == type_key.0)
+        {
+            Some(d) => (d.wrap)(client),
+            None => client,
+        }
+    }
+}
+
+/// Wraps `Arc<dyn Trait>` clients of one trait; submitted by
+/// `#[modkit::instrumented_client]`.
+#[doc(hidden)]
+pub struct ClientDecorator {
+    /// `type_name::<dyn Trait>()`.
+    pub type_name: fn() -> &'static str,
+    /// Takes and returns a boxed `Arc<dyn Trait>`.
+    pub wrap: fn(Box<dyn Any + Send + Sync>) -> Box<dyn Any + Send + Sync>,
+}
+
+inventory::collect!(ClientDecorator);
+
+/// Run one call of an instrumented client: in a `client_call` span, counted
+/// and timed by [`ClientMetrics`](crate::telemetry::ClientMetrics) with the
+/// outcome taken from the `Result`.
+#[doc(hidden)]
+pub async fn observe_call<F, T, E>(
+    contract: &'static str,
+    method: &'static str,
+    call: F,
+) -> F::Output
+where
+    F: std::future::Future<Output = Result<T, E>>,
+{
+    observe(contract, method, call, Result::is_err).await
+}
+
+/// Like [`observe_call`] for methods that do not return a `Result`.
+#[doc(hidden)]
+pub async fn observe_call_infallible<F: std::future::Future>(
+    contract: &'static str,
+    method: &'static str,
+    call: F,
+) -> F::Output {
+    observe(contract, method, call, |_| false).await
+}
+
+async fn observe<F: std::future::Future>(
+    contract: &'static str,
+    method: &'static str,
+    call: F,
+    is_err: fn(&F::Output) -> bool,
+) -> F::Output {
+    use tracing::Instrument;
+
+    let span = tracing::info_span!("client_call", contract, method);
+    let started = std::time::Instant::now();
+    let out = call.instrument(span).await;
+    let outcome = if is_err(&out) { "error" } else { "ok" };
+    crate::telemetry::ClientMetrics::global().observe(contract, method, outcome, started.elapsed());
+    out
+}
+
+/// Guard returned by [`ClientHub::scoped_override`]; dropping it restores
+/// what the key resolved to before.
+#[must_use = "the override is removed when the guard is dropped"]
+pub struct ClientOverride<'a> {
+    hub: &'a ClientHub,
+    key: ClientKey,
+    previous: Option<Boxed>,
+}
+
+impl Drop for ClientOverride<'_> {
+    fn drop(&mut self) {
+        let mut w = self.hub.overrides.write();
+        match self.previous.take() {
+            Some(prev) => w.insert(self.key.clone(), prev),
+            None => w.remove(&self.key),
+        };
+    }
 }
 
 impl Default for ClientHub {
@@ -111,10 +332,55 @@ impl ClientHub {
     where
         T: ?Sized + Send + Sync + 'static,
     {
-        let type_key = TypeKey::of::<T>();
-        let scope_key = ScopeKey::named(scope);
-        let mut w = self.map.write();
-        w.insert((type_key, scope_key), Box::new(client));
+        self.insert((TypeKey::of::<T>(), ScopeKey::named(scope), None), client);
+    }
+
+    /// Register one of several clients of the interface type `T` under `name`
+    /// (e.g. `"primary"`). Independent of the unnamed client of `T`.
+    pub fn register_named<T>(&self, name: impl Into<Arc<str>>, client: Arc<T>)
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let key = (
+            TypeKey::of::<T>(),
+            ScopeKey::named(GLOBAL_SCOPE),
+            Some(name.into()),
+        );
+        self.insert(key, client);
+    }
+
+    fn insert<T>(&self, key: ClientKey, client: Arc<T>)
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let client = self.decorate(&key.0, Box::new(client));
+        let provider = PROVIDER.try_with(|m| *m).ok();
+        self.registrations
+            .write()
+            .insert(key.clone(), (provider, chrono::Utc::now()));
+        self.map.write().insert(key, client);
+    }
+
+    /// Every registered client (overrides aside), sorted by contract, scope and name.
+    pub fn list(&self) -> Vec<ClientInfo> {
+        let mut out: Vec<_> = self
+            .registrations
+            .read()
+            .iter()
+            .map(|((type_key, scope, name), (provider, at))| ClientInfo {
+                contract: type_key.0,
+                scope: scope
+                    .0
+                    .as_deref()
+                    .filter(|s| *s != GLOBAL_SCOPE)
+                    .map(str::to_string),
+                name: name.as_deref().map(str::to_string),
+                provider: *provider,
+                registered_at: *at,
+            })
+            .collect();
+        out.sort_by(|a, b| (a.contract, &a.scope, &a.name).cmp(&(b.contract, &b.scope, &b.name)));
+        out
     }
 
     /// Fetch a client from the *global* scope by interface type `T`.
@@ -125,30 +391,179 @@ impl ClientHub {
         self.get_scoped::<T>(GLOBAL_SCOPE)
     }
 
+    /// Make lookups of `T` in the global scope return `client` until the guard
+    /// drops, even if a provider registers `T` meanwhile. Meant for tests that
+    /// keep the real wiring but replace one dependency with a mock.
+    pub fn scoped_override<T>(&self, client: Arc<T>) -> ClientOverride<'_>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.install_override(
+            (TypeKey::of::<T>(), ScopeKey::named(GLOBAL_SCOPE), None),
+            client,
+        )
+    }
+
+    /// Like [`ClientHub::scoped_override`], for the client registered under `name`.
+    pub fn scoped_override_named<T>(
+        &self,
+        name: impl Into<Arc<str>>,
+        client: Arc<T>,
+    ) -> ClientOverride<'_>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let key = (
+            TypeKey::of::<T>(),
+            ScopeKey::named(GLOBAL_SCOPE),
+            Some(name.into()),
+        );
+        self.install_override(key, client)
+    }
+
+    fn install_override<T>(&self, key: ClientKey, client: Arc<T>) -> ClientOverride<'_>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let previous = self.overrides.write().insert(key.clone(), Box::new(client));
+        ClientOverride {
+            hub: self,
+            key,
+            previous,
+        }
+    }
+
+    /// Fetch a client that may legitimately be absent, e.g. one published by
+    /// an optional (`"name?"`) dependency that is not compiled in.
+    pub fn try_get<T>(&self) -> Option<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.get::<T>().ok()
+    }
+
     /// Fetch a client from a *named* scope by interface type `T`.
     pub fn get_scoped<T>(&self, scope: impl Into<Arc<str>>) -> Result<Arc<T>, ClientHubError>
     where
         T: ?Sized + Send + Sync + 'static,
     {
-        let type_key = TypeKey::of::<T>();
-        let scope_key = ScopeKey::named(scope);
-        let r = self.map.read();
+        self.lookup::<T>((TypeKey::of::<T>(), ScopeKey::named(scope), None), None)
+    }
+
+    /// Fetch the client registered under `name` for interface type `T`.
+    pub fn get_named<T>(&self, name: impl Into<Arc<str>>) -> Result<Arc<T>, ClientHubError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let key = (
+            TypeKey::of::<T>(),
+            ScopeKey::named(GLOBAL_SCOPE),
+            Some(name.into()),
+        );
+        self.lookup::<T>(key, None)
+    }
+
+    /// Like [`ClientHub::get_scoped`] / [`ClientHub::get_named`], but a
+    /// [`MissingClient`] names `module` as the one asking. Used by
+    /// `ModuleCtx::client*`.
+    pub(crate) fn get_for<T>(
+        &self,
+        module: Option<&str>,
+        scope: &str,
+        name: Option<&str>,
+    ) -> Result<Arc<T>, ClientHubError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let key = (
+            TypeKey::of::<T>(),
+            ScopeKey::named(scope),
+            name.map(Arc::from),
+        );
+        self.lookup::<T>(key, module)
+    }
 
-        let boxed =
-            r.get(&(type_key.clone(), scope_key.clone()))
-                .ok_or(ClientHubError::NotFound {
-                    type_key: type_key.clone(),
-                    scope: scope_key.clone(),
-                })?;
+    fn lookup<T>(
+        &self,
+        key: ClientKey,
+        requested_by: Option<&str>,
+    ) -> Result<Arc<T>, ClientHubError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let o = self.overrides.read();
+        let r = self.map.read();
+        let Some(boxed) = o.get(&key).or_else(|| r.get(&key)) else {
+            return Err(ClientHubError::NotFound(Box::new(Self::missing(
+                &r,
+                key,
+                requested_by,
+            ))));
+        };
 
         // Stored value is exactly `Arc<T>`; downcast is safe and cheap.
         if let Some(arc_t) = boxed.downcast_ref::<Arc<T>>() {
             return Ok(arc_t.clone());
         }
-        Err(ClientHubError::TypeMismatch {
+        let (type_key, scope, _) = key;
+        Err(ClientHubError::TypeMismatch { type_key, scope })
+    }
+
+    fn missing(map: &ClientMap, key: ClientKey, requested_by: Option<&str>) -> MissingClient {
+        let mut registered: Vec<String> = map.keys().map(describe).collect();
+        registered.sort_unstable();
+
+        let wanted = short_type_name(key.0 .0).to_ascii_lowercase();
+        let mut suggestions: Vec<String> = map
+            .keys()
+            .filter(|other| {
+                if other.0 == key.0 {
+                    return true; // same interface, other scope or name
+                }
+                let candidate = short_type_name(other.0 .0).to_ascii_lowercase();
+                edit_distance(&wanted, &candidate) <= 2
+            })
+            .map(describe)
+            .collect();
+        suggestions.sort_unstable();
+        suggestions.truncate(MAX_SUGGESTIONS);
+
+        let (type_key, scope, name) = key;
+        MissingClient {
             type_key,
-            scope: scope_key,
-        })
+            scope,
+            name,
+            requested_by: requested_by.map(str::to_string),
+            registered,
+            suggestions,
+        }
+    }
+
+    /// Fetch a named client that may legitimately be absent.
+    pub fn try_get_named<T>(&self, name: impl Into<Arc<str>>) -> Option<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.get_named::<T>(name).ok()
+    }
+
+    /// Names under which clients of interface type `T` are registered, sorted.
+    pub fn names<T>(&self) -> Vec<Arc<str>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let type_key = TypeKey::of::<T>();
+        let o = self.overrides.read();
+        let r = self.map.read();
+        let mut names: Vec<_> = r
+            .keys()
+            .chain(o.keys())
+            .filter(|(t, _, _)| *t == type_key)
+            .filter_map(|(_, _, name)| name.clone())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
     }
 
     /// Remove a client; returns the removed client if it was present.
@@ -156,15 +571,35 @@ impl ClientHub {
     where
         T: ?Sized + Send + Sync + 'static,
     {
-        let type_key = TypeKey::of::<T>();
-        let scope_key = ScopeKey::named(scope);
-        let mut w = self.map.write();
-        let boxed = w.remove(&(type_key, scope_key))?;
+        let key = (TypeKey::of::<T>(), ScopeKey::named(scope), None);
+        self.remove_key::<T>(&key)
+    }
+
+    /// Remove a named client; returns it if it was present.
+    pub fn remove_named<T>(&self, name: impl Into<Arc<str>>) -> Option<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let key = (
+            TypeKey::of::<T>(),
+            ScopeKey::named(GLOBAL_SCOPE),
+            Some(name.into()),
+        );
+        self.remove_key::<T>(&key)
+    }
+
+    fn remove_key<T>(&self, key: &ClientKey) -> Option<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.registrations.write().remove(key);
+        let boxed = self.map.write().remove(key)?;
         boxed.downcast::<Arc<T>>().ok().map(|b| *b)
     }
 
-    /// Clear everything (useful in tests).
+    /// Clear everything (useful in tests). Overrides stay until their guards drop.
     pub fn clear(&self) {
+        self.registrations.write().clear();
         self.map.write().clear();
     }
 
@@ -207,6 +642,35 @@ mod tests {
         assert_eq!(Arc::as_ptr(&api), Arc::as_ptr(&got));
     }
 
+    #[tokio::test]
+    async fn list_reports_contract_scope_and_provider() {
+        let hub = ClientHub::new();
+        providing("users_info", async {
+            hub.register::<dyn TestApi>(Arc::new(ImplA(1)));
+            hub.register_named::<dyn TestApi>("replica", Arc::new(ImplA(2)));
+        })
+        .await;
+        hub.register_scoped::<dyn TestApi>("tenant-1", Arc::new(ImplA(3)));
+
+        let listed: Vec<_> = hub
+            .list()
+            .into_iter()
+            .map(|c| (c.scope, c.name, c.provider))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                (None, None, Some("users_info")),
+                (None, Some("replica".to_string()), Some("users_info")),
+                (Some("tenant-1".to_string()), None, None),
+            ]
+        );
+        assert!(hub.list()[0].contract.ends_with("TestApi"));
+
+        hub.remove::<dyn TestApi>("tenant-1");
+        assert_eq!(hub.list().len(), 2);
+    }
+
     #[tokio::test]
     async fn scopes_are_independent() {
         let hub = ClientHub::new();
@@ -228,5 +692,106 @@ mod tests {
             2
         );
         assert!(hub.get::<dyn TestApi>().is_err()); // global not set
+        assert!(hub.try_get::<dyn TestApi>().is_none());
+    }
+
+    #[tokio::test]
+    async fn named_clients_coexist() {
+        let hub = ClientHub::new();
+        hub.register_named::<dyn TestApi>("primary", Arc::new(ImplA(1)));
+        hub.register_named::<dyn TestApi>("fallback", Arc::new(ImplA(2)));
+
+        assert_eq!(
+            hub.get_named::<dyn TestApi>("primary").unwrap().id().await,
+            1
+        );
+        assert_eq!(
+            hub.get_named::<dyn TestApi>("fallback").unwrap().id().await,
+            2
+        );
+        assert_eq!(
+            hub.names::<dyn TestApi>(),
+            vec![Arc::<str>::from("fallback"), Arc::from("primary")]
+        );
+        assert!(hub.get::<dyn TestApi>().is_err()); // unnamed client not set
+        assert!(matches!(
+            hub.get_named::<dyn TestApi>("other"),
+            Err(ClientHubError::NotFound(_))
+        ));
+
+        assert!(hub.remove_named::<dyn TestApi>("primary").is_some());
+        assert!(hub.try_get_named::<dyn TestApi>("primary").is_none());
+        assert_eq!(hub.names::<dyn TestApi>().len(), 1);
+    }
+
+    trait TestApis: Send + Sync {}
+    trait Unrelated: Send + Sync {}
+    impl TestApis for ImplA {}
+    impl Unrelated for ImplA {}
+
+    #[test]
+    fn missing_client_lists_registered_and_suggestions() {
+        let hub = ClientHub::new();
+        hub.register_scoped::<dyn TestApi>("tenant-1", Arc::new(ImplA(1)));
+        hub.register::<dyn TestApis>(Arc::new(ImplA(2)));
+        hub.register::<dyn Unrelated>(Arc::new(ImplA(3)));
+
+        let Err(err) = hub.get_for::<dyn TestApi>(Some("consumer"), GLOBAL_SCOPE, None) else {
+            panic!("expected a miss");
+        };
+        let ClientHubError::NotFound(missing) = &err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(missing.requested_by.as_deref(), Some("consumer"));
+        assert_eq!(missing.registered.len(), 3);
+        assert_eq!(missing.suggestions.len(), 2);
+        assert!(missing
+            .suggestions
+            .iter()
+            .any(|s| s.ends_with("(scope=tenant-1)")));
+        assert!(missing.suggestions.iter().any(|s| s.ends_with("TestApis")));
+
+        let msg = err.to_string();
+        assert!(msg.contains("requested by module 'consumer'"), "{msg}");
+        assert!(msg.contains("did you mean"), "{msg}");
+    }
+
+    #[test]
+    fn edit_distance_counts_single_edits() {
+        assert_eq!(edit_distance("llmapi", "llmapi"), 0);
+        assert_eq!(edit_distance("llmapi", "llmapis"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(short_type_name("dyn a::b::LlmApi"), "LlmApi");
+    }
+
+    #[tokio::test]
+    async fn override_shadows_registrations_until_dropped() {
+        let hub = ClientHub::new();
+        hub.register::<dyn TestApi>(Arc::new(ImplA(1)));
+        {
+            let _mock = hub.scoped_override::<dyn TestApi>(Arc::new(ImplA(99)));
+            assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 99);
+
+            // A provider registering later does not replace the mock.
+            hub.register::<dyn TestApi>(Arc::new(ImplA(2)));
+            assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 99);
+
+            {
+                let _inner = hub.scoped_override::<dyn TestApi>(Arc::new(ImplA(100)));
+                assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 100);
+            }
+            assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 99);
+        }
+        assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 2);
+
+        let _named = hub.scoped_override_named::<dyn TestApi>("primary", Arc::new(ImplA(5)));
+        assert_eq!(
+            hub.get_named::<dyn TestApi>("primary").unwrap().id().await,
+            5
+        );
+        assert_eq!(
+            hub.names::<dyn TestApi>(),
+            vec![Arc::<str>::from("primary")]
+        );
     }
 }
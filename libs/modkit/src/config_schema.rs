@@ -0,0 +1,235 @@
+//! Declarative constraints on module config structs.
+//!
+//! `#[derive(ConfigSchema)]` turns field attributes into checks that run after
+//! the section deserializes, for every module with `#[module(config = T)]`,
+//! before any `init`:
+//!
+//! ```rust,ignore
+//! #[derive(serde::Deserialize, modkit::ConfigSchema)]
+//! struct CacheConfig {
+//!     #[config(min = 1, max = 10_000)]
+//!     capacity: u32,
+//!     #[config(one_of = ["lru", "lfu"])]
+//!     policy: String,
+//!     #[config(required_with = "tls_cert")]
+//!     tls_key: Option<String>,
+//!     tls_cert: Option<String>,
+//!     #[config(nested)]
+//!     retry: RetryConfig,
+//! }
+//! ```
+//!
+//! Every violation is reported, not just the first one.
+
+use serde::de::DeserializeOwned;
+
+use crate::context::{module_config_typed, ConfigError, ConfigProvider};
+
+/// One broken constraint, e.g. `capacity: must be at most 10000, got 20000`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigViolation {
+    /// Dotted path of the field within the module's `config` section.
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Constraints of a config struct, usually derived.
+pub trait ConfigSchema {
+    fn violations(&self) -> Vec<ConfigViolation>;
+}
+
+impl<T: ConfigSchema> ConfigSchema for Option<T> {
+    fn violations(&self) -> Vec<ConfigViolation> {
+        self.as_ref().map(T::violations).unwrap_or_default()
+    }
+}
+
+/// Deserialize the `config` section of `module` into `T` and check its constraints.
+pub fn check_typed<T>(provider: &dyn ConfigProvider, module: &str) -> Result<(), ConfigError>
+where
+    T: DeserializeOwned + ConfigSchema,
+{
+    let violations = module_config_typed::<T>(provider, module)?.violations();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(ConfigError::Violations {
+            module: module.to_string(),
+            violations,
+        })
+    }
+}
+
+/// Field values the derived checks understand.
+#[doc(hidden)]
+pub trait SchemaValue {
+    /// `false` for `None` and empty collections; unset fields skip value checks.
+    fn is_set(&self) -> bool {
+        true
+    }
+    fn as_f64(&self) -> Option<f64> {
+        None
+    }
+    fn as_str(&self) -> Option<&str> {
+        None
+    }
+}
+
+macro_rules! numeric_schema_value {
+    ($($t:ty),*) => {$(
+        impl SchemaValue for $t {
+            fn as_f64(&self) -> Option<f64> {
+                Some(*self as f64)
+            }
+        }
+    )*};
+}
+
+numeric_schema_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl SchemaValue for bool {}
+
+impl SchemaValue for String {
+    fn as_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: SchemaValue> SchemaValue for Option<T> {
+    fn is_set(&self) -> bool {
+        self.as_ref().is_some_and(T::is_set)
+    }
+    fn as_f64(&self) -> Option<f64> {
+        self.as_ref().and_then(T::as_f64)
+    }
+    fn as_str(&self) -> Option<&str> {
+        self.as_ref().and_then(T::as_str)
+    }
+}
+
+impl<T> SchemaValue for Vec<T> {
+    fn is_set(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+fn push(out: &mut Vec<ConfigViolation>, field: &str, message: String) {
+    out.push(ConfigViolation {
+        field: field.to_string(),
+        message,
+    });
+}
+
+#[doc(hidden)]
+pub fn check_range(
+    out: &mut Vec<ConfigViolation>,
+    field: &str,
+    value: &impl SchemaValue,
+    min: Option<f64>,
+    max: Option<f64>,
+) {
+    let Some(v) = value.as_f64() else { return };
+    let message = match (min, max) {
+        (Some(min), Some(max)) if v < min || v > max => format!("must be between {min} and {max}"),
+        (Some(min), None) if v < min => format!("must be at least {min}"),
+        (None, Some(max)) if v > max => format!("must be at most {max}"),
+        _ => return,
+    };
+    push(out, field, format!("{message}, got {v}"));
+}
+
+#[doc(hidden)]
+pub fn check_one_of(
+    out: &mut Vec<ConfigViolation>,
+    field: &str,
+    value: &impl SchemaValue,
+    allowed: &[&str],
+) {
+    let Some(v) = value.as_str() else { return };
+    if !allowed.contains(&v) {
+        push(
+            out,
+            field,
+            format!("must be one of [{}], got '{v}'", allowed.join(", ")),
+        );
+    }
+}
+
+#[doc(hidden)]
+pub fn check_required_with(
+    out: &mut Vec<ConfigViolation>,
+    field: &str,
+    value: &impl SchemaValue,
+    other: &str,
+    other_value: &impl SchemaValue,
+) {
+    if other_value.is_set() && !value.is_set() {
+        push(out, field, format!("is required when '{other}' is set"));
+    }
+}
+
+#[doc(hidden)]
+pub fn check_nested(out: &mut Vec<ConfigViolation>, field: &str, value: &impl ConfigSchema) {
+    out.extend(value.violations().into_iter().map(|v| ConfigViolation {
+        field: format!("{field}.{}", v.field),
+        message: v.message,
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Retry {
+        attempts: u32,
+    }
+
+    impl ConfigSchema for Retry {
+        fn violations(&self) -> Vec<ConfigViolation> {
+            let mut out = Vec::new();
+            check_range(&mut out, "attempts", &self.attempts, Some(1.0), None);
+            out
+        }
+    }
+
+    #[test]
+    fn checks_collect_every_violation() {
+        let mut out = Vec::new();
+        check_range(&mut out, "capacity", &20_000u32, Some(1.0), Some(10_000.0));
+        check_range(&mut out, "ratio", &None::<f64>, Some(0.0), Some(1.0));
+        check_one_of(&mut out, "policy", &"fifo".to_string(), &["lru", "lfu"]);
+        check_one_of(&mut out, "mode", &Some("lru".to_string()), &["lru"]);
+        check_required_with(
+            &mut out,
+            "tls_key",
+            &None::<String>,
+            "tls_cert",
+            &Some("cert.pem".to_string()),
+        );
+        check_required_with(
+            &mut out,
+            "user",
+            &None::<String>,
+            "hosts",
+            &Vec::<String>::new(),
+        );
+        check_nested(&mut out, "retry", &Retry { attempts: 0 });
+
+        let rendered: Vec<String> = out.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            [
+                "capacity: must be between 1 and 10000, got 20000",
+                "policy: must be one of [lru, lfu], got 'fifo'",
+                "tls_key: is required when 'tls_cert' is set",
+                "retry.attempts: must be at least 1, got 0",
+            ]
+        );
+    }
+}
@@ -1,5 +1,10 @@
+use anyhow::Context as _;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 
 // Note: runtime-dependent features are conditionally compiled
@@ -19,6 +24,34 @@ pub enum ConfigError {
         #[source]
         source: serde_json::Error,
     },
+    #[error("invalid config for module '{module}': {}", violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    Violations {
+        module: String,
+        violations: Vec<crate::config_schema::ConfigViolation>,
+    },
+    #[error("unknown keys in config of module '{module}': {}", keys.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    UnknownKeys {
+        module: String,
+        keys: Vec<UnknownKey>,
+    },
+}
+
+/// A key of a module's `config` section that its struct does not have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    /// Dotted path within the section, e.g. `cors.allowed_origin`.
+    pub path: String,
+    /// The closest valid key at the same level, if one is close enough.
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.suggestion {
+            Some(s) => write!(f, "'{}' (did you mean '{s}'?)", self.path),
+            None => write!(f, "'{}'", self.path),
+        }
+    }
 }
 
 /// Provider of module-specific configuration (raw JSON sections only).
@@ -120,6 +153,276 @@ pub fn module_config_typed<T: DeserializeOwned>(
     Ok(config)
 }
 
+/// Strict variant of [`module_config_typed`]: the section is merged over
+/// `T::default()` (file values win, nested objects merge key by key), and
+/// keys that `T::default()` does not serialize are rejected with the closest
+/// valid name, so `bind_adress` fails instead of being silently ignored.
+/// A missing module or `config` section yields `T::default()`.
+///
+/// Empty objects in the defaults (maps such as `routes: {}`) accept any key.
+pub fn module_config_strict<T>(
+    provider: &dyn ConfigProvider,
+    module_name: &str,
+) -> Result<T, ConfigError>
+where
+    T: DeserializeOwned + Serialize + Default,
+{
+    let section = match provider.get_module_config(module_name) {
+        None => serde_json::Value::Object(serde_json::Map::new()),
+        Some(raw) => raw
+            .as_object()
+            .ok_or_else(|| ConfigError::InvalidModuleStructure {
+                module: module_name.to_string(),
+            })?
+            .get("config")
+            .cloned()
+            .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new())),
+    };
+    let invalid = |source| ConfigError::InvalidConfig {
+        module: module_name.to_string(),
+        source,
+    };
+    let mut merged = serde_json::to_value(T::default()).map_err(invalid)?;
+
+    let mut keys = Vec::new();
+    unknown_keys(&section, &merged, "", &mut keys);
+    if !keys.is_empty() {
+        return Err(ConfigError::UnknownKeys {
+            module: module_name.to_string(),
+            keys,
+        });
+    }
+    merge_over(&mut merged, section);
+    serde_json::from_value(merged).map_err(invalid)
+}
+
+/// Collect keys of `section` that `known` lacks, recursing into shared objects.
+fn unknown_keys(
+    section: &serde_json::Value,
+    known: &serde_json::Value,
+    prefix: &str,
+    out: &mut Vec<UnknownKey>,
+) {
+    let (Some(section), Some(known)) = (section.as_object(), known.as_object()) else {
+        return;
+    };
+    if known.is_empty() {
+        return;
+    }
+    for (key, value) in section {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match known.get(key) {
+            Some(k) => unknown_keys(value, k, &path, out),
+            None => {
+                let suggestion = known
+                    .keys()
+                    .map(|k| (crate::client_hub::edit_distance(key, k), k))
+                    .filter(|(d, k)| *d <= key.len().max(k.len()) / 2)
+                    .min()
+                    .map(|(_, k)| k.clone());
+                out.push(UnknownKey { path, suggestion });
+            }
+        }
+    }
+}
+
+/// Deep-merge `over` into `base`: objects merge per key, anything else replaces.
+fn merge_over(base: &mut serde_json::Value, over: serde_json::Value) {
+    match (base, over) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(over)) => {
+            for (key, value) in over {
+                match base.get_mut(&key) {
+                    Some(slot) => merge_over(slot, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, over) => *base = over,
+    }
+}
+
+/// Whether `modules.<name>.enabled` allows the module to run (absent means enabled).
+pub fn module_enabled(provider: &dyn ConfigProvider, module_name: &str) -> bool {
+    provider
+        .get_module_config(module_name)
+        .and_then(|raw| raw.get("enabled"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(true)
+}
+
+/// The `config` section of `modules.<name>`, or an empty object.
+pub(crate) fn config_section(
+    provider: &dyn ConfigProvider,
+    module_name: &str,
+) -> serde_json::Value {
+    provider
+        .get_module_config(module_name)
+        .and_then(|raw| raw.get("config"))
+        .cloned()
+        .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()))
+}
+
+/// Deserialize a module's `config` section into `T`.
+fn parse_section<T: DeserializeOwned>(
+    module: &str,
+    section: &serde_json::Value,
+) -> Result<T, ConfigError> {
+    serde_json::from_value(section.clone()).map_err(|source| ConfigError::InvalidConfig {
+        module: module.to_string(),
+        source,
+    })
+}
+
+/// Per-module channels carrying reloaded `config` sections (see [`ModuleCtx::config_updates`]).
+#[derive(Default)]
+pub struct ConfigUpdates {
+    senders: parking_lot::Mutex<HashMap<String, watch::Sender<serde_json::Value>>>,
+}
+
+impl ConfigUpdates {
+    fn subscribe(
+        &self,
+        module: &str,
+        current: &serde_json::Value,
+    ) -> watch::Receiver<serde_json::Value> {
+        self.senders
+            .lock()
+            .entry(module.to_string())
+            .or_insert_with(|| watch::channel(current.clone()).0)
+            .subscribe()
+    }
+
+    /// Hand `value` to the module's subscribers, if it has any.
+    pub fn publish(&self, module: &str, value: serde_json::Value) {
+        if let Some(tx) = self.senders.lock().get(module) {
+            tx.send_replace(value);
+        }
+    }
+}
+
+/// Shared infrastructure handed to every module, keyed by type (cache, blob
+/// store, event bus, ...). See [`ModuleCtx::resource`].
+#[derive(Clone, Default)]
+pub struct Resources {
+    map: HashMap<TypeId, Resource>,
+}
+
+/// The `Arc<T>` behind `dyn Any`, with `T`'s name for diagnostics.
+type Resource = (&'static str, Arc<dyn Any + Send + Sync>);
+
+impl Resources {
+    /// Add `resource` as the `T`, replacing any previous one. `T` may be a
+    /// trait object (`dyn BlobStore`).
+    pub fn insert<T>(&mut self, resource: Arc<T>)
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.map.insert(
+            TypeId::of::<T>(),
+            (std::any::type_name::<T>(), Arc::new(resource)),
+        );
+    }
+
+    pub fn get<T>(&self) -> Option<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|(_, r)| r.downcast_ref::<Arc<T>>())
+            .cloned()
+    }
+
+    pub fn contains<T>(&self) -> bool
+    where
+        T: ?Sized + 'static,
+    {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Names of the registered types, sorted.
+    pub fn type_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.map.values().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+/// A named child of the module's cancellation token (see [`ModuleCtx::child_scope`]).
+///
+/// Cancelling a scope cancels its children; every scope is cancelled when the
+/// registry's stop phase begins, if not earlier.
+#[derive(Clone, Debug)]
+pub struct CancelScope {
+    name: Arc<str>,
+    token: CancellationToken,
+}
+
+impl CancelScope {
+    fn new(name: String, token: CancellationToken) -> Self {
+        Self {
+            name: Arc::from(name),
+            token,
+        }
+    }
+
+    /// `<module>/<scope>[/<child>...]`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// A scope cancelled with this one, named `<this>/<name>`.
+    pub fn child_scope(&self, name: &str) -> CancelScope {
+        Self::new(format!("{}/{name}", self.name), self.token.child_token())
+    }
+
+    /// Cancel the scope once `after` has elapsed.
+    pub fn cancel_after(self, after: std::time::Duration) -> Self {
+        self.cancel_at(tokio::time::Instant::now() + after)
+    }
+
+    /// Cancel the scope at `deadline`.
+    pub fn cancel_at(self, deadline: tokio::time::Instant) -> Self {
+        let scope = self.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = scope.token.cancelled() => {}
+                _ = tokio::time::sleep_until(deadline) => {
+                    tracing::debug!(scope = %scope.name, "Cancellation scope deadline reached");
+                    scope.token.cancel();
+                }
+            }
+        });
+        self
+    }
+
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await;
+    }
+}
+
 #[derive(Clone)]
 pub struct ModuleCtx {
     pub(crate) db: Option<Arc<modkit_db::DbHandle>>,
@@ -127,7 +430,13 @@ pub struct ModuleCtx {
     pub(crate) config_provider: Option<Arc<dyn ConfigProvider>>,
     pub(crate) client_hub: Arc<crate::client_hub::ClientHub>,
     pub(crate) cancellation_token: CancellationToken,
+    /// Parent of every [`CancelScope`]; cancelled by the registry at stop.
+    pub(crate) scopes: CancellationToken,
     pub(crate) module_name: Option<Arc<str>>,
+    pub(crate) registry: Option<Arc<crate::registry::ModuleRegistry>>,
+    pub(crate) config_updates: Option<Arc<ConfigUpdates>>,
+    pub(crate) resources: Arc<Resources>,
+    pub(crate) home_dir: Option<Arc<std::path::Path>>,
 }
 
 // ---- construction/scoping (crate-private) ----
@@ -154,10 +463,40 @@ impl ModuleCtxBuilder {
         self.inner.config_provider = Some(p);
         self
     }
-    pub(crate) fn with_client_hub(mut self, hub: Arc<crate::client_hub::ClientHub>) -> Self {
+    pub fn with_client_hub(mut self, hub: Arc<crate::client_hub::ClientHub>) -> Self {
         self.inner.client_hub = hub;
         self
     }
+    /// Build the context as seen by `name` (what the registry passes to its `init`).
+    pub fn for_module(mut self, name: &str) -> Self {
+        self.inner = self.inner.for_module(name);
+        self
+    }
+    pub fn with_registry(mut self, registry: Arc<crate::registry::ModuleRegistry>) -> Self {
+        self.inner.registry = Some(registry);
+        self
+    }
+    pub fn with_config_updates(mut self, updates: Arc<ConfigUpdates>) -> Self {
+        self.inner.config_updates = Some(updates);
+        self
+    }
+    /// Share `resource` with every module as a `T` (see [`ModuleCtx::resource`]).
+    pub fn with_resource<T>(mut self, resource: Arc<T>) -> Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.inner.resources).insert(resource);
+        self
+    }
+    pub fn with_resources(mut self, resources: Resources) -> Self {
+        self.inner.resources = Arc::new(resources);
+        self
+    }
+    /// Base directory of the per-module data directories (see [`ModuleCtx::data_dir`]).
+    pub fn with_home_dir(mut self, home_dir: impl Into<std::path::PathBuf>) -> Self {
+        self.inner.home_dir = Some(Arc::from(home_dir.into()));
+        self
+    }
     pub fn build(self) -> ModuleCtx {
         self.inner
     }
@@ -170,8 +509,13 @@ impl ModuleCtx {
             db_manager: None,
             config_provider: None,
             client_hub: Arc::new(crate::client_hub::ClientHub::default()),
+            scopes: token.child_token(),
             cancellation_token: token,
             module_name: None,
+            registry: None,
+            config_updates: None,
+            resources: Arc::default(),
+            home_dir: None,
         }
     }
 
@@ -192,19 +536,22 @@ impl ModuleCtx {
         })
     }
 
-    /// Get a database handle for this module using the DbManager.
-    /// Returns None if the module has no database configuration.
+    /// Get a database handle for this module using the DbManager, falling back
+    /// to the shared handle (`db()`) when the module has no database configuration.
+    /// Returns None if neither exists.
     pub async fn db_async(&self) -> anyhow::Result<Option<Arc<modkit_db::DbHandle>>> {
-        match (&self.db_manager, &self.module_name) {
-            (Some(manager), Some(module_name)) => {
-                manager.get(module_name).await.map_err(anyhow::Error::from)
-            }
-            _ => Ok(None),
-        }
+        let own = match (&self.db_manager, &self.module_name) {
+            (Some(manager), Some(module_name)) => manager
+                .get(module_name)
+                .await
+                .map_err(anyhow::Error::from)?,
+            _ => None,
+        };
+        Ok(own.or_else(|| self.db.clone()))
     }
 
-    /// Get a required database handle for this module using the DbManager.
-    /// Returns an error if the module has no database configuration.
+    /// Get a required database handle for this module using the DbManager (or
+    /// the shared fallback handle). Returns an error if neither provides one.
     pub async fn db_required_async(&self) -> anyhow::Result<Arc<modkit_db::DbHandle>> {
         let module_name = self
             .module_name
@@ -220,6 +567,7 @@ impl ModuleCtx {
             .get(module_name)
             .await
             .map_err(anyhow::Error::from)?
+            .or_else(|| self.db.clone())
             .ok_or_else(|| {
                 anyhow::anyhow!("Database is not configured for module '{}'", module_name)
             })
@@ -229,14 +577,186 @@ impl ModuleCtx {
         self.client_hub.clone()
     }
 
+    /// Fetch the global client of interface type `T` from the hub. On a miss
+    /// the error names this module and lists what is registered.
+    pub fn client<T>(&self) -> Result<Arc<T>, crate::client_hub::ClientHubError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.client_scoped::<T>(crate::client_hub::GLOBAL_SCOPE)
+    }
+
+    /// Like [`ModuleCtx::client`], in a named scope.
+    pub fn client_scoped<T>(&self, scope: &str) -> Result<Arc<T>, crate::client_hub::ClientHubError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.client_hub
+            .get_for::<T>(self.current_module(), scope, None)
+    }
+
+    /// Like [`ModuleCtx::client`], for a client registered under `name`.
+    pub fn client_named<T>(&self, name: &str) -> Result<Arc<T>, crate::client_hub::ClientHubError>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.client_hub.get_for::<T>(
+            self.current_module(),
+            crate::client_hub::GLOBAL_SCOPE,
+            Some(name),
+        )
+    }
+
     pub fn cancellation_token(&self) -> &CancellationToken {
         &self.cancellation_token
     }
 
+    /// A named child token for a group of sub-tasks, cancelled with the
+    /// module's token and at the latest when the stop phase begins:
+    ///
+    /// ```rust,ignore
+    /// let sync = ctx.child_scope("sync").cancel_after(Duration::from_secs(30));
+    /// tokio::spawn(async move { sync.cancelled().await; /* wind down */ });
+    /// ```
+    pub fn child_scope(&self, name: &str) -> CancelScope {
+        let module = self.module_name.as_deref().unwrap_or("unknown");
+        CancelScope::new(format!("{module}/{name}"), self.scopes.child_token())
+    }
+
     pub fn current_module(&self) -> Option<&str> {
         self.module_name.as_deref()
     }
 
+    /// This module's metrics, prefixed with and labeled by the module name and
+    /// exported with the global registry (`/metrics`).
+    pub fn metrics(&self) -> crate::telemetry::Metrics {
+        crate::telemetry::Metrics::new(self.module_name.as_deref().unwrap_or("unknown"))
+    }
+
+    /// Run `f` periodically under this context's cancellation token (see
+    /// [`crate::scheduler`]). The task ends when the token fires and any
+    /// in-flight run has returned.
+    pub fn schedule<F, Fut>(
+        &self,
+        name: &str,
+        spec: crate::scheduler::JobSpec,
+        f: F,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        crate::scheduler::spawn_job(
+            self.module_name.as_deref().unwrap_or("unknown"),
+            crate::scheduler::Job::new(name, spec, f),
+            self.cancellation_token.child_token(),
+        )
+    }
+
+    /// The shared resource registered as `T`, if any.
+    ///
+    /// ```rust,ignore
+    /// let blobs = ctx.resource::<dyn BlobStore>().context("no blob store")?;
+    /// ```
+    pub fn resource<T>(&self) -> Option<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.resources.get::<T>()
+    }
+
+    /// Like [`ModuleCtx::resource`], failing when `T` was not registered.
+    pub fn resource_required<T>(&self) -> anyhow::Result<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.resource::<T>().ok_or_else(|| {
+            anyhow::anyhow!(
+                "resource {} is not registered (module '{}'; registered: [{}])",
+                std::any::type_name::<T>(),
+                self.current_module().unwrap_or("unknown"),
+                self.resources.type_names().join(", ")
+            )
+        })
+    }
+
+    /// The module registry driving this run (for introspection such as readiness checks).
+    pub fn module_registry(&self) -> Option<&Arc<crate::registry::ModuleRegistry>> {
+        self.registry.as_ref()
+    }
+
+    /// Follow this module's raw `config` section across reloads. The receiver
+    /// starts at the current section; `ctx.config()` keeps returning the
+    /// startup value. `None` when the runtime does not reload config.
+    pub fn raw_config_updates(&self) -> Option<watch::Receiver<serde_json::Value>> {
+        let updates = self.config_updates.as_ref()?;
+        let name = self.module_name.as_deref()?;
+        Some(updates.subscribe(name, self.raw_config()))
+    }
+
+    /// Follow this module's `config` section across reloads, deserialized
+    /// into `T`. The receiver starts at the current section. A reloaded
+    /// section that does not deserialize is logged and skipped. When the
+    /// runtime does not reload config, `changed()` fails right away.
+    ///
+    /// Call from within the tokio runtime (e.g. `init` or `start`): a task
+    /// forwards updates until every receiver is dropped.
+    pub fn config_updates<T>(&self) -> Result<watch::Receiver<T>, ConfigError>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let module = self.current_module().unwrap_or("unknown").to_string();
+        let Some(mut raw) = self.raw_config_updates() else {
+            return Ok(watch::channel(parse_section(&module, self.raw_config())?).1);
+        };
+        let initial = parse_section(&module, &raw.borrow_and_update())?;
+        let (tx, rx) = watch::channel(initial);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    () = tx.closed() => break,
+                    changed = raw.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        match parse_section(&module, &raw.borrow_and_update()) {
+                            Ok(value) => tx.send_replace(value),
+                            Err(e) => {
+                                tracing::warn!(module = %module, error = %e, "Ignoring config update");
+                                continue;
+                            }
+                        };
+                    }
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    /// The module's own directory, `<home_dir>/<module>`, created owner-only on
+    /// first use. Relative SQLite paths of the module resolve against it too.
+    ///
+    /// The home dir comes from the runner (`RunOptions::home_dir`), falling
+    /// back to the database manager's.
+    pub fn data_dir(&self) -> anyhow::Result<std::path::PathBuf> {
+        let module = self
+            .module_name
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Module name not set in context"))?;
+        let home_dir = self
+            .home_dir
+            .as_deref()
+            .or_else(|| self.db_manager.as_deref().map(|m| m.home_dir()))
+            .ok_or_else(|| anyhow::anyhow!("No home dir configured for module '{module}'"))?;
+        modkit_db::module_data_dir(home_dir, module)
+            .with_context(|| format!("cannot create data dir of module '{module}'"))
+    }
+
+    /// The shared database manager, if the runtime was configured with one.
+    pub fn db_manager(&self) -> Option<&Arc<modkit_db::DbManager>> {
+        self.db_manager.as_ref()
+    }
+
     /// Deserialize the module's config section into T.
     ///
     /// This method uses the new typed configuration system and provides better error messages.
@@ -271,6 +791,24 @@ impl ModuleCtx {
         module_config_typed(prov.as_ref(), name)
     }
 
+    /// Like [`ModuleCtx::config`], with [`module_config_strict`] semantics:
+    /// defaults from `T::default()` and unknown keys rejected.
+    pub fn config_strict<T>(&self) -> Result<T, ConfigError>
+    where
+        T: DeserializeOwned + Serialize + Default,
+    {
+        let name = self
+            .module_name
+            .as_deref()
+            .ok_or_else(|| ConfigError::ModuleNotFound {
+                module: "unknown".to_string(),
+            })?;
+        match &self.config_provider {
+            Some(prov) => module_config_strict(prov.as_ref(), name),
+            None => Ok(T::default()),
+        }
+    }
+
     /// Get the raw JSON value of the module's config section.
     /// Returns the 'config' field from: modules.<name> = { database: ..., config: ... }
     pub fn raw_config(&self) -> &serde_json::Value {
@@ -295,16 +833,22 @@ impl ModuleCtx {
         }
     }
 
+    /// Path prefix for the module's REST routes, from `modules.<name>.rest_prefix`.
+    pub fn rest_prefix(&self) -> Option<&str> {
+        let name = self.module_name.as_deref()?;
+        self.config_provider
+            .as_ref()?
+            .get_module_config(name)?
+            .get("rest_prefix")?
+            .as_str()
+    }
+
     /// Create a derivative context with the same references but a different DB handle.
     /// This allows reusing the stable base context while providing per-module DB access.
     pub fn with_db(&self, db: Arc<modkit_db::DbHandle>) -> ModuleCtx {
         ModuleCtx {
             db: Some(db),
-            db_manager: self.db_manager.clone(),
-            config_provider: self.config_provider.clone(),
-            client_hub: self.client_hub.clone(),
-            cancellation_token: self.cancellation_token.clone(),
-            module_name: self.module_name.clone(),
+            ..self.clone()
         }
     }
 
@@ -313,11 +857,7 @@ impl ModuleCtx {
     pub fn without_db(&self) -> ModuleCtx {
         ModuleCtx {
             db: None,
-            db_manager: self.db_manager.clone(),
-            config_provider: self.config_provider.clone(),
-            client_hub: self.client_hub.clone(),
-            cancellation_token: self.cancellation_token.clone(),
-            module_name: self.module_name.clone(),
+            ..self.clone()
         }
     }
 }
@@ -325,7 +865,7 @@ impl ModuleCtx {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
     use serde_json::json;
     use std::collections::HashMap;
 
@@ -476,6 +1016,212 @@ mod tests {
         assert!(config.enabled);
     }
 
+    trait BlobStore: Send + Sync {
+        fn bucket(&self) -> &str;
+    }
+
+    struct MemoryBlobs;
+
+    impl BlobStore for MemoryBlobs {
+        fn bucket(&self) -> &str {
+            "memory"
+        }
+    }
+
+    #[test]
+    fn test_resources_are_looked_up_by_type() {
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new())
+            .with_resource::<dyn BlobStore>(Arc::new(MemoryBlobs))
+            .with_resource(Arc::new(42u32))
+            .for_module("consumer")
+            .build();
+
+        assert_eq!(ctx.resource::<dyn BlobStore>().unwrap().bucket(), "memory");
+        assert_eq!(*ctx.resource_required::<u32>().unwrap(), 42);
+        assert!(ctx.resource::<u64>().is_none());
+        let err = ctx.resource_required::<u64>().unwrap_err().to_string();
+        assert!(err.contains("u64") && err.contains("consumer"), "{err}");
+        assert!(err.contains("u32") && err.contains("BlobStore"), "{err}");
+        assert!(ctx.resources.contains::<dyn BlobStore>());
+        assert_eq!(ctx.resources.type_names().len(), 2);
+    }
+
+    #[test]
+    fn test_data_dir_is_created_under_home_dir() {
+        let home = std::env::temp_dir().join(format!("modkit-home-{}", std::process::id()));
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new())
+            .with_home_dir(&home)
+            .for_module("notes")
+            .build();
+
+        let dir = ctx.data_dir().unwrap();
+        assert_eq!(dir, home.join("notes"));
+        assert!(dir.is_dir());
+        assert_eq!(ctx.data_dir().unwrap(), dir);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        std::fs::remove_dir_all(&home).unwrap();
+
+        let no_home = ModuleCtxBuilder::new(CancellationToken::new())
+            .for_module("notes")
+            .build();
+        assert!(no_home.data_dir().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_child_scopes_cancel_with_parent_and_deadline() {
+        let root = CancellationToken::new();
+        let ctx = ModuleCtxBuilder::new(root.clone())
+            .for_module("indexer")
+            .build();
+
+        let sync = ctx.child_scope("sync");
+        let batch = sync.child_scope("batch");
+        assert_eq!(batch.name(), "indexer/sync/batch");
+        sync.cancel();
+        assert!(batch.is_cancelled());
+        assert!(!ctx.child_scope("other").is_cancelled());
+
+        let timed = ctx
+            .child_scope("timed")
+            .cancel_after(std::time::Duration::from_millis(10));
+        tokio::time::timeout(std::time::Duration::from_secs(1), timed.cancelled())
+            .await
+            .unwrap();
+
+        let late = ctx.child_scope("late");
+        root.cancel();
+        assert!(late.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_typed_config_updates() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Rate {
+            per_sec: u32,
+        }
+
+        let provider = Arc::new(MockConfigProvider {
+            modules: HashMap::from([(
+                "limiter".to_string(),
+                json!({ "config": { "per_sec": 10 } }),
+            )]),
+        });
+        let updates = Arc::new(ConfigUpdates::default());
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new())
+            .with_config_provider(provider.clone())
+            .with_config_updates(updates.clone())
+            .for_module("limiter")
+            .build();
+
+        let mut rx = ctx.config_updates::<Rate>().unwrap();
+        assert_eq!(*rx.borrow(), Rate { per_sec: 10 });
+
+        updates.publish("limiter", json!({ "per_sec": "fast" }));
+        updates.publish("limiter", json!({ "per_sec": 20 }));
+        tokio::time::timeout(std::time::Duration::from_secs(1), rx.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(*rx.borrow_and_update(), Rate { per_sec: 20 });
+
+        // Without reloads the receiver holds the startup value and never changes.
+        let static_ctx = ModuleCtxBuilder::new(CancellationToken::new())
+            .with_config_provider(provider)
+            .for_module("limiter")
+            .build();
+        let mut rx = static_ctx.config_updates::<Rate>().unwrap();
+        assert_eq!(*rx.borrow(), Rate { per_sec: 10 });
+        assert!(rx.changed().await.is_err());
+    }
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    #[serde(default)]
+    struct Ingress {
+        bind_addr: String,
+        cors: Cors,
+        routes: HashMap<String, u32>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    #[serde(default)]
+    struct Cors {
+        enabled: bool,
+        max_age: u32,
+    }
+
+    impl Default for Ingress {
+        fn default() -> Self {
+            Self {
+                bind_addr: "127.0.0.1:8087".into(),
+                cors: Cors::default(),
+                routes: HashMap::new(),
+            }
+        }
+    }
+
+    impl Default for Cors {
+        fn default() -> Self {
+            Self {
+                enabled: false,
+                max_age: 600,
+            }
+        }
+    }
+
+    #[test]
+    fn test_module_config_strict() {
+        let mut provider = MockConfigProvider::new();
+        provider.modules.insert(
+            "ingress".to_string(),
+            json!({ "config": { "cors": { "enabled": true }, "routes": { "/a": 1 } } }),
+        );
+        provider.modules.insert(
+            "typo".to_string(),
+            json!({ "config": { "bind_adress": "0.0.0.0:80", "cors": { "enable": true, "xyz": 1 } } }),
+        );
+
+        // File values win, everything else comes from the defaults.
+        let cfg: Ingress = module_config_strict(&provider, "ingress").unwrap();
+        assert_eq!(cfg.bind_addr, "127.0.0.1:8087");
+        assert_eq!(
+            cfg.cors,
+            Cors {
+                enabled: true,
+                max_age: 600
+            }
+        );
+        assert_eq!(cfg.routes, HashMap::from([("/a".to_string(), 1)]));
+        let missing: Ingress = module_config_strict(&provider, "absent").unwrap();
+        assert_eq!(missing, Ingress::default());
+
+        let err = module_config_strict::<Ingress>(&provider, "typo").unwrap_err();
+        let ConfigError::UnknownKeys { keys, .. } = &err else {
+            panic!("unexpected error: {err:?}");
+        };
+        let found: Vec<_> = keys
+            .iter()
+            .map(|k| (k.path.as_str(), k.suggestion.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("bind_adress", Some("bind_addr")),
+                ("cors.enable", Some("enabled")),
+                ("cors.xyz", None),
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "unknown keys in config of module 'typo': 'bind_adress' (did you mean 'bind_addr'?), \
+             'cors.enable' (did you mean 'enabled'?), 'cors.xyz'"
+        );
+    }
+
     #[test]
     fn test_config_error_messages() {
         let module_not_found = ConfigError::ModuleNotFound {
@@ -17,9 +17,10 @@ pub trait DbModule: Send + Sync {
     async fn migrate(&self, db: &modkit_db::DbHandle) -> anyhow::Result<()>;
 }
 
-/// Pure wiring; must be sync. Runs AFTER DB migrations.
+/// Route wiring; may await (e.g. to load route config). Runs AFTER DB migrations.
+#[async_trait]
 pub trait RestfulModule: Send + Sync {
-    fn register_rest(
+    async fn register_rest(
         &self,
         ctx: &crate::context::ModuleCtx,
         router: Router,
@@ -28,12 +29,13 @@ pub trait RestfulModule: Send + Sync {
 }
 
 /// REST host module: handles ingress hosting with prepare/finalize phases.
-/// Must be sync. Runs during REST phase, but doesn't start the server.
+/// Runs during REST phase, but doesn't start the server.
 #[allow(dead_code)]
+#[async_trait]
 pub trait RestHostModule: Send + Sync + 'static {
     /// Prepare a base Router (e.g., global middlewares, /healthz) and optionally touch OpenAPI meta.
     /// Do NOT start the server here.
-    fn rest_prepare(
+    async fn rest_prepare(
         &self,
         ctx: &crate::context::ModuleCtx,
         router: Router,
@@ -41,7 +43,7 @@ pub trait RestHostModule: Send + Sync + 'static {
 
     /// Finalize before start: attach /openapi.json, /docs, persist the Router internally if needed.
     /// Do NOT start the server here.
-    fn rest_finalize(
+    async fn rest_finalize(
         &self,
         ctx: &crate::context::ModuleCtx,
         router: Router,
@@ -55,4 +57,107 @@ pub trait RestHostModule: Send + Sync + 'static {
 pub trait StatefulModule: Send + Sync {
     async fn start(&self, cancel: CancellationToken) -> anyhow::Result<()>;
     async fn stop(&self, cancel: CancellationToken) -> anyhow::Result<()>;
+
+    /// Current lifecycle status, if the module tracks one (used by readiness checks).
+    fn status(&self) -> Option<crate::lifecycle::Status> {
+        None
+    }
+
+    /// Why the last `stop` ended, if the module tracks it (used by the shutdown report).
+    fn last_stop_reason(&self) -> Option<crate::lifecycle::StopReason> {
+        None
+    }
+
+    /// When the module last became `Running`, if it tracks it and is running.
+    fn started_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        None
+    }
+
+    /// How long the module has been `Running`, if it tracks it and is running.
+    fn uptime(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// The module's most recent status change, if it tracks it.
+    fn last_transition(&self) -> Option<crate::lifecycle::Transition> {
+        None
+    }
+
+    /// Error of the last failed run, if the module tracks it (reported with
+    /// [`Status::Failed`](crate::lifecycle::Status::Failed)).
+    fn last_error(&self) -> Option<String> {
+        None
+    }
+
+    /// Message and backtrace of the panic behind `last_error`, if the module
+    /// tracks it and the last failure was a panic.
+    fn last_panic(&self) -> Option<crate::lifecycle::TaskPanic> {
+        None
+    }
+
+    /// How long `stop` may take before its token fires, if the module declares it
+    /// (`lifecycle(stop_timeout = ...)`).
+    fn stop_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+}
+
+/// Result of a module health probe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthStatus {
+    Healthy,
+    /// Serving, but impaired (e.g. a fallback is in use); does not fail readiness.
+    Degraded(String),
+    /// Not able to serve; fails readiness.
+    Unhealthy(String),
+}
+
+impl HealthStatus {
+    /// Human-readable name (used in readiness reports).
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            HealthStatus::Healthy => "healthy",
+            HealthStatus::Degraded(_) => "degraded",
+            HealthStatus::Unhealthy(_) => "unhealthy",
+        }
+    }
+
+    /// The reason given for a degraded or unhealthy status.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            HealthStatus::Healthy => None,
+            HealthStatus::Degraded(r) | HealthStatus::Unhealthy(r) => Some(r),
+        }
+    }
+
+    fn severity(&self) -> u8 {
+        match self {
+            HealthStatus::Healthy => 0,
+            HealthStatus::Degraded(_) => 1,
+            HealthStatus::Unhealthy(_) => 2,
+        }
+    }
+
+    /// The worse of two statuses.
+    pub fn worst(self, other: HealthStatus) -> HealthStatus {
+        if other.severity() > self.severity() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// Optional self-check (capability `health`). Probes should be cheap: they run
+/// on every readiness request and are cut off after a short timeout.
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    async fn health(&self) -> HealthStatus;
+}
+
+/// Live config updates (capability `config_reload`). Called after startup with
+/// the module's new `config` section whenever a reload changes it.
+#[async_trait]
+pub trait ConfigReloadable: Send + Sync {
+    async fn on_config_update(&self, new: serde_json::Value) -> anyhow::Result<()>;
 }
@@ -0,0 +1,297 @@
+//! Outbound HTTP client with tracing.
+//!
+//! [`TracedClient`] wraps a `reqwest::Client` and runs every attempt inside an
+//! `http.client` span that records method, URL, status and latency, so calls to
+//! other services show up next to the inbound request that triggered them.
+//! When trace export is on, each attempt carries that span's W3C `traceparent`
+//! (and `tracestate`), so the upstream joins the same trace.
+//!
+//! With a [`RetryPolicy`], idempotent requests (`GET`, `HEAD`, `OPTIONS`,
+//! `PUT`, `DELETE`, `TRACE`) are retried on connect errors, timeouts and
+//! `429`/`502`/`503`/`504`, with exponential backoff or the upstream's
+//! `Retry-After`. Requests with a streaming body are never retried.
+
+use std::time::{Duration, Instant};
+
+use reqwest::header::{HeaderValue, RETRY_AFTER};
+use reqwest::Method;
+use tracing::Instrument;
+
+/// Status codes worth another attempt.
+const RETRY_STATUSES: &[u16] = &[429, 502, 503, 504];
+
+/// When and how often [`TracedClient`] retries idempotent requests.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts after the first one.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Cap for the exponential backoff and for `Retry-After`.
+    pub max_backoff: Duration,
+    /// Backoff multiplier for exponential backoff.
+    pub backoff_multiplier: f64,
+    /// Jitter percentage in [0.0, 1.0]; e.g. 0.2 means ±20% jitter.
+    pub jitter_pct: f32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            backoff_multiplier: 2.0,
+            jitter_pct: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn jittered(&self, backoff: Duration) -> Duration {
+        let jitter = f64::from(self.jitter_pct.clamp(0.0, 1.0));
+        let spread = crate::scheduler::random_below(backoff.mul_f64(2.0 * jitter));
+        (backoff.mul_f64(1.0 - jitter) + spread).min(self.max_backoff)
+    }
+}
+
+/// `reqwest::Client` wrapper that instruments each request.
+#[derive(Clone, Debug, Default)]
+pub struct TracedClient {
+    inner: reqwest::Client,
+    retry: Option<RetryPolicy>,
+}
+
+impl TracedClient {
+    pub fn new(inner: reqwest::Client) -> Self {
+        Self { inner, retry: None }
+    }
+
+    /// Retry idempotent requests according to `policy`.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// The wrapped client, e.g. to build requests with `inner().get(url)`.
+    pub fn inner(&self) -> &reqwest::Client {
+        &self.inner
+    }
+
+    /// Send a prepared request, retrying it if the policy allows.
+    pub async fn execute(&self, req: reqwest::Request) -> reqwest::Result<reqwest::Response> {
+        let policy = self.retry.as_ref().filter(|_| is_idempotent(req.method()));
+        let mut backoff = policy.map(|p| p.initial_backoff).unwrap_or_default();
+        let mut req = req;
+        let mut attempt = 0;
+        loop {
+            let retry = policy
+                .filter(|p| attempt < p.max_retries)
+                .and_then(|p| Some((p, req.try_clone()?)));
+            let result = self.attempt(req, attempt).await;
+            let Some((policy, next)) = retry else {
+                return result;
+            };
+            let retry_after = match &result {
+                Ok(resp) if RETRY_STATUSES.contains(&resp.status().as_u16()) => {
+                    retry_after(resp.headers().get(RETRY_AFTER))
+                }
+                Err(e) if e.is_connect() || e.is_timeout() => None,
+                _ => return result,
+            };
+            let delay = match retry_after {
+                Some(delay) => delay.min(policy.max_backoff),
+                None => policy.jittered(backoff),
+            };
+            tracing::debug!(
+                attempt,
+                delay_ms = delay.as_millis() as u64,
+                "retrying upstream request"
+            );
+            tokio::time::sleep(delay).await;
+            backoff = backoff
+                .mul_f64(policy.backoff_multiplier)
+                .min(policy.max_backoff);
+            attempt += 1;
+            req = next;
+        }
+    }
+
+    async fn attempt(
+        &self,
+        mut req: reqwest::Request,
+        attempt: u32,
+    ) -> reqwest::Result<reqwest::Response> {
+        let span = tracing::info_span!(
+            "http.client",
+            otel.kind = "client",
+            method = %req.method(),
+            url = %req.url(),
+            attempt,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+        );
+        crate::telemetry::inject_trace_context(&span, req.headers_mut());
+        let started = Instant::now();
+        let result = self.inner.execute(req).instrument(span.clone()).await;
+        span.record("latency_ms", started.elapsed().as_millis() as u64);
+        match &result {
+            Ok(resp) => {
+                span.record("status", resp.status().as_u16());
+                if resp.status().is_server_error() {
+                    span.record("otel.status_code", "error");
+                }
+                tracing::debug!(parent: &span, status = resp.status().as_u16(), "upstream responded");
+            }
+            Err(e) => {
+                span.record("otel.status_code", "error");
+                tracing::warn!(parent: &span, error = %e, "upstream request failed");
+            }
+        }
+        result
+    }
+
+    /// Build and send a request.
+    pub async fn send(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        self.execute(builder.build()?).await
+    }
+}
+
+impl From<reqwest::Client> for TracedClient {
+    fn from(inner: reqwest::Client) -> Self {
+        Self::new(inner)
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE | Method::TRACE
+    )
+}
+
+/// `Retry-After` in seconds (the HTTP-date form is ignored).
+fn retry_after(value: Option<&HeaderValue>) -> Option<Duration> {
+    let secs: u64 = value?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::HeaderMap, http::StatusCode, routing::any, Router};
+    use opentelemetry::trace::TraceContextExt;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Upstream answering 503 `failures` times, then 200; records `traceparent`s.
+    async fn upstream(failures: usize) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let app = Router::new().route(
+            "/flaky",
+            any(move |headers: HeaderMap| {
+                let log = log.clone();
+                async move {
+                    let mut log = log.lock();
+                    log.push(
+                        headers
+                            .get("traceparent")
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_string),
+                    );
+                    if log.len() <= failures {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{addr}/flaky"), seen)
+    }
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn idempotent_requests_are_retried_with_trace_context() {
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        let layer = crate::telemetry::otlp::layer(&provider);
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(layer));
+
+        let (url, seen) = upstream(2).await;
+        let client = TracedClient::default().with_retry(fast_retry());
+        let caller = tracing::info_span!("caller");
+        let resp = client
+            .send(client.inner().get(&url))
+            .instrument(caller.clone())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let seen = seen.lock();
+        assert_eq!(seen.len(), 3);
+        let trace_id = caller.context().span().span_context().trace_id();
+        // `00-<trace id>-<span id>-<flags>`
+        let parents: Vec<Vec<&str>> = seen
+            .iter()
+            .map(|h| h.as_deref().unwrap().split('-').collect())
+            .collect();
+        assert!(parents
+            .iter()
+            .all(|p| p[1] == trace_id.to_string() && p[3] == "01"));
+        // One span per attempt.
+        assert_ne!(parents[0][2], parents[1][2]);
+    }
+
+    #[tokio::test]
+    async fn non_idempotent_requests_are_sent_once() {
+        let (url, seen) = upstream(1).await;
+        let client = TracedClient::default().with_retry(fast_retry());
+        let resp = client.send(client.inner().post(&url)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        // No trace export installed: nothing to propagate.
+        assert_eq!(*seen.lock(), [None]);
+
+        let no_policy = TracedClient::default();
+        let (url, seen) = upstream(1).await;
+        let resp = no_policy.send(no_policy.inner().get(&url)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(seen.lock().len(), 1);
+    }
+
+    #[test]
+    fn retry_after_and_jitter_stay_bounded() {
+        assert_eq!(
+            retry_after(Some(&HeaderValue::from_static("3"))),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            retry_after(Some(&HeaderValue::from_static(
+                "Wed, 21 Oct 2015 07:28:00 GMT"
+            ))),
+            None
+        );
+        let policy = RetryPolicy::default();
+        for _ in 0..100 {
+            let delay = policy.jittered(Duration::from_millis(100));
+            assert!(delay >= Duration::from_millis(80) && delay <= Duration::from_millis(120));
+        }
+    }
+}
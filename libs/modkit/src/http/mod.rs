@@ -3,4 +3,9 @@
 //! This module provides shared HTTP types and utilities for building
 //! modular web applications.
 
+pub mod client;
+pub mod multipart;
 pub mod sse;
+
+pub use client::{RetryPolicy, TracedClient};
+pub use multipart::{LimitedMultipart, MultipartError, MultipartPart};
@@ -0,0 +1,210 @@
+//! Size-limited `multipart/form-data` extractor.
+//!
+//! [`LimitedMultipart`] reads the form through axum's `Multipart` (multer)
+//! with a body limit of `MAX` bytes and buffers it into [`MultipartPart`]s.
+//! Oversized uploads are rejected with `413`, malformed ones with `400`, both
+//! as Problem responses. Document the fields with
+//! `OperationBuilder::multipart_request`.
+
+use std::convert::Infallible;
+
+use axum::{
+    body::Bytes,
+    extract::{
+        multipart::{MultipartError as AxumMultipartError, MultipartRejection},
+        DefaultBodyLimit, FromRequest, Multipart, Request,
+    },
+    http::StatusCode,
+};
+use thiserror::Error;
+use tower::{Layer, Service};
+
+use crate::api::problem::{Problem, ProblemResponse};
+
+/// Default upload limit of [`LimitedMultipart`] (10 MiB).
+pub const DEFAULT_MULTIPART_LIMIT: usize = 10 * 1024 * 1024;
+
+/// One field of a multipart form.
+#[derive(Clone, Debug)]
+pub struct MultipartPart {
+    pub name: String,
+    /// Client-side file name; `None` for plain form fields.
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Bytes,
+}
+
+impl MultipartPart {
+    /// The field value as UTF-8 text.
+    pub fn text(&self) -> Result<&str, MultipartError> {
+        std::str::from_utf8(&self.data)
+            .map_err(|_| MultipartError::Malformed(format!("field '{}' is not UTF-8", self.name)))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum MultipartError {
+    #[error("expected a multipart/form-data request with a boundary")]
+    NotMultipart,
+    #[error("upload exceeds the limit of {0} bytes")]
+    TooLarge(usize),
+    #[error("malformed multipart body: {0}")]
+    Malformed(String),
+}
+
+impl From<MultipartError> for ProblemResponse {
+    fn from(e: MultipartError) -> Self {
+        let (status, title) = match e {
+            MultipartError::TooLarge(_) => (StatusCode::PAYLOAD_TOO_LARGE, "Payload Too Large"),
+            _ => (StatusCode::BAD_REQUEST, "Bad Request"),
+        };
+        Problem::new(status, title, e.to_string())
+            .with_code("INVALID_MULTIPART")
+            .into()
+    }
+}
+
+/// Buffered multipart form, limited to `MAX` bytes.
+#[derive(Clone, Debug, Default)]
+pub struct LimitedMultipart<const MAX: usize = DEFAULT_MULTIPART_LIMIT> {
+    pub parts: Vec<MultipartPart>,
+}
+
+impl<const MAX: usize> LimitedMultipart<MAX> {
+    /// First part with the given field name.
+    pub fn get(&self, name: &str) -> Option<&MultipartPart> {
+        self.parts.iter().find(|p| p.name == name)
+    }
+
+    /// First part with the given field name, or a `400` naming the field.
+    pub fn require(&self, name: &str) -> Result<&MultipartPart, MultipartError> {
+        self.get(name)
+            .ok_or_else(|| MultipartError::Malformed(format!("missing field '{name}'")))
+    }
+
+    fn read_error(e: AxumMultipartError) -> MultipartError {
+        if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            MultipartError::TooLarge(MAX)
+        } else {
+            MultipartError::Malformed(e.body_text())
+        }
+    }
+}
+
+impl From<MultipartRejection> for MultipartError {
+    fn from(_: MultipartRejection) -> Self {
+        Self::NotMultipart
+    }
+}
+
+impl<S, const MAX: usize> FromRequest<S> for LimitedMultipart<MAX>
+where
+    S: Send + Sync,
+{
+    type Rejection = ProblemResponse;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // `MAX` replaces the router's default body limit for this extractor.
+        let Ok(req) = DefaultBodyLimit::max(MAX)
+            .layer(tower::service_fn(|req: Request| async {
+                Ok::<_, Infallible>(req)
+            }))
+            .call(req)
+            .await;
+        let mut multipart = Multipart::from_request(req, state)
+            .await
+            .map_err(MultipartError::from)?;
+
+        let mut parts = Vec::new();
+        while let Some(field) = multipart.next_field().await.map_err(Self::read_error)? {
+            let name = field
+                .name()
+                .ok_or_else(|| MultipartError::Malformed("part without a field name".into()))?
+                .to_string();
+            let filename = field.file_name().map(str::to_string);
+            let content_type = field.content_type().map(str::to_string);
+            let data = field.bytes().await.map_err(Self::read_error)?;
+            parts.push(MultipartPart {
+                name,
+                filename,
+                content_type,
+                data,
+            });
+        }
+        Ok(Self { parts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::header, response::IntoResponse, routing::post, Router};
+    use tower::ServiceExt;
+
+    const BODY: &str = "--XyZ\r\n\
+        Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+        Quarterly\r\n\
+        --XyZ\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"q3; final.csv\"\r\n\
+        Content-Type: text/csv\r\n\r\n\
+        a,b\r\n1,2\r\n\
+        --XyZ--\r\n";
+
+    fn request(body: impl Into<Body>) -> Request {
+        Request::post("/upload")
+            .header(
+                header::CONTENT_TYPE,
+                "multipart/form-data; boundary=\"XyZ\"",
+            )
+            .body(body.into())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn parses_fields_and_files() {
+        let form = LimitedMultipart::<1024>::from_request(request(BODY), &())
+            .await
+            .unwrap();
+        assert_eq!(form.parts.len(), 2);
+        assert_eq!(form.require("title").unwrap().text().unwrap(), "Quarterly");
+        let file = form.require("file").unwrap();
+        assert_eq!(file.filename.as_deref(), Some("q3; final.csv"));
+        assert_eq!(file.content_type.as_deref(), Some("text/csv"));
+        assert_eq!(&file.data[..], b"a,b\r\n1,2");
+        assert!(form.require("missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn limit_overrides_the_default_body_limit() {
+        // Above axum's 2 MiB default, below the extractor's limit.
+        let big = "x".repeat(3 * 1024 * 1024);
+        let body = format!(
+            "--XyZ\r\nContent-Disposition: form-data; name=\"file\"; filename=\"big\"\r\n\r\n\
+             {big}\r\n--XyZ--\r\n"
+        );
+        let form = LimitedMultipart::<DEFAULT_MULTIPART_LIMIT>::from_request(request(body), &())
+            .await
+            .unwrap();
+        assert_eq!(form.require("file").unwrap().data.len(), big.len());
+    }
+
+    async fn upload<const MAX: usize>(body: &'static str) -> StatusCode {
+        let app = Router::new().route(
+            "/upload",
+            post(|form: LimitedMultipart<MAX>| async move {
+                match form.require("file") {
+                    Ok(file) => file.data.len().to_string().into_response(),
+                    Err(e) => ProblemResponse::from(e).into_response(),
+                }
+            }),
+        );
+        app.oneshot(request(body)).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn extractor_enforces_limit() {
+        assert_eq!(upload::<1024>(BODY).await, StatusCode::OK);
+        assert_eq!(upload::<16>(BODY).await, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(upload::<1024>("garbage").await, StatusCode::BAD_REQUEST);
+    }
+}
@@ -29,22 +29,27 @@ pub use inventory;
 
 // Module system exports
 pub use crate::contracts::*;
+pub mod config_schema;
 pub mod context;
+pub use config_schema::ConfigSchema;
 pub use context::{
-    module_config_typed, ConfigError, ConfigProvider, ConfigProviderExt, ModuleCtx,
-    ModuleCtxBuilder,
+    module_config_strict, module_config_typed, CancelScope, ConfigError, ConfigProvider,
+    ConfigProviderExt, ModuleCtx, ModuleCtxBuilder, Resources,
 };
 
 // Module system implementations for macro code
 pub mod client_hub;
 pub mod registry;
+pub mod report;
 
 // Re-export main types
 pub use client_hub::ClientHub;
 pub use registry::ModuleRegistry;
 
 // Re-export the macros from the proc-macro crate
-pub use modkit_macros::{lifecycle, module};
+pub use modkit_macros::{
+    instrumented_client, lifecycle, module, route, ConfigSchema, IntoApiError,
+};
 
 // Core module contracts and traits
 pub mod contracts;
@@ -56,15 +61,26 @@ pub use odata_core::{Page, PageInfo};
 // HTTP utilities
 pub mod http;
 pub use api::problem::{
-    bad_request, conflict, internal_error, not_found, Problem, ProblemResponse, ValidationError,
+    bad_request, conflict, forbidden, internal_error, not_found, unauthorized, Problem,
+    ProblemResponse, ValidationError,
 };
+pub use api::AuthContext;
 pub use http::sse::SseBroadcaster;
 
 pub mod lifecycle;
+pub mod remote;
 pub mod runtime;
+pub mod scheduler;
+pub mod secrets;
+pub mod telemetry;
 
-pub use lifecycle::{Lifecycle, Runnable, Status, StopReason, WithLifecycle};
-pub use runtime::{run, DbOptions, RunOptions, ShutdownOptions};
+pub use lifecycle::{
+    Lifecycle, Runnable, Status, StopReason, TaskPanic, Transition, WithLifecycle,
+};
+pub use runtime::{
+    run, ConfigReload, DbOptions, PhaseHooks, RunOptions, ServiceManager, ShutdownHandle,
+    ShutdownOptions,
+};
 
 #[cfg(test)]
 mod tests;
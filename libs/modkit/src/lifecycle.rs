@@ -1,9 +1,14 @@
 use async_trait::async_trait;
 use parking_lot::Mutex;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
 use std::sync::{
     atomic::{AtomicBool, AtomicU8, Ordering},
     Arc,
 };
+use std::task::{Context, Poll};
 use std::time::Duration;
 use tokio::sync::{oneshot, Notify};
 use tokio::task::JoinHandle;
@@ -25,19 +30,39 @@ type ReadyFn<T> = fn(
 )
     -> std::pin::Pin<Box<dyn std::future::Future<Output = TaskResult<()>> + Send>>;
 
+/// Type alias for the entry point of a named task (see [`WithLifecycle::with_task`]).
+type TaskFn<T> = fn(
+    Arc<T>,
+    CancellationToken,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = TaskResult<()>> + Send>>;
+
 // ----- Status model ----------------------------------------------------------
 
 /// Terminal/transition states for a background job.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 #[repr(u8)]
 pub enum Status {
     Stopped,
     Starting,
     Running,
     Stopping,
+    /// The task returned an error; see [`Lifecycle::last_error`]. Can be started again.
+    Failed,
 }
 
 impl Status {
+    /// Human-readable name (used in readiness reports).
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Status::Stopped => "stopped",
+            Status::Starting => "starting",
+            Status::Running => "running",
+            Status::Stopping => "stopping",
+            Status::Failed => "failed",
+        }
+    }
+
     #[inline]
     pub const fn as_u8(self) -> u8 {
         match self {
@@ -45,6 +70,7 @@ impl Status {
             Status::Starting => 1,
             Status::Running => 2,
             Status::Stopping => 3,
+            Status::Failed => 4,
         }
     }
     #[inline]
@@ -53,19 +79,112 @@ impl Status {
             1 => Status::Starting,
             2 => Status::Running,
             3 => Status::Stopping,
+            4 => Status::Failed,
             _ => Status::Stopped,
         }
     }
 }
 
 /// Reason why a task stopped.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum StopReason {
     Finished,
     Cancelled,
     Timeout,
 }
 
+/// A status change recorded by [`Lifecycle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct Transition {
+    pub to: Status,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A panic caught in a lifecycle task; the task is then `Failed`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TaskPanic {
+    pub message: String,
+    /// Backtrace of the panicking thread, captured at the panic site.
+    pub backtrace: String,
+}
+
+/// When the task last changed status and since when it has been running.
+#[derive(Default)]
+struct Timeline {
+    running_since: Option<(chrono::DateTime<chrono::Utc>, tokio::time::Instant)>,
+    last: Option<Transition>,
+}
+
+impl Timeline {
+    fn record(&mut self, to: Status) {
+        let at = chrono::Utc::now();
+        match to {
+            Status::Running => self.running_since = Some((at, tokio::time::Instant::now())),
+            Status::Stopped | Status::Failed => self.running_since = None,
+            Status::Starting | Status::Stopping => {}
+        }
+        self.last = Some(Transition { to, at });
+    }
+}
+
+// ----- Panic capture ---------------------------------------------------------
+
+thread_local! {
+    /// Set while a [`CatchPanic`] future is being polled on this thread.
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    /// Backtrace recorded by the panic hook for the enclosing [`CatchPanic`].
+    static BACKTRACE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Put a hook in front of the current panic hook that records a backtrace
+/// for panics raised under [`CatchPanic`]. Installed once per process.
+fn install_panic_hook() {
+    static ONCE: std::sync::Once = std::sync::Once::new();
+    ONCE.call_once(|| {
+        let prev = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if CAPTURING.with(Cell::get) {
+                let bt = std::backtrace::Backtrace::force_capture().to_string();
+                BACKTRACE.with(|b| *b.borrow_mut() = Some(bt));
+            }
+            prev(info);
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Polls `inner`, turning a panic into [`TaskPanic`].
+struct CatchPanic<F> {
+    inner: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CatchPanic<F> {
+    type Output = Result<F::Output, TaskPanic>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let outer = CAPTURING.with(|c| c.replace(true));
+        let res = std::panic::catch_unwind(AssertUnwindSafe(|| self.inner.as_mut().poll(cx)));
+        CAPTURING.with(|c| c.set(outer));
+        match res {
+            Ok(poll) => poll.map(Ok),
+            Err(payload) => Poll::Ready(Err(TaskPanic {
+                message: panic_message(&*payload),
+                backtrace: BACKTRACE
+                    .with(|b| b.borrow_mut().take())
+                    .unwrap_or_default(),
+            })),
+        }
+    }
+}
+
 // ----- Ready signal ----------------------------------------------------------
 
 /// Ready signal used by `start_with_ready*` to flip Starting -> Running.
@@ -120,6 +239,15 @@ pub struct Lifecycle {
     was_cancelled: Arc<AtomicBool>,
     /// Notifies all waiters when the task finishes.
     finished_notify: Arc<Notify>,
+    /// Error of the most recent task that failed, formatted with its causes.
+    last_error: Arc<Mutex<Option<String>>>,
+    /// Set when that failure was a panic.
+    last_panic: Arc<Mutex<Option<TaskPanic>>>,
+    /// Why the most recent `stop` ended.
+    last_stop: Mutex<Option<StopReason>>,
+    timeline: Arc<Mutex<Timeline>>,
+    /// Runtime the task is spawned on; the caller's runtime when `None`.
+    runtime: Mutex<Option<tokio::runtime::Handle>>,
 }
 
 impl Lifecycle {
@@ -131,9 +259,20 @@ impl Lifecycle {
             finished: Arc::new(AtomicBool::new(false)),
             was_cancelled: Arc::new(AtomicBool::new(false)),
             finished_notify: Arc::new(Notify::new()),
+            last_error: Arc::new(Mutex::new(None)),
+            last_panic: Arc::new(Mutex::new(None)),
+            last_stop: Mutex::new(None),
+            timeline: Arc::new(Mutex::new(Timeline::default())),
+            runtime: Mutex::new(None),
         }
     }
 
+    /// Spawn future tasks on `handle` instead of the caller's runtime.
+    /// Takes effect on the next start.
+    pub fn set_runtime(&self, handle: tokio::runtime::Handle) {
+        *self.runtime.lock() = Some(handle);
+    }
+
     // --- small helpers for atomics (keeps Ordering unified and code concise) ---
 
     #[inline]
@@ -144,6 +283,7 @@ impl Lifecycle {
     #[inline]
     fn store_status(&self, s: Status) {
         self.status.store(s.as_u8(), Ordering::Release);
+        self.timeline.lock().record(s);
     }
 
     // --- public start APIs delegate to start_core --------------------------------
@@ -199,6 +339,67 @@ impl Lifecycle {
         )
     }
 
+    /// Run `f` every `interval` (first run after one interval) until stopped.
+    ///
+    /// Runs never overlap: ticks missed while a run is still going are
+    /// skipped. An error or panic in one run is logged and the next tick runs
+    /// as usual. Each run gets a child of the lifecycle token.
+    pub fn start_periodic<F, Fut>(&self, interval: Duration, f: F) -> LcResult
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = TaskResult<()>> + Send + 'static,
+    {
+        self.start_periodic_with_jitter(interval, Duration::ZERO, f)
+    }
+
+    /// Like [`Lifecycle::start_periodic`], but delays each run by a random
+    /// amount below `jitter` so replicas do not fire in lockstep.
+    pub fn start_periodic_with_jitter<F, Fut>(
+        &self,
+        interval: Duration,
+        jitter: Duration,
+        f: F,
+    ) -> LcResult
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = TaskResult<()>> + Send + 'static,
+    {
+        self.start(move |cancel| async move {
+            let start = tokio::time::Instant::now() + interval;
+            let mut ticks = tokio::time::interval_at(start, interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = ticks.tick() => {}
+                }
+                let delay = crate::scheduler::random_below(jitter);
+                if !delay.is_zero() {
+                    tokio::select! {
+                        _ = cancel.cancelled() => break,
+                        _ = tokio::time::sleep(delay) => {}
+                    }
+                }
+
+                // Own task per run, so a panic does not take the loop down;
+                // aborted with the loop if the lifecycle aborts it.
+                let run =
+                    tokio_util::task::AbortOnDropHandle::new(tokio::spawn(f(cancel.child_token())));
+                match run.await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => {
+                        tracing::warn!(error = %format!("{e:#}"), "periodic run failed");
+                    }
+                    Err(e) if e.is_panic() => {
+                        tracing::error!(error = %e, "periodic run panicked");
+                    }
+                    Err(_) => {}
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Unified start core
     ///
     /// `ready_mode = true`   => we expect a ReadySignal to flip `Starting -> Running` (upon notify).
@@ -208,19 +409,25 @@ impl Lifecycle {
         F: Send + 'static + FnOnce(CancellationToken, Option<ReadySignal>) -> Fut,
         Fut: std::future::Future<Output = TaskResult<()>> + Send + 'static,
     {
-        // Stopped -> Starting (via CAS)
-        let cas_ok = self
-            .status
-            .compare_exchange(
-                Status::Stopped.as_u8(),
-                Status::Starting.as_u8(),
-                Ordering::AcqRel,
-                Ordering::Acquire,
-            )
-            .is_ok();
+        // Stopped | Failed -> Starting (via CAS)
+        let cas_ok = [Status::Stopped, Status::Failed].iter().any(|from| {
+            self.status
+                .compare_exchange(
+                    from.as_u8(),
+                    Status::Starting.as_u8(),
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                )
+                .is_ok()
+        });
         if !cas_ok {
             return Err(LifecycleError::AlreadyStarted);
         }
+        self.timeline.lock().record(Status::Starting);
+        install_panic_hook();
+
+        // A failed task is done; its handle can go.
+        drop(self.handle.lock().take());
 
         self.finished.store(false, Ordering::Release);
         self.was_cancelled.store(false, Ordering::Release);
@@ -236,14 +443,20 @@ impl Lifecycle {
         let (ready_tx, ready_rx) = oneshot::channel::<()>();
         if ready_mode {
             let status_on_ready = self.status.clone();
+            let timeline = self.timeline.clone();
             tokio::spawn(async move {
                 if ready_rx.await.is_ok() {
-                    let _ = status_on_ready.compare_exchange(
-                        Status::Starting.as_u8(),
-                        Status::Running.as_u8(),
-                        Ordering::AcqRel,
-                        Ordering::Acquire,
-                    );
+                    let flipped = status_on_ready
+                        .compare_exchange(
+                            Status::Starting.as_u8(),
+                            Status::Running.as_u8(),
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                        )
+                        .is_ok();
+                    if flipped {
+                        timeline.lock().record(Status::Running);
+                    }
                     tracing::debug!("lifecycle status -> running (ready)");
                 } else {
                     // Sender dropped: task didn't signal readiness; we will remain in Starting
@@ -259,23 +472,52 @@ impl Lifecycle {
         let finished_flag = self.finished.clone();
         let finished_notify = self.finished_notify.clone();
         let status_on_finish = self.status.clone();
+        let last_error = self.last_error.clone();
+        let last_panic = self.last_panic.clone();
+        let timeline = self.timeline.clone();
 
         // Spawn the actual task with descriptive logging
         let task_id = format!("lifecycle-{:p}", self);
-        let handle = tokio::spawn({
+        let task = {
             let task_id = task_id.clone();
             async move {
                 tracing::debug!(task_id = %task_id, "lifecycle task starting");
-                let res = make(token, ready_mode.then(|| ReadySignal(ready_tx))).await;
-                if let Err(e) = res {
-                    tracing::error!(error=%e, task_id=%task_id, "lifecycle task error");
-                }
+                let ready = ready_mode.then(|| ReadySignal(ready_tx));
+                let run = CatchPanic {
+                    inner: Box::pin(async move { make(token, ready).await }),
+                };
+                let end = match run.await {
+                    Ok(Ok(())) => Status::Stopped,
+                    Ok(Err(e)) => {
+                        tracing::error!(error=%e, task_id=%task_id, "lifecycle task error");
+                        *last_error.lock() = Some(format!("{e:#}"));
+                        *last_panic.lock() = None;
+                        Status::Failed
+                    }
+                    Err(panic) => {
+                        tracing::error!(
+                            task_id = %task_id,
+                            panic_message = %panic.message,
+                            backtrace = %panic.backtrace,
+                            "lifecycle task panicked"
+                        );
+                        *last_error.lock() = Some(format!("panicked: {}", panic.message));
+                        *last_panic.lock() = Some(panic);
+                        Status::Failed
+                    }
+                };
+                // Record the end state before waking waiters so they observe it.
+                status_on_finish.store(end.as_u8(), Ordering::Release);
+                timeline.lock().record(end);
                 finished_flag.store(true, Ordering::Release);
                 finished_notify.notify_waiters();
-                status_on_finish.store(Status::Stopped.as_u8(), Ordering::Release);
                 tracing::debug!(task_id=%task_id, "lifecycle task finished");
             }
-        });
+        };
+        let handle = match self.runtime.lock().as_ref() {
+            Some(rt) => rt.spawn(task),
+            None => tokio::spawn(task),
+        };
 
         // store handle (bounded lock scope)
         {
@@ -287,8 +529,18 @@ impl Lifecycle {
     }
 
     /// Request graceful shutdown and wait up to `timeout`.
-    #[tracing::instrument(skip(self, timeout), level = "debug")]
     pub async fn stop(&self, timeout: Duration) -> LcResult<StopReason> {
+        self.stop_or_force(timeout, &CancellationToken::new()).await
+    }
+
+    /// Like [`Lifecycle::stop`], but stop waiting and abort the task as soon
+    /// as `force` fires (immediately if it already has).
+    #[tracing::instrument(skip(self, timeout, force), level = "debug")]
+    pub async fn stop_or_force(
+        &self,
+        timeout: Duration,
+        force: &CancellationToken,
+    ) -> LcResult<StopReason> {
         let task_id = format!("lifecycle-{:p}", self);
         let st = self.load_status();
         if !matches!(st, Status::Starting | Status::Running | Status::Stopping) {
@@ -296,7 +548,16 @@ impl Lifecycle {
             return Ok(StopReason::Finished);
         }
 
-        self.store_status(Status::Stopping);
+        // Don't mask a failure recorded by a task that just finished.
+        if self
+            .status
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |cur| {
+                (cur != Status::Failed.as_u8()).then_some(Status::Stopping.as_u8())
+            })
+            .is_ok()
+        {
+            self.timeline.lock().record(Status::Stopping);
+        }
 
         // Request cancellation only once (idempotent if multiple callers race here).
         if let Some(tok) = { self.cancel.lock().take() } {
@@ -323,6 +584,7 @@ impl Lifecycle {
                 }
             }
             _ = tokio::time::sleep(timeout) => StopReason::Timeout,
+            _ = force.cancelled() => StopReason::Timeout,
         };
 
         // Join and ensure we notify waiters even if the task was aborted/panicked.
@@ -343,11 +605,7 @@ impl Lifecycle {
                 Err(e) if e.is_panic() => {
                     // Extract panic information if possible
                     if let Ok(panic_payload) = e.try_into_panic() {
-                        let panic_msg = panic_payload
-                            .downcast_ref::<&str>()
-                            .map(|s| s.to_string())
-                            .or_else(|| panic_payload.downcast_ref::<String>().cloned())
-                            .unwrap_or_else(|| "unknown panic".to_string());
+                        let panic_msg = panic_message(&*panic_payload);
 
                         tracing::error!(
                             task_id = %task_id,
@@ -370,11 +628,47 @@ impl Lifecycle {
             self.finished_notify.notify_waiters();
         }
 
-        self.store_status(Status::Stopped);
+        // A task that failed while stopping stays `Failed`.
+        if self
+            .status
+            .compare_exchange(
+                Status::Stopping.as_u8(),
+                Status::Stopped.as_u8(),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .is_ok()
+        {
+            self.timeline.lock().record(Status::Stopped);
+        }
+        *self.last_stop.lock() = Some(reason);
         tracing::info!(?reason, "lifecycle stopped");
         Ok(reason)
     }
 
+    /// Stop the current task (waiting up to `timeout`), then start `make` on a
+    /// fresh token. Works from any state, including `Failed`.
+    pub async fn restart<F, Fut>(&self, timeout: Duration, make: F) -> LcResult<StopReason>
+    where
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = TaskResult<()>> + Send + 'static,
+    {
+        let reason = self.stop(timeout).await?;
+        self.start(make)?;
+        Ok(reason)
+    }
+
+    /// Error of the most recent task that failed. Kept across restarts until
+    /// another task fails.
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().clone()
+    }
+
+    /// The panic behind [`Lifecycle::last_error`], if that failure was a panic.
+    pub fn last_panic(&self) -> Option<TaskPanic> {
+        self.last_panic.lock().clone()
+    }
+
     /// Current status.
     #[inline]
     #[must_use]
@@ -382,6 +676,29 @@ impl Lifecycle {
         self.load_status()
     }
 
+    /// When the task last became `Running`; `None` unless it is running or stopping.
+    pub fn started_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.timeline.lock().running_since.map(|(at, _)| at)
+    }
+
+    /// Time since the task last became `Running`; `None` unless it is running or stopping.
+    pub fn uptime(&self) -> Option<Duration> {
+        self.timeline
+            .lock()
+            .running_since
+            .map(|(_, since)| since.elapsed())
+    }
+
+    /// The most recent status change.
+    pub fn last_transition(&self) -> Option<Transition> {
+        self.timeline.lock().last
+    }
+
+    /// Why the most recent `stop` ended; `None` before the first stop.
+    pub fn last_stop_reason(&self) -> Option<StopReason> {
+        *self.last_stop.lock()
+    }
+
     /// Whether it is in `Starting` or `Running`.
     #[inline]
     pub fn is_running(&self) -> bool {
@@ -429,6 +746,10 @@ impl Drop for Lifecycle {
 // ----- WithLifecycle wrapper -------------------------------------------------
 
 /// Wrapper that implements `StatefulModule` for any `T: Runnable`.
+///
+/// Besides `run()`, the wrapper can drive extra named tasks (see
+/// [`WithLifecycle::with_task`]). They start after `run()`, share its
+/// cancellation, report their own status, and are stopped together with it.
 pub struct WithLifecycle<T: Runnable> {
     inner: Arc<T>,
     lc: Arc<Lifecycle>,
@@ -437,6 +758,49 @@ pub struct WithLifecycle<T: Runnable> {
     await_ready: bool,
     has_ready_handler: bool,
     run_ready_fn: Option<ReadyFn<T>>,
+    tasks: Vec<NamedTask<T>>,
+    last_stop: Mutex<Option<StopReason>>,
+    /// Thread name prefix and worker count of the module's own runtime.
+    runtime_spec: Option<(&'static str, usize)>,
+    /// Built on first start, kept across restarts.
+    runtime: Mutex<Option<DedicatedRuntime>>,
+}
+
+/// A runtime owned by one module. Shut down without blocking, so it can be
+/// dropped from async code.
+struct DedicatedRuntime(Option<tokio::runtime::Runtime>);
+
+impl DedicatedRuntime {
+    fn build(name: &str, worker_threads: usize) -> std::io::Result<Self> {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(worker_threads)
+            .thread_name(format!("{name}-worker"))
+            .enable_all()
+            .build()?;
+        Ok(Self(Some(rt)))
+    }
+
+    fn handle(&self) -> tokio::runtime::Handle {
+        self.0
+            .as_ref()
+            .expect("runtime is only taken on drop")
+            .handle()
+            .clone()
+    }
+}
+
+impl Drop for DedicatedRuntime {
+    fn drop(&mut self) {
+        if let Some(rt) = self.0.take() {
+            rt.shutdown_background();
+        }
+    }
+}
+
+struct NamedTask<T> {
+    name: &'static str,
+    run: TaskFn<T>,
+    lc: Lifecycle,
 }
 
 impl<T: Runnable> WithLifecycle<T> {
@@ -448,6 +812,10 @@ impl<T: Runnable> WithLifecycle<T> {
             await_ready: false,
             has_ready_handler: false,
             run_ready_fn: None,
+            tasks: Vec::new(),
+            last_stop: Mutex::new(None),
+            runtime_spec: None,
+            runtime: Mutex::new(None),
         }
     }
 
@@ -459,6 +827,10 @@ impl<T: Runnable> WithLifecycle<T> {
             await_ready: false,
             has_ready_handler: false,
             run_ready_fn: None,
+            tasks: Vec::new(),
+            last_stop: Mutex::new(None),
+            runtime_spec: None,
+            runtime: Mutex::new(None),
         }
     }
 
@@ -467,11 +839,85 @@ impl<T: Runnable> WithLifecycle<T> {
         self
     }
 
+    /// Run `run()` and the named tasks on a runtime of their own with
+    /// `worker_threads` workers (threads named `<name>-worker`), so a
+    /// CPU-heavy module cannot starve the shared executor. The runtime is
+    /// built on first start and lives as long as the wrapper.
+    ///
+    /// # Panics
+    /// If `worker_threads` is zero.
+    pub fn with_dedicated_runtime(mut self, name: &'static str, worker_threads: usize) -> Self {
+        assert!(
+            worker_threads > 0,
+            "dedicated runtime needs at least one worker"
+        );
+        self.runtime_spec = Some((name, worker_threads));
+        self
+    }
+
+    /// Add a named background task next to `run()`.
+    ///
+    /// # Panics
+    /// If a task with the same name was already added.
+    pub fn with_task(mut self, name: &'static str, run: TaskFn<T>) -> Self {
+        assert!(
+            self.tasks.iter().all(|t| t.name != name),
+            "duplicate lifecycle task '{name}'"
+        );
+        self.tasks.push(NamedTask {
+            name,
+            run,
+            lc: Lifecycle::new(),
+        });
+        self
+    }
+
+    /// Status of the main `run()` task.
     #[inline]
     pub fn status(&self) -> Status {
         self.lc.status()
     }
 
+    /// Status of the named task, or `None` if there is no such task.
+    pub fn task_status(&self, name: &str) -> Option<Status> {
+        self.tasks
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| t.lc.status())
+    }
+
+    /// Name and status of every named task, in the order they were added.
+    pub fn task_statuses(&self) -> Vec<(&'static str, Status)> {
+        self.tasks.iter().map(|t| (t.name, t.lc.status())).collect()
+    }
+
+    /// Error of the last failed run, if any (see [`Lifecycle::last_error`]).
+    /// Errors of named tasks are prefixed with the task name.
+    pub fn last_error(&self) -> Option<String> {
+        self.lc.last_error().or_else(|| {
+            self.tasks
+                .iter()
+                .find_map(|t| t.lc.last_error().map(|e| format!("{}: {e}", t.name)))
+        })
+    }
+
+    /// Panic behind [`WithLifecycle::last_error`], if the failure was a panic.
+    pub fn last_panic(&self) -> Option<TaskPanic> {
+        if self.lc.last_error().is_some() {
+            return self.lc.last_panic();
+        }
+        self.tasks.iter().find_map(|t| t.lc.last_panic())
+    }
+
+    /// Stop the module (if it is running) and start it again under `cancel`.
+    /// Intended for supervisors reacting to [`Status::Failed`], whether the
+    /// task returned an error or panicked.
+    pub async fn restart(&self, cancel: CancellationToken) -> TaskResult<()> {
+        use crate::contracts::StatefulModule;
+        StatefulModule::stop(self, cancel.clone()).await?;
+        StatefulModule::start(self, cancel).await
+    }
+
     #[inline]
     pub fn inner(&self) -> &T {
         self.inner.as_ref()
@@ -510,40 +956,112 @@ impl<T: Runnable> crate::contracts::StatefulModule for WithLifecycle<T> {
         let inner = self.inner.clone();
         let composed = external_cancel.child_token();
 
+        if let Some((name, workers)) = self.runtime_spec {
+            let mut rt = self.runtime.lock();
+            let handle = match rt.as_ref() {
+                Some(rt) => rt.handle(),
+                None => {
+                    let built = DedicatedRuntime::build(name, workers)?;
+                    tracing::debug!(module = name, workers, "dedicated runtime started");
+                    rt.insert(built).handle()
+                }
+            };
+            self.lc.set_runtime(handle.clone());
+            for task in &self.tasks {
+                task.lc.set_runtime(handle.clone());
+            }
+        }
+
         if !self.await_ready {
+            let inner = inner.clone();
             self.lc
-                .start_with_token(composed, move |cancel| inner.run(cancel))
-                .map_err(anyhow::Error::from)
+                .start_with_token(composed.clone(), move |cancel| inner.run(cancel))?;
         } else if self.has_ready_handler {
             let f = self
                 .run_ready_fn
                 .expect("run_ready_fn must be set when has_ready_handler");
+            let inner = inner.clone();
             self.lc
-                .start_with_ready_and_token(composed, move |cancel, ready| f(inner, cancel, ready))
-                .map_err(anyhow::Error::from)
+                .start_with_ready_and_token(composed.clone(), move |cancel, ready| {
+                    f(inner, cancel, ready)
+                })?;
         } else {
-            self.lc
-                .start_with_ready_and_token(composed, move |cancel, ready| async move {
+            let inner = inner.clone();
+            self.lc.start_with_ready_and_token(
+                composed.clone(),
+                move |cancel, ready| async move {
                     // Auto-notify readiness and continue with normal run()
                     ready.notify();
                     inner.run(cancel).await
-                })
-                .map_err(anyhow::Error::from)
+                },
+            )?;
         }
+
+        // Named tasks share the main token, so stopping one stops them all.
+        for task in &self.tasks {
+            let inner = inner.clone();
+            let run = task.run;
+            task.lc
+                .start_with_token(composed.clone(), move |cancel| run(inner, cancel))?;
+        }
+        Ok(())
     }
 
     #[tracing::instrument(skip(self, external_cancel), level = "debug")]
     async fn stop(&self, external_cancel: CancellationToken) -> TaskResult<()> {
-        tokio::select! {
-            res = self.lc.stop(self.stop_timeout) => {
-                let _ = res.map_err(anyhow::Error::from)?;
-                Ok(())
-            }
-            _ = external_cancel.cancelled() => {
-                let _ = self.lc.stop(Duration::from_millis(0)).await?;
-                Ok(())
-            }
+        let stops = std::iter::once(&*self.lc)
+            .chain(self.tasks.iter().map(|t| &t.lc))
+            .map(|lc| lc.stop_or_force(self.stop_timeout, &external_cancel));
+        let mut reason = StopReason::Finished;
+        for res in futures::future::join_all(stops).await {
+            // Report the worst outcome across all tasks.
+            reason = match (reason, res?) {
+                (StopReason::Timeout, _) | (_, StopReason::Timeout) => StopReason::Timeout,
+                (StopReason::Cancelled, _) | (_, StopReason::Cancelled) => StopReason::Cancelled,
+                _ => StopReason::Finished,
+            };
         }
+        *self.last_stop.lock() = Some(reason);
+        Ok(())
+    }
+
+    /// Status of `run()`, or `Failed` once any named task has failed.
+    fn status(&self) -> Option<Status> {
+        let status = self.lc.status();
+        let task_failed = self.tasks.iter().any(|t| t.lc.status() == Status::Failed);
+        Some(if task_failed && status != Status::Failed {
+            Status::Failed
+        } else {
+            status
+        })
+    }
+
+    fn last_stop_reason(&self) -> Option<StopReason> {
+        *self.last_stop.lock()
+    }
+
+    fn last_error(&self) -> Option<String> {
+        WithLifecycle::last_error(self)
+    }
+
+    fn last_panic(&self) -> Option<TaskPanic> {
+        WithLifecycle::last_panic(self)
+    }
+
+    fn started_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.lc.started_at()
+    }
+
+    fn uptime(&self) -> Option<Duration> {
+        self.lc.uptime()
+    }
+
+    fn last_transition(&self) -> Option<Transition> {
+        self.lc.last_transition()
+    }
+
+    fn stop_timeout(&self) -> Option<Duration> {
+        Some(self.stop_timeout)
     }
 }
 
@@ -614,6 +1132,112 @@ mod tests {
         assert_eq!(lc.status(), Status::Stopped);
     }
 
+    #[tokio::test]
+    async fn task_error_marks_failed_and_restart_recovers() {
+        let lc = Lifecycle::new();
+        lc.start(|_cancel| async { Err(anyhow::anyhow!("boom").context("connecting")) })
+            .unwrap();
+        lc.wait_stopped().await;
+        assert_eq!(lc.status(), Status::Failed);
+        assert_eq!(lc.last_error().as_deref(), Some("connecting: boom"));
+
+        // Stopping a failed task keeps the failure visible.
+        lc.stop(Duration::from_millis(100)).await.unwrap();
+        assert_eq!(lc.status(), Status::Failed);
+
+        let reason = lc
+            .restart(Duration::from_millis(100), |cancel| async move {
+                cancel.cancelled().await;
+                Ok(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(reason, StopReason::Finished);
+        assert_eq!(lc.status(), Status::Running);
+
+        lc.stop(Duration::from_millis(100)).await.unwrap();
+        assert_eq!(lc.status(), Status::Stopped);
+        // The error stays around for diagnostics.
+        assert_eq!(lc.last_error().as_deref(), Some("connecting: boom"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn periodic_runs_survive_errors_and_panics() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let lc = Lifecycle::new();
+        lc.start_periodic(Duration::from_secs(10), {
+            let runs = runs.clone();
+            move |_cancel| {
+                let n = runs.fetch_add(1, AOrd::SeqCst) + 1;
+                async move {
+                    match n {
+                        1 => anyhow::bail!("first run fails"),
+                        2 => panic!("second run panics"),
+                        _ => Ok(()),
+                    }
+                }
+            }
+        })
+        .unwrap();
+
+        sleep(Duration::from_secs(5)).await;
+        assert_eq!(runs.load(AOrd::SeqCst), 0);
+        sleep(Duration::from_secs(30)).await;
+        assert_eq!(runs.load(AOrd::SeqCst), 3);
+        assert_eq!(lc.status(), Status::Running);
+
+        let reason = lc.stop(Duration::from_secs(1)).await.unwrap();
+        assert_eq!(reason, StopReason::Cancelled);
+        assert_eq!(lc.status(), Status::Stopped);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn periodic_skips_ticks_missed_by_slow_runs() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let lc = Lifecycle::new();
+        lc.start_periodic_with_jitter(Duration::from_secs(1), Duration::from_millis(100), {
+            let runs = runs.clone();
+            move |_cancel| {
+                let runs = runs.clone();
+                async move {
+                    runs.fetch_add(1, AOrd::SeqCst);
+                    sleep(Duration::from_millis(2500)).await;
+                    Ok(())
+                }
+            }
+        })
+        .unwrap();
+
+        // Runs take 2.5s on a 1s interval: roughly one run every 3s, no catch-up bursts.
+        sleep(Duration::from_secs(10)).await;
+        let n = runs.load(AOrd::SeqCst);
+        assert!((3..=4).contains(&n), "unexpected run count {n}");
+        lc.stop(Duration::from_secs(5)).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn uptime_and_transitions_follow_status() {
+        let lc = Lifecycle::new();
+        assert!(lc.last_transition().is_none() && lc.uptime().is_none());
+
+        lc.start(|cancel| async move {
+            cancel.cancelled().await;
+            Ok(())
+        })
+        .unwrap();
+        let started_at = lc.started_at().unwrap();
+        assert_eq!(lc.last_transition().unwrap().to, Status::Running);
+        sleep(Duration::from_secs(90)).await;
+        assert_eq!(lc.uptime(), Some(Duration::from_secs(90)));
+
+        lc.stop(Duration::from_secs(1)).await.unwrap();
+        let last = lc.last_transition().unwrap();
+        assert_eq!(last.to, Status::Stopped);
+        assert!(last.at >= started_at);
+        assert!(lc.started_at().is_none() && lc.uptime().is_none());
+        assert_eq!(lc.last_stop_reason(), Some(StopReason::Cancelled));
+    }
+
     #[tokio::test]
     async fn with_lifecycle_wrapper_basics() {
         let runnable = TestRunnable::new();
@@ -769,6 +1393,32 @@ mod tests {
         assert_eq!(wrapper.status(), Status::Stopped);
     }
 
+    #[tokio::test]
+    async fn dedicated_runtime_runs_tasks_on_own_workers() {
+        use crate::contracts::StatefulModule;
+
+        struct ThreadProbe(Mutex<Option<String>>);
+
+        #[async_trait::async_trait]
+        impl Runnable for ThreadProbe {
+            async fn run(self: Arc<Self>, cancel: CancellationToken) -> TaskResult<()> {
+                *self.0.lock() = std::thread::current().name().map(str::to_string);
+                cancel.cancelled().await;
+                Ok(())
+            }
+        }
+
+        let wrapper =
+            WithLifecycle::new(ThreadProbe(Mutex::new(None))).with_dedicated_runtime("heavy", 1);
+        for _ in 0..2 {
+            wrapper.start(CancellationToken::new()).await.unwrap();
+            sleep(Duration::from_millis(50)).await;
+            assert_eq!(wrapper.inner().0.lock().as_deref(), Some("heavy-worker"));
+            wrapper.stop(CancellationToken::new()).await.unwrap();
+            assert_eq!(wrapper.status(), Status::Stopped);
+        }
+    }
+
     #[tokio::test]
     async fn with_lifecycle_double_start_fails() {
         use crate::contracts::StatefulModule;
@@ -813,13 +1463,26 @@ mod tests {
         // Stop should handle the panic gracefully
         let reason = lc.stop(Duration::from_millis(1000)).await.unwrap();
 
-        // The task panicked, but stop should complete successfully
-        // The exact reason depends on timing, but it should not hang or fail
-        assert!(matches!(
-            reason,
-            StopReason::Finished | StopReason::Cancelled | StopReason::Timeout
-        ));
-        assert_eq!(lc.status(), Status::Stopped);
+        // The panic is caught and recorded as a failure.
+        assert_eq!(reason, StopReason::Finished);
+        assert_eq!(lc.status(), Status::Failed);
+        assert_eq!(
+            lc.last_error().as_deref(),
+            Some("panicked: test panic message")
+        );
+        let panic = lc.last_panic().expect("panic recorded");
+        assert_eq!(panic.message, "test panic message");
+        assert!(!panic.backtrace.is_empty());
+
+        // A failed task can be restarted; a later error replaces the panic.
+        lc.restart(Duration::from_millis(100), |_cancel| async {
+            anyhow::bail!("plain error")
+        })
+        .await
+        .unwrap();
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(lc.last_error().as_deref(), Some("plain error"));
+        assert!(lc.last_panic().is_none());
     }
 
     #[tokio::test]
@@ -1,7 +1,10 @@
 // modkit/src/registry/mod.rs
 use axum::Router;
-use std::collections::{HashMap, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
 use thiserror::Error;
@@ -9,19 +12,145 @@ use thiserror::Error;
 // Re-exported contracts are referenced but not defined here.
 use crate::context;
 use crate::contracts;
+use crate::lifecycle::StopReason;
+use crate::report::{
+    ModuleRuntime, ModuleShutdown, ModuleStartup, ShutdownReport, StartupReport, StopOutcome,
+};
 use modkit_db;
 
-/// Type alias for REST host module configuration.
-type RestHostEntry = (&'static str, Arc<dyn contracts::RestHostModule>);
+/// Host that REST modules without a `host = ...` binding use when more than one
+/// module has the `rest_host` capability.
+pub const DEFAULT_REST_HOST: &str = "api_ingress";
 
 pub struct ModuleEntry {
     pub name: &'static str,
+    /// As declared; optional deps keep their `?` suffix and requirements their
+    /// `@req` part (see [`parse_dep`]).
     pub deps: &'static [&'static str],
+    /// Semver version from `#[module(version = "...")]`.
+    pub version: Option<&'static str>,
     pub core: Arc<dyn contracts::Module>,
     pub rest: Option<Arc<dyn contracts::RestfulModule>>,
     pub rest_host: Option<Arc<dyn contracts::RestHostModule>>,
+    /// Host this module's routes go to (`#[module(host = "...")]`); `None` for the default host.
+    pub rest_bind: Option<&'static str>,
     pub db: Option<Arc<dyn contracts::DbModule>>,
     pub stateful: Option<Arc<dyn contracts::StatefulModule>>,
+    pub health: Option<Arc<dyn contracts::HealthProbe>>,
+    pub reloadable: Option<Arc<dyn contracts::ConfigReloadable>>,
+    /// The module as its concrete type, for [`ModuleRegistry::get_module_as`].
+    pub typed: Option<Arc<dyn std::any::Any + Send + Sync>>,
+    /// Typed check of the module's config section (`#[module(config = T)]`).
+    pub config_check: Option<ConfigCheck>,
+    /// Per-module overrides of the registry-wide phase timeouts.
+    pub timeouts: HashMap<Phase, Duration>,
+    /// Periodic jobs declared with `#[module(jobs(...))]`, started after `start`.
+    pub jobs: Vec<crate::scheduler::Job>,
+}
+
+impl ModuleEntry {
+    /// Names of the capabilities this module registered, besides its core.
+    pub fn capabilities(&self) -> Vec<&'static str> {
+        [
+            (self.db.is_some(), "db"),
+            (self.rest.is_some(), "rest"),
+            (self.rest_host.is_some(), "rest_host"),
+            (self.stateful.is_some(), "stateful"),
+            (self.health.is_some(), "health"),
+            (self.reloadable.is_some(), "config_reload"),
+        ]
+        .into_iter()
+        .filter_map(|(has, name)| has.then_some(name))
+        .collect()
+    }
+}
+
+/// Output format of [`ModuleRegistry::export_graph`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT.
+    #[default]
+    Dot,
+    Json,
+}
+
+impl std::str::FromStr for GraphFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "dot" | "gv" => Ok(Self::Dot),
+            "json" => Ok(Self::Json),
+            other => anyhow::bail!("unknown graph format '{other}' (expected dot or json)"),
+        }
+    }
+}
+
+/// Split a declared dependency into its module name and whether it is
+/// optional (`"cache?"`): an optional dep orders init when present and is
+/// ignored when the module is not compiled in or is disabled. A version
+/// requirement (`"users_info@^1"`, see [`dep_version_req`]) is left out.
+pub fn parse_dep(dep: &'static str) -> (&'static str, bool) {
+    let (dep, optional) = match dep.strip_suffix('?') {
+        Some(dep) => (dep, true),
+        None => (dep, false),
+    };
+    let name = dep.split_once('@').map_or(dep, |(name, _)| name);
+    (name, optional)
+}
+
+/// The semver requirement of a declared dependency: `"^1"` for
+/// `"users_info@^1"` (or `"users_info@^1?"`), `None` without one.
+pub fn dep_version_req(dep: &'static str) -> Option<&'static str> {
+    let dep = dep.strip_suffix('?').unwrap_or(dep);
+    dep.split_once('@').map(|(_, req)| req)
+}
+
+/// A dependency whose version does not satisfy the dependent's requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConflict {
+    pub module: &'static str,
+    pub depends_on: &'static str,
+    /// Requirement as declared, e.g. `^1`.
+    pub required: &'static str,
+    /// Version of `depends_on`; `None` when it declares none.
+    pub found: Option<&'static str>,
+}
+
+impl std::fmt::Display for VersionConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' requires '{}' {}, found {}",
+            self.module,
+            self.depends_on,
+            self.required,
+            self.found.unwrap_or("no version")
+        )
+    }
+}
+
+/// A runner phase, used to key timeouts and hooks. Timeouts apply to the
+/// async per-module phases only (`Init`, `DbMigrate`, `Start`, `Stop`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Init,
+    DbMigrate,
+    Rest,
+    Start,
+    Stop,
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Phase::Init => "init",
+            Phase::DbMigrate => "db migration",
+            Phase::Rest => "rest",
+            Phase::Start => "start",
+            Phase::Stop => "stop",
+        })
+    }
 }
 
 impl std::fmt::Debug for ModuleEntry {
@@ -29,25 +158,100 @@ impl std::fmt::Debug for ModuleEntry {
         f.debug_struct("ModuleEntry")
             .field("name", &self.name)
             .field("deps", &self.deps)
+            .field("version", &self.version)
             .field("has_rest", &self.rest.is_some())
             .field("is_rest_host", &self.rest_host.is_some())
+            .field("rest_bind", &self.rest_bind)
             .field("has_db", &self.db.is_some())
             .field("has_stateful", &self.stateful.is_some())
+            .field("has_health", &self.health.is_some())
+            .field("has_config_reload", &self.reloadable.is_some())
+            .field("is_typed", &self.typed.is_some())
+            .field("has_config_check", &self.config_check.is_some())
+            .field("timeouts", &self.timeouts)
+            .field("jobs", &self.jobs)
             .finish()
     }
 }
 
+/// Deserializes a module's config section into its typed struct, discarding the result.
+pub type ConfigCheck = fn(&dyn context::ConfigProvider) -> Result<(), context::ConfigError>;
+
 /// The function type submitted by the macro via `inventory::submit!`.
 /// NOTE: It now takes a *builder*, not the final registry.
 pub struct Registrator(pub fn(&mut RegistryBuilder));
 
 inventory::collect!(Registrator);
 
+/// Implemented by `#[module]` for its type, so an instance can be registered by
+/// hand with [`RegistryBuilder::register`] instead of through inventory.
+pub trait ModuleRegistration: contracts::Module + Sized {
+    /// The `name` given to `#[module]`.
+    const NAME: &'static str;
+
+    /// Register `module` under [`Self::NAME`] with everything `#[module]` declares
+    /// for the type (deps, capabilities, lifecycle, jobs, timeouts, ...).
+    fn register_into(module: Arc<Self>, b: &mut RegistryBuilder);
+}
+
+/// Registers modules that are not compiled in (e.g. plugins loaded at startup).
+/// Applied after the inventory registrators, so the usual duplicate, dependency
+/// and `enabled` checks cover these modules too.
+pub trait ModuleSource: Send + Sync {
+    fn register(&self, b: &mut RegistryBuilder);
+}
+
 /// The final, topo-sorted runtime registry.
 pub struct ModuleRegistry {
     modules: Vec<ModuleEntry>, // topo-sorted
+    /// Abort the REST phase on duplicate routes instead of skipping the
+    /// conflicting module's routes.
+    strict_routes: bool,
+    /// Upper bound on modules initialized concurrently.
+    init_parallelism: usize,
+    /// Registry-wide timeouts; phases without an entry are unbounded.
+    phase_timeouts: HashMap<Phase, Duration>,
+    /// Stop modules of the same dependency depth concurrently.
+    parallel_stop: bool,
+    /// Declared jobs' tasks and the token stopping them, once started.
+    jobs: parking_lot::Mutex<Option<(CancellationToken, Vec<tokio::task::JoinHandle<()>>)>>,
+    /// Parent of the modules' `ctx.child_scope` tokens, from the init context.
+    scopes: parking_lot::Mutex<Option<CancellationToken>>,
+    /// Filled in by the phases as they run; entries follow `modules`.
+    startup: parking_lot::Mutex<StartupReport>,
+    shutdown: parking_lot::Mutex<Option<ShutdownReport>>,
+    /// Modules served by another process, sorted (see [`crate::remote`]).
+    remote: Vec<&'static str>,
 }
 
+/// How long [`ModuleRegistry::health_snapshot`] waits for one probe before
+/// reporting the module unhealthy.
+pub const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Health of every module with the `health` capability, in start order.
+#[derive(Debug, Clone, Default)]
+pub struct HealthSnapshot {
+    pub modules: Vec<(&'static str, contracts::HealthStatus)>,
+}
+
+impl HealthSnapshot {
+    /// The worst status reported by any module (`Healthy` when there are none).
+    pub fn overall(&self) -> contracts::HealthStatus {
+        self.modules
+            .iter()
+            .fold(contracts::HealthStatus::Healthy, |acc, (_, status)| {
+                acc.worst(status.clone())
+            })
+    }
+}
+
+/// How long a module may keep running `stop` after its stop token fired
+/// before [`ModuleRegistry::run_stop_phase`] moves on without it.
+pub const STOP_GRACE: Duration = Duration::from_secs(1);
+
+/// Default bound on concurrent `init` calls in [`ModuleRegistry::run_init_phase`].
+pub const DEFAULT_INIT_PARALLELISM: usize = 8;
+
 impl std::fmt::Debug for ModuleRegistry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let names: Vec<&'static str> = self.modules.iter().map(|m| m.name).collect();
@@ -62,80 +266,352 @@ impl ModuleRegistry {
         &self.modules
     }
 
+    /// Fail `run_rest_phase` on duplicate routes (default: on in debug builds).
+    /// Otherwise the module that claims an already taken route is logged and
+    /// mounted without any of its routes.
+    pub fn with_strict_routes(mut self, strict: bool) -> Self {
+        self.strict_routes = strict;
+        self
+    }
+
+    /// Initialize at most `n` modules at once (clamped to 1, which is fully sequential).
+    pub fn with_init_parallelism(mut self, n: usize) -> Self {
+        self.init_parallelism = n.max(1);
+        self
+    }
+
+    /// Stop independent modules concurrently: all modules at the same
+    /// dependency depth at once, deepest first (default: one at a time).
+    pub fn with_parallel_stop(mut self, parallel: bool) -> Self {
+        self.parallel_stop = parallel;
+        self
+    }
+
+    /// Bound every module's `phase` call by `timeout`, unless the module
+    /// declares its own (`#[module(timeouts(init = "..."))]`).
+    pub fn with_phase_timeout(mut self, phase: Phase, timeout: Duration) -> Self {
+        self.phase_timeouts.insert(phase, timeout);
+        self
+    }
+
+    /// Phase durations and route counts so far; complete once `start` has run.
+    pub fn startup_report(&self) -> StartupReport {
+        self.startup.lock().clone()
+    }
+
+    /// How the last `run_stop_phase` went; `None` before it has run.
+    pub fn shutdown_report(&self) -> Option<ShutdownReport> {
+        self.shutdown.lock().clone()
+    }
+
+    /// Modules left out because another process serves them, sorted.
+    pub fn remote_modules(&self) -> &[&'static str] {
+        &self.remote
+    }
+
+    /// Live lifecycle state of each stateful module, in start order.
+    pub fn runtime_report(&self) -> Vec<ModuleRuntime> {
+        self.modules
+            .iter()
+            .filter_map(|e| {
+                let s = e.stateful.as_ref()?;
+                Some(ModuleRuntime {
+                    module: e.name,
+                    status: s.status(),
+                    started_at: s.started_at(),
+                    uptime: s.uptime(),
+                    last_transition: s.last_transition(),
+                    last_stop_reason: s.last_stop_reason(),
+                    last_error: s.last_error(),
+                    last_panic: s.last_panic(),
+                })
+            })
+            .collect()
+    }
+
+    /// Record the config profile in the startup report.
+    pub(crate) fn record_profile(&self, profile: Option<String>) {
+        self.startup.lock().profile = profile;
+    }
+
+    /// Attach the connect times collected by the runtime's `DbManager`.
+    pub(crate) fn record_db_connects(&self, connects: Vec<(String, Duration)>) {
+        self.startup.lock().db_connects = connects
+            .into_iter()
+            .map(|(module, connect)| crate::report::DbConnect { module, connect })
+            .collect();
+    }
+
+    /// Await one module's phase call, bounded by its effective timeout.
+    async fn bounded<F>(
+        &self,
+        e: &ModuleEntry,
+        phase: Phase,
+        fut: F,
+    ) -> Result<anyhow::Result<()>, RegistryError>
+    where
+        F: std::future::Future<Output = anyhow::Result<()>>,
+    {
+        let limit = e
+            .timeouts
+            .get(&phase)
+            .or_else(|| self.phase_timeouts.get(&phase));
+        match limit {
+            Some(&timeout) => {
+                tokio::time::timeout(timeout, fut)
+                    .await
+                    .map_err(|_| RegistryError::PhaseTimeout {
+                        module: e.name,
+                        phase,
+                        timeout,
+                    })
+            }
+            None => Ok(fut.await),
+        }
+    }
+
     /// Discover via inventory, have registrators fill the builder, then build & topo-sort.
     pub fn discover_and_build() -> Result<Self, RegistryError> {
-        let mut b = RegistryBuilder::default();
-        for r in ::inventory::iter::<Registrator> {
-            r.0(&mut b);
+        RegistryBuilder::discover().build_topo_sorted()
+    }
+
+    /// Like [`Self::discover_and_build`], but leaves out every module whose
+    /// config sets `modules.<name>.enabled = false`.
+    pub fn discover_and_build_with(
+        cfg: &dyn context::ConfigProvider,
+    ) -> Result<Self, RegistryError> {
+        Self::discover_and_build_with_sources(cfg, &[])
+    }
+
+    /// Like [`Self::discover_and_build_with`], adding the modules of `sources`.
+    pub fn discover_and_build_with_sources(
+        cfg: &dyn context::ConfigProvider,
+        sources: &[Arc<dyn ModuleSource>],
+    ) -> Result<Self, RegistryError> {
+        Self::build_with_sources(RegistryBuilder::discover(), cfg, sources)
+    }
+
+    /// Like [`Self::discover_and_build_with_sources`], but starting from the
+    /// modules already in `b` instead of the compiled-in ones.
+    pub fn build_with_sources(
+        mut b: RegistryBuilder,
+        cfg: &dyn context::ConfigProvider,
+        sources: &[Arc<dyn ModuleSource>],
+    ) -> Result<Self, RegistryError> {
+        for source in sources {
+            source.register(&mut b);
+        }
+        let disabled: Vec<&'static str> = b
+            .core
+            .keys()
+            .copied()
+            .filter(|name| !context::module_enabled(cfg, name))
+            .collect();
+        for name in disabled {
+            b.disable(name);
+        }
+        let remote: Vec<&'static str> = b
+            .core
+            .keys()
+            .copied()
+            .filter(|name| crate::remote::is_remote(cfg, name))
+            .collect();
+        for name in remote {
+            b.mark_remote(name);
         }
         b.build_topo_sorted()
     }
 
-    // ---- Ordered phases: init → DB → REST (sync) → start → stop ----
+    // ---- Ordered phases: init → DB → REST → start → stop ----
 
+    /// Run every module's `init`, starting a module as soon as all of its
+    /// dependencies have finished. Independent modules overlap, up to the
+    /// configured parallelism; the first failure aborts the phase. Module
+    /// configs are checked first and all failures returned together as
+    /// [`RegistryError::InvalidConfigs`].
     pub async fn run_init_phase(&self, base_ctx: &context::ModuleCtx) -> Result<(), RegistryError> {
-        for e in &self.modules {
-            let ctx = base_ctx.clone().for_module(e.name);
-            e.core
-                .init(&ctx)
-                .await
-                .map_err(|source| RegistryError::Init {
-                    module: e.name,
-                    source,
-                })?;
+        use futures::stream::{FuturesUnordered, StreamExt};
+
+        // Every bad config in one error, before any module starts its init.
+        if let Some(provider) = base_ctx.config_provider.as_deref() {
+            let errors: Vec<_> = self
+                .validate_configs(provider)
+                .into_iter()
+                .filter_map(|e| match e {
+                    RegistryError::Config { source, .. } => Some(source),
+                    _ => None,
+                })
+                .collect();
+            if !errors.is_empty() {
+                return Err(RegistryError::InvalidConfigs { errors });
+            }
+        }
+        *self.scopes.lock() = Some(base_ctx.scopes.clone());
+
+        let idx: HashMap<&'static str, usize> = self
+            .modules
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.name, i))
+            .collect();
+        let mut pending = vec![0usize; self.modules.len()];
+        let mut dependents = vec![Vec::<usize>::new(); self.modules.len()];
+        for (i, e) in self.modules.iter().enumerate() {
+            for d in e.deps.iter().filter_map(|d| idx.get(parse_dep(d).0)) {
+                pending[i] += 1;
+                dependents[*d].push(i);
+            }
+        }
+
+        // Topo order doubles as the tie-breaker among ready modules.
+        let mut ready: VecDeque<usize> = (0..self.modules.len())
+            .filter(|&i| pending[i] == 0)
+            .collect();
+        let mut running = FuturesUnordered::new();
+
+        loop {
+            while running.len() < self.init_parallelism {
+                let Some(i) = ready.pop_front() else { break };
+                let e = &self.modules[i];
+                let ctx = base_ctx.clone().for_module(e.name);
+                running.push(async move {
+                    let started = Instant::now();
+                    let init = crate::client_hub::providing(e.name, e.core.init(&ctx));
+                    let result = self.bounded(e, Phase::Init, init).await;
+                    (i, result, started.elapsed())
+                });
+            }
+            let Some((i, result, took)) = running.next().await else {
+                break;
+            };
+            self.startup.lock().modules[i].init = Some(took);
+            result?.map_err(|source| RegistryError::Init {
+                module: self.modules[i].name,
+                source,
+            })?;
+            for &w in &dependents[i] {
+                pending[w] -= 1;
+                if pending[w] == 0 {
+                    ready.push_back(w);
+                }
+            }
         }
         Ok(())
     }
 
     pub async fn run_db_phase(&self, db: &modkit_db::DbHandle) -> Result<(), RegistryError> {
-        for e in &self.modules {
+        for (i, e) in self.modules.iter().enumerate() {
             if let Some(dbm) = &e.db {
                 // If you want advisory locks, do it here (kept minimal for portability):
                 // let _lock = db.lock(e.name, "migration").await?;
-                dbm.migrate(db)
-                    .await
+                let started = Instant::now();
+                self.bounded(e, Phase::DbMigrate, dbm.migrate(db))
+                    .await?
                     .map_err(|source| RegistryError::DbMigrate {
                         module: e.name,
                         source,
                     })?;
+                self.startup.lock().modules[i].db_migrate = Some(started.elapsed());
             }
         }
         Ok(())
     }
 
-    pub fn run_rest_phase(
+    /// The host REST modules without a `host = ...` binding go to: the only
+    /// host, or [`DEFAULT_REST_HOST`] when there are several.
+    fn default_rest_host(&self) -> Option<&'static str> {
+        let mut hosts = self.modules.iter().filter(|e| e.rest_host.is_some());
+        match (hosts.next(), hosts.next()) {
+            (Some(only), None) => Some(only.name),
+            (Some(_), Some(_)) => self
+                .modules
+                .iter()
+                .find(|e| e.rest_host.is_some() && e.name == DEFAULT_REST_HOST)
+                .map(|e| e.name),
+            _ => None,
+        }
+    }
+
+    /// Compose every REST host's router from the modules bound to it. Each
+    /// host gets its own OpenAPI registry (and so its own document). `router`
+    /// is the base for the default host, whose finished router is returned;
+    /// other hosts start from an empty router and keep theirs.
+    pub async fn run_rest_phase(
         &self,
         base_ctx: &context::ModuleCtx,
-        mut router: Router,
+        router: Router,
     ) -> Result<Router, RegistryError> {
-        // Find host(s) and whether any rest modules exist
-        let hosts: Vec<_> = self
+        let hosts: Vec<&'static str> = self
             .modules
             .iter()
             .filter(|e| e.rest_host.is_some())
+            .map(|e| e.name)
             .collect();
+        if hosts.is_empty() {
+            return if self.modules.iter().any(|e| e.rest.is_some()) {
+                Err(RegistryError::RestRequiresHost)
+            } else {
+                Ok(router)
+            };
+        }
 
-        match hosts.len() {
-            0 => {
-                return if self.modules.iter().any(|e| e.rest.is_some()) {
-                    Err(RegistryError::RestRequiresHost)
-                } else {
-                    Ok(router)
+        // Bind every REST provider to its host, keeping topo order per host.
+        let default_host = self.default_rest_host();
+        let mut bound: HashMap<&'static str, Vec<usize>> = HashMap::new();
+        for (i, e) in self.modules.iter().enumerate() {
+            if e.rest.is_none() {
+                continue;
+            }
+            let host = match e.rest_bind {
+                Some(host) if hosts.contains(&host) => host,
+                Some(host) => {
+                    return Err(RegistryError::UnknownRestHost {
+                        module: e.name,
+                        host,
+                    })
                 }
+                None => default_host.ok_or_else(|| RegistryError::AmbiguousRestHost {
+                    module: e.name,
+                    hosts: hosts.clone(),
+                })?,
+            };
+            bound.entry(host).or_default().push(i);
+        }
+
+        let mut base = Some(router);
+        let mut default_router = None;
+        for (host_idx, host_entry) in self.modules.iter().enumerate() {
+            if host_entry.rest_host.is_none() {
+                continue;
+            }
+            let is_default = Some(host_entry.name) == default_host;
+            let start = if is_default {
+                base.take().unwrap_or_default()
+            } else {
+                Router::new()
+            };
+            let providers = bound.remove(host_entry.name).unwrap_or_default();
+            let composed = self
+                .compose_rest_host(base_ctx, host_idx, &providers, start)
+                .await?;
+            if is_default {
+                default_router = Some(composed);
             }
-            1 => { /* proceed */ }
-            _ => return Err(RegistryError::MultipleRestHosts),
         }
+        Ok(default_router.or(base).unwrap_or_default())
+    }
 
-        // Resolve the single host entry and its module context
-        let host_idx = self
-            .modules
-            .iter()
-            .position(|e| e.rest_host.is_some())
-            .ok_or(RegistryError::RestHostNotFoundAfterValidation)?;
+    /// prepare → each bound provider → finalize, for one host.
+    async fn compose_rest_host(
+        &self,
+        base_ctx: &context::ModuleCtx,
+        host_idx: usize,
+        providers: &[usize],
+        mut router: Router,
+    ) -> Result<Router, RegistryError> {
         let host_entry = &self.modules[host_idx];
         let Some(host) = host_entry.rest_host.as_ref() else {
-            return Err(RegistryError::RestHostMissingFromEntry);
+            return Ok(router);
         };
         let host_ctx = base_ctx.clone().for_module(host_entry.name);
 
@@ -143,146 +619,722 @@ impl ModuleRegistry {
         let registry: &dyn contracts::OpenApiRegistry = host.as_registry();
 
         // 1) Host prepare: base Router / global middlewares / basic OAS meta
-        router =
-            host.rest_prepare(&host_ctx, router)
-                .map_err(|source| RegistryError::RestPrepare {
-                    module: host_entry.name,
-                    source,
-                })?;
+        router = host
+            .rest_prepare(&host_ctx, router)
+            .await
+            .map_err(|source| RegistryError::RestPrepare {
+                module: host_entry.name,
+                source,
+            })?;
+
+        // 2) Register the host's REST providers (in topo order).
+        //    Each module builds on its own sub-router, so layers it adds only
+        //    wrap its routes; a configured `rest_prefix` nests them.
+        let claims = RouteClaims::default();
+        let claiming = ClaimingRegistry {
+            inner: registry,
+            claims: &claims,
+        };
+        for &i in providers {
+            let e = &self.modules[i];
+            let Some(rest) = &e.rest else { continue };
+            let started = Instant::now();
+            let ctx = base_ctx.clone().for_module(e.name);
+            let register_err = |source| RegistryError::RestRegister {
+                module: e.name,
+                source,
+            };
+            let mut scoped = crate::api::ModuleOpenApiRegistry::new(&claiming, e.name);
+            let prefix = ctx
+                .rest_prefix()
+                .map(validate_rest_prefix)
+                .transpose()
+                .map_err(register_err)?;
+            if let Some(prefix) = prefix {
+                scoped = scoped.with_prefix(prefix);
+            }
+            let sub = rest
+                .register_rest(&ctx, Router::new(), &scoped)
+                .await
+                .map_err(register_err)?;
 
-        // 2) Register all REST providers (in the current discovery order)
-        for e in &self.modules {
-            if let Some(rest) = &e.rest {
-                let ctx = base_ctx.clone().for_module(e.name);
-                router = rest
-                    .register_rest(&ctx, router, registry)
-                    .map_err(|source| RegistryError::RestRegister {
-                        module: e.name,
-                        source,
-                    })?;
+            // Check before merging: axum would panic on the overlapping route
+            let mut duplicates = std::mem::take(&mut *claims.duplicates.lock());
+            if !duplicates.is_empty() {
+                if self.strict_routes {
+                    return Err(duplicates.swap_remove(0));
+                }
+                for dup in &duplicates {
+                    tracing::error!(module = e.name, "{dup}");
+                }
+                tracing::error!(
+                    module = e.name,
+                    "Skipping the module's REST routes because of duplicate routes"
+                );
+                claims.release(e.name);
+                continue;
+            }
+            claims.publish(registry);
+
+            router = match prefix {
+                Some(prefix) => {
+                    tracing::info!(module = e.name, prefix, "Mounted REST routes under prefix");
+                    router.nest(prefix, sub)
+                }
+                None => router.merge(sub),
+            };
+            self.startup.lock().modules[i].rest = Some(started.elapsed());
+        }
+
+        {
+            let owners = claims.owners.lock();
+            let mut startup = self.startup.lock();
+            for &i in providers {
+                let m = &mut startup.modules[i];
+                m.routes = owners.values().filter(|owner| *owner == m.module).count();
             }
         }
 
         // 3) Host finalize: attach /openapi.json and /docs, persist Router if needed (no server start)
-        router = host.rest_finalize(&host_ctx, router).map_err(|source| {
-            RegistryError::RestFinalize {
+        router = host
+            .rest_finalize(&host_ctx, router)
+            .await
+            .map_err(|source| RegistryError::RestFinalize {
                 module: host_entry.name,
                 source,
-            }
-        })?;
+            })?;
 
         Ok(router)
     }
 
+    /// Start stateful modules in order, then every declared job.
     pub async fn run_start_phase(&self, cancel: CancellationToken) -> Result<(), RegistryError> {
-        for e in &self.modules {
+        for (i, e) in self.modules.iter().enumerate() {
             if let Some(s) = &e.stateful {
-                s.start(cancel.clone())
-                    .await
+                let started = Instant::now();
+                self.bounded(e, Phase::Start, s.start(cancel.clone()))
+                    .await?
                     .map_err(|source| RegistryError::Start {
                         module: e.name,
                         source,
                     })?;
+                self.startup.lock().modules[i].start = Some(started.elapsed());
             }
         }
+
+        let jobs_cancel = cancel.child_token();
+        let tasks: Vec<_> = self
+            .modules
+            .iter()
+            .flat_map(|e| e.jobs.iter().map(move |job| (e.name, job)))
+            .map(|(module, job)| {
+                crate::scheduler::spawn_job(module, job.clone(), jobs_cancel.clone())
+            })
+            .collect();
+        *self.jobs.lock() = Some((jobs_cancel, tasks));
         Ok(())
     }
 
+    /// Cancel the modules' `ctx.child_scope`s, stop declared jobs (waiting for
+    /// in-flight runs), then stateful modules, dependents before their dependencies.
+    ///
+    /// Each module's `stop` gets its own token, fired once its deadline passes:
+    /// a `Phase::Stop` timeout registered for the module, else its declared
+    /// `stop_timeout`, else the registry-wide `Phase::Stop` timeout. A module
+    /// still stopping [`STOP_GRACE`] after that is left behind and reported as
+    /// timed out. `cancel` forces every pending stop at once when it fires.
     pub async fn run_stop_phase(&self, cancel: CancellationToken) -> Result<(), RegistryError> {
-        for e in self.modules.iter().rev() {
-            if let Some(s) = &e.stateful {
-                if let Err(err) = s.stop(cancel.clone()).await {
-                    tracing::warn!(module = e.name, error = %err, "Failed to stop module");
-                }
+        if let Some(scopes) = self.scopes.lock().take() {
+            scopes.cancel();
+        }
+        let jobs = self.jobs.lock().take();
+        if let Some((jobs_cancel, tasks)) = jobs {
+            jobs_cancel.cancel();
+            futures::future::join_all(tasks).await;
+        }
+
+        let mut report = ShutdownReport::default();
+        for level in self.stop_levels() {
+            let stops = level
+                .into_iter()
+                .map(|i| self.stop_module(&self.modules[i], &cancel));
+            for stopped in futures::future::join_all(stops).await {
+                report.push(stopped);
             }
         }
+        if report.timed_out > 0 {
+            let timed_out: Vec<_> = report
+                .modules
+                .iter()
+                .filter(|m| m.outcome == StopOutcome::TimedOut)
+                .map(|m| m.module)
+                .collect();
+            tracing::warn!(modules = ?timed_out, "Modules did not stop in time");
+        }
+        *self.shutdown.lock() = Some(report);
         Ok(())
     }
 
-    /// (Optional) quick lookup if you need it.
-    pub fn get_module(&self, name: &str) -> Option<Arc<dyn contracts::Module>> {
-        self.modules
+    /// Stateful modules grouped into batches stopped one after another. With
+    /// parallel stop, a batch holds the modules at one dependency depth, deepest
+    /// first; otherwise every module is its own batch, in reverse topo order.
+    fn stop_levels(&self) -> Vec<Vec<usize>> {
+        let stateful = (0..self.modules.len()).filter(|&i| self.modules[i].stateful.is_some());
+        if !self.parallel_stop {
+            return stateful.rev().map(|i| vec![i]).collect();
+        }
+
+        let idx: HashMap<&'static str, usize> = self
+            .modules
             .iter()
-            .find(|e| e.name == name)
-            .map(|e| e.core.clone())
+            .enumerate()
+            .map(|(i, e)| (e.name, i))
+            .collect();
+        // Topo order guarantees a module's deps already have their depth.
+        let mut depth = vec![0usize; self.modules.len()];
+        for (i, e) in self.modules.iter().enumerate() {
+            depth[i] = e
+                .deps
+                .iter()
+                .filter_map(|d| idx.get(parse_dep(d).0))
+                .map(|&d| depth[d] + 1)
+                .max()
+                .unwrap_or(0);
+        }
+        let mut levels: std::collections::BTreeMap<usize, Vec<usize>> = Default::default();
+        for i in stateful {
+            levels.entry(depth[i]).or_default().push(i);
+        }
+        levels.into_values().rev().collect()
     }
-}
 
-/// Internal builder that macro registrators will feed.
-/// Keys are module **names**; uniqueness enforced at build time.
-#[derive(Default)]
-pub struct RegistryBuilder {
-    core: HashMap<&'static str, Arc<dyn contracts::Module>>,
-    deps: HashMap<&'static str, &'static [&'static str]>,
-    rest: HashMap<&'static str, Arc<dyn contracts::RestfulModule>>,
-    rest_host: Option<RestHostEntry>,
-    db: HashMap<&'static str, Arc<dyn contracts::DbModule>>,
-    stateful: HashMap<&'static str, Arc<dyn contracts::StatefulModule>>,
-    errors: Vec<String>,
-}
+    async fn stop_module(&self, e: &ModuleEntry, force: &CancellationToken) -> ModuleShutdown {
+        let started = Instant::now();
+        let Some(s) = &e.stateful else {
+            return ModuleShutdown {
+                module: e.name,
+                stop: Duration::ZERO,
+                outcome: StopOutcome::Stopped,
+            };
+        };
+        let token = force.child_token();
+        let limit = e
+            .timeouts
+            .get(&Phase::Stop)
+            .copied()
+            .or_else(|| s.stop_timeout())
+            .or_else(|| self.phase_timeouts.get(&Phase::Stop).copied());
+
+        let stop = s.stop(token.clone());
+        tokio::pin!(stop);
+        let result = match limit {
+            None => Some(stop.await),
+            Some(limit) => tokio::select! {
+                res = &mut stop => Some(res),
+                _ = tokio::time::sleep(limit) => {
+                    token.cancel();
+                    tokio::time::timeout(STOP_GRACE, &mut stop).await.ok()
+                }
+            },
+        };
 
-impl RegistryBuilder {
-    pub fn register_core_with_meta(
-        &mut self,
-        name: &'static str,
-        deps: &'static [&'static str],
-        m: Arc<dyn contracts::Module>,
-    ) {
-        if self.core.contains_key(name) {
-            self.errors
-                .push(format!("Module '{name}' is already registered"));
-            return;
+        let outcome = match result {
+            None => {
+                tracing::warn!(module = e.name, timeout = ?limit, "Module did not stop in time");
+                StopOutcome::TimedOut
+            }
+            Some(Ok(())) if s.last_stop_reason() == Some(StopReason::Timeout) => {
+                StopOutcome::Aborted
+            }
+            Some(Ok(())) => StopOutcome::Stopped,
+            Some(Err(err)) => {
+                tracing::warn!(module = e.name, error = %err, "Failed to stop module");
+                StopOutcome::Failed {
+                    error: format!("{err:#}"),
+                }
+            }
+        };
+        ModuleShutdown {
+            module: e.name,
+            stop: started.elapsed(),
+            outcome,
         }
-        self.core.insert(name, m);
-        self.deps.insert(name, deps);
-    }
-
-    pub fn register_rest_with_meta(
-        &mut self,
-        name: &'static str,
-        m: Arc<dyn contracts::RestfulModule>,
-    ) {
-        self.rest.insert(name, m);
     }
 
-    pub fn register_rest_host_with_meta(
-        &mut self,
-        name: &'static str,
-        m: Arc<dyn contracts::RestHostModule>,
-    ) {
-        if let Some((existing, _)) = &self.rest_host {
-            self.errors.push(format!(
-                "Multiple REST host modules detected: '{}' and '{}'. Only one REST host is allowed.",
-                existing, name
-            ));
-            return;
-        }
-        self.rest_host = Some((name, m));
+    /// Lifecycle status of each stateful module, in start order.
+    /// `None` means the module does not report a status.
+    pub fn stateful_statuses(&self) -> Vec<(&'static str, Option<crate::lifecycle::Status>)> {
+        self.modules
+            .iter()
+            .filter_map(|e| e.stateful.as_ref().map(|s| (e.name, s.status())))
+            .collect()
     }
 
-    pub fn register_db_with_meta(&mut self, name: &'static str, m: Arc<dyn contracts::DbModule>) {
-        self.db.insert(name, m);
+    /// Error of the module's last failed run, if it reports one.
+    pub fn last_error(&self, module: &str) -> Option<String> {
+        self.modules
+            .iter()
+            .find(|e| e.name == module)
+            .and_then(|e| e.stateful.as_ref())
+            .and_then(|s| s.last_error())
     }
 
-    pub fn register_stateful_with_meta(
-        &mut self,
-        name: &'static str,
-        m: Arc<dyn contracts::StatefulModule>,
-    ) {
-        self.stateful.insert(name, m);
+    /// Whether every stateful module is `Running`; modules that do not report
+    /// a status count as running. HTTP hosts gate traffic on this so early
+    /// requests do not reach modules still starting.
+    pub fn all_running(&self) -> bool {
+        self.modules
+            .iter()
+            .filter_map(|e| e.stateful.as_ref())
+            .all(|s| {
+                s.status()
+                    .is_none_or(|st| st == crate::lifecycle::Status::Running)
+            })
     }
 
-    /// Detect cycles in the dependency graph using DFS with path tracking.
-    /// Returns the cycle path if found, None otherwise.
-    fn detect_cycle_with_path(
-        names: &[&'static str],
-        adj: &[Vec<usize>],
-    ) -> Option<Vec<&'static str>> {
-        #[derive(Clone, Copy, PartialEq)]
-        enum Color {
-            White, // unvisited
-            Gray,  // visiting (on current path)
-            Black, // visited (finished)
+    /// Probe every module with the `health` capability concurrently. A probe
+    /// that overruns [`HEALTH_PROBE_TIMEOUT`] counts as unhealthy.
+    pub async fn health_snapshot(&self) -> HealthSnapshot {
+        let probes = self.modules.iter().filter_map(|e| {
+            let probe = e.health.as_ref()?;
+            Some(async move {
+                let status = tokio::time::timeout(HEALTH_PROBE_TIMEOUT, probe.health())
+                    .await
+                    .unwrap_or_else(|_| {
+                        contracts::HealthStatus::Unhealthy(format!(
+                            "health probe timed out after {HEALTH_PROBE_TIMEOUT:?}"
+                        ))
+                    });
+                (e.name, status)
+            })
+        });
+        HealthSnapshot {
+            modules: futures::future::join_all(probes).await,
+        }
+    }
+
+    /// Check every module's config against its declared typed struct,
+    /// reporting each failure rather than stopping at the first.
+    pub fn validate_configs(&self, cfg: &dyn context::ConfigProvider) -> Vec<RegistryError> {
+        self.modules
+            .iter()
+            .filter_map(|e| {
+                let check = e.config_check?;
+                check(cfg).err().map(|source| RegistryError::Config {
+                    module: e.name,
+                    source,
+                })
+            })
+            .collect()
+    }
+
+    /// Deliver a reloaded config: every module whose `config` section differs
+    /// between `old` and `new` has it re-checked against its typed struct
+    /// (when declared), then published on `updates` and handed to its
+    /// `config_reload` capability. A module failing the check sees nothing.
+    pub async fn apply_config_update(
+        &self,
+        old: &dyn context::ConfigProvider,
+        new: &dyn context::ConfigProvider,
+        updates: Option<&context::ConfigUpdates>,
+    ) -> Vec<RegistryError> {
+        let mut errors = Vec::new();
+        for e in &self.modules {
+            let section = context::config_section(new, e.name);
+            if section == context::config_section(old, e.name) {
+                continue;
+            }
+            if let Some(Err(source)) = e.config_check.map(|check| check(new)) {
+                errors.push(RegistryError::Config {
+                    module: e.name,
+                    source,
+                });
+                continue;
+            }
+            tracing::info!(module = e.name, "Config section changed");
+            if let Some(updates) = updates {
+                updates.publish(e.name, section.clone());
+            }
+            if let Some(r) = &e.reloadable {
+                if let Err(source) = r.on_config_update(section).await {
+                    errors.push(RegistryError::ConfigReload {
+                        module: e.name,
+                        source,
+                    });
+                }
+            }
+        }
+        errors
+    }
+
+    /// Render the dependency graph: every module with its capabilities and
+    /// declared deps. In DOT, edges point from a module to its dependency and
+    /// optional deps are dashed (omitted when the dep is not registered).
+    pub fn export_graph(&self, format: GraphFormat) -> String {
+        let present: HashSet<&str> = self.modules.iter().map(|e| e.name).collect();
+        match format {
+            GraphFormat::Dot => {
+                let mut out = String::from("digraph modules {\n    rankdir=LR;\n");
+                for e in &self.modules {
+                    let caps = e.capabilities();
+                    let label = if caps.is_empty() {
+                        e.name.to_string()
+                    } else {
+                        format!("{}\\n({})", e.name, caps.join(", "))
+                    };
+                    out.push_str(&format!("    \"{}\" [label=\"{label}\"];\n", e.name));
+                }
+                for e in &self.modules {
+                    for (dep, optional) in e.deps.iter().map(|d| parse_dep(d)) {
+                        if !present.contains(dep) {
+                            continue;
+                        }
+                        let style = if optional { " [style=dashed]" } else { "" };
+                        out.push_str(&format!("    \"{}\" -> \"{dep}\"{style};\n", e.name));
+                    }
+                }
+                out.push_str("}\n");
+                out
+            }
+            GraphFormat::Json => {
+                let modules: Vec<_> = self
+                    .modules
+                    .iter()
+                    .map(|e| {
+                        let deps: Vec<_> = e
+                            .deps
+                            .iter()
+                            .map(|d| {
+                                let (name, optional) = parse_dep(d);
+                                serde_json::json!({
+                                    "name": name,
+                                    "optional": optional,
+                                    "present": present.contains(name),
+                                })
+                            })
+                            .collect();
+                        serde_json::json!({
+                            "name": e.name,
+                            "capabilities": e.capabilities(),
+                            "deps": deps,
+                        })
+                    })
+                    .collect();
+                let graph = serde_json::json!({ "modules": modules });
+                serde_json::to_string_pretty(&graph).unwrap_or_default()
+            }
+        }
+    }
+
+    /// (Optional) quick lookup if you need it.
+    pub fn get_module(&self, name: &str) -> Option<Arc<dyn contracts::Module>> {
+        self.modules
+            .iter()
+            .find(|e| e.name == name)
+            .map(|e| e.core.clone())
+    }
+
+    /// The first module whose concrete type is `T`. Only modules registered with
+    /// their type (`#[module]`, or [`RegistryBuilder::register_typed_with_meta`])
+    /// can be found.
+    pub fn get_module_as<T: contracts::Module>(&self) -> Option<Arc<T>> {
+        self.modules
+            .iter()
+            .filter_map(|e| e.typed.clone())
+            .find_map(|m| m.downcast::<T>().ok())
+    }
+
+    /// Name and capability handle of every module providing `C`, in topological
+    /// order, e.g. `registry.modules_with_capability::<dyn HealthProbe>()`.
+    pub fn modules_with_capability<C: Capability + ?Sized>(
+        &self,
+    ) -> impl Iterator<Item = (&'static str, &Arc<C>)> + '_ {
+        self.modules
+            .iter()
+            .filter_map(|e| C::of(e).map(|c| (e.name, c)))
+    }
+}
+
+/// A capability a module can register; see [`ModuleRegistry::modules_with_capability`].
+pub trait Capability: 'static {
+    /// The module's handle for this capability, if it has one.
+    fn of(entry: &ModuleEntry) -> Option<&Arc<Self>>;
+}
+
+impl Capability for dyn contracts::RestfulModule {
+    fn of(entry: &ModuleEntry) -> Option<&Arc<Self>> {
+        entry.rest.as_ref()
+    }
+}
+
+impl Capability for dyn contracts::RestHostModule {
+    fn of(entry: &ModuleEntry) -> Option<&Arc<Self>> {
+        entry.rest_host.as_ref()
+    }
+}
+
+impl Capability for dyn contracts::DbModule {
+    fn of(entry: &ModuleEntry) -> Option<&Arc<Self>> {
+        entry.db.as_ref()
+    }
+}
+
+impl Capability for dyn contracts::StatefulModule {
+    fn of(entry: &ModuleEntry) -> Option<&Arc<Self>> {
+        entry.stateful.as_ref()
+    }
+}
+
+impl Capability for dyn contracts::HealthProbe {
+    fn of(entry: &ModuleEntry) -> Option<&Arc<Self>> {
+        entry.health.as_ref()
+    }
+}
+
+impl Capability for dyn contracts::ConfigReloadable {
+    fn of(entry: &ModuleEntry) -> Option<&Arc<Self>> {
+        entry.reloadable.as_ref()
+    }
+}
+
+/// Internal builder that macro registrators will feed.
+/// Keys are module **names**; uniqueness enforced at build time.
+#[derive(Default)]
+pub struct RegistryBuilder {
+    core: HashMap<&'static str, Arc<dyn contracts::Module>>,
+    deps: HashMap<&'static str, &'static [&'static str]>,
+    versions: HashMap<&'static str, &'static str>,
+    rest: HashMap<&'static str, Arc<dyn contracts::RestfulModule>>,
+    rest_hosts: HashMap<&'static str, Arc<dyn contracts::RestHostModule>>,
+    rest_bindings: HashMap<&'static str, &'static str>,
+    db: HashMap<&'static str, Arc<dyn contracts::DbModule>>,
+    stateful: HashMap<&'static str, Arc<dyn contracts::StatefulModule>>,
+    health: HashMap<&'static str, Arc<dyn contracts::HealthProbe>>,
+    reloadable: HashMap<&'static str, Arc<dyn contracts::ConfigReloadable>>,
+    typed: HashMap<&'static str, Arc<dyn std::any::Any + Send + Sync>>,
+    config_checks: HashMap<&'static str, ConfigCheck>,
+    disabled: HashSet<&'static str>,
+    remote: HashSet<&'static str>,
+    timeouts: HashMap<&'static str, HashMap<Phase, Duration>>,
+    jobs: HashMap<&'static str, Vec<crate::scheduler::Job>>,
+    errors: Vec<String>,
+}
+
+impl RegistryBuilder {
+    /// An empty builder for explicit registration: only modules passed to
+    /// [`register`](Self::register) (or the `register_*_with_meta` calls) end
+    /// up in the registry, whatever else is linked in.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a `#[module]` instance built by the caller, e.g. with
+    /// constructor arguments the `ctor` cannot provide.
+    pub fn register<M: ModuleRegistration>(&mut self, module: M) -> &mut Self {
+        self.register_arc(Arc::new(module))
+    }
+
+    /// Like [`register`](Self::register), for an instance the caller keeps a handle to.
+    pub fn register_arc<M: ModuleRegistration>(&mut self, module: Arc<M>) -> &mut Self {
+        M::register_into(module, self);
+        self
+    }
+
+    /// By-value form of [`register`](Self::register), for chaining.
+    #[must_use]
+    pub fn with_module<M: ModuleRegistration>(mut self, module: M) -> Self {
+        self.register(module);
+        self
+    }
+
+    /// A builder holding every compiled-in module (inventory discovery).
+    pub fn discover() -> Self {
+        let mut b = Self::default();
+        for r in ::inventory::iter::<Registrator> {
+            r.0(&mut b);
+        }
+        b
+    }
+
+    /// A builder holding only the named compiled-in modules. Building fails
+    /// if one of them is unknown or depends on a module left out.
+    pub fn discover_only(names: &[&str]) -> Self {
+        let mut b = Self::discover();
+        for name in names {
+            if !b.core.contains_key(name) {
+                b.errors.push(format!("Module '{name}' is not compiled in"));
+            }
+        }
+        let left_out: Vec<&'static str> = b
+            .core
+            .keys()
+            .copied()
+            .filter(|name| !names.contains(name))
+            .collect();
+        for name in left_out {
+            b.disable(name);
+        }
+        b
+    }
+
+    pub fn register_core_with_meta(
+        &mut self,
+        name: &'static str,
+        deps: &'static [&'static str],
+        m: Arc<dyn contracts::Module>,
+    ) {
+        if self.core.contains_key(name) {
+            self.errors
+                .push(format!("Module '{name}' is already registered"));
+            return;
+        }
+        for dep in deps {
+            if let Some(Err(e)) = dep_version_req(dep).map(semver::VersionReq::parse) {
+                self.errors.push(format!(
+                    "Module '{name}' dependency '{dep}': invalid version requirement: {e}"
+                ));
+            }
+        }
+        self.core.insert(name, m);
+        self.deps.insert(name, deps);
+    }
+
+    pub fn register_rest_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::RestfulModule>,
+    ) {
+        self.rest.insert(name, m);
+    }
+
+    pub fn register_rest_host_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::RestHostModule>,
+    ) {
+        self.rest_hosts.insert(name, m);
+    }
+
+    /// Send module `name`'s routes to the REST host `host` instead of the default one.
+    pub fn register_rest_binding_with_meta(&mut self, name: &'static str, host: &'static str) {
+        self.rest_bindings.insert(name, host);
+    }
+
+    pub fn register_db_with_meta(&mut self, name: &'static str, m: Arc<dyn contracts::DbModule>) {
+        self.db.insert(name, m);
+    }
+
+    pub fn register_stateful_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::StatefulModule>,
+    ) {
+        self.stateful.insert(name, m);
+    }
+
+    pub fn register_health_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::HealthProbe>,
+    ) {
+        self.health.insert(name, m);
+    }
+
+    pub fn register_reloadable_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::ConfigReloadable>,
+    ) {
+        self.reloadable.insert(name, m);
+    }
+
+    /// Record the module's concrete type so [`ModuleRegistry::get_module_as`] can
+    /// find it; `#[module]` does this for every module.
+    pub fn register_typed_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn std::any::Any + Send + Sync>,
+    ) {
+        self.typed.insert(name, m);
+    }
+
+    /// Declare a periodic job; `spec` is parsed as a [`crate::scheduler::JobSpec`]
+    /// and an invalid one fails the build.
+    pub fn register_job_with_meta(
+        &mut self,
+        name: &'static str,
+        job: &'static str,
+        spec: &str,
+        jitter: Option<Duration>,
+        run: crate::scheduler::JobFn,
+    ) {
+        match spec.parse::<crate::scheduler::JobSpec>() {
+            Ok(spec) => self
+                .jobs
+                .entry(name)
+                .or_default()
+                .push(crate::scheduler::Job {
+                    name: job.to_string(),
+                    spec: spec.with_jitter(jitter.unwrap_or_default()),
+                    run,
+                }),
+            Err(e) => self
+                .errors
+                .push(format!("Module '{name}' job '{job}': {e}")),
+        }
+    }
+
+    pub fn register_config_with_meta(&mut self, name: &'static str, check: ConfigCheck) {
+        self.config_checks.insert(name, check);
+    }
+
+    /// Declare the semver version of module `name`, checked against its
+    /// dependents' `@req` requirements.
+    pub fn register_version_with_meta(&mut self, name: &'static str, version: &'static str) {
+        if let Err(e) = semver::Version::parse(version) {
+            self.errors
+                .push(format!("Module '{name}' version '{version}': {e}"));
+            return;
+        }
+        self.versions.insert(name, version);
+    }
+
+    /// Leave `name` out of the built registry. Building fails if an enabled
+    /// module depends on it.
+    pub fn disable(&mut self, name: &'static str) {
+        self.disabled.insert(name);
+    }
+
+    /// Leave `name` out of the built registry because another process serves
+    /// it. Unlike [`RegistryBuilder::disable`], modules may still depend on it.
+    pub fn mark_remote(&mut self, name: &'static str) {
+        self.remote.insert(name);
+    }
+
+    /// Override the registry-wide timeout of `phase` for one module.
+    pub fn register_timeout_with_meta(
+        &mut self,
+        name: &'static str,
+        phase: Phase,
+        timeout: Duration,
+    ) {
+        self.timeouts
+            .entry(name)
+            .or_default()
+            .insert(phase, timeout);
+    }
+
+    /// Detect cycles in the dependency graph using DFS with path tracking.
+    /// Returns the cycle path if found, None otherwise.
+    fn detect_cycle_with_path(
+        names: &[&'static str],
+        adj: &[Vec<usize>],
+    ) -> Option<Vec<&'static str>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Color {
+            White, // unvisited
+            Gray,  // visiting (on current path)
+            Black, // visited (finished)
         }
 
         let mut colors = vec![Color::White; names.len()];
@@ -340,10 +1392,17 @@ impl RegistryBuilder {
     }
 
     /// Finalize & topo-sort; verify deps & capability binding to known cores.
-    pub fn build_topo_sorted(self) -> Result<ModuleRegistry, RegistryError> {
-        if let Some((host_name, _)) = &self.rest_host {
-            if !self.core.contains_key(host_name) {
-                return Err(RegistryError::UnknownModule(host_name.to_string()));
+    ///
+    /// The order is deterministic: a module comes after all of its
+    /// dependencies, and among modules that could go next the one whose name
+    /// sorts first goes first. Registration order does not matter.
+    pub fn build_topo_sorted(mut self) -> Result<ModuleRegistry, RegistryError> {
+        self.drop_disabled()?;
+        for (n, host) in self.rest_bindings.iter() {
+            if !self.rest.contains_key(n) {
+                self.errors.push(format!(
+                    "Module '{n}' binds to REST host '{host}' but has no 'rest' capability"
+                ));
             }
         }
         if !self.errors.is_empty() {
@@ -358,7 +1417,7 @@ impl RegistryBuilder {
                 return Err(RegistryError::UnknownModule((*n).to_string()));
             }
         }
-        if let Some((n, _)) = &self.rest_host {
+        for (n, _) in self.rest_hosts.iter() {
             if !self.core.contains_key(n) {
                 return Err(RegistryError::UnknownModule((*n).to_string()));
             }
@@ -373,9 +1432,31 @@ impl RegistryBuilder {
                 return Err(RegistryError::UnknownModule((*n).to_string()));
             }
         }
+        for (n, _) in self.health.iter() {
+            if !self.core.contains_key(n) {
+                return Err(RegistryError::UnknownModule((*n).to_string()));
+            }
+        }
+        for (n, _) in self.reloadable.iter() {
+            if !self.core.contains_key(n) {
+                return Err(RegistryError::UnknownModule((*n).to_string()));
+            }
+        }
+        for (n, _) in self.typed.iter() {
+            if !self.core.contains_key(n) {
+                return Err(RegistryError::UnknownModule((*n).to_string()));
+            }
+        }
+        for (n, _) in self.jobs.iter() {
+            if !self.core.contains_key(n) {
+                return Err(RegistryError::UnknownModule((*n).to_string()));
+            }
+        }
 
-        // 2) build graph over core modules and detect cycles
-        let names: Vec<&'static str> = self.core.keys().copied().collect();
+        // 2) build graph over core modules and detect cycles; indices follow
+        // name order so everything below is independent of hash order
+        let mut names: Vec<&'static str> = self.core.keys().copied().collect();
+        names.sort_unstable();
         let mut idx: HashMap<&'static str, usize> = HashMap::new();
         for (i, &n) in names.iter().enumerate() {
             idx.insert(n, i);
@@ -383,26 +1464,42 @@ impl RegistryBuilder {
 
         let mut adj = vec![Vec::<usize>::new(); names.len()];
 
-        for (&n, &deps) in self.deps.iter() {
+        let mut all_deps: Vec<_> = self.deps.iter().map(|(&n, &d)| (n, d)).collect();
+        all_deps.sort_unstable_by_key(|&(n, _)| n);
+        for (n, deps) in all_deps {
             let u = *idx
                 .get(n)
                 .ok_or_else(|| RegistryError::UnknownModule(n.to_string()))?;
             for &d in deps {
-                let v = *idx.get(d).ok_or_else(|| RegistryError::UnknownDependency {
-                    module: n.to_string(),
-                    depends_on: d.to_string(),
-                })?;
+                let (d, optional) = parse_dep(d);
+                let v = match idx.get(d) {
+                    Some(&v) => v,
+                    None if optional || self.remote.contains(d) => continue,
+                    None => {
+                        return Err(RegistryError::UnknownDependency {
+                            module: n.to_string(),
+                            depends_on: d.to_string(),
+                        })
+                    }
+                };
                 // edge d -> n (dep before module)
                 adj[v].push(u);
             }
         }
 
+        // Version requirements of the deps that are present
+        let conflicts = self.version_conflicts();
+        if !conflicts.is_empty() {
+            return Err(RegistryError::IncompatibleDependencies { conflicts });
+        }
+
         // 3) Cycle detection using DFS with path tracking
         if let Some(cycle_path) = Self::detect_cycle_with_path(&names, &adj) {
             return Err(RegistryError::CycleDetected { path: cycle_path });
         }
 
-        // 4) Kahn's algorithm for topological sorting (we know there are no cycles)
+        // 4) Kahn's algorithm for topological sorting (we know there are no cycles),
+        // always taking the ready module with the smallest name
         let mut indeg = vec![0usize; names.len()];
         for adj_list in &adj {
             for &target in adj_list {
@@ -410,20 +1507,20 @@ impl RegistryBuilder {
             }
         }
 
-        let mut q = VecDeque::new();
+        let mut q = BinaryHeap::new();
         for (i, &degree) in indeg.iter().enumerate() {
             if degree == 0 {
-                q.push_back(i);
+                q.push(Reverse(i));
             }
         }
 
         let mut order = Vec::with_capacity(names.len());
-        while let Some(u) = q.pop_front() {
+        while let Some(Reverse(u)) = q.pop() {
             order.push(u);
             for &w in &adj[u] {
                 indeg[w] -= 1;
                 if indeg[w] == 0 {
-                    q.push_back(w);
+                    q.push(Reverse(w));
                 }
             }
         }
@@ -446,15 +1543,19 @@ impl RegistryBuilder {
             let entry = ModuleEntry {
                 name,
                 deps,
+                version: self.versions.get(name).copied(),
                 core,
                 rest: self.rest.get(name).cloned(),
-                rest_host: self
-                    .rest_host
-                    .as_ref()
-                    .filter(|(host_name, _)| *host_name == name)
-                    .map(|(_, module)| module.clone()),
+                rest_host: self.rest_hosts.get(name).cloned(),
+                rest_bind: self.rest_bindings.get(name).copied(),
                 db: self.db.get(name).cloned(),
                 stateful: self.stateful.get(name).cloned(),
+                health: self.health.get(name).cloned(),
+                reloadable: self.reloadable.get(name).cloned(),
+                typed: self.typed.get(name).cloned(),
+                config_check: self.config_checks.get(name).copied(),
+                timeouts: self.timeouts.remove(name).unwrap_or_default(),
+                jobs: self.jobs.remove(name).unwrap_or_default(),
             };
             entries.push(entry);
         }
@@ -464,45 +1565,258 @@ impl RegistryBuilder {
             "Module dependency order resolved (topo)"
         );
 
-        Ok(ModuleRegistry { modules: entries })
+        let mut remote: Vec<&'static str> = self.remote.iter().copied().collect();
+        remote.sort_unstable();
+
+        let startup = StartupReport {
+            modules: entries.iter().map(|e| ModuleStartup::new(e.name)).collect(),
+            db_connects: Vec::new(),
+            ..Default::default()
+        };
+        Ok(ModuleRegistry {
+            modules: entries,
+            strict_routes: cfg!(debug_assertions),
+            init_parallelism: DEFAULT_INIT_PARALLELISM,
+            phase_timeouts: HashMap::new(),
+            parallel_stop: false,
+            jobs: parking_lot::Mutex::new(None),
+            scopes: parking_lot::Mutex::new(None),
+            startup: parking_lot::Mutex::new(startup),
+            shutdown: parking_lot::Mutex::new(None),
+            remote,
+        })
     }
 }
 
-/// Structured errors for the module registry.
-#[derive(Debug, Error)]
-pub enum RegistryError {
-    // Phase errors with module context
-    #[error("initialization failed for module '{module}'")]
-    Init {
-        module: &'static str,
-        #[source]
-        source: anyhow::Error,
-    },
-    #[error("start failed for '{module}'")]
-    Start {
-        module: &'static str,
-        #[source]
-        source: anyhow::Error,
-    },
+impl RegistryBuilder {
+    /// Every `@req` dependency whose target is present but does not satisfy it,
+    /// sorted by module.
+    fn version_conflicts(&self) -> Vec<VersionConflict> {
+        let mut conflicts = Vec::new();
+        for (&module, &deps) in self.deps.iter() {
+            for &dep in deps {
+                let Some(required) = dep_version_req(dep) else {
+                    continue;
+                };
+                let depends_on = parse_dep(dep).0;
+                if !self.core.contains_key(depends_on) {
+                    continue;
+                }
+                let found = self.versions.get(depends_on).copied();
+                // Requirements and versions were validated on registration
+                let satisfied = match (semver::VersionReq::parse(required), found) {
+                    (Ok(req), Some(v)) => semver::Version::parse(v).is_ok_and(|v| req.matches(&v)),
+                    _ => false,
+                };
+                if !satisfied {
+                    conflicts.push(VersionConflict {
+                        module,
+                        depends_on,
+                        required,
+                        found,
+                    });
+                }
+            }
+        }
+        conflicts.sort_by_key(|c| (c.module, c.depends_on));
+        conflicts
+    }
 
-    #[error("DB migration failed for module '{module}'")]
-    DbMigrate {
-        module: &'static str,
-        #[source]
-        source: anyhow::Error,
-    },
-    #[error("REST prepare failed for host module '{module}'")]
-    RestPrepare {
-        module: &'static str,
-        #[source]
-        source: anyhow::Error,
-    },
-    #[error("REST registration failed for module '{module}'")]
-    RestRegister {
-        module: &'static str,
-        #[source]
-        source: anyhow::Error,
-    },
+    /// Remove disabled and remote modules with all their capabilities,
+    /// refusing if an enabled module still depends on a disabled one.
+    fn drop_disabled(&mut self) -> Result<(), RegistryError> {
+        self.remote.retain(|name| !self.disabled.contains(name));
+        if self.disabled.is_empty() && self.remote.is_empty() {
+            return Ok(());
+        }
+        let mut modules: Vec<_> = self.deps.iter().collect();
+        modules.sort_by_key(|(name, _)| **name);
+        for (&module, &deps) in modules {
+            if self.disabled.contains(module) {
+                continue;
+            }
+            let disabled_dep = deps
+                .iter()
+                .map(|d| parse_dep(d))
+                .find(|(d, optional)| !optional && self.disabled.contains(d));
+            if let Some((dep, _)) = disabled_dep {
+                return Err(RegistryError::DisabledDependency {
+                    module: module.to_string(),
+                    depends_on: dep.to_string(),
+                });
+            }
+        }
+
+        let remote: Vec<&'static str> = self.remote.iter().copied().collect();
+        for name in std::mem::take(&mut self.disabled).into_iter().chain(remote) {
+            if self.core.remove(name).is_none() {
+                self.remote.remove(name);
+                continue;
+            }
+            if self.remote.contains(name) {
+                tracing::info!(module = name, "Module served remotely");
+            } else {
+                tracing::info!(module = name, "Module disabled by configuration");
+            }
+            self.deps.remove(name);
+            self.versions.remove(name);
+            self.rest.remove(name);
+            self.db.remove(name);
+            self.stateful.remove(name);
+            self.health.remove(name);
+            self.reloadable.remove(name);
+            self.typed.remove(name);
+            self.config_checks.remove(name);
+            self.timeouts.remove(name);
+            self.jobs.remove(name);
+            self.rest_hosts.remove(name);
+            self.rest_bindings.remove(name);
+        }
+        Ok(())
+    }
+}
+
+/// Check a module `rest_prefix`: `/segment[/segment…]`, no trailing slash.
+fn validate_rest_prefix(prefix: &str) -> anyhow::Result<&str> {
+    let valid = prefix.starts_with('/')
+        && prefix.len() > 1
+        && !prefix.ends_with('/')
+        && !prefix.contains(['{', '}', '*']);
+    if !valid {
+        anyhow::bail!(
+            "invalid rest_prefix '{prefix}': expected a static path like '/api/my-module'"
+        );
+    }
+    Ok(prefix)
+}
+
+/// Which module registered each (method, path), and any collisions seen.
+#[derive(Default)]
+struct RouteClaims {
+    owners: parking_lot::Mutex<HashMap<(http::Method, String), String>>,
+    duplicates: parking_lot::Mutex<Vec<RegistryError>>,
+    /// Operations of the module being registered, held back until it is accepted.
+    pending: parking_lot::Mutex<Vec<crate::api::OperationSpec>>,
+}
+
+impl RouteClaims {
+    /// Hand the pending operations of an accepted module to the host registry.
+    fn publish(&self, registry: &dyn contracts::OpenApiRegistry) {
+        for spec in std::mem::take(&mut *self.pending.lock()) {
+            registry.register_operation(&spec);
+        }
+    }
+
+    /// Forget a rejected module: drop its operations and free its routes.
+    fn release(&self, module: &str) {
+        self.pending.lock().clear();
+        self.owners.lock().retain(|_, owner| owner != module);
+    }
+}
+
+/// Registry wrapper recording route ownership; operations reach the host
+/// only once the module's routes are accepted (see [`RouteClaims::publish`]).
+struct ClaimingRegistry<'a> {
+    inner: &'a dyn contracts::OpenApiRegistry,
+    claims: &'a RouteClaims,
+}
+
+impl contracts::OpenApiRegistry for ClaimingRegistry<'_> {
+    fn register_operation(&self, spec: &crate::api::OperationSpec) {
+        let owner = spec.module.clone().unwrap_or_default();
+        let key = (spec.method.clone(), spec.path.clone());
+        match self.claims.owners.lock().entry(key) {
+            std::collections::hash_map::Entry::Occupied(first) => {
+                self.claims
+                    .duplicates
+                    .lock()
+                    .push(RegistryError::DuplicateRoute {
+                        method: spec.method.to_string(),
+                        path: spec.path.clone(),
+                        first: first.get().clone(),
+                        second: owner,
+                    });
+            }
+            std::collections::hash_map::Entry::Vacant(slot) => {
+                slot.insert(owner);
+            }
+        }
+        self.claims.pending.lock().push(spec.clone());
+    }
+
+    fn ensure_schema_raw(
+        &self,
+        name: &str,
+        schemas: Vec<(
+            String,
+            utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+        )>,
+    ) -> String {
+        self.inner.ensure_schema_raw(name, schemas)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+
+    fn add_error_mapper(&self, mapper: std::sync::Arc<crate::api::ErrorMapper>) {
+        self.inner.add_error_mapper(mapper);
+    }
+}
+
+/// Structured errors for the module registry.
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    // Phase errors with module context
+    #[error("initialization failed for module '{module}'")]
+    Init {
+        module: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("{phase} of module '{module}' did not finish within {timeout:?}")]
+    PhaseTimeout {
+        module: &'static str,
+        phase: Phase,
+        timeout: Duration,
+    },
+    #[error("config check failed for '{module}'")]
+    Config {
+        module: &'static str,
+        #[source]
+        source: context::ConfigError,
+    },
+    #[error("config reload failed for '{module}'")]
+    ConfigReload {
+        module: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("start failed for '{module}'")]
+    Start {
+        module: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
+
+    #[error("DB migration failed for module '{module}'")]
+    DbMigrate {
+        module: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("REST prepare failed for host module '{module}'")]
+    RestPrepare {
+        module: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("REST registration failed for module '{module}'")]
+    RestRegister {
+        module: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
     #[error("REST finalize failed for host module '{module}'")]
     RestFinalize {
         module: &'static str,
@@ -511,24 +1825,41 @@ pub enum RegistryError {
     },
     #[error("REST phase requires an ingress host: modules with capability 'rest' found, but no module with capability 'rest_host'")]
     RestRequiresHost,
-    #[error("multiple 'rest_host' modules detected; exactly one is allowed")]
-    MultipleRestHosts,
-    #[error("REST host module not found after validation")]
-    RestHostNotFoundAfterValidation,
-    #[error("REST host missing from entry")]
-    RestHostMissingFromEntry,
+    #[error("module '{module}' binds its routes to '{host}', which is not a REST host")]
+    UnknownRestHost {
+        module: &'static str,
+        host: &'static str,
+    },
+    #[error("module '{module}' must choose a REST host with `host = ...`: several exist ({hosts:?}) and none is '{DEFAULT_REST_HOST}'")]
+    AmbiguousRestHost {
+        module: &'static str,
+        hosts: Vec<&'static str>,
+    },
+    #[error("duplicate route {method} {path}: registered by '{first}' and '{second}'")]
+    DuplicateRoute {
+        method: String,
+        path: String,
+        first: String,
+        second: String,
+    },
 
     // Build/topo-sort errors
     #[error("unknown module '{0}'")]
     UnknownModule(String),
     #[error("module '{module}' depends on unknown '{depends_on}'")]
     UnknownDependency { module: String, depends_on: String },
+    #[error("module '{module}' depends on '{depends_on}', which is disabled")]
+    DisabledDependency { module: String, depends_on: String },
     #[error("cyclic dependency detected: {}", path.join(" -> "))]
     CycleDetected { path: Vec<&'static str> },
     #[error("missing deps for '{0}'")]
     MissingDeps(String),
     #[error("core not found for '{0}'")]
     CoreNotFound(String),
+    #[error("incompatible module versions: {}", conflicts.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    IncompatibleDependencies { conflicts: Vec<VersionConflict> },
+    #[error("invalid module configuration: {}", errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    InvalidConfigs { errors: Vec<context::ConfigError> },
     #[error("invalid registry configuration:\n{errors:#?}")]
     InvalidRegistryConfiguration { errors: Vec<String> },
 }
@@ -544,6 +1875,7 @@ mod tests {
     use crate::api::OpenApiRegistry;
     use crate::context::{ModuleCtx, ModuleCtxBuilder};
     use crate::contracts;
+    use crate::lifecycle::Status;
 
     /* --------------------------- Test helpers ------------------------- */
     #[derive(Default)]
@@ -583,10 +1915,18 @@ mod tests {
     }
     #[async_trait::async_trait]
     impl contracts::RestHostModule for DummyRestHost {
-        fn rest_prepare(&self, _ctx: &ModuleCtx, router: Router) -> Result<Router, anyhow::Error> {
+        async fn rest_prepare(
+            &self,
+            _ctx: &ModuleCtx,
+            router: Router,
+        ) -> Result<Router, anyhow::Error> {
             Ok(router)
         }
-        fn rest_finalize(&self, _ctx: &ModuleCtx, router: Router) -> Result<Router, anyhow::Error> {
+        async fn rest_finalize(
+            &self,
+            _ctx: &ModuleCtx,
+            router: Router,
+        ) -> Result<Router, anyhow::Error> {
             Ok(router)
         }
         fn as_registry(&self) -> &dyn OpenApiRegistry {
@@ -598,7 +1938,7 @@ mod tests {
     struct DummyRest;
     #[async_trait::async_trait]
     impl contracts::RestfulModule for DummyRest {
-        fn register_rest(
+        async fn register_rest(
             &self,
             _ctx: &ModuleCtx,
             router: Router,
@@ -622,6 +1962,33 @@ mod tests {
         assert_eq!(order, vec!["core_a", "core_b"]);
     }
 
+    #[test]
+    fn topo_order_breaks_ties_by_name() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("zeta", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("beta", &["zeta"], Arc::new(DummyCore));
+        b.register_core_with_meta("gamma", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("alpha", &["gamma"], Arc::new(DummyCore));
+        b.register_core_with_meta("delta", &[], Arc::new(DummyCore));
+
+        let reg = b.build_topo_sorted().unwrap();
+        let order: Vec<_> = reg.modules().iter().map(|m| m.name).collect();
+        assert_eq!(order, vec!["delta", "gamma", "alpha", "zeta", "beta"]);
+    }
+
+    #[test]
+    fn remote_modules_satisfy_dependencies() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("users", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("orders", &["users"], Arc::new(DummyCore));
+        b.mark_remote("users");
+
+        let reg = b.build_topo_sorted().unwrap();
+        let order: Vec<_> = reg.modules().iter().map(|m| m.name).collect();
+        assert_eq!(order, vec!["orders"]);
+        assert_eq!(reg.remote_modules(), ["users"]);
+    }
+
     #[test]
     fn unknown_dependency_error() {
         let mut b = RegistryBuilder::default();
@@ -703,8 +2070,37 @@ mod tests {
         }
     }
 
-    #[test]
-    fn rest_requires_host_if_rest_modules_exist() {
+    #[tokio::test(start_paused = true)]
+    async fn declared_jobs_run_between_start_and_stop() {
+        let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let job: crate::scheduler::JobFn = {
+            let runs = runs.clone();
+            Arc::new(move |_| {
+                runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Box::pin(async { Ok(()) })
+            })
+        };
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("a", &[], Arc::new(DummyCore));
+        b.register_job_with_meta("a", "tick", "every 10s", None, job.clone());
+        let reg = b.build_topo_sorted().unwrap();
+
+        let cancel = CancellationToken::new();
+        reg.run_start_phase(cancel.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(25)).await;
+        reg.run_stop_phase(cancel).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("a", &[], Arc::new(DummyCore));
+        b.register_job_with_meta("a", "bad", "every now and then", None, job);
+        let err = b.build_topo_sorted().unwrap_err();
+        assert!(err.to_string().contains("job 'bad'"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn rest_requires_host_if_rest_modules_exist() {
         // Build with 1 core that has REST capability, but no rest host.
         let mut b = RegistryBuilder::default();
         b.register_core_with_meta("svc", &[], Arc::new(DummyCore));
@@ -713,12 +2109,12 @@ mod tests {
 
         let router = Router::new();
         let base_ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
-        let err = reg.run_rest_phase(&base_ctx, router).unwrap_err();
+        let err = reg.run_rest_phase(&base_ctx, router).await.unwrap_err();
         matches!(err, RegistryError::RestRequiresHost);
     }
 
-    #[test]
-    fn rest_single_host_and_provider_happy_path() {
+    #[tokio::test]
+    async fn rest_single_host_and_provider_happy_path() {
         // Build with one host and one REST provider
         let mut b = RegistryBuilder::default();
         b.register_core_with_meta("host", &[], Arc::new(DummyCore));
@@ -731,13 +2127,923 @@ mod tests {
 
         let router = Router::new();
         let base_ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
-        let router = reg.run_rest_phase(&base_ctx, router).unwrap();
+        let router = reg.run_rest_phase(&base_ctx, router).await.unwrap();
 
         // The DummyRest adds /dummy endpoint during register_rest
         // (We don't spin a server; just ensure Router returned successfully.)
         let _ = router;
     }
 
+    #[test]
+    fn optional_deps_order_when_present_and_are_ignored_when_absent() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("app", &["cache?", "metrics?"], Arc::new(DummyCore));
+        b.register_core_with_meta("cache", &[], Arc::new(DummyCore));
+
+        let reg = b.build_topo_sorted().unwrap();
+        let order: Vec<_> = reg.modules().iter().map(|m| m.name).collect();
+        assert_eq!(order, vec!["cache", "app"]);
+
+        // Disabling an optional dep is fine too.
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("app", &["cache?"], Arc::new(DummyCore));
+        b.register_core_with_meta("cache", &[], Arc::new(DummyCore));
+        b.disable("cache");
+        let reg = b.build_topo_sorted().unwrap();
+        assert_eq!(reg.modules().len(), 1);
+    }
+
+    #[test]
+    fn dependency_version_requirements_are_checked() {
+        assert_eq!(parse_dep("users_info@^1?"), ("users_info", true));
+        assert_eq!(dep_version_req("users_info@^1?"), Some("^1"));
+        assert_eq!(dep_version_req("cache?"), None);
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("users", &[], Arc::new(DummyCore));
+        b.register_version_with_meta("users", "1.4.0");
+        b.register_core_with_meta("plain", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("ok", &["users@^1", "cache@^3?"], Arc::new(DummyCore));
+        b.register_core_with_meta("new", &["users@>=2", "plain@^1"], Arc::new(DummyCore));
+        let err = b.build_topo_sorted().unwrap_err();
+        let RegistryError::IncompatibleDependencies { conflicts } = &err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(
+            conflicts,
+            &[
+                VersionConflict {
+                    module: "new",
+                    depends_on: "plain",
+                    required: "^1",
+                    found: None,
+                },
+                VersionConflict {
+                    module: "new",
+                    depends_on: "users",
+                    required: ">=2",
+                    found: Some("1.4.0"),
+                },
+            ]
+        );
+        assert!(err
+            .to_string()
+            .contains("'new' requires 'users' >=2, found 1.4.0"));
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("users", &[], Arc::new(DummyCore));
+        b.register_version_with_meta("users", "1.4.0");
+        b.register_core_with_meta("ok", &["users@~1.4"], Arc::new(DummyCore));
+        let reg = b.build_topo_sorted().unwrap();
+        assert_eq!(reg.modules()[0].version, Some("1.4.0"));
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("users", &[], Arc::new(DummyCore));
+        b.register_version_with_meta("users", "one");
+        b.register_core_with_meta("bad", &["users@not a req"], Arc::new(DummyCore));
+        let RegistryError::InvalidRegistryConfiguration { errors } =
+            b.build_topo_sorted().unwrap_err()
+        else {
+            panic!("expected configuration errors");
+        };
+        assert_eq!(errors.len(), 2, "{errors:?}");
+    }
+
+    #[test]
+    fn export_graph_renders_dot_and_json() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("host", &[], Arc::new(DummyCore));
+        b.register_rest_host_with_meta("host", Arc::new(DummyRestHost::default()));
+        b.register_core_with_meta("svc", &["host", "cache?"], Arc::new(DummyCore));
+        b.register_rest_with_meta("svc", Arc::new(DummyRest));
+        let reg = b.build_topo_sorted().unwrap();
+
+        let dot = reg.export_graph(GraphFormat::Dot);
+        assert!(dot.starts_with("digraph modules {"));
+        assert!(dot.contains("\"host\" [label=\"host\\n(rest_host)\"];"));
+        assert!(dot.contains("\"svc\" -> \"host\";"));
+        assert!(!dot.contains("cache"), "absent optional deps have no edge");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&reg.export_graph(GraphFormat::Json)).unwrap();
+        let svc = &json["modules"][1];
+        assert_eq!(svc["name"], "svc");
+        assert_eq!(svc["capabilities"], serde_json::json!(["rest"]));
+        assert_eq!(
+            svc["deps"][1],
+            serde_json::json!({ "name": "cache", "optional": true, "present": false })
+        );
+        assert_eq!("JSON".parse::<GraphFormat>().unwrap(), GraphFormat::Json);
+    }
+
+    #[test]
+    fn validate_configs_reports_every_failure() {
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Limits {
+            max: u32,
+        }
+
+        let mut b = RegistryBuilder::default();
+        for name in ["good", "bad", "missing", "unchecked"] {
+            b.register_core_with_meta(name, &[], Arc::new(DummyCore));
+        }
+        b.register_config_with_meta("good", |p| {
+            crate::context::module_config_typed::<Limits>(p, "good").map(|_| ())
+        });
+        b.register_config_with_meta("bad", |p| {
+            crate::context::module_config_typed::<Limits>(p, "bad").map(|_| ())
+        });
+        b.register_config_with_meta("missing", |p| {
+            crate::context::module_config_typed::<Limits>(p, "missing").map(|_| ())
+        });
+        let reg = b.build_topo_sorted().unwrap();
+
+        let cfg = PrefixConfig(serde_json::json!({
+            "good": { "config": { "max": 3 } },
+            "bad": { "config": { "max": "many" } },
+        }));
+        let mut failed: Vec<_> = reg
+            .validate_configs(&cfg)
+            .into_iter()
+            .map(|e| match e {
+                RegistryError::Config { module, .. } => module,
+                other => panic!("unexpected error: {other:?}"),
+            })
+            .collect();
+        failed.sort();
+        assert_eq!(failed, vec!["bad", "missing"]);
+    }
+
+    #[tokio::test]
+    async fn init_phase_rejects_all_bad_configs_up_front() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(serde::Deserialize)]
+        struct Pool {
+            size: u32,
+        }
+
+        impl crate::config_schema::ConfigSchema for Pool {
+            fn violations(&self) -> Vec<crate::config_schema::ConfigViolation> {
+                let mut out = Vec::new();
+                crate::config_schema::check_range(&mut out, "size", &self.size, Some(1.0), None);
+                out
+            }
+        }
+
+        struct CountingCore(Arc<AtomicUsize>);
+        #[async_trait::async_trait]
+        impl contracts::Module for CountingCore {
+            async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+
+        let inits = Arc::new(AtomicUsize::new(0));
+        let mut b = RegistryBuilder::default();
+        for name in ["a", "b", "c"] {
+            b.register_core_with_meta(name, &[], Arc::new(CountingCore(inits.clone())));
+        }
+        b.register_config_with_meta("a", |p| crate::config_schema::check_typed::<Pool>(p, "a"));
+        b.register_config_with_meta("b", |p| crate::config_schema::check_typed::<Pool>(p, "b"));
+        b.register_config_with_meta("c", |p| crate::config_schema::check_typed::<Pool>(p, "c"));
+        let reg = b.build_topo_sorted().unwrap();
+
+        let cfg = Arc::new(PrefixConfig(serde_json::json!({
+            "a": { "config": { "size": 0 } },
+            "b": { "config": { "size": "big" } },
+            "c": { "config": { "size": 4 } },
+        })));
+        let ctx = crate::context::ModuleCtxBuilder::new(CancellationToken::new())
+            .with_config_provider(cfg)
+            .build();
+
+        let err = reg.run_init_phase(&ctx).await.unwrap_err();
+        let RegistryError::InvalidConfigs { errors } = &err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(errors.len(), 2);
+        let msg = err.to_string();
+        assert!(
+            msg.contains("module 'a': size: must be at least 1, got 0"),
+            "{msg}"
+        );
+        assert!(msg.contains("module 'b'"), "{msg}");
+        assert_eq!(inits.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn config_update_reaches_changed_modules_only() {
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Limits {
+            max: u32,
+        }
+        #[derive(Default)]
+        struct Knobs(parking_lot::Mutex<Vec<serde_json::Value>>);
+        #[async_trait::async_trait]
+        impl contracts::ConfigReloadable for Knobs {
+            async fn on_config_update(&self, new: serde_json::Value) -> anyhow::Result<()> {
+                self.0.lock().push(new);
+                Ok(())
+            }
+        }
+
+        let knobs = Arc::new(Knobs::default());
+        let strict = Arc::new(Knobs::default());
+        let mut b = RegistryBuilder::default();
+        for name in ["knobs", "strict", "same"] {
+            b.register_core_with_meta(name, &[], Arc::new(DummyCore));
+        }
+        b.register_reloadable_with_meta("knobs", knobs.clone());
+        b.register_reloadable_with_meta("strict", strict.clone());
+        b.register_reloadable_with_meta("same", Arc::new(Knobs::default()));
+        b.register_config_with_meta("strict", |p| {
+            crate::context::module_config_typed::<Limits>(p, "strict").map(|_| ())
+        });
+        let reg = b.build_topo_sorted().unwrap();
+
+        let old = PrefixConfig(serde_json::json!({
+            "strict": { "config": { "max": 1 } },
+            "same": { "config": { "level": "info" } },
+        }));
+        let new = PrefixConfig(serde_json::json!({
+            "knobs": { "config": { "batch": 64 } },
+            "strict": { "config": { "max": "lots" } },
+            "same": { "config": { "level": "info" } },
+        }));
+        let updates = crate::context::ConfigUpdates::default();
+        let ctx = crate::context::ModuleCtxBuilder::new(CancellationToken::new())
+            .with_config_provider(Arc::new(PrefixConfig(serde_json::json!({}))))
+            .with_config_updates(Arc::new(updates))
+            .build()
+            .for_module("knobs");
+        let mut rx = ctx.raw_config_updates().unwrap();
+        assert_eq!(*rx.borrow(), serde_json::json!({}));
+
+        let updates = ctx.config_updates.as_deref().unwrap();
+        let errors = reg.apply_config_update(&old, &new, Some(updates)).await;
+        assert!(matches!(
+            errors.as_slice(),
+            [RegistryError::Config {
+                module: "strict",
+                ..
+            }]
+        ));
+        assert_eq!(*knobs.0.lock(), vec![serde_json::json!({ "batch": 64 })]);
+        assert!(strict.0.lock().is_empty());
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(*rx.borrow_and_update(), serde_json::json!({ "batch": 64 }));
+    }
+
+    #[test]
+    fn disabled_modules_are_left_out() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("core_a", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("extra", &["core_a"], Arc::new(DummyCore));
+        b.register_rest_with_meta("extra", Arc::new(DummyRest));
+        b.disable("extra");
+
+        // The REST capability went with the module, so no host is required.
+        let reg = b.build_topo_sorted().unwrap();
+        let names: Vec<_> = reg.modules().iter().map(|m| m.name).collect();
+        assert_eq!(names, vec!["core_a"]);
+    }
+
+    #[test]
+    fn module_sources_join_discovery() {
+        struct Plugged;
+        impl ModuleSource for Plugged {
+            fn register(&self, b: &mut RegistryBuilder) {
+                b.register_core_with_meta("plugged", &[], Arc::new(DummyCore));
+                b.register_core_with_meta("unplugged", &["plugged"], Arc::new(DummyCore));
+            }
+        }
+
+        let cfg = PrefixConfig(serde_json::json!({ "unplugged": { "enabled": false } }));
+        let sources: Vec<Arc<dyn ModuleSource>> = vec![Arc::new(Plugged)];
+        let reg = ModuleRegistry::discover_and_build_with_sources(&cfg, &sources).unwrap();
+        let names: Vec<_> = reg.modules().iter().map(|m| m.name).collect();
+        assert_eq!(names, vec!["plugged"]);
+    }
+
+    #[test]
+    fn disabling_a_dependency_is_rejected() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("core_a", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("core_b", &["core_a"], Arc::new(DummyCore));
+        b.disable("core_a");
+
+        let err = b.build_topo_sorted().unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::DisabledDependency { ref module, ref depends_on }
+                if module == "core_b" && depends_on == "core_a"
+        ));
+    }
+
+    #[test]
+    fn module_enabled_reads_config_flag() {
+        let cfg = PrefixConfig(serde_json::json!({
+            "off": { "enabled": false },
+            "on": { "config": {} },
+        }));
+        assert!(!crate::context::module_enabled(&cfg, "off"));
+        assert!(crate::context::module_enabled(&cfg, "on"));
+        assert!(crate::context::module_enabled(&cfg, "unknown"));
+    }
+
+    struct PrefixConfig(serde_json::Value);
+    impl crate::context::ConfigProvider for PrefixConfig {
+        fn get_module_config(&self, module_name: &str) -> Option<&serde_json::Value> {
+            self.0.get(module_name)
+        }
+    }
+
+    async fn prefixed_rest_phase(prefix: &str) -> Result<Router, RegistryError> {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("host", &[], Arc::new(DummyCore));
+        b.register_rest_host_with_meta("host", Arc::new(DummyRestHost::default()));
+        b.register_core_with_meta("svc", &[], Arc::new(DummyCore));
+        b.register_rest_with_meta("svc", Arc::new(DummyRest));
+        let reg = b.build_topo_sorted().unwrap();
+
+        let config = PrefixConfig(serde_json::json!({ "svc": { "rest_prefix": prefix } }));
+        let base_ctx = ModuleCtxBuilder::new(CancellationToken::new())
+            .with_config_provider(Arc::new(config))
+            .build();
+        reg.run_rest_phase(&base_ctx, Router::new()).await
+    }
+
+    #[tokio::test]
+    async fn rest_prefix_nests_module_routes() {
+        use tower::ServiceExt;
+
+        let router = prefixed_rest_phase("/api/svc").await.unwrap();
+        let status = |path: &'static str| {
+            let router = router.clone();
+            async move {
+                router
+                    .oneshot(
+                        axum::http::Request::get(path)
+                            .body(axum::body::Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+        assert_eq!(status("/api/svc/dummy").await, axum::http::StatusCode::OK);
+        assert_eq!(status("/dummy").await, axum::http::StatusCode::NOT_FOUND);
+    }
+
+    struct DocumentedRest;
+    #[async_trait::async_trait]
+    impl contracts::RestfulModule for DocumentedRest {
+        async fn register_rest(
+            &self,
+            _ctx: &ModuleCtx,
+            router: Router,
+            registry: &dyn OpenApiRegistry,
+        ) -> Result<Router, anyhow::Error> {
+            Ok(crate::api::OperationBuilder::<_, _, ()>::get("/shared")
+                .handler(|| async { "ok" })
+                .json_response(200, "OK")
+                .register(router, registry))
+        }
+    }
+
+    #[tokio::test]
+    async fn rest_modules_can_await_while_registering() {
+        /// Reads its route from an async source, as a DB-backed module would.
+        struct AsyncRest(tokio::sync::RwLock<&'static str>);
+        #[async_trait::async_trait]
+        impl contracts::RestfulModule for AsyncRest {
+            async fn register_rest(
+                &self,
+                _ctx: &ModuleCtx,
+                router: Router,
+                _registry: &dyn OpenApiRegistry,
+            ) -> Result<Router, anyhow::Error> {
+                let path = *self.0.read().await;
+                Ok(router.route(path, axum::routing::get(|| async { "ok" })))
+            }
+        }
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("host", &[], Arc::new(DummyCore));
+        b.register_rest_host_with_meta("host", Arc::new(DummyRestHost::default()));
+        b.register_core_with_meta("svc", &[], Arc::new(DummyCore));
+        b.register_rest_with_meta(
+            "svc",
+            Arc::new(AsyncRest(tokio::sync::RwLock::new("/loaded"))),
+        );
+        let reg = b.build_topo_sorted().unwrap();
+
+        let base_ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let router = reg.run_rest_phase(&base_ctx, Router::new()).await.unwrap();
+        let response = tower::ServiceExt::oneshot(
+            router,
+            axum::http::Request::get("/loaded")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn duplicate_routes_fail_rest_phase_in_strict_mode() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("host", &[], Arc::new(DummyCore));
+        b.register_rest_host_with_meta("host", Arc::new(DummyRestHost::default()));
+        b.register_core_with_meta("alpha", &[], Arc::new(DummyCore));
+        b.register_rest_with_meta("alpha", Arc::new(DocumentedRest));
+        b.register_core_with_meta("beta", &["alpha"], Arc::new(DummyCore));
+        b.register_rest_with_meta("beta", Arc::new(DocumentedRest));
+        let reg = b.build_topo_sorted().unwrap().with_strict_routes(true);
+
+        let base_ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let err = reg
+            .run_rest_phase(&base_ctx, Router::new())
+            .await
+            .unwrap_err();
+        match err {
+            RegistryError::DuplicateRoute {
+                method,
+                path,
+                first,
+                second,
+            } => {
+                assert_eq!((method.as_str(), path.as_str()), ("GET", "/shared"));
+                assert_eq!((first.as_str(), second.as_str()), ("alpha", "beta"));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn duplicate_routes_skip_the_conflicting_module_when_not_strict() {
+        /// Claims `/shared` like `DocumentedRest`, plus a route of its own.
+        struct ConflictingRest;
+        #[async_trait::async_trait]
+        impl contracts::RestfulModule for ConflictingRest {
+            async fn register_rest(
+                &self,
+                ctx: &ModuleCtx,
+                router: Router,
+                registry: &dyn OpenApiRegistry,
+            ) -> Result<Router, anyhow::Error> {
+                let router = DocumentedRest.register_rest(ctx, router, registry).await?;
+                Ok(crate::api::OperationBuilder::<_, _, ()>::get("/beta-only")
+                    .handler(|| async { "ok" })
+                    .json_response(200, "OK")
+                    .register(router, registry))
+            }
+        }
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("host", &[], Arc::new(DummyCore));
+        b.register_rest_host_with_meta("host", Arc::new(DummyRestHost::default()));
+        b.register_core_with_meta("alpha", &[], Arc::new(DummyCore));
+        b.register_rest_with_meta("alpha", Arc::new(DocumentedRest));
+        b.register_core_with_meta("beta", &["alpha"], Arc::new(DummyCore));
+        b.register_rest_with_meta("beta", Arc::new(ConflictingRest));
+        let reg = b.build_topo_sorted().unwrap().with_strict_routes(false);
+
+        let base_ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let router = reg.run_rest_phase(&base_ctx, Router::new()).await.unwrap();
+        let status = |path: &'static str| {
+            let router = router.clone();
+            async move {
+                tower::ServiceExt::oneshot(
+                    router,
+                    axum::http::Request::get(path)
+                        .body(axum::body::Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+                .status()
+            }
+        };
+        assert_eq!(status("/shared").await, axum::http::StatusCode::OK);
+        assert_eq!(
+            status("/beta-only").await,
+            axum::http::StatusCode::NOT_FOUND
+        );
+
+        let startup = reg.startup_report();
+        let routes = |name: &str| {
+            startup
+                .modules
+                .iter()
+                .find(|m| m.module == name)
+                .unwrap()
+                .routes
+        };
+        assert_eq!((routes("alpha"), routes("beta")), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn phases_fill_startup_and_shutdown_reports() {
+        /// Ignores cancellation, so stopping it always runs into the timeout.
+        struct Stubborn;
+        #[async_trait::async_trait]
+        impl crate::lifecycle::Runnable for Stubborn {
+            async fn run(self: Arc<Self>, _cancel: CancellationToken) -> anyhow::Result<()> {
+                std::future::pending().await
+            }
+        }
+
+        let stubborn = crate::lifecycle::WithLifecycle::new(Stubborn)
+            .with_stop_timeout(Duration::from_millis(10));
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("host", &[], Arc::new(DummyCore));
+        b.register_rest_host_with_meta("host", Arc::new(DummyRestHost::default()));
+        b.register_core_with_meta("alpha", &[], Arc::new(DummyCore));
+        b.register_rest_with_meta("alpha", Arc::new(DocumentedRest));
+        b.register_core_with_meta("worker", &["alpha"], Arc::new(DummyCore));
+        b.register_stateful_with_meta("worker", Arc::new(stubborn));
+        let reg = b.build_topo_sorted().unwrap();
+        assert!(reg.shutdown_report().is_none());
+
+        let cancel = CancellationToken::new();
+        let base_ctx = ModuleCtxBuilder::new(cancel.clone()).build();
+        reg.run_init_phase(&base_ctx).await.unwrap();
+        let _ = reg.run_rest_phase(&base_ctx, Router::new()).await.unwrap();
+        reg.run_start_phase(cancel.clone()).await.unwrap();
+
+        let startup = reg.startup_report();
+        let module = |name| startup.modules.iter().find(|m| m.module == name).unwrap();
+        assert!(startup.modules.iter().all(|m| m.init.is_some()));
+        assert_eq!(module("alpha").routes, 1);
+        assert!(module("alpha").rest.is_some());
+        assert!(module("host").rest.is_none());
+        assert!(module("worker").start.is_some());
+        assert!(serde_json::to_value(&startup)
+            .unwrap()
+            .get("profile")
+            .is_none());
+        reg.record_profile(Some("staging".into()));
+        let json = serde_json::to_value(reg.startup_report()).unwrap();
+        assert_eq!(json["profile"], "staging");
+
+        reg.run_stop_phase(cancel).await.unwrap();
+        let shutdown = reg.shutdown_report().unwrap();
+        assert_eq!((shutdown.aborted, shutdown.failed), (1, 0));
+        let json = serde_json::to_value(&shutdown).unwrap();
+        assert_eq!(json["modules"][0]["module"], "worker");
+        assert_eq!(json["modules"][0]["outcome"], "aborted");
+        assert!(json["modules"][0]["stop_ms"].as_f64().unwrap() >= 10.0);
+    }
+
+    /// Takes `delay` to wind down once cancelled, or ignores cancellation.
+    struct SlowStop {
+        delay: Option<Duration>,
+        stopped: Arc<parking_lot::Mutex<Vec<(&'static str, Duration)>>>,
+        name: &'static str,
+        origin: tokio::time::Instant,
+    }
+    #[async_trait::async_trait]
+    impl contracts::StatefulModule for SlowStop {
+        async fn start(&self, _cancel: CancellationToken) -> anyhow::Result<()> {
+            Ok(())
+        }
+        async fn stop(&self, cancel: CancellationToken) -> anyhow::Result<()> {
+            match self.delay {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => {
+                    cancel.cancelled().await;
+                    std::future::pending::<()>().await;
+                }
+            }
+            self.stopped.lock().push((self.name, self.origin.elapsed()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn all_running_follows_stateful_modules() {
+        struct Idle;
+        #[async_trait::async_trait]
+        impl crate::lifecycle::Runnable for Idle {
+            async fn run(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+                cancel.cancelled().await;
+                Ok(())
+            }
+        }
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("idle", &[], Arc::new(DummyCore));
+        b.register_stateful_with_meta("idle", Arc::new(crate::lifecycle::WithLifecycle::new(Idle)));
+        // Reports no status, so never holds readiness back
+        b.register_core_with_meta("silent", &[], Arc::new(DummyCore));
+        b.register_stateful_with_meta(
+            "silent",
+            Arc::new(SlowStop {
+                delay: Some(Duration::ZERO),
+                stopped: Arc::default(),
+                name: "silent",
+                origin: tokio::time::Instant::now(),
+            }),
+        );
+        let reg = b.build_topo_sorted().unwrap();
+        assert!(!reg.all_running());
+
+        let cancel = CancellationToken::new();
+        reg.run_start_phase(cancel.clone()).await.unwrap();
+        assert!(reg.all_running());
+        let runtime = |name: &str| {
+            reg.runtime_report()
+                .into_iter()
+                .find(|m| m.module == name)
+                .unwrap()
+        };
+        let idle = runtime("idle");
+        assert_eq!(idle.status, Some(Status::Running));
+        assert!(idle.started_at.is_some() && idle.uptime.is_some());
+        let silent = runtime("silent");
+        assert!(silent.status.is_none() && silent.uptime.is_none());
+
+        cancel.cancel();
+        reg.run_stop_phase(CancellationToken::new()).await.unwrap();
+        assert!(!reg.all_running());
+        let idle = runtime("idle");
+        assert!(idle.uptime.is_none());
+        assert!(idle.last_stop_reason.is_some());
+        assert_eq!(idle.last_transition.map(|t| t.to), Some(Status::Stopped));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stop_phase_enforces_deadlines() {
+        struct Graceful;
+        #[async_trait::async_trait]
+        impl crate::lifecycle::Runnable for Graceful {
+            async fn run(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+                cancel.cancelled().await;
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                Ok(())
+            }
+        }
+
+        let stopped = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let origin = tokio::time::Instant::now();
+        let graceful = crate::lifecycle::WithLifecycle::new(Graceful)
+            .with_stop_timeout(Duration::from_secs(5));
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("graceful", &[], Arc::new(DummyCore));
+        b.register_stateful_with_meta("graceful", Arc::new(graceful));
+        b.register_core_with_meta("stuck", &[], Arc::new(DummyCore));
+        b.register_stateful_with_meta(
+            "stuck",
+            Arc::new(SlowStop {
+                delay: None,
+                stopped: stopped.clone(),
+                name: "stuck",
+                origin,
+            }),
+        );
+        b.register_timeout_with_meta("stuck", Phase::Stop, Duration::from_secs(2));
+        let reg = b.build_topo_sorted().unwrap();
+
+        let cancel = CancellationToken::new();
+        reg.run_start_phase(cancel.clone()).await.unwrap();
+        cancel.cancel();
+        // The run's token has fired, as in the runner; deadlines still apply.
+        reg.run_stop_phase(CancellationToken::new()).await.unwrap();
+
+        let report = reg.shutdown_report().unwrap();
+        let outcome = |name| {
+            report
+                .modules
+                .iter()
+                .find(|m| m.module == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(outcome("graceful").outcome, StopOutcome::Stopped);
+        assert_eq!(outcome("stuck").outcome, StopOutcome::TimedOut);
+        assert_eq!(outcome("stuck").stop, Duration::from_secs(2) + STOP_GRACE);
+        assert_eq!(report.timed_out, 1);
+        assert!(stopped.lock().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn parallel_stop_runs_independent_modules_together() {
+        let stopped = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let origin = tokio::time::Instant::now();
+        let slow = |name| {
+            Arc::new(SlowStop {
+                delay: Some(Duration::from_secs(1)),
+                stopped: stopped.clone(),
+                name,
+                origin,
+            })
+        };
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("db", &[], Arc::new(DummyCore));
+        b.register_stateful_with_meta("db", slow("db"));
+        b.register_core_with_meta("left", &["db"], Arc::new(DummyCore));
+        b.register_stateful_with_meta("left", slow("left"));
+        b.register_core_with_meta("right", &["db"], Arc::new(DummyCore));
+        b.register_stateful_with_meta("right", slow("right"));
+        let reg = b.build_topo_sorted().unwrap().with_parallel_stop(true);
+
+        reg.run_stop_phase(CancellationToken::new()).await.unwrap();
+        let mut stopped = stopped.lock().clone();
+        stopped.sort();
+        assert_eq!(
+            stopped,
+            vec![
+                ("db", Duration::from_secs(2)),
+                ("left", Duration::from_secs(1)),
+                ("right", Duration::from_secs(1)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_rest_prefix_is_rejected() {
+        for prefix in ["api", "/api/", "/", "/api/{id}"] {
+            let err = prefixed_rest_phase(prefix).await.unwrap_err();
+            assert!(matches!(
+                err,
+                RegistryError::RestRegister { module: "svc", .. }
+            ));
+        }
+    }
+
+    /// Records how many `init` calls overlap and the order they complete in.
+    #[derive(Default)]
+    struct InitProbe {
+        active: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+        done: parking_lot::Mutex<Vec<&'static str>>,
+    }
+
+    struct SlowCore {
+        name: &'static str,
+        probe: Arc<InitProbe>,
+    }
+    #[async_trait::async_trait]
+    impl contracts::Module for SlowCore {
+        async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+            use std::sync::atomic::Ordering;
+            let now = self.probe.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.probe.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.probe.active.fetch_sub(1, Ordering::SeqCst);
+            self.probe.done.lock().push(self.name);
+            Ok(())
+        }
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    async fn run_diamond_init(parallelism: usize) -> (usize, Vec<&'static str>) {
+        let probe = Arc::new(InitProbe::default());
+        let mut b = RegistryBuilder::default();
+        let graph: [(&'static str, &'static [&'static str]); 5] = [
+            ("base", &[]),
+            ("left", &["base"]),
+            ("middle", &["base"]),
+            ("right", &["base"]),
+            ("top", &["left", "middle", "right"]),
+        ];
+        for (name, deps) in graph {
+            let core = SlowCore {
+                name,
+                probe: probe.clone(),
+            };
+            b.register_core_with_meta(name, deps, Arc::new(core));
+        }
+        let reg = b
+            .build_topo_sorted()
+            .unwrap()
+            .with_init_parallelism(parallelism);
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        reg.run_init_phase(&ctx).await.unwrap();
+
+        let done = probe.done.lock().clone();
+        (probe.peak.load(std::sync::atomic::Ordering::SeqCst), done)
+    }
+
+    #[tokio::test]
+    async fn init_phase_overlaps_independent_modules_within_bound() {
+        let (peak, done) = run_diamond_init(8).await;
+        assert_eq!(peak, 3, "left/middle/right should initialize together");
+        assert_eq!(done.first(), Some(&"base"));
+        assert_eq!(done.last(), Some(&"top"));
+
+        let (peak, _) = run_diamond_init(2).await;
+        assert_eq!(peak, 2);
+
+        let (peak, done) = run_diamond_init(1).await;
+        assert_eq!(peak, 1);
+        assert_eq!(done.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn init_phase_reports_failing_module() {
+        struct FailingCore;
+        #[async_trait::async_trait]
+        impl contracts::Module for FailingCore {
+            async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+                anyhow::bail!("boom")
+            }
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("ok", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("bad", &[], Arc::new(FailingCore));
+        let reg = b.build_topo_sorted().unwrap();
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let err = reg.run_init_phase(&ctx).await.unwrap_err();
+        assert!(matches!(err, RegistryError::Init { module: "bad", .. }));
+    }
+
+    // Paused clock: under load the 20ms init could otherwise finish before
+    // the 5ms timeout is polled.
+    #[tokio::test(start_paused = true)]
+    async fn init_phase_times_out_unless_module_overrides() {
+        let build = |override_init: bool| {
+            let probe = Arc::new(InitProbe::default());
+            let mut b = RegistryBuilder::default();
+            b.register_core_with_meta("fast", &[], Arc::new(DummyCore));
+            let core = SlowCore {
+                name: "slow",
+                probe,
+            };
+            b.register_core_with_meta("slow", &[], Arc::new(core));
+            if override_init {
+                b.register_timeout_with_meta("slow", Phase::Init, Duration::from_secs(5));
+            }
+            b.build_topo_sorted()
+                .unwrap()
+                .with_phase_timeout(Phase::Init, Duration::from_millis(5))
+        };
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+
+        let err = build(false).run_init_phase(&ctx).await.unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::PhaseTimeout {
+                module: "slow",
+                phase: Phase::Init,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "init of module 'slow' did not finish within 5ms"
+        );
+
+        build(true).run_init_phase(&ctx).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn health_snapshot_aggregates_probes() {
+        struct Probe(contracts::HealthStatus);
+        #[async_trait::async_trait]
+        impl contracts::HealthProbe for Probe {
+            async fn health(&self) -> contracts::HealthStatus {
+                self.0.clone()
+            }
+        }
+
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("a", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("b", &["a"], Arc::new(DummyCore));
+        b.register_core_with_meta("silent", &[], Arc::new(DummyCore));
+        b.register_health_with_meta("a", Arc::new(Probe(contracts::HealthStatus::Healthy)));
+        let degraded = contracts::HealthStatus::Degraded("cache offline".into());
+        b.register_health_with_meta("b", Arc::new(Probe(degraded.clone())));
+        let reg = b.build_topo_sorted().unwrap();
+
+        let snapshot = reg.health_snapshot().await;
+        let names: Vec<_> = snapshot.modules.iter().map(|(n, _)| *n).collect();
+        assert!(!names.contains(&"silent"));
+        assert_eq!(snapshot.modules.len(), 2);
+        assert_eq!(snapshot.overall(), degraded);
+        assert_eq!(snapshot.overall().reason(), Some("cache offline"));
+        assert_eq!(
+            HealthSnapshot::default().overall(),
+            contracts::HealthStatus::Healthy
+        );
+    }
+
     #[tokio::test]
     async fn phases_run_without_errors_with_empty_implementations() {
         // No REST, DB, or stateful modules; only init/start/stop with defaults.
@@ -757,4 +3063,37 @@ mod tests {
         reg.run_start_phase(cancel.child_token()).await.unwrap();
         reg.run_stop_phase(cancel.child_token()).await.unwrap();
     }
+
+    #[derive(Default)]
+    struct ScopeHolder(parking_lot::Mutex<Option<crate::context::CancelScope>>);
+
+    #[async_trait::async_trait]
+    impl contracts::Module for ScopeHolder {
+        async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+            *self.0.lock() = Some(ctx.child_scope("worker"));
+            Ok(())
+        }
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn stop_phase_cancels_child_scopes() {
+        let holder = Arc::new(ScopeHolder::default());
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("holder", &[], holder.clone());
+        let reg = b.build_topo_sorted().unwrap();
+
+        // The root token never fires; the stop phase alone tears scopes down.
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        reg.run_init_phase(&ctx).await.unwrap();
+        let scope = holder.0.lock().clone().unwrap();
+        assert_eq!(scope.name(), "holder/worker");
+        reg.run_start_phase(CancellationToken::new()).await.unwrap();
+        assert!(!scope.is_cancelled());
+
+        reg.run_stop_phase(CancellationToken::new()).await.unwrap();
+        assert!(scope.is_cancelled());
+    }
 }
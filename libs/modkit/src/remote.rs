@@ -0,0 +1,144 @@
+//! Modules running in another process.
+//!
+//! A module whose config has a `remote` section is not run locally. Its
+//! client is built from the endpoint instead and published in the
+//! [`ClientHub`], so consumers keep calling `hub.get::<dyn Contract>()`:
+//!
+//! ```yaml
+//! modules:
+//!   users_info:
+//!     remote:
+//!       url: http://users-info:8080
+//!       timeout: 5s   # optional
+//! ```
+//!
+//! The module provides the client with `#[module(client = Contract, remote = connect)]`,
+//! where `connect(&RemoteEndpoint) -> anyhow::Result<Arc<dyn Contract>>` usually
+//! adapts a client generated by `modkit-client-gen`. Dependencies on a remote
+//! module are satisfied without it being in the registry.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+
+use crate::client_hub::ClientHub;
+use crate::context::ConfigProvider;
+use crate::registry::ModuleRegistry;
+
+/// Where a remote module is served, from `modules.<name>.remote`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteEndpoint {
+    pub module: String,
+    /// Base URL of the process serving the module.
+    pub url: String,
+    /// Per-request timeout, if configured.
+    pub timeout: Option<Duration>,
+}
+
+/// Registers the remote client of one module; submitted by `#[module(remote = ...)]`.
+#[doc(hidden)]
+pub struct RemoteClient {
+    pub module: &'static str,
+    pub register: fn(&ClientHub, &RemoteEndpoint) -> anyhow::Result<()>,
+}
+
+inventory::collect!(RemoteClient);
+
+/// Whether `modules.<name>.remote` is set.
+pub fn is_remote(provider: &dyn ConfigProvider, module: &str) -> bool {
+    provider
+        .get_module_config(module)
+        .is_some_and(|raw| raw.get("remote").is_some())
+}
+
+/// The endpoint in `modules.<name>.remote`, or `None` if the module runs locally.
+pub fn remote_endpoint(
+    provider: &dyn ConfigProvider,
+    module: &str,
+) -> anyhow::Result<Option<RemoteEndpoint>> {
+    let Some(remote) = provider
+        .get_module_config(module)
+        .and_then(|raw| raw.get("remote"))
+    else {
+        return Ok(None);
+    };
+    let url = remote
+        .get("url")
+        .and_then(serde_json::Value::as_str)
+        .with_context(|| format!("modules.{module}.remote.url must be a string"))?;
+    let timeout = match remote.get("timeout") {
+        None => None,
+        Some(v) => {
+            let s = v
+                .as_str()
+                .with_context(|| format!("modules.{module}.remote.timeout must be a string"))?;
+            Some(
+                humantime::parse_duration(s)
+                    .with_context(|| format!("modules.{module}.remote.timeout '{s}'"))?,
+            )
+        }
+    };
+    Ok(Some(RemoteEndpoint {
+        module: module.to_string(),
+        url: url.to_string(),
+        timeout,
+    }))
+}
+
+/// Publish the client of every remote module of `registry` in `hub`.
+pub(crate) fn register_remote_clients(
+    hub: &ClientHub,
+    registry: &ModuleRegistry,
+    provider: &dyn ConfigProvider,
+) -> anyhow::Result<()> {
+    for &module in registry.remote_modules() {
+        let endpoint = remote_endpoint(provider, module)?
+            .with_context(|| format!("module '{module}' has no remote endpoint"))?;
+        let client = inventory::iter::<RemoteClient>
+            .into_iter()
+            .find(|c| c.module == module)
+            .with_context(|| {
+                format!("module '{module}' is configured as remote but provides no remote client")
+            })?;
+        crate::client_hub::providing_sync(module, || (client.register)(hub, &endpoint))
+            .with_context(|| format!("failed to connect remote module '{module}'"))?;
+        tracing::info!(module, url = %endpoint.url, "Using remote module");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Cfg(serde_json::Value);
+
+    impl ConfigProvider for Cfg {
+        fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+            self.0.get(module)
+        }
+    }
+
+    #[test]
+    fn reads_endpoint_from_module_config() {
+        let cfg = Cfg(serde_json::json!({
+            "users": { "remote": { "url": "http://users:8080", "timeout": "5s" } },
+            "broken": { "remote": { "timeout": "5s" } },
+            "local": { "config": {} },
+        }));
+
+        assert_eq!(
+            remote_endpoint(&cfg, "users").unwrap(),
+            Some(RemoteEndpoint {
+                module: "users".into(),
+                url: "http://users:8080".into(),
+                timeout: Some(Duration::from_secs(5)),
+            })
+        );
+        assert!(is_remote(&cfg, "users"));
+        assert!(!is_remote(&cfg, "local"));
+        assert_eq!(remote_endpoint(&cfg, "local").unwrap(), None);
+        let err = remote_endpoint(&cfg, "broken").unwrap_err();
+        assert!(err.to_string().contains("modules.broken.remote.url"));
+    }
+}
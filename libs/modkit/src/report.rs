@@ -0,0 +1,136 @@
+//! Machine-readable startup and shutdown reports.
+//!
+//! [`ModuleRegistry`](crate::ModuleRegistry) fills these in as its phases
+//! run; read them back with `startup_report()` / `shutdown_report()`. The
+//! runner logs both as JSON. Durations serialize as fractional
+//! milliseconds under `*_ms` keys.
+//!
+//! `runtime_report()` snapshots live module state.
+
+use crate::lifecycle::{Status, StopReason, TaskPanic, Transition};
+use serde::{Serialize, Serializer};
+use std::time::Duration;
+
+/// What startup cost, per module and per database connection.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StartupReport {
+    /// Config profile the process runs with (`dev`, `prod`, ...), if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// One entry per module, in topological order.
+    pub modules: Vec<ModuleStartup>,
+    /// Connections opened through the `DbManager` so far.
+    pub db_connects: Vec<DbConnect>,
+}
+
+/// Phase durations of one module; `None` when the module has no part in the phase
+/// or the phase has not run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleStartup {
+    pub module: &'static str,
+    #[serde(rename = "init_ms", serialize_with = "opt_millis")]
+    pub init: Option<Duration>,
+    #[serde(rename = "db_migrate_ms", serialize_with = "opt_millis")]
+    pub db_migrate: Option<Duration>,
+    #[serde(rename = "rest_ms", serialize_with = "opt_millis")]
+    pub rest: Option<Duration>,
+    #[serde(rename = "start_ms", serialize_with = "opt_millis")]
+    pub start: Option<Duration>,
+    /// Operations the module registered with the REST host.
+    pub routes: usize,
+}
+
+impl ModuleStartup {
+    pub(crate) fn new(module: &'static str) -> Self {
+        Self {
+            module,
+            init: None,
+            db_migrate: None,
+            rest: None,
+            start: None,
+            routes: 0,
+        }
+    }
+}
+
+/// Time taken to open one module's database connection.
+#[derive(Debug, Clone, Serialize)]
+pub struct DbConnect {
+    pub module: String,
+    #[serde(rename = "connect_ms", serialize_with = "millis")]
+    pub connect: Duration,
+}
+
+/// How the stop phase went.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ShutdownReport {
+    /// Stateful modules, in the order they were stopped.
+    pub modules: Vec<ModuleShutdown>,
+    /// Modules whose task had to be aborted after its stop timeout.
+    pub aborted: usize,
+    /// Modules whose `stop` returned an error.
+    pub failed: usize,
+    /// Modules still stopping after their deadline and grace period; left running.
+    pub timed_out: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleShutdown {
+    pub module: &'static str,
+    #[serde(rename = "stop_ms", serialize_with = "millis")]
+    pub stop: Duration,
+    #[serde(flatten)]
+    pub outcome: StopOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum StopOutcome {
+    Stopped,
+    /// The task overran its stop timeout and was aborted.
+    Aborted,
+    /// `stop` did not return in time and was abandoned.
+    TimedOut,
+    Failed {
+        error: String,
+    },
+}
+
+impl ShutdownReport {
+    pub(crate) fn push(&mut self, module: ModuleShutdown) {
+        match module.outcome {
+            StopOutcome::Stopped => {}
+            StopOutcome::Aborted => self.aborted += 1,
+            StopOutcome::TimedOut => self.timed_out += 1,
+            StopOutcome::Failed { .. } => self.failed += 1,
+        }
+        self.modules.push(module);
+    }
+}
+
+/// Live lifecycle state of one stateful module, from
+/// `ModuleRegistry::runtime_report()`. Fields the module does not track are `None`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleRuntime {
+    pub module: &'static str,
+    pub status: Option<Status>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(rename = "uptime_ms", serialize_with = "opt_millis")]
+    pub uptime: Option<Duration>,
+    pub last_transition: Option<Transition>,
+    pub last_stop_reason: Option<StopReason>,
+    pub last_error: Option<String>,
+    /// Set when the last failure was a panic.
+    pub last_panic: Option<TaskPanic>,
+}
+
+fn millis<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64(d.as_secs_f64() * 1000.0)
+}
+
+fn opt_millis<S: Serializer>(d: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+    match d {
+        Some(d) => millis(d, s),
+        None => s.serialize_none(),
+    }
+}
@@ -0,0 +1,86 @@
+//! Embedder callbacks around the runner's phases.
+//!
+//! Hooks run in registration order. A failing hook aborts the run like a
+//! failing phase does, so a `before(Phase::Init)` hook is the place for checks
+//! that must pass before any module is touched.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+
+use crate::registry::{ModuleRegistry, Phase};
+
+type Hook =
+    Arc<dyn Fn(Arc<ModuleRegistry>) -> BoxFuture<'static, anyhow::Result<()>> + Send + Sync>;
+
+/// Callbacks to run before and after each runner phase.
+#[derive(Clone, Default)]
+pub struct PhaseHooks {
+    before: Vec<(Phase, Hook)>,
+    after: Vec<(Phase, Hook)>,
+}
+
+impl PhaseHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `hook` right before `phase` starts.
+    pub fn before<F, Fut>(mut self, phase: Phase, hook: F) -> Self
+    where
+        F: Fn(Arc<ModuleRegistry>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        self.before.push((phase, boxed(hook)));
+        self
+    }
+
+    /// Run `hook` once `phase` has completed successfully.
+    pub fn after<F, Fut>(mut self, phase: Phase, hook: F) -> Self
+    where
+        F: Fn(Arc<ModuleRegistry>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        self.after.push((phase, boxed(hook)));
+        self
+    }
+
+    pub(crate) async fn run_before(
+        &self,
+        phase: Phase,
+        registry: &Arc<ModuleRegistry>,
+    ) -> anyhow::Result<()> {
+        run_matching(&self.before, phase, registry, "before").await
+    }
+
+    pub(crate) async fn run_after(
+        &self,
+        phase: Phase,
+        registry: &Arc<ModuleRegistry>,
+    ) -> anyhow::Result<()> {
+        run_matching(&self.after, phase, registry, "after").await
+    }
+}
+
+fn boxed<F, Fut>(hook: F) -> Hook
+where
+    F: Fn(Arc<ModuleRegistry>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+    Arc::new(move |registry| Box::pin(hook(registry)))
+}
+
+async fn run_matching(
+    hooks: &[(Phase, Hook)],
+    phase: Phase,
+    registry: &Arc<ModuleRegistry>,
+    when: &str,
+) -> anyhow::Result<()> {
+    for (_, hook) in hooks.iter().filter(|(p, _)| *p == phase) {
+        hook(registry.clone())
+            .await
+            .map_err(|e| e.context(format!("hook {when} {phase} failed")))?;
+    }
+    Ok(())
+}
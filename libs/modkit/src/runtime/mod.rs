@@ -1,4 +1,12 @@
+mod hooks;
+mod reload;
 mod runner;
+mod service;
 mod shutdown;
 
-pub use runner::{run, DbOptions, RunOptions, ShutdownOptions};
+pub use hooks::PhaseHooks;
+pub use reload::ConfigReload;
+pub use runner::{
+    run, run_until_rest, DbOptions, RunOptions, RunOptionsBuilder, ShutdownHandle, ShutdownOptions,
+};
+pub use service::ServiceManager;
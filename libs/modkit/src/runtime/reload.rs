@@ -0,0 +1,147 @@
+//! Live config reload for a running server.
+//!
+//! Once modules have started, the watcher re-reads the config on `SIGHUP`
+//! (Unix) or when the watched file's modification time changes, and hands
+//! the result to [`ModuleRegistry::apply_config_update`]. A config that fails
+//! to load is logged and the previous one stays in effect.
+
+use crate::context::{ConfigProvider, ConfigUpdates};
+use crate::registry::ModuleRegistry;
+use crate::secrets::Secrets;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tokio_util::sync::CancellationToken;
+
+type Loader = Arc<dyn Fn() -> anyhow::Result<Arc<dyn ConfigProvider>> + Send + Sync>;
+
+/// How to reload the config while running (see [`RunOptions::config_reload`]).
+///
+/// [`RunOptions::config_reload`]: crate::runtime::RunOptions::config_reload
+#[derive(Clone)]
+pub struct ConfigReload {
+    load: Loader,
+    watch_file: Option<PathBuf>,
+    poll_interval: Duration,
+}
+
+impl ConfigReload {
+    /// How often a watched file is checked unless overridden.
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+    /// Reload by calling `load`; triggered by `SIGHUP` until [`watch_file`](Self::watch_file) adds a file.
+    pub fn new<F>(load: F) -> Self
+    where
+        F: Fn() -> anyhow::Result<Arc<dyn ConfigProvider>> + Send + Sync + 'static,
+    {
+        Self {
+            load: Arc::new(load),
+            watch_file: None,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Also reload whenever `path` is modified.
+    pub fn watch_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.watch_file = Some(path.into());
+        self
+    }
+
+    pub fn poll_interval(mut self, every: Duration) -> Self {
+        self.poll_interval = every;
+        self
+    }
+
+    /// Resolve secret references of `modules` in every reloaded config.
+    pub(crate) fn resolving(mut self, secrets: Secrets, modules: Vec<&'static str>) -> Self {
+        let load = self.load;
+        self.load = Arc::new(move || Ok(secrets.resolve_config(load()?, &modules)?));
+        self
+    }
+}
+
+/// Spawn the watcher; it exits when `cancel` fires.
+pub(crate) fn spawn_watcher(
+    reload: ConfigReload,
+    mut current: Arc<dyn ConfigProvider>,
+    registry: Arc<ModuleRegistry>,
+    updates: Arc<ConfigUpdates>,
+    cancel: CancellationToken,
+) {
+    tokio::spawn(async move {
+        let mut hangup = hangup_signal();
+        let mut ticker = tokio::time::interval(reload.poll_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut seen = reload.watch_file.as_deref().and_then(modified);
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = next_hangup(&mut hangup) => {
+                    tracing::info!("config reload: SIGHUP received");
+                }
+                _ = ticker.tick(), if reload.watch_file.is_some() => {
+                    let now = reload.watch_file.as_deref().and_then(modified);
+                    if now == seen {
+                        continue;
+                    }
+                    seen = now;
+                    tracing::info!("config reload: file changed");
+                }
+            }
+
+            let new = match (reload.load)() {
+                Ok(new) => new,
+                Err(e) => {
+                    tracing::warn!(error = %format!("{e:#}"), "config reload: keeping previous config");
+                    continue;
+                }
+            };
+            for e in registry
+                .apply_config_update(current.as_ref(), new.as_ref(), Some(&updates))
+                .await
+            {
+                tracing::warn!(error = %format!("{e:#}"), "config reload");
+            }
+            current = new;
+        }
+    });
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(unix)]
+type Hangup = Option<tokio::signal::unix::Signal>;
+#[cfg(not(unix))]
+type Hangup = ();
+
+#[cfg(unix)]
+fn hangup_signal() -> Hangup {
+    use tokio::signal::unix::{signal, SignalKind};
+    signal(SignalKind::hangup())
+        .map_err(|e| tracing::warn!(error = %e, "config reload: cannot listen for SIGHUP"))
+        .ok()
+}
+
+#[cfg(not(unix))]
+fn hangup_signal() -> Hangup {}
+
+#[cfg(unix)]
+async fn next_hangup(hangup: &mut Hangup) {
+    if let Some(sig) = hangup {
+        if sig.recv().await.is_some() {
+            return;
+        }
+        *hangup = None;
+    }
+    std::future::pending().await
+}
+
+#[cfg(not(unix))]
+async fn next_hangup(_: &mut Hangup) {
+    std::future::pending().await
+}
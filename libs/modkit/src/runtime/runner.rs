@@ -3,17 +3,22 @@
 //! Supported DB modes:
 //!   - `DbOptions::None` — modules get no DB in their contexts.
 //!   - `DbOptions::Manager` — modules use async DB access through DbManager.
+//!   - `DbOptions::ManagerWithFallback` — DbManager for modules with their own
+//!     `database` section, one shared handle for the rest.
 //!
 //! Design notes:
 //! - We build **one stable ModuleCtx** (`base_ctx`) and reuse it across all phases
 //!   (init → db → rest → start → wait → stop). When using DbManager, modules
 //!   access databases asynchronously through the shared manager context.
 //! - Shutdown can be driven by OS signals, an external `CancellationToken`,
-//!   or an arbitrary future.
+//!   a [`ShutdownHandle`], or an arbitrary future.
 
-use crate::context::{ConfigProvider, ModuleCtxBuilder};
-use crate::runtime::shutdown;
-use std::{future::Future, pin::Pin, sync::Arc};
+use crate::context::{ConfigProvider, ConfigUpdates, ModuleCtxBuilder, Resources};
+use crate::registry::{ModuleRegistry, ModuleSource, Phase, RegistryBuilder};
+use crate::runtime::service::{ServiceManager, ServiceNotifier};
+use crate::runtime::{reload, shutdown, ConfigReload, PhaseHooks};
+use crate::secrets::{SecretProvider, Secrets};
+use std::{future::Future, path::PathBuf, pin::Pin, sync::Arc};
 use tokio_util::sync::CancellationToken;
 
 /// How the runtime should provide DBs to modules.
@@ -22,6 +27,25 @@ pub enum DbOptions {
     None,
     /// Use a DbManager to handle database connections with Figment-based configuration.
     Manager(Arc<modkit_db::DbManager>),
+    /// Like `Manager`, but modules without their own `database` section share
+    /// `fallback` (also what `ModuleCtx::db()` returns), easing the move from
+    /// one global database to per-module ones.
+    ManagerWithFallback {
+        manager: Arc<modkit_db::DbManager>,
+        fallback: Arc<modkit_db::DbHandle>,
+    },
+}
+
+impl DbOptions {
+    /// The DbManager behind these options, if any.
+    pub fn manager(&self) -> Option<&Arc<modkit_db::DbManager>> {
+        match self {
+            DbOptions::None => None,
+            DbOptions::Manager(manager) | DbOptions::ManagerWithFallback { manager, .. } => {
+                Some(manager)
+            }
+        }
+    }
 }
 
 /// How the runtime should decide when to stop.
@@ -32,6 +56,50 @@ pub enum ShutdownOptions {
     Token(CancellationToken),
     /// An arbitrary future; when it completes, we initiate shutdown.
     Future(Pin<Box<dyn Future<Output = ()> + Send>>),
+    /// A trigger owned by the embedder: cancelling it starts graceful shutdown.
+    /// Unlike `Token`, modules run on the runtime's own token, so the runtime
+    /// never cancels the embedder's.
+    External(CancellationToken),
+}
+
+/// Triggers graceful shutdown of a [`run`] from the outside, e.g. from an
+/// embedding application or a test.
+///
+/// ```ignore
+/// let handle = ShutdownHandle::new();
+/// let server = tokio::spawn(run(RunOptions { shutdown: handle.options(), ..opts }));
+/// // ...
+/// handle.shutdown();
+/// server.await??;
+/// ```
+#[derive(Clone, Default)]
+pub struct ShutdownHandle {
+    trigger: CancellationToken,
+}
+
+impl ShutdownHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shutdown options that make `run` stop when this handle fires.
+    pub fn options(&self) -> ShutdownOptions {
+        ShutdownOptions::External(self.trigger.clone())
+    }
+
+    /// Request graceful shutdown; repeated calls are no-ops.
+    pub fn shutdown(&self) {
+        self.trigger.cancel();
+    }
+
+    pub fn is_shutdown_requested(&self) -> bool {
+        self.trigger.is_cancelled()
+    }
+
+    /// Resolves once shutdown has been requested.
+    pub async fn requested(&self) {
+        self.trigger.cancelled().await;
+    }
 }
 
 /// Options for running the ModKit runner.
@@ -42,12 +110,155 @@ pub struct RunOptions {
     pub db: DbOptions,
     /// Shutdown strategy.
     pub shutdown: ShutdownOptions,
+    /// Embedder callbacks around each phase (`PhaseHooks::default()` for none).
+    pub hooks: PhaseHooks,
+    /// Re-read the config while running and deliver changed module sections
+    /// (`None` to keep the startup config for the whole run).
+    pub config_reload: Option<ConfigReload>,
+    /// Modules registered at startup alongside the compiled-in ones (e.g. plugins).
+    pub module_sources: Vec<Arc<dyn ModuleSource>>,
+    /// Run these modules instead of every compiled-in one (`None` discovers them
+    /// through inventory). `module_sources` and `enabled = false` still apply.
+    pub registry: Option<RegistryBuilder>,
+    /// Service manager to report readiness and shutdown to (systemd, Windows SCM).
+    pub service: ServiceManager,
+    /// Hub the modules publish and look up clients in (`None` for a fresh one).
+    /// Tests pass a hub with overrides to swap single clients for mocks.
+    pub client_hub: Option<Arc<crate::client_hub::ClientHub>>,
+    /// Shared infrastructure every module can fetch with `ctx.resource::<T>()`.
+    pub resources: Resources,
+    /// Providers for `${secret:<provider>:<key>}` references in module configs.
+    pub secrets: Secrets,
+    /// Base of the per-module data directories (`ctx.data_dir()`); `None`
+    /// falls back to the home dir of the `DbManager`, if any.
+    pub home_dir: Option<PathBuf>,
+    /// Config profile the embedder loaded, recorded in the startup report.
+    pub profile: Option<String>,
+}
+
+impl RunOptions {
+    /// Options with no config, no DB, OS-signal shutdown and the compiled-in modules.
+    pub fn builder() -> RunOptionsBuilder {
+        RunOptionsBuilder {
+            opts: RunOptions {
+                modules_cfg: Arc::new(NoConfig),
+                db: DbOptions::None,
+                shutdown: ShutdownOptions::Signals,
+                hooks: PhaseHooks::default(),
+                config_reload: None,
+                module_sources: Vec::new(),
+                registry: None,
+                service: ServiceManager::None,
+                client_hub: None,
+                resources: Resources::default(),
+                secrets: Secrets::default(),
+                home_dir: None,
+                profile: None,
+            },
+        }
+    }
 }
 
-/// Full cycle: init → db → rest (sync) → start → wait → stop.
+/// Fluent construction of [`RunOptions`]; see [`RunOptions::builder`].
+pub struct RunOptionsBuilder {
+    opts: RunOptions,
+}
+
+impl RunOptionsBuilder {
+    pub fn modules_cfg(mut self, cfg: Arc<dyn ConfigProvider>) -> Self {
+        self.opts.modules_cfg = cfg;
+        self
+    }
+
+    pub fn db(mut self, db: DbOptions) -> Self {
+        self.opts.db = db;
+        self
+    }
+
+    pub fn shutdown(mut self, shutdown: ShutdownOptions) -> Self {
+        self.opts.shutdown = shutdown;
+        self
+    }
+
+    pub fn hooks(mut self, hooks: PhaseHooks) -> Self {
+        self.opts.hooks = hooks;
+        self
+    }
+
+    pub fn config_reload(mut self, reload: ConfigReload) -> Self {
+        self.opts.config_reload = Some(reload);
+        self
+    }
+
+    pub fn module_source(mut self, source: Arc<dyn ModuleSource>) -> Self {
+        self.opts.module_sources.push(source);
+        self
+    }
+
+    /// Run the modules in `b` instead of discovering the compiled-in ones.
+    pub fn registry(mut self, b: RegistryBuilder) -> Self {
+        self.opts.registry = Some(b);
+        self
+    }
+
+    pub fn client_hub(mut self, hub: Arc<crate::client_hub::ClientHub>) -> Self {
+        self.opts.client_hub = Some(hub);
+        self
+    }
+
+    /// Share `resource` with every module as a `T` (`ctx.resource::<T>()`).
+    pub fn resource<T>(mut self, resource: Arc<T>) -> Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.opts.resources.insert(resource);
+        self
+    }
+
+    /// Resolve `${secret:<name>:<key>}` references with `provider`.
+    pub fn secret_provider(mut self, name: &str, provider: Arc<dyn SecretProvider>) -> Self {
+        self.opts.secrets.insert(name, provider);
+        self
+    }
+
+    pub fn home_dir(mut self, home_dir: impl Into<PathBuf>) -> Self {
+        self.opts.home_dir = Some(home_dir.into());
+        self
+    }
+
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.opts.profile = Some(profile.into());
+        self
+    }
+
+    /// Run only the named compiled-in modules ([`RegistryBuilder::discover_only`]).
+    pub fn modules(self, names: &[&str]) -> Self {
+        self.registry(RegistryBuilder::discover_only(names))
+    }
+
+    pub fn service(mut self, service: ServiceManager) -> Self {
+        self.opts.service = service;
+        self
+    }
+
+    pub fn build(self) -> RunOptions {
+        self.opts
+    }
+}
+
+/// Config provider with no module sections.
+struct NoConfig;
+
+impl ConfigProvider for NoConfig {
+    fn get_module_config(&self, _module_name: &str) -> Option<&serde_json::Value> {
+        None
+    }
+}
+
+/// Full cycle: init → db → rest → start → wait → stop.
 pub async fn run(opts: RunOptions) -> anyhow::Result<()> {
     // Stable components shared across all phases.
-    let hub = Arc::new(crate::client_hub::ClientHub::default());
+    let hub = opts.client_hub.clone().unwrap_or_default();
     let cancel = match &opts.shutdown {
         ShutdownOptions::Token(t) => t.clone(),
         _ => CancellationToken::new(),
@@ -82,43 +293,186 @@ pub async fn run(opts: RunOptions) -> anyhow::Result<()> {
                 c.cancel();
             });
         }
+        ShutdownOptions::External(trigger) => {
+            let c = cancel.clone();
+            tokio::spawn(async move {
+                trigger.cancelled().await;
+                tracing::info!("shutdown: external trigger fired");
+                c.cancel();
+            });
+        }
         ShutdownOptions::Token(_) => {
             // External owner controls lifecycle; nothing to spawn.
             tracing::info!("shutdown: external token will control lifecycle");
         }
     }
 
-    // Discover modules upfront.
-    let registry = crate::registry::ModuleRegistry::discover_and_build()?;
+    // Before the phases, so a Windows service can be stopped while starting.
+    let service = ServiceNotifier::attach(opts.service, &cancel).await?;
+
+    // Discover modules and run init → db → rest against ONE stable base context.
+    let hooks = opts.hooks;
+    let updates = Arc::new(ConfigUpdates::default());
+    let registry = build_registry(
+        opts.registry,
+        opts.modules_cfg.as_ref(),
+        &opts.module_sources,
+    )?;
+    let config_modules = config_modules(&registry);
+    let modules_cfg = opts
+        .secrets
+        .resolve_config(opts.modules_cfg, &config_modules)?;
+    let mut base = ModuleCtxBuilder::new(cancel.clone())
+        .with_client_hub(hub)
+        .with_resources(opts.resources);
+    if let Some(home_dir) = opts.home_dir {
+        base = base.with_home_dir(home_dir);
+    }
+    let registry = prepare_registry(
+        base,
+        modules_cfg.clone(),
+        registry,
+        &opts.db,
+        &hooks,
+        Some(updates.clone()),
+    )
+    .await?;
+
+    // START phase
+    tracing::info!("Phase: start");
+    hooks.run_before(Phase::Start, &registry).await?;
+    registry.run_start_phase(cancel.clone()).await?;
+    hooks.run_after(Phase::Start, &registry).await?;
+    if let Some(manager) = opts.db.manager() {
+        registry.record_db_connects(manager.connect_times());
+    }
+    registry.record_profile(opts.profile);
+    log_report("Startup report", &registry.startup_report());
+    service.ready();
+
+    if let Some(config_reload) = opts.config_reload {
+        reload::spawn_watcher(
+            config_reload.resolving(opts.secrets, config_modules),
+            modules_cfg,
+            registry.clone(),
+            updates,
+            cancel.clone(),
+        );
+    }
+
+    // WAIT
+    cancel.cancelled().await;
+    service.stopping();
+
+    // STOP phase
+    tracing::info!("Phase: stop");
+    hooks.run_before(Phase::Stop, &registry).await?;
+    // `cancel` has fired by now; modules get their own stop deadlines instead.
+    registry.run_stop_phase(CancellationToken::new()).await?;
+    hooks.run_after(Phase::Stop, &registry).await?;
+    if let Some(report) = registry.shutdown_report() {
+        log_report("Shutdown report", &report);
+    }
+    Ok(())
+}
+
+fn log_report(message: &str, report: &impl serde::Serialize) {
+    match serde_json::to_string(report) {
+        Ok(json) => tracing::info!(report = %json, "{message}"),
+        Err(e) => tracing::warn!(error = %e, "{message} could not be serialized"),
+    }
+}
+
+/// Boot modules through the REST phase (init → db → rest) without starting them.
+///
+/// Nothing is bound or spawned, so tooling can inspect the composed registry,
+/// e.g. to export the OpenAPI document.
+pub async fn run_until_rest(
+    modules_cfg: Arc<dyn ConfigProvider>,
+    module_sources: &[Arc<dyn ModuleSource>],
+    db: DbOptions,
+) -> anyhow::Result<Arc<ModuleRegistry>> {
+    let hooks = PhaseHooks::default();
+    let registry = build_registry(None, modules_cfg.as_ref(), module_sources)?;
+    let modules_cfg = Secrets::default().resolve_config(modules_cfg, &config_modules(&registry))?;
+    prepare_registry(
+        ModuleCtxBuilder::new(CancellationToken::new()),
+        modules_cfg,
+        registry,
+        &db,
+        &hooks,
+        None,
+    )
+    .await
+}
+
+/// Modules whose config sections are read: the local ones and the remote ones.
+fn config_modules(registry: &ModuleRegistry) -> Vec<&'static str> {
+    registry
+        .modules()
+        .iter()
+        .map(|m| m.name)
+        .chain(registry.remote_modules().iter().copied())
+        .collect()
+}
+
+/// Discover modules upfront (unless given), leaving out those disabled in config.
+fn build_registry(
+    modules: Option<RegistryBuilder>,
+    modules_cfg: &dyn ConfigProvider,
+    module_sources: &[Arc<dyn ModuleSource>],
+) -> anyhow::Result<Arc<ModuleRegistry>> {
+    let registry = match modules {
+        Some(b) => ModuleRegistry::build_with_sources(b, modules_cfg, module_sources)?,
+        None => ModuleRegistry::discover_and_build_with_sources(modules_cfg, module_sources)?,
+    };
+    Ok(Arc::new(registry))
+}
 
+/// `base` carries the cancellation token, client hub and resources of the run.
+async fn prepare_registry(
+    base: ModuleCtxBuilder,
+    modules_cfg: Arc<dyn ConfigProvider>,
+    registry: Arc<ModuleRegistry>,
+    db: &DbOptions,
+    hooks: &PhaseHooks,
+    updates: Option<Arc<ConfigUpdates>>,
+) -> anyhow::Result<Arc<ModuleRegistry>> {
     // Build ONE stable base context used across all phases.
-    let mut ctx_builder = ModuleCtxBuilder::new(cancel.clone())
-        .with_client_hub(hub.clone())
-        .with_config_provider(opts.modules_cfg.clone());
+    let mut ctx_builder = base
+        .with_config_provider(modules_cfg.clone())
+        .with_registry(registry.clone());
 
     // Add DbManager if using the new approach
-    if let DbOptions::Manager(ref manager) = opts.db {
+    if let Some(manager) = db.manager() {
         ctx_builder = ctx_builder.with_db_manager(manager.clone());
     }
+    if let DbOptions::ManagerWithFallback { fallback, .. } = db {
+        ctx_builder = ctx_builder.with_db(fallback.clone());
+    }
+    if let Some(updates) = updates {
+        ctx_builder = ctx_builder.with_config_updates(updates);
+    }
 
     let base_ctx = ctx_builder.build();
 
+    // Clients of modules served by another process go in before any init.
+    crate::remote::register_remote_clients(
+        &base_ctx.client_hub(),
+        &registry,
+        modules_cfg.as_ref(),
+    )?;
+
     // INIT phase
     tracing::info!("Phase: init");
-    match &opts.db {
-        DbOptions::Manager(_) => {
-            // DbManager: modules use async DB access through the manager.
-            registry.run_init_phase(&base_ctx).await?;
-        }
-        DbOptions::None => {
-            // No DB at all — just run init with the shared base context.
-            registry.run_init_phase(&base_ctx).await?;
-        }
-    }
+    hooks.run_before(Phase::Init, &registry).await?;
+    registry.run_init_phase(&base_ctx).await?;
+    hooks.run_after(Phase::Init, &registry).await?;
 
     // DB MIGRATION phase
-    match &opts.db {
-        DbOptions::Manager(_) => {
+    hooks.run_before(Phase::DbMigrate, &registry).await?;
+    match db {
+        DbOptions::Manager(_) | DbOptions::ManagerWithFallback { .. } => {
             tracing::info!("Phase: db (manager)");
             // DbManager approach: modules will handle their own DB migration
             // during their lifecycle using async DB access
@@ -128,22 +482,20 @@ pub async fn run(opts: RunOptions) -> anyhow::Result<()> {
             // No DB — nothing to migrate.
         }
     }
+    hooks.run_after(Phase::DbMigrate, &registry).await?;
 
-    // REST phase (synchronous router composition against ingress).
-    tracing::info!("Phase: rest (sync)");
-    let _ = registry.run_rest_phase(&base_ctx, axum::Router::new())?;
-
-    // START phase
-    tracing::info!("Phase: start");
-    registry.run_start_phase(cancel.clone()).await?;
-
-    // WAIT
-    cancel.cancelled().await;
+    // REST phase (router composition against ingress).
+    tracing::info!("Phase: rest");
+    hooks.run_before(Phase::Rest, &registry).await?;
+    let _ = registry
+        .run_rest_phase(&base_ctx, axum::Router::new())
+        .await?;
+    hooks.run_after(Phase::Rest, &registry).await?;
+    if let Some(manager) = db.manager() {
+        registry.record_db_connects(manager.connect_times());
+    }
 
-    // STOP phase
-    tracing::info!("Phase: stop");
-    registry.run_stop_phase(cancel).await?;
-    Ok(())
+    Ok(registry)
 }
 
 #[cfg(feature = "hs-runtime")]
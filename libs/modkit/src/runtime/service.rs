@@ -0,0 +1,349 @@
+//! Service manager integration for [`run`](super::run), selected with
+//! `RunOptions::service`.
+//!
+//! - **systemd** (`Type=notify` units): `READY=1` once the start phase is done,
+//!   `STOPPING=1` when shutdown begins, and `WATCHDOG=1` every half
+//!   `WatchdogSec` from then on until `run` returns. Outside systemd
+//!   (no `NOTIFY_SOCKET`) nothing is sent.
+//! - **Windows services**: `run` connects to the Service Control Manager,
+//!   reports start pending → running → stop pending → stopped, and turns the
+//!   Stop and Shutdown controls into graceful shutdown. The process must have
+//!   been started by the SCM under the given service name.
+
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// The service manager `run` reports its state to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ServiceManager {
+    /// No integration.
+    #[default]
+    None,
+    /// systemd readiness, stopping and watchdog notifications (Unix only).
+    Systemd,
+    /// Windows service registered as `name` (Windows only).
+    Windows { name: String },
+}
+
+impl ServiceManager {
+    /// `Systemd` when started by systemd with a notify socket, else `None`.
+    /// Windows services cannot be detected and must be selected explicitly.
+    pub fn detect() -> Self {
+        if cfg!(unix) && std::env::var_os("NOTIFY_SOCKET").is_some() {
+            ServiceManager::Systemd
+        } else {
+            ServiceManager::None
+        }
+    }
+}
+
+/// Reports the milestones of one `run` to the selected service manager.
+/// Dropping it marks the service as stopped.
+pub(crate) struct ServiceNotifier {
+    inner: Inner,
+    /// Stops the systemd watchdog pings.
+    done: CancellationToken,
+}
+
+enum Inner {
+    None,
+    /// The notify socket, read from `NOTIFY_SOCKET` when attaching.
+    #[cfg(unix)]
+    Systemd(std::path::PathBuf),
+    #[cfg(windows)]
+    Windows(windows::Session),
+}
+
+impl ServiceNotifier {
+    /// Connect to the service manager. For Windows services this registers the
+    /// control handler, which cancels `cancel` on Stop/Shutdown.
+    pub(crate) async fn attach(
+        manager: ServiceManager,
+        #[cfg_attr(not(windows), allow(unused_variables))] cancel: &CancellationToken,
+    ) -> anyhow::Result<Self> {
+        let inner = match manager {
+            ServiceManager::None => Inner::None,
+            #[cfg(unix)]
+            ServiceManager::Systemd => match std::env::var_os("NOTIFY_SOCKET") {
+                Some(socket) => Inner::Systemd(socket.into()),
+                None => Inner::None,
+            },
+            #[cfg(windows)]
+            ServiceManager::Windows { name } => {
+                Inner::Windows(windows::Session::connect(name, cancel.clone()).await?)
+            }
+            #[allow(unreachable_patterns)]
+            other => anyhow::bail!("service manager {other:?} is not supported on this platform"),
+        };
+        Ok(Self::new(inner))
+    }
+
+    fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            done: CancellationToken::new(),
+        }
+    }
+
+    /// All modules started.
+    pub(crate) fn ready(&self) {
+        match &self.inner {
+            Inner::None => {}
+            #[cfg(unix)]
+            Inner::Systemd(socket) => {
+                systemd_notify(socket, sd_notify::NotifyState::Ready);
+                let mut usec = 0;
+                if sd_notify::watchdog_enabled(false, &mut usec) {
+                    spawn_watchdog(
+                        socket.clone(),
+                        Duration::from_micros(usec) / 2,
+                        self.done.clone(),
+                    );
+                }
+            }
+            #[cfg(windows)]
+            Inner::Windows(session) => session.ready(),
+        }
+    }
+
+    /// Shutdown has begun.
+    pub(crate) fn stopping(&self) {
+        match &self.inner {
+            Inner::None => {}
+            #[cfg(unix)]
+            Inner::Systemd(socket) => systemd_notify(socket, sd_notify::NotifyState::Stopping),
+            #[cfg(windows)]
+            Inner::Windows(session) => session.stopping(),
+        }
+    }
+}
+
+impl Drop for ServiceNotifier {
+    fn drop(&mut self) {
+        self.done.cancel();
+    }
+}
+
+#[cfg(unix)]
+fn systemd_notify(socket: &std::path::Path, state: sd_notify::NotifyState<'_>) {
+    let message = format!("{state}\n");
+    let sent = std::os::unix::net::UnixDatagram::unbound()
+        .and_then(|sock| sock.send_to(message.as_bytes(), socket));
+    if let Err(e) = sent {
+        tracing::warn!(error = %e, "systemd notification failed");
+    }
+}
+
+#[cfg(unix)]
+fn spawn_watchdog(socket: std::path::PathBuf, period: Duration, done: CancellationToken) {
+    tracing::info!(?period, "systemd watchdog enabled");
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(period);
+        loop {
+            tokio::select! {
+                _ = done.cancelled() => break,
+                _ = ticks.tick() => systemd_notify(&socket, sd_notify::NotifyState::Watchdog),
+            }
+        }
+    });
+}
+
+#[cfg(windows)]
+mod windows {
+    use super::*;
+    use std::ffi::OsString;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{mpsc, Mutex};
+    use windows_service::service::{
+        ServiceControl, ServiceControlAccept, ServiceExitCode, ServiceState, ServiceStatus,
+        ServiceType,
+    };
+    use windows_service::service_control_handler::{
+        self, ServiceControlHandlerResult, ServiceStatusHandle,
+    };
+    use windows_service::service_dispatcher;
+
+    /// How long the SCM waits for the next update while starting or stopping.
+    const WAIT_HINT: Duration = Duration::from_secs(30);
+
+    type Registered =
+        tokio::sync::mpsc::UnboundedSender<windows_service::Result<ServiceStatusHandle>>;
+
+    /// Handed to `service_main`, which the SCM calls on a thread of its own.
+    struct Pending {
+        name: String,
+        cancel: CancellationToken,
+        registered: Registered,
+        stopped: mpsc::Receiver<()>,
+    }
+
+    static PENDING: Mutex<Option<Pending>> = Mutex::new(None);
+
+    windows_service::define_windows_service!(ffi_service_main, service_main);
+
+    fn service_main(_args: Vec<OsString>) {
+        let Some(pending) = PENDING.lock().unwrap_or_else(|e| e.into_inner()).take() else {
+            return;
+        };
+        let cancel = pending.cancel;
+        let handler = move |control: ServiceControl| match control {
+            ServiceControl::Stop | ServiceControl::Shutdown => {
+                tracing::info!("shutdown: service stop requested");
+                cancel.cancel();
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            _ => ServiceControlHandlerResult::NotImplemented,
+        };
+        let registered = service_control_handler::register(&pending.name, handler);
+        let ok = registered.is_ok();
+        let _ = pending.registered.send(registered);
+        if ok {
+            // The SCM expects `service_main` to return once the service has stopped.
+            let _ = pending.stopped.recv();
+        }
+    }
+
+    pub(super) struct Session {
+        status: ServiceStatusHandle,
+        stopped: mpsc::Sender<()>,
+        stopping: AtomicBool,
+    }
+
+    impl Session {
+        pub(super) async fn connect(
+            name: String,
+            cancel: CancellationToken,
+        ) -> anyhow::Result<Self> {
+            let (registered_tx, mut registered_rx) = tokio::sync::mpsc::unbounded_channel();
+            let (stopped_tx, stopped_rx) = mpsc::channel();
+            *PENDING.lock().unwrap_or_else(|e| e.into_inner()) = Some(Pending {
+                name: name.clone(),
+                cancel,
+                registered: registered_tx.clone(),
+                stopped: stopped_rx,
+            });
+
+            // Blocks until the service stops; fails right away outside the SCM.
+            let dispatcher_name = name.clone();
+            std::thread::Builder::new()
+                .name("service-dispatcher".into())
+                .spawn(move || {
+                    if let Err(e) = service_dispatcher::start(&dispatcher_name, ffi_service_main) {
+                        PENDING.lock().unwrap_or_else(|e| e.into_inner()).take();
+                        let _ = registered_tx.send(Err(e));
+                    }
+                })?;
+
+            let status = match registered_rx.recv().await {
+                Some(Ok(status)) => status,
+                Some(Err(e)) => anyhow::bail!("Windows service '{name}': {e}"),
+                None => anyhow::bail!("Windows service '{name}': dispatcher exited"),
+            };
+            let session = Self {
+                status,
+                stopped: stopped_tx,
+                stopping: AtomicBool::new(false),
+            };
+            session.report(ServiceState::StartPending, ServiceExitCode::NO_ERROR);
+            Ok(session)
+        }
+
+        pub(super) fn ready(&self) {
+            self.report(ServiceState::Running, ServiceExitCode::NO_ERROR);
+        }
+
+        pub(super) fn stopping(&self) {
+            self.stopping.store(true, Ordering::Release);
+            self.report(ServiceState::StopPending, ServiceExitCode::NO_ERROR);
+        }
+
+        fn report(&self, state: ServiceState, exit_code: ServiceExitCode) {
+            let pending = matches!(
+                state,
+                ServiceState::StartPending | ServiceState::StopPending
+            );
+            let status = ServiceStatus {
+                service_type: ServiceType::OWN_PROCESS,
+                current_state: state,
+                controls_accepted: if state == ServiceState::Running {
+                    ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN
+                } else {
+                    ServiceControlAccept::empty()
+                },
+                exit_code,
+                checkpoint: u32::from(pending),
+                wait_hint: if pending { WAIT_HINT } else { Duration::ZERO },
+                process_id: None,
+            };
+            if let Err(e) = self.status.set_service_status(status) {
+                tracing::warn!(error = %e, ?state, "Windows service status update failed");
+            }
+        }
+    }
+
+    impl Drop for Session {
+        fn drop(&mut self) {
+            // Stopped without a shutdown request means startup failed.
+            let exit_code = if self.stopping.load(Ordering::Acquire) {
+                ServiceExitCode::NO_ERROR
+            } else {
+                ServiceExitCode::ServiceSpecific(1)
+            };
+            self.report(ServiceState::Stopped, exit_code);
+            let _ = self.stopped.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn none_is_a_no_op() {
+        let cancel = CancellationToken::new();
+        let notifier = ServiceNotifier::attach(ServiceManager::None, &cancel)
+            .await
+            .unwrap();
+        notifier.ready();
+        notifier.stopping();
+        drop(notifier);
+        assert!(!cancel.is_cancelled());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn systemd_receives_ready_and_stopping() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let socket = std::os::unix::net::UnixDatagram::bind(&path).unwrap();
+        socket
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+
+        let notifier = ServiceNotifier::new(Inner::Systemd(path));
+        let mut buf = [0u8; 64];
+        notifier.ready();
+        let n = socket.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1\n");
+        notifier.stopping();
+        let n = socket.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"STOPPING=1\n");
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn windows_service_is_rejected_elsewhere() {
+        let err = ServiceNotifier::attach(
+            ServiceManager::Windows {
+                name: "hyperspot".into(),
+            },
+            &CancellationToken::new(),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("not supported"), "{err}");
+    }
+}
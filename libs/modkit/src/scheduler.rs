@@ -0,0 +1,335 @@
+//! Periodic background jobs.
+//!
+//! A job is declared with a [`JobSpec`] — `"every 5m"` or a cron expression
+//! with a leading seconds field (`"0 */15 * * * *"`) — either in
+//! `#[module(jobs(...))]` or at runtime through `ModuleCtx::schedule`.
+//! Each job runs on its own task until its cancellation token fires:
+//!
+//! - a tick that arrives while the previous run is still going is skipped,
+//!   so runs of one job never overlap;
+//! - an optional jitter delays every run by a random amount up to the limit,
+//!   so replicas do not fire in lockstep;
+//! - runs, failures, skips and durations are recorded in the module's
+//!   [`Metrics`](crate::telemetry::Metrics) under a `job` label.
+
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// The body of a job; receives the job's cancellation token.
+pub type JobFn =
+    Arc<dyn Fn(CancellationToken) -> BoxFuture<'static, anyhow::Result<()>> + Send + Sync>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobSpecError {
+    #[error("invalid interval '{spec}': {message}")]
+    Interval { spec: String, message: String },
+    #[error("invalid cron expression '{spec}': {message}")]
+    Cron { spec: String, message: String },
+}
+
+#[derive(Clone, Debug)]
+enum Schedule {
+    Every(Duration),
+    Cron(Box<cron::Schedule>),
+}
+
+/// When a job runs.
+#[derive(Clone, Debug)]
+pub struct JobSpec {
+    schedule: Schedule,
+    jitter: Duration,
+}
+
+impl JobSpec {
+    /// Run every `period`, first after one full period.
+    pub fn every(period: Duration) -> Self {
+        Self {
+            schedule: Schedule::Every(period),
+            jitter: Duration::ZERO,
+        }
+    }
+
+    /// Run on a cron schedule (UTC, seconds field first).
+    pub fn cron(expr: &str) -> Result<Self, JobSpecError> {
+        let schedule = cron::Schedule::from_str(expr).map_err(|e| JobSpecError::Cron {
+            spec: expr.to_string(),
+            message: e.to_string(),
+        })?;
+        Ok(Self {
+            schedule: Schedule::Cron(Box::new(schedule)),
+            jitter: Duration::ZERO,
+        })
+    }
+
+    /// Delay each run by a random amount below `max`.
+    pub fn with_jitter(mut self, max: Duration) -> Self {
+        self.jitter = max;
+        self
+    }
+
+    /// Time from now until the next run, jitter included; `None` when a cron
+    /// schedule has no future occurrence.
+    fn next_delay(&self) -> Option<Duration> {
+        let base = match &self.schedule {
+            Schedule::Every(period) => *period,
+            Schedule::Cron(schedule) => {
+                let next = schedule.upcoming(chrono::Utc).next()?;
+                (next - chrono::Utc::now()).to_std().unwrap_or_default()
+            }
+        };
+        Some(base + random_below(self.jitter))
+    }
+}
+
+impl FromStr for JobSpec {
+    type Err = JobSpecError;
+
+    /// `"every <duration>"` (humantime syntax, e.g. `every 1h 30m`) or a cron expression.
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let spec = spec.trim();
+        match spec.strip_prefix("every ") {
+            Some(period) => {
+                let interval_err = |message: String| JobSpecError::Interval {
+                    spec: spec.to_string(),
+                    message,
+                };
+                let period = humantime::parse_duration(period.trim())
+                    .map_err(|e| interval_err(e.to_string()))?;
+                if period.is_zero() {
+                    return Err(interval_err("period must be positive".into()));
+                }
+                Ok(Self::every(period))
+            }
+            None => Self::cron(spec),
+        }
+    }
+}
+
+/// A named job with its schedule, as declared by a module.
+#[derive(Clone)]
+pub struct Job {
+    pub name: String,
+    pub spec: JobSpec,
+    pub run: JobFn,
+}
+
+impl Job {
+    pub fn new<F, Fut>(name: impl Into<String>, spec: JobSpec, f: F) -> Self
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            spec,
+            run: Arc::new(move |cancel| Box::pin(f(cancel))),
+        }
+    }
+}
+
+impl std::fmt::Debug for Job {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Job")
+            .field("name", &self.name)
+            .field("spec", &self.spec)
+            .finish()
+    }
+}
+
+struct JobMetrics {
+    runs: prometheus::IntCounterVec,
+    skipped: prometheus::IntCounterVec,
+    duration: prometheus::HistogramVec,
+}
+
+impl JobMetrics {
+    fn new(module: &str) -> prometheus::Result<Self> {
+        let m = crate::telemetry::Metrics::new(module);
+        Ok(Self {
+            runs: m.counter_vec(
+                "job_runs_total",
+                "Completed job runs by outcome",
+                &["job", "outcome"],
+            )?,
+            skipped: m.counter_vec(
+                "job_skipped_total",
+                "Job ticks skipped because the previous run was still going",
+                &["job"],
+            )?,
+            duration: m.histogram_vec(
+                "job_duration_seconds",
+                "Job run time in seconds",
+                &["job"],
+                prometheus::DEFAULT_BUCKETS.to_vec(),
+            )?,
+        })
+    }
+}
+
+/// Run `job` for `module` until `cancel` fires. The returned task finishes
+/// once the loop has stopped and an in-flight run has returned.
+pub fn spawn_job(module: &str, job: Job, cancel: CancellationToken) -> JoinHandle<()> {
+    let module = module.to_string();
+    let metrics = JobMetrics::new(&module)
+        .map_err(|e| tracing::warn!(module, job = job.name, error = %e, "Job metrics unavailable"))
+        .ok()
+        .map(Arc::new);
+
+    tokio::spawn(async move {
+        let mut running: Option<JoinHandle<()>> = None;
+        loop {
+            let Some(delay) = job.spec.next_delay() else {
+                tracing::info!(module, job = job.name, "Job schedule has no further runs");
+                break;
+            };
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = tokio::time::sleep(delay) => {}
+            }
+
+            if running.as_ref().is_some_and(|run| !run.is_finished()) {
+                tracing::warn!(
+                    module,
+                    job = job.name,
+                    "Previous run still going, skipping tick"
+                );
+                if let Some(m) = &metrics {
+                    m.skipped.with_label_values(&[&job.name]).inc();
+                }
+                continue;
+            }
+
+            let run = (job.run)(cancel.child_token());
+            let (module, name, metrics) = (module.clone(), job.name.clone(), metrics.clone());
+            running = Some(tokio::spawn(async move {
+                let started = Instant::now();
+                let result = run.await;
+                let outcome = match &result {
+                    Ok(()) => "ok",
+                    Err(e) => {
+                        tracing::warn!(module, job = name, error = %format!("{e:#}"), "Job failed");
+                        "error"
+                    }
+                };
+                if let Some(m) = metrics {
+                    m.runs.with_label_values(&[&name, outcome]).inc();
+                    m.duration
+                        .with_label_values(&[&name])
+                        .observe(started.elapsed().as_secs_f64());
+                }
+            }));
+        }
+
+        if let Some(run) = running {
+            let _ = run.await;
+        }
+    })
+}
+
+/// A uniformly-ish distributed duration in `[0, max)`; good enough to spread
+/// job runs without pulling in an RNG.
+pub(crate) fn random_below(max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+    // Every `RandomState` is seeded differently, so an empty hash is random.
+    let hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    let nanos = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+    Duration::from_nanos(hasher.finish() % nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn spec_parses_intervals_and_cron() {
+        let every: JobSpec = "every 1m 30s".parse().unwrap();
+        assert!(matches!(every.schedule, Schedule::Every(d) if d == Duration::from_secs(90)));
+        let cron: JobSpec = "0 */15 * * * *".parse().unwrap();
+        assert!(matches!(cron.schedule, Schedule::Cron(_)));
+
+        assert!(matches!(
+            "every soon".parse::<JobSpec>(),
+            Err(JobSpecError::Interval { .. })
+        ));
+        assert!(matches!(
+            "every 0s".parse::<JobSpec>(),
+            Err(JobSpecError::Interval { .. })
+        ));
+        assert!(matches!(
+            "not a schedule".parse::<JobSpec>(),
+            Err(JobSpecError::Cron { .. })
+        ));
+    }
+
+    #[test]
+    fn jitter_stays_below_limit() {
+        let max = Duration::from_millis(50);
+        for _ in 0..100 {
+            assert!(random_below(max) < max);
+        }
+        assert_eq!(random_below(Duration::ZERO), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn interval_job_runs_until_cancelled() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let job = Job::new("tick", JobSpec::every(Duration::from_secs(10)), {
+            let runs = runs.clone();
+            move |_| {
+                let runs = runs.clone();
+                async move {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            }
+        });
+        let cancel = CancellationToken::new();
+        let handle = spawn_job("sched_test", job, cancel.clone());
+
+        tokio::time::sleep(Duration::from_secs(35)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        cancel.cancel();
+        handle.await.unwrap();
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_runs_do_not_overlap() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let max_active = Arc::new(AtomicUsize::new(0));
+        let job = Job::new("slow", JobSpec::every(Duration::from_secs(1)), {
+            let (active, max_active) = (active.clone(), max_active.clone());
+            move |_| {
+                let (active, max_active) = (active.clone(), max_active.clone());
+                async move {
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_active.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(2500)).await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            }
+        });
+        let cancel = CancellationToken::new();
+        let handle = spawn_job("sched_test", job, cancel.clone());
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        cancel.cancel();
+        handle.await.unwrap();
+        assert_eq!(max_active.load(Ordering::SeqCst), 1);
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+        assert!(crate::telemetry::render_prometheus()
+            .contains(r#"sched_test_job_skipped_total{job="slow",module="sched_test"}"#));
+    }
+}
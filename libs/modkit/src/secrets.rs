@@ -0,0 +1,315 @@
+//! Secret references in module configs.
+//!
+//! Any string in a module section may contain `${secret:<provider>:<key>}`;
+//! the runner replaces it before modules read their config:
+//!
+//! ```yaml
+//! modules:
+//!   billing:
+//!     config:
+//!       api_key: "${secret:env:BILLING_API_KEY}"
+//!       auth_header: "Bearer ${secret:file:/run/secrets/billing_token}"
+//! ```
+//!
+//! `env` and `file` are built in; embedders add others (Vault, a cloud secret
+//! manager) with `RunOptions::builder().secret_provider("vault", provider)`.
+//! Every reference that cannot be resolved is reported, and startup stops.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::context::ConfigProvider;
+
+const MARKER: &str = "${secret:";
+
+/// Source of secrets for one `${secret:<provider>:...}` prefix.
+pub trait SecretProvider: Send + Sync {
+    fn get(&self, key: &str) -> anyhow::Result<String>;
+}
+
+/// `${secret:env:NAME}`: the environment variable `NAME`.
+pub struct EnvSecrets;
+
+impl SecretProvider for EnvSecrets {
+    fn get(&self, key: &str) -> anyhow::Result<String> {
+        std::env::var(key).map_err(|_| anyhow::anyhow!("environment variable is not set"))
+    }
+}
+
+/// `${secret:file:/path}`: the file's contents without the trailing newline
+/// (Docker and Kubernetes secret mounts).
+pub struct FileSecrets;
+
+impl SecretProvider for FileSecrets {
+    fn get(&self, key: &str) -> anyhow::Result<String> {
+        let raw = std::fs::read_to_string(key)?;
+        Ok(raw.trim_end_matches(['\r', '\n']).to_string())
+    }
+}
+
+/// A reference that could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedSecret {
+    pub module: String,
+    /// Dotted path of the string within the module section.
+    pub path: String,
+    pub reference: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for UnresolvedSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "module '{}' {}: {}: {}",
+            self.module, self.path, self.reference, self.reason
+        )
+    }
+}
+
+/// Every reference of a config that could not be resolved.
+#[derive(Debug, thiserror::Error)]
+#[error("unresolved secrets: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+pub struct UnresolvedSecrets(pub Vec<UnresolvedSecret>);
+
+/// Secret providers by name; `env` and `file` by default.
+#[derive(Clone)]
+pub struct Secrets {
+    providers: HashMap<String, Arc<dyn SecretProvider>>,
+}
+
+impl Default for Secrets {
+    fn default() -> Self {
+        Self::empty()
+            .with_provider("env", Arc::new(EnvSecrets))
+            .with_provider("file", Arc::new(FileSecrets))
+    }
+}
+
+impl Secrets {
+    /// No providers at all, not even the built-in ones.
+    pub fn empty() -> Self {
+        Self {
+            providers: HashMap::new(),
+        }
+    }
+
+    /// Serve `${secret:<name>:...}` from `provider`, replacing any previous one.
+    pub fn with_provider(mut self, name: &str, provider: Arc<dyn SecretProvider>) -> Self {
+        self.insert(name, provider);
+        self
+    }
+
+    pub fn insert(&mut self, name: &str, provider: Arc<dyn SecretProvider>) {
+        self.providers.insert(name.to_string(), provider);
+    }
+
+    /// Resolve the references in the sections of `modules`. Returns `config`
+    /// itself when none of them has any.
+    pub fn resolve_config(
+        &self,
+        config: Arc<dyn ConfigProvider>,
+        modules: &[&str],
+    ) -> Result<Arc<dyn ConfigProvider>, UnresolvedSecrets> {
+        let mut sections = HashMap::new();
+        let mut errors = Vec::new();
+        for &module in modules {
+            let Some(raw) = config.get_module_config(module) else {
+                continue;
+            };
+            if !has_reference(raw) {
+                continue;
+            }
+            let mut section = raw.clone();
+            self.resolve_value(module, "", &mut section, &mut errors);
+            sections.insert(module.to_string(), section);
+        }
+        if !errors.is_empty() {
+            return Err(UnresolvedSecrets(errors));
+        }
+        if sections.is_empty() {
+            return Ok(config);
+        }
+        Ok(Arc::new(ResolvedConfig {
+            inner: config,
+            sections,
+        }))
+    }
+
+    fn resolve_value(
+        &self,
+        module: &str,
+        path: &str,
+        value: &mut serde_json::Value,
+        errors: &mut Vec<UnresolvedSecret>,
+    ) {
+        let child = |key: &dyn std::fmt::Display| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{path}.{key}")
+            }
+        };
+        match value {
+            serde_json::Value::String(s) if s.contains(MARKER) => {
+                *s = self.resolve_str(module, path, s, errors);
+            }
+            serde_json::Value::Array(items) => {
+                for (i, item) in items.iter_mut().enumerate() {
+                    self.resolve_value(module, &child(&i), item, errors);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    self.resolve_value(module, &child(key), item, errors);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn resolve_str(
+        &self,
+        module: &str,
+        path: &str,
+        s: &str,
+        errors: &mut Vec<UnresolvedSecret>,
+    ) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(start) = rest.find(MARKER) {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + MARKER.len()..];
+            let Some(end) = after.find('}') else {
+                // Unterminated: keep the tail as written.
+                out.push_str(&rest[start..]);
+                return out;
+            };
+            let reference = &rest[start..start + MARKER.len() + end + 1];
+            let resolved = match after[..end].split_once(':') {
+                None => Err("expected ${secret:<provider>:<key>}".to_string()),
+                Some((provider, key)) => match self.providers.get(provider) {
+                    None => Err(format!("no secret provider '{provider}'")),
+                    Some(p) => p.get(key).map_err(|e| format!("{e:#}")),
+                },
+            };
+            match resolved {
+                Ok(secret) => out.push_str(&secret),
+                Err(reason) => errors.push(UnresolvedSecret {
+                    module: module.to_string(),
+                    path: path.to_string(),
+                    reference: reference.to_string(),
+                    reason,
+                }),
+            }
+            rest = &after[end + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+fn has_reference(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::String(s) => s.contains(MARKER),
+        serde_json::Value::Array(items) => items.iter().any(has_reference),
+        serde_json::Value::Object(map) => map.values().any(has_reference),
+        _ => false,
+    }
+}
+
+/// `inner` with some module sections replaced by their resolved copies.
+struct ResolvedConfig {
+    inner: Arc<dyn ConfigProvider>,
+    sections: HashMap<String, serde_json::Value>,
+}
+
+impl ConfigProvider for ResolvedConfig {
+    fn get_module_config(&self, module_name: &str) -> Option<&serde_json::Value> {
+        self.sections
+            .get(module_name)
+            .or_else(|| self.inner.get_module_config(module_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Cfg(serde_json::Value);
+
+    impl ConfigProvider for Cfg {
+        fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+            self.0.get(module)
+        }
+    }
+
+    struct Vault;
+
+    impl SecretProvider for Vault {
+        fn get(&self, key: &str) -> anyhow::Result<String> {
+            match key {
+                "kv/billing:token" => Ok("t0k3n".into()),
+                _ => anyhow::bail!("no such secret"),
+            }
+        }
+    }
+
+    #[test]
+    fn resolves_references_anywhere_in_the_section() {
+        let file = std::env::temp_dir().join(format!("modkit-secret-{}", std::process::id()));
+        std::fs::write(&file, "from-file\n").unwrap();
+        let cfg = Arc::new(Cfg(serde_json::json!({
+            "billing": { "config": {
+                "token": "${secret:vault:kv/billing:token}",
+                "header": format!("Bearer ${{secret:file:{}}}", file.display()),
+                "hosts": ["a", "${secret:vault:kv/billing:token}"],
+                "port": 443,
+            } },
+            "plain": { "config": { "level": "info" } },
+        })));
+        let secrets = Secrets::default().with_provider("vault", Arc::new(Vault));
+
+        let resolved = secrets
+            .resolve_config(cfg, &["billing", "plain", "absent"])
+            .unwrap();
+        let billing = &resolved.get_module_config("billing").unwrap()["config"];
+        assert_eq!(billing["token"], "t0k3n");
+        assert_eq!(billing["header"], "Bearer from-file");
+        assert_eq!(billing["hosts"], serde_json::json!(["a", "t0k3n"]));
+        assert_eq!(billing["port"], 443);
+        assert_eq!(
+            resolved.get_module_config("plain").unwrap()["config"]["level"],
+            "info"
+        );
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn reports_every_unresolved_reference() {
+        let cfg = Arc::new(Cfg(serde_json::json!({
+            "billing": { "config": {
+                "a": "${secret:nope:x}",
+                "b": "${secret:vault:missing}",
+                "c": "${secret:bad}",
+            } },
+        })));
+        let secrets = Secrets::empty().with_provider("vault", Arc::new(Vault));
+
+        let Err(UnresolvedSecrets(errors)) = secrets.resolve_config(cfg, &["billing"]) else {
+            panic!("expected unresolved secrets");
+        };
+        let found: Vec<_> = errors
+            .iter()
+            .map(|e| (e.path.as_str(), e.reason.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("config.a", "no secret provider 'nope'"),
+                ("config.b", "no such secret"),
+                ("config.c", "expected ${secret:<provider>:<key>}"),
+            ]
+        );
+    }
+}
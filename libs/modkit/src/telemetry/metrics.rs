@@ -0,0 +1,392 @@
+//! Process-wide Prometheus metrics registry.
+//!
+//! All collectors are registered in a single global [`prometheus::Registry`]
+//! so one exporter endpoint (see api_ingress `/metrics`) can render them all.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+/// The global metrics registry.
+pub fn registry() -> &'static Registry {
+    REGISTRY.get_or_init(Registry::new)
+}
+
+/// Settings of the global registry, applied once by [`crate::telemetry::init`].
+#[derive(Debug, Clone, Default)]
+pub struct MetricsOptions {
+    /// Prepended to every metric name (`hyperspot` → `hyperspot_http_requests_total`).
+    pub prefix: Option<String>,
+    /// Labels added to every series (`env`, `region`, ...).
+    pub const_labels: HashMap<String, String>,
+}
+
+/// Install the global registry built from `opts`. Fails when a metric was
+/// recorded (and the default registry created) before, or on invalid names.
+pub(crate) fn init_registry(opts: &MetricsOptions) -> anyhow::Result<()> {
+    let registry = build_registry(opts)?;
+    REGISTRY
+        .set(registry)
+        .map_err(|_| anyhow::anyhow!("metrics registry is already initialized"))
+}
+
+fn build_registry(opts: &MetricsOptions) -> anyhow::Result<Registry> {
+    let names = opts.prefix.iter().chain(opts.const_labels.keys());
+    if let Some(bad) = names.clone().find(|n| !is_valid_name(n)) {
+        anyhow::bail!("invalid metric prefix or label name '{bad}'");
+    }
+    let labels = (!opts.const_labels.is_empty()).then(|| opts.const_labels.clone());
+    Ok(Registry::new_custom(opts.prefix.clone(), labels)?)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Render every registered collector in the Prometheus text exposition format.
+pub fn render_prometheus() -> String {
+    let mut buf = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&registry().gather(), &mut buf) {
+        tracing::warn!(error = %e, "failed to encode metrics");
+    }
+    String::from_utf8(buf).unwrap_or_default()
+}
+
+/// Standard HTTP server metrics, labeled by method, route template and status.
+#[derive(Clone)]
+pub struct HttpMetrics {
+    requests: IntCounterVec,
+    duration: HistogramVec,
+    in_flight: IntGaugeVec,
+    shed: IntCounterVec,
+}
+
+impl HttpMetrics {
+    /// Shared instance registered in the global registry.
+    pub fn global() -> &'static HttpMetrics {
+        static HTTP: OnceLock<HttpMetrics> = OnceLock::new();
+        HTTP.get_or_init(|| {
+            let m = HttpMetrics::new();
+            m.register(registry());
+            m
+        })
+    }
+
+    fn new() -> Self {
+        let labels = &["method", "route", "status"];
+        let requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total number of HTTP requests"),
+            labels,
+        )
+        .expect("valid http_requests_total metric");
+        let duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds",
+            ),
+            labels,
+        )
+        .expect("valid http_request_duration_seconds metric");
+        let in_flight = IntGaugeVec::new(
+            Opts::new(
+                "http_requests_in_flight",
+                "Requests currently holding a concurrency permit",
+            ),
+            &["scope"],
+        )
+        .expect("valid http_requests_in_flight metric");
+        let shed = IntCounterVec::new(
+            Opts::new(
+                "http_requests_shed_total",
+                "Requests rejected because a concurrency limit was reached",
+            ),
+            &["scope"],
+        )
+        .expect("valid http_requests_shed_total metric");
+        Self {
+            requests,
+            duration,
+            in_flight,
+            shed,
+        }
+    }
+
+    fn register(&self, registry: &Registry) {
+        for c in [
+            Box::new(self.requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(self.duration.clone()),
+            Box::new(self.in_flight.clone()),
+            Box::new(self.shed.clone()),
+        ] {
+            if let Err(e) = registry.register(c) {
+                tracing::warn!(error = %e, "failed to register HTTP metrics");
+            }
+        }
+    }
+
+    /// Record one completed request.
+    pub fn observe(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.requests.with_label_values(&labels).inc();
+        self.duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Adjust the in-flight gauge of a concurrency scope (`global` or a route).
+    pub fn add_in_flight(&self, scope: &str, delta: i64) {
+        self.in_flight.with_label_values(&[scope]).add(delta);
+    }
+
+    /// Record one request rejected by the concurrency limit of `scope`.
+    pub fn record_shed(&self, scope: &str) {
+        self.shed.with_label_values(&[scope]).inc();
+    }
+}
+
+/// Calls between modules through instrumented ClientHub clients, labeled by
+/// contract (trait name) and method.
+#[derive(Clone)]
+pub struct ClientMetrics {
+    calls: IntCounterVec,
+    duration: HistogramVec,
+}
+
+impl ClientMetrics {
+    /// Shared instance registered in the global registry.
+    pub fn global() -> &'static ClientMetrics {
+        static CLIENT: OnceLock<ClientMetrics> = OnceLock::new();
+        CLIENT.get_or_init(|| {
+            let calls = IntCounterVec::new(
+                Opts::new(
+                    "client_calls_total",
+                    "Total number of inter-module client calls",
+                ),
+                &["contract", "method", "outcome"],
+            )
+            .expect("valid client_calls_total metric");
+            let duration = HistogramVec::new(
+                HistogramOpts::new(
+                    "client_call_duration_seconds",
+                    "Inter-module client call latency in seconds",
+                ),
+                &["contract", "method"],
+            )
+            .expect("valid client_call_duration_seconds metric");
+            for c in [
+                Box::new(calls.clone()) as Box<dyn prometheus::core::Collector>,
+                Box::new(duration.clone()),
+            ] {
+                if let Err(e) = registry().register(c) {
+                    tracing::warn!(error = %e, "failed to register client metrics");
+                }
+            }
+            ClientMetrics { calls, duration }
+        })
+    }
+
+    /// Record one completed call; `outcome` is `ok` or `error`.
+    pub fn observe(&self, contract: &str, method: &str, outcome: &str, elapsed: Duration) {
+        self.calls
+            .with_label_values(&[contract, method, outcome])
+            .inc();
+        self.duration
+            .with_label_values(&[contract, method])
+            .observe(elapsed.as_secs_f64());
+    }
+}
+
+/// Metrics of one module (see `ModuleCtx::metrics`).
+///
+/// Names get the module name as prefix (`users_info_cache_hits_total`) and
+/// every series carries a `module` label. Collectors live in the global
+/// registry, so they are exported with everything else; asking twice for the
+/// same name returns the collector registered first.
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    module: Arc<str>,
+}
+
+impl Metrics {
+    pub fn new(module: &str) -> Self {
+        Self {
+            module: Arc::from(module),
+        }
+    }
+
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    pub fn counter(&self, name: &str, help: &str) -> prometheus::Result<IntCounter> {
+        self.get_or_register(name, || IntCounter::with_opts(self.opts(name, help)))
+    }
+
+    pub fn counter_vec(
+        &self,
+        name: &str,
+        help: &str,
+        labels: &[&str],
+    ) -> prometheus::Result<IntCounterVec> {
+        self.get_or_register(name, || IntCounterVec::new(self.opts(name, help), labels))
+    }
+
+    pub fn gauge(&self, name: &str, help: &str) -> prometheus::Result<IntGauge> {
+        self.get_or_register(name, || IntGauge::with_opts(self.opts(name, help)))
+    }
+
+    pub fn gauge_vec(
+        &self,
+        name: &str,
+        help: &str,
+        labels: &[&str],
+    ) -> prometheus::Result<IntGaugeVec> {
+        self.get_or_register(name, || IntGaugeVec::new(self.opts(name, help), labels))
+    }
+
+    /// A histogram with the default buckets (`prometheus::DEFAULT_BUCKETS`).
+    pub fn histogram(&self, name: &str, help: &str) -> prometheus::Result<Histogram> {
+        self.get_or_register(name, || {
+            Histogram::with_opts(HistogramOpts::from(self.opts(name, help)))
+        })
+    }
+
+    pub fn histogram_vec(
+        &self,
+        name: &str,
+        help: &str,
+        labels: &[&str],
+        buckets: Vec<f64>,
+    ) -> prometheus::Result<HistogramVec> {
+        self.get_or_register(name, || {
+            HistogramVec::new(
+                HistogramOpts::from(self.opts(name, help)).buckets(buckets),
+                labels,
+            )
+        })
+    }
+
+    fn opts(&self, name: &str, help: &str) -> Opts {
+        Opts::new(name, help)
+            .namespace(sanitize(&self.module))
+            .const_label("module", self.module.as_ref())
+    }
+
+    fn get_or_register<C>(
+        &self,
+        name: &str,
+        make: impl FnOnce() -> prometheus::Result<C>,
+    ) -> prometheus::Result<C>
+    where
+        C: Collector + Clone + Send + Sync + 'static,
+    {
+        static COLLECTORS: OnceLock<
+            parking_lot::Mutex<HashMap<String, Box<dyn Any + Send + Sync>>>,
+        > = OnceLock::new();
+        let key = format!("{}_{name}", sanitize(&self.module));
+        let mut collectors = COLLECTORS.get_or_init(Default::default).lock();
+        if let Some(existing) = collectors.get(&key) {
+            return existing.downcast_ref::<C>().cloned().ok_or_else(|| {
+                prometheus::Error::Msg(format!("metric '{key}' is registered with another type"))
+            });
+        }
+        let collector = make()?;
+        registry().register(Box::new(collector.clone()))?;
+        collectors.insert(key, Box::new(collector.clone()));
+        Ok(collector)
+    }
+}
+
+/// Module names may contain characters Prometheus does not allow in metric names.
+fn sanitize(module: &str) -> String {
+    module
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_metrics_are_rendered() {
+        HttpMetrics::global().observe("GET", "/users/{id}", 200, Duration::from_millis(5));
+        let text = render_prometheus();
+        assert!(text.contains("http_requests_total"));
+        assert!(text.contains(r#"route="/users/{id}""#));
+        assert!(text.contains("http_request_duration_seconds_bucket"));
+    }
+
+    #[test]
+    fn module_metrics_are_namespaced_and_labeled() {
+        let metrics = Metrics::new("report-gen");
+        metrics.counter("jobs_total", "Jobs run").unwrap().inc();
+        // Same name again: the registered counter, not a registration error.
+        metrics.counter("jobs_total", "Jobs run").unwrap().inc();
+        metrics
+            .histogram_vec("job_seconds", "Job time", &["kind"], vec![0.1, 1.0])
+            .unwrap()
+            .with_label_values(&["pdf"])
+            .observe(0.5);
+
+        let text = render_prometheus();
+        assert!(
+            text.contains(r#"report_gen_jobs_total{module="report-gen"} 2"#),
+            "{text}"
+        );
+        assert!(
+            text.contains(
+                r#"report_gen_job_seconds_bucket{kind="pdf",module="report-gen",le="1"} 1"#
+            ),
+            "{text}"
+        );
+        assert!(metrics.gauge("jobs_total", "Jobs run").is_err());
+    }
+
+    #[test]
+    fn registry_options_prefix_and_label_every_series() {
+        let opts = MetricsOptions {
+            prefix: Some("hyperspot".into()),
+            const_labels: HashMap::from([("env".to_string(), "prod".to_string())]),
+        };
+        let custom = build_registry(&opts).unwrap();
+        let counter = IntCounter::new("jobs_total", "Jobs run").unwrap();
+        custom.register(Box::new(counter.clone())).unwrap();
+        counter.inc();
+
+        let mut buf = Vec::new();
+        TextEncoder::new()
+            .encode(&custom.gather(), &mut buf)
+            .unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert!(
+            text.contains(r#"hyperspot_jobs_total{env="prod"} 1"#),
+            "{text}"
+        );
+
+        let bad = MetricsOptions {
+            prefix: Some("hyper-spot".into()),
+            ..Default::default()
+        };
+        assert!(build_registry(&bad).is_err());
+
+        // The default registry exists as soon as anything records a metric.
+        registry();
+        assert!(init_registry(&MetricsOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+//! Telemetry facilities shared by modules: metrics and trace export.
+
+pub mod metrics;
+pub mod otlp;
+
+pub use metrics::{
+    registry, render_prometheus, ClientMetrics, HttpMetrics, Metrics, MetricsOptions,
+};
+pub use otlp::{
+    inject_trace_context, sampler, set_remote_parent, set_sampler, OtlpLayer, OtlpOptions,
+    OtlpProtocol, Sampler,
+};
+
+/// Runtime control of log levels, implemented by whoever installed the logging
+/// layers and shared as a resource (`ctx.resource::<dyn LogLevels>()`).
+pub trait LogLevels: Send + Sync {
+    /// Directives in force, e.g. `api_ingress=debug`; empty means the config levels.
+    fn directives(&self) -> String;
+
+    /// Replace the directives; an empty string restores the config levels.
+    fn set_directives(&self, directives: &str) -> anyhow::Result<()>;
+}
+
+/// Set up process-wide telemetry. Call once at startup, before any module
+/// records a metric; without it the registry has no prefix or const labels.
+pub fn init(metrics: &MetricsOptions) -> anyhow::Result<()> {
+    metrics::init_registry(metrics)
+}
+
+/// Start exporting spans to the OTLP collector described by `opts` and return
+/// the layer to install with the logging layers. gRPC export needs a Tokio runtime.
+pub fn init_tracing(opts: &OtlpOptions) -> anyhow::Result<OtlpLayer> {
+    otlp::build(opts)
+}
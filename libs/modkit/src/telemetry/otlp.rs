@@ -0,0 +1,562 @@
+//! OTLP trace export.
+//!
+//! `tracing` spans are bridged to OpenTelemetry by `tracing-opentelemetry` and
+//! shipped in batches by `opentelemetry-otlp`, over OTLP/HTTP (JSON) or, with
+//! the `otlp-grpc` feature, OTLP/gRPC. [`OtlpOptions`] is mapped onto the
+//! exporter and tracer provider builders. Child spans join their parent's
+//! trace; [`set_remote_parent`] continues the trace of an inbound request and
+//! [`inject_trace_context`] propagates it on outbound calls.
+//!
+//! A few span fields are read as OpenTelemetry hints rather than attributes:
+//! `otel.kind` (`server`, `client`, `producer`, `consumer`, `internal`) and
+//! `otel.status_code` (`error` / `ok`). An `ERROR` event inside a span also
+//! marks it failed. Build the layer with [`crate::telemetry::init_tracing`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::Context as _;
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::trace::{
+    Link, SamplingResult, SpanKind, TraceContextExt, TraceId, TracerProvider,
+};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{SdkTracerProvider, ShouldSample};
+use opentelemetry_sdk::Resource;
+use parking_lot::RwLock;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::filter::{FilterFn, Filtered};
+use tracing_subscriber::{Layer, Registry};
+
+/// Targets whose spans are never exported: the exporter's own HTTP/gRPC stack.
+const IGNORED_TARGETS: &[&str] = &["h2", "hyper", "reqwest", "tonic", "tower"];
+
+/// Wire protocol of the collector endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OtlpProtocol {
+    /// OTLP/gRPC, usually port 4317. Needs the `otlp-grpc` feature.
+    #[default]
+    Grpc,
+    /// OTLP/HTTP with JSON bodies, usually port 4318.
+    Http,
+}
+
+impl OtlpProtocol {
+    fn default_endpoint(self) -> &'static str {
+        match self {
+            OtlpProtocol::Grpc => "http://localhost:4317",
+            OtlpProtocol::Http => "http://localhost:4318/v1/traces",
+        }
+    }
+}
+
+impl std::str::FromStr for OtlpProtocol {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "grpc" => Ok(OtlpProtocol::Grpc),
+            "http" | "http/json" => Ok(OtlpProtocol::Http),
+            other => anyhow::bail!("unknown OTLP protocol '{other}' (expected grpc or http)"),
+        }
+    }
+}
+
+/// Which traces are recorded, as named by `OTEL_TRACES_SAMPLER`. Maps onto
+/// the OpenTelemetry SDK sampler of the same name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sampler {
+    AlwaysOn,
+    AlwaysOff,
+    /// Fraction of traces in `0.0..=1.0`, decided from the trace id.
+    TraceIdRatio(f64),
+    /// Follow the parent's sampled flag; the inner sampler decides new traces.
+    ParentBased(Box<Sampler>),
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Sampler::ParentBased(Box::new(Sampler::AlwaysOn))
+    }
+}
+
+impl Sampler {
+    /// Parse an `OTEL_TRACES_SAMPLER` name (`always_on`, `traceidratio`,
+    /// `parentbased_traceidratio`, ...); `arg` is the ratio, 1.0 when absent.
+    pub fn from_name(name: &str, arg: Option<f64>) -> anyhow::Result<Self> {
+        let ratio = arg.unwrap_or(1.0);
+        if !(0.0..=1.0).contains(&ratio) {
+            anyhow::bail!("sampler ratio {ratio} is outside 0.0..=1.0");
+        }
+        let (parent_based, root) = match name.strip_prefix("parentbased_") {
+            Some(root) => (true, root),
+            None => (false, name),
+        };
+        let root = match root {
+            "always_on" => Sampler::AlwaysOn,
+            "always_off" => Sampler::AlwaysOff,
+            "traceidratio" => Sampler::TraceIdRatio(ratio),
+            _ => anyhow::bail!("unknown sampler '{name}'"),
+        };
+        Ok(if parent_based {
+            Sampler::ParentBased(Box::new(root))
+        } else {
+            root
+        })
+    }
+
+    /// The `OTEL_TRACES_SAMPLER` name and ratio argument; the inverse of [`Sampler::from_name`].
+    pub fn to_name(&self) -> (String, Option<f64>) {
+        match self {
+            Sampler::AlwaysOn => ("always_on".to_string(), None),
+            Sampler::AlwaysOff => ("always_off".to_string(), None),
+            Sampler::TraceIdRatio(ratio) => ("traceidratio".to_string(), Some(*ratio)),
+            Sampler::ParentBased(root) => {
+                let (name, ratio) = root.to_name();
+                (format!("parentbased_{name}"), ratio)
+            }
+        }
+    }
+}
+
+impl From<&Sampler> for opentelemetry_sdk::trace::Sampler {
+    fn from(sampler: &Sampler) -> Self {
+        use opentelemetry_sdk::trace::Sampler as Sdk;
+        match sampler {
+            Sampler::AlwaysOn => Sdk::AlwaysOn,
+            Sampler::AlwaysOff => Sdk::AlwaysOff,
+            Sampler::TraceIdRatio(ratio) => Sdk::TraceIdRatioBased(*ratio),
+            Sampler::ParentBased(root) => Sdk::ParentBased(Box::new(Sdk::from(root.as_ref()))),
+        }
+    }
+}
+
+/// Collector connection and trace settings for [`crate::telemetry::init_tracing`].
+#[derive(Debug, Clone, Default)]
+pub struct OtlpOptions {
+    /// Collector URL; empty means the protocol's localhost default. For HTTP a
+    /// bare `http://host:4318` gets `/v1/traces` appended.
+    pub endpoint: String,
+    pub protocol: OtlpProtocol,
+    /// Sent with every export, e.g. `authorization: Bearer ...`.
+    pub headers: HashMap<String, String>,
+    /// Resource attributes such as `service.name` and `deployment.environment`.
+    pub resource: HashMap<String, String>,
+    pub sampler: Sampler,
+    /// Per-export timeout; zero means 10 seconds.
+    pub timeout: Duration,
+}
+
+/// The sampler in force, next to the SDK sampler built from it.
+#[derive(Debug)]
+struct ActiveSampler {
+    config: Sampler,
+    sdk: opentelemetry_sdk::trace::Sampler,
+}
+
+impl ActiveSampler {
+    fn new(config: Sampler) -> Self {
+        Self {
+            sdk: (&config).into(),
+            config,
+        }
+    }
+}
+
+/// Sampler of the tracer provider, shared so it can change at runtime.
+#[derive(Debug, Clone)]
+struct SharedSampler(Arc<RwLock<ActiveSampler>>);
+
+impl ShouldSample for SharedSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&opentelemetry::Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        self.0.read().sdk.should_sample(
+            parent_context,
+            trace_id,
+            name,
+            span_kind,
+            attributes,
+            links,
+        )
+    }
+}
+
+static SAMPLER: OnceLock<SharedSampler> = OnceLock::new();
+
+/// The sampler in force, or `None` when trace export was not initialized.
+pub fn sampler() -> Option<Sampler> {
+    SAMPLER.get().map(|s| s.0.read().config.clone())
+}
+
+/// Replace the sampler without a restart; traces already started keep their decision.
+pub fn set_sampler(sampler: Sampler) -> anyhow::Result<()> {
+    let shared = SAMPLER
+        .get()
+        .context("trace export is not initialized (no telemetry.tracing section)")?;
+    *shared.0.write() = ActiveSampler::new(sampler);
+    Ok(())
+}
+
+/// `tracing` layer recording spans for OTLP export.
+pub type OtlpLayer = Filtered<
+    tracing_opentelemetry::OpenTelemetryLayer<Registry, opentelemetry_sdk::trace::Tracer>,
+    FilterFn<fn(&tracing::Metadata<'_>) -> bool>,
+    Registry,
+>;
+
+fn exported(meta: &tracing::Metadata<'_>) -> bool {
+    let target = meta.target();
+    !IGNORED_TARGETS.iter().any(|t| {
+        target
+            .strip_prefix(t)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    })
+}
+
+pub(crate) fn layer(provider: &SdkTracerProvider) -> OtlpLayer {
+    tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer("modkit"))
+        .with_filter(FilterFn::new(
+            exported as fn(&tracing::Metadata<'_>) -> bool,
+        ))
+}
+
+fn tracer_provider(
+    exporter: impl opentelemetry_sdk::trace::SpanExporter + 'static,
+    opts: &OtlpOptions,
+) -> SdkTracerProvider {
+    let sampler = SAMPLER.get_or_init(|| {
+        SharedSampler(Arc::new(RwLock::new(
+            ActiveSampler::new(Sampler::default()),
+        )))
+    });
+    *sampler.0.write() = ActiveSampler::new(opts.sampler.clone());
+    let resource = Resource::builder()
+        .with_attributes(
+            opts.resource
+                .iter()
+                .map(|(k, v)| KeyValue::new(k.clone(), v.clone())),
+        )
+        .build();
+    SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(sampler.clone())
+        .with_resource(resource)
+        .build()
+}
+
+/// Validate `opts`, start the batch exporter and return the layer that feeds it.
+/// gRPC export needs a Tokio runtime.
+pub(crate) fn build(opts: &OtlpOptions) -> anyhow::Result<OtlpLayer> {
+    let provider = tracer_provider(span_exporter(opts)?, opts);
+    Ok(layer(&provider))
+}
+
+fn span_exporter(opts: &OtlpOptions) -> anyhow::Result<opentelemetry_otlp::SpanExporter> {
+    let endpoint = if opts.endpoint.trim().is_empty() {
+        opts.protocol.default_endpoint()
+    } else {
+        opts.endpoint.trim()
+    };
+    let timeout = if opts.timeout.is_zero() {
+        Duration::from_secs(10)
+    } else {
+        opts.timeout
+    };
+    let exporter = opentelemetry_otlp::SpanExporter::builder();
+
+    match opts.protocol {
+        OtlpProtocol::Http => {
+            let mut url = reqwest::Url::parse(endpoint)
+                .with_context(|| format!("invalid OTLP endpoint '{endpoint}'"))?;
+            if url.path() == "/" {
+                url.set_path("/v1/traces");
+            }
+            for (name, value) in &opts.headers {
+                reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| format!("invalid OTLP header name '{name}'"))?;
+                reqwest::header::HeaderValue::from_str(value)
+                    .with_context(|| format!("invalid value of OTLP header '{name}'"))?;
+            }
+            Ok(exporter
+                .with_http()
+                .with_protocol(opentelemetry_otlp::Protocol::HttpJson)
+                .with_endpoint(url.as_str())
+                .with_headers(opts.headers.clone())
+                .with_timeout(timeout)
+                .build()?)
+        }
+        #[cfg(feature = "otlp-grpc")]
+        OtlpProtocol::Grpc => {
+            use opentelemetry_otlp::WithTonicConfig;
+
+            tokio::runtime::Handle::try_current()
+                .context("OTLP over gRPC needs a running Tokio runtime")?;
+            let mut metadata = tonic::metadata::MetadataMap::new();
+            for (name, value) in &opts.headers {
+                let key =
+                    tonic::metadata::MetadataKey::from_bytes(name.to_ascii_lowercase().as_bytes())
+                        .with_context(|| format!("invalid OTLP header name '{name}'"))?;
+                let value = value
+                    .parse()
+                    .with_context(|| format!("invalid value of OTLP header '{name}'"))?;
+                metadata.insert(key, value);
+            }
+            Ok(exporter
+                .with_tonic()
+                .with_endpoint(endpoint)
+                .with_metadata(metadata)
+                .with_timeout(timeout)
+                .build()?)
+        }
+        #[cfg(not(feature = "otlp-grpc"))]
+        OtlpProtocol::Grpc => anyhow::bail!(
+            "OTLP over gRPC needs modkit's `otlp-grpc` feature; use protocol `http` instead"
+        ),
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut http::HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            http::HeaderName::from_bytes(key.as_bytes()),
+            http::HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+struct HeaderExtractor<'a>(&'a http::HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(http::HeaderName::as_str).collect()
+    }
+}
+
+/// Add the W3C `traceparent` (and `tracestate`) of `span` to `headers`, so the
+/// callee joins the trace. Does nothing when the span is not exported.
+pub fn inject_trace_context(span: &tracing::Span, headers: &mut http::HeaderMap) {
+    let context = span.context();
+    if context.span().span_context().is_valid() {
+        TraceContextPropagator::new().inject_context(&context, &mut HeaderInjector(headers));
+    }
+}
+
+/// Make `span` continue the trace named by the `traceparent` (and
+/// `tracestate`) of an inbound request. Call before the span is entered.
+pub fn set_remote_parent(span: &tracing::Span, headers: &http::HeaderMap) {
+    let context = TraceContextPropagator::new().extract(&HeaderExtractor(headers));
+    if context.span().span_context().is_valid() {
+        span.set_parent(context);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::BoxFuture;
+    use opentelemetry::trace::Status;
+    use opentelemetry_sdk::error::OTelSdkResult;
+    use opentelemetry_sdk::trace::SpanData;
+    use parking_lot::Mutex;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Collects exported spans.
+    #[derive(Clone, Debug, Default)]
+    struct Collected(Arc<Mutex<Vec<SpanData>>>);
+
+    impl opentelemetry_sdk::trace::SpanExporter for Collected {
+        fn export(&mut self, batch: Vec<SpanData>) -> BoxFuture<'static, OTelSdkResult> {
+            self.0.lock().extend(batch);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    /// Run `f` with an OTLP layer exporting into memory; spans in end order.
+    fn record(sampler: Sampler, f: impl FnOnce()) -> Vec<SpanData> {
+        let collected = Collected::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(collected.clone())
+            .with_sampler(opentelemetry_sdk::trace::Sampler::from(&sampler))
+            .build();
+        let subscriber = tracing_subscriber::registry().with(layer(&provider));
+        tracing::subscriber::with_default(subscriber, f);
+        let spans = std::mem::take(&mut *collected.0.lock());
+        spans
+    }
+
+    fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a opentelemetry::Value> {
+        span.attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| &kv.value)
+    }
+
+    #[test]
+    fn children_join_the_trace_and_errors_mark_the_span() {
+        let spans = record(Sampler::default(), || {
+            let root = tracing::info_span!("request", user = 7, path = "/users");
+            let _guard = root.enter();
+            tracing::info_span!("http.client", otel.kind = "client").in_scope(|| {
+                tracing::error!("upstream down");
+            });
+            tracing::info_span!(target: "hyper::client", "connect").in_scope(|| {});
+        });
+
+        let [child, root] = spans.as_slice() else {
+            panic!("expected two spans, got {spans:?}");
+        };
+        assert_eq!(root.name, "request");
+        assert_eq!(child.span_context.trace_id(), root.span_context.trace_id());
+        assert_eq!(child.parent_span_id, root.span_context.span_id());
+        assert_eq!(root.parent_span_id, opentelemetry::trace::SpanId::INVALID);
+        assert_eq!(child.span_kind, SpanKind::Client);
+        assert!(matches!(child.status, Status::Error { .. }));
+        assert_eq!(child.events.events[0].name, "upstream down");
+        assert_eq!(root.status, Status::Unset);
+        assert_eq!(attribute(root, "user"), Some(&opentelemetry::Value::I64(7)));
+    }
+
+    #[test]
+    fn remote_parent_is_continued_and_decides_sampling() {
+        let parent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let mut headers = http::HeaderMap::new();
+        headers.insert("traceparent", parent.parse().unwrap());
+        headers.insert("tracestate", "k=v".parse().unwrap());
+        let spans = record(Sampler::default(), || {
+            let span = tracing::info_span!("inbound");
+            set_remote_parent(&span, &headers);
+            span.in_scope(|| {});
+        });
+        assert_eq!(spans.len(), 1);
+        assert_eq!(
+            spans[0].span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(spans[0].parent_span_id.to_string(), "00f067aa0ba902b7");
+        assert_eq!(spans[0].span_context.trace_state().header(), "k=v");
+
+        let mut unsampled = http::HeaderMap::new();
+        unsampled.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00"
+                .parse()
+                .unwrap(),
+        );
+        let spans = record(Sampler::default(), || {
+            let span = tracing::info_span!("inbound");
+            set_remote_parent(&span, &unsampled);
+            span.in_scope(|| {
+                tracing::info_span!("child").in_scope(|| {});
+            });
+        });
+        assert!(spans.is_empty());
+
+        assert!(record(Sampler::AlwaysOff, || {
+            tracing::info_span!("root").in_scope(|| {});
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn samplers_parse_and_map_onto_the_sdk() {
+        assert_eq!(
+            Sampler::from_name("parentbased_traceidratio", Some(0.25)).unwrap(),
+            Sampler::ParentBased(Box::new(Sampler::TraceIdRatio(0.25)))
+        );
+        assert_eq!(
+            Sampler::from_name("parentbased_traceidratio", Some(0.25))
+                .unwrap()
+                .to_name(),
+            ("parentbased_traceidratio".to_string(), Some(0.25))
+        );
+        assert!(Sampler::from_name("sometimes", None).is_err());
+        assert!(Sampler::from_name("traceidratio", Some(1.5)).is_err());
+        let root = || tracing::info_span!("root").in_scope(|| {});
+        assert!(record(Sampler::TraceIdRatio(0.0), root).is_empty());
+        assert_eq!(record(Sampler::TraceIdRatio(1.0), root).len(), 1);
+    }
+
+    #[test]
+    fn http_exporter_posts_json_with_headers() {
+        use axum::{extract::State, http::HeaderMap, routing::post, Json, Router};
+        use std::sync::mpsc;
+
+        // The collector runs on its own runtime: the HTTP exporter blocks.
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (seen_tx, seen_rx) = mpsc::channel::<(Option<String>, serde_json::Value)>();
+        let app = Router::new()
+            .route(
+                "/v1/traces",
+                post(
+                    |State(seen): State<mpsc::Sender<_>>,
+                     headers: HeaderMap,
+                     Json(body): Json<serde_json::Value>| async move {
+                        let auth = headers
+                            .get("authorization")
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_string);
+                        seen.send((auth, body)).unwrap();
+                    },
+                ),
+            )
+            .with_state(seen_tx);
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let opts = OtlpOptions {
+            endpoint: format!("http://{addr}"),
+            protocol: OtlpProtocol::Http,
+            headers: HashMap::from([("authorization".into(), "Bearer t0k3n".into())]),
+            resource: HashMap::from([("service.name".into(), "hyperspot".into())]),
+            ..Default::default()
+        };
+        let provider = tracer_provider(span_exporter(&opts).unwrap(), &opts);
+        let subscriber = tracing_subscriber::registry().with(layer(&provider));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info_span!("job").in_scope(|| {});
+        });
+        provider.force_flush().unwrap();
+
+        let (auth, body) = seen_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(auth.as_deref(), Some("Bearer t0k3n"));
+        let resource = body["resourceSpans"][0]["resource"]["attributes"]
+            .as_array()
+            .unwrap();
+        assert!(resource
+            .iter()
+            .any(|kv| kv["key"] == "service.name" && kv["value"]["stringValue"] == "hyperspot"));
+        assert_eq!(
+            body["resourceSpans"][0]["scopeSpans"][0]["spans"][0]["name"],
+            "job"
+        );
+
+        let bad = OtlpOptions {
+            protocol: OtlpProtocol::Http,
+            headers: HashMap::from([("bad header".into(), "x".into())]),
+            ..Default::default()
+        };
+        assert!(span_exporter(&bad).is_err());
+    }
+}
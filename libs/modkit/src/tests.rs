@@ -54,8 +54,9 @@ mod module_tests {
         }
     }
 
+    #[async_trait::async_trait]
     impl RestfulModule for TestModule {
-        fn register_rest(
+        async fn register_rest(
             &self,
             _ctx: &crate::context::ModuleCtx,
             router: Router,
@@ -98,12 +99,13 @@ mod module_tests {
         }
     }
 
+    #[async_trait::async_trait]
     impl RestHostModule for TestRestHost {
         fn as_registry(&self) -> &dyn crate::contracts::OpenApiRegistry {
             static REG: MockOpenApi = MockOpenApi;
             &REG
         }
-        fn rest_prepare(
+        async fn rest_prepare(
             &self,
             _ctx: &crate::context::ModuleCtx,
             router: Router,
@@ -113,7 +115,7 @@ mod module_tests {
             Ok(router.route("/healthz", get(|| async { "ok" })))
         }
 
-        fn rest_finalize(
+        async fn rest_finalize(
             &self,
             _ctx: &crate::context::ModuleCtx,
             router: Router,
@@ -149,6 +151,7 @@ mod module_tests {
         let router = Router::new();
         let _router = registry
             .run_rest_phase(&ctx, router)
+            .await
             .expect("rest registration should succeed on empty registry");
 
         // start / stop
@@ -162,8 +165,8 @@ mod module_tests {
             .expect("stop should succeed");
     }
 
-    #[test]
-    fn test_rest_host_no_host_with_rest_modules_fails() {
+    #[tokio::test]
+    async fn test_rest_host_no_host_with_rest_modules_fails() {
         let mut builder = RegistryBuilder::default();
 
         // Register a REST module without a host
@@ -178,12 +181,12 @@ mod module_tests {
 
         let router = Router::new();
         // Should fail with specific error type
-        let result = registry.run_rest_phase(&ctx, router);
+        let result = registry.run_rest_phase(&ctx, router).await;
         assert!(matches!(result, Err(RegistryError::RestRequiresHost)));
     }
 
-    #[test]
-    fn test_rest_host_with_one_host_and_rest_modules_succeeds() {
+    #[tokio::test]
+    async fn test_rest_host_with_one_host_and_rest_modules_succeeds() {
         let mut builder = RegistryBuilder::default();
         let call_tracker = Arc::new(Mutex::new(Vec::new()));
 
@@ -207,7 +210,7 @@ mod module_tests {
 
         let router = Router::new();
         // Should succeed
-        let result = registry.run_rest_phase(&ctx, router);
+        let result = registry.run_rest_phase(&ctx, router).await;
         assert!(result.is_ok());
 
         // Verify the correct call sequence: prepare -> finalize
@@ -215,35 +218,105 @@ mod module_tests {
         assert_eq!(*calls, vec!["prepare", "finalize"]);
     }
 
-    #[test]
-    fn test_multiple_rest_hosts_fails_at_registration() {
+    fn register_host(builder: &mut RegistryBuilder, name: &'static str, calls: CallTracker) {
+        let host = Arc::new(TestRestHost::new(name, calls));
+        builder.register_core_with_meta(name, &[], host.clone() as Arc<dyn Module>);
+        builder.register_rest_host_with_meta(name, host as Arc<dyn RestHostModule>);
+    }
+
+    fn register_rest(builder: &mut RegistryBuilder, name: &'static str) {
+        let module = Arc::new(TestModule::new(name));
+        builder.register_core_with_meta(name, &[], module.clone() as Arc<dyn Module>);
+        builder.register_rest_with_meta(name, module as Arc<dyn RestfulModule>);
+    }
+
+    async fn status_of(router: &Router, path: &str) -> axum::http::StatusCode {
+        use tower::ServiceExt;
+        let req = axum::http::Request::get(path)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        router.clone().oneshot(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_multiple_rest_hosts_compose_separately() {
         let mut builder = RegistryBuilder::default();
-        let call_tracker1 = Arc::new(Mutex::new(Vec::new()));
-        let call_tracker2 = Arc::new(Mutex::new(Vec::new()));
-
-        // Register first REST host
-        let host1 = Arc::new(TestRestHost::new("host1", call_tracker1));
-        builder.register_core_with_meta("host1", &[], host1.clone() as Arc<dyn Module>);
-        builder.register_rest_host_with_meta("host1", host1.clone() as Arc<dyn RestHostModule>);
-
-        // Register second REST host - should panic
-        let host2 = Arc::new(TestRestHost::new("host2", call_tracker2));
-        builder.register_core_with_meta("host2", &[], host2.clone() as Arc<dyn Module>);
-
-        // Registering a second host should be reported as a configuration error at build time
-        builder.register_rest_host_with_meta("host2", host2.clone() as Arc<dyn RestHostModule>);
-        let err = builder.build_topo_sorted().unwrap_err();
-        match err {
-            RegistryError::InvalidRegistryConfiguration { errors } => {
-                assert!(
-                    errors
-                        .iter()
-                        .any(|e| e.contains("Multiple REST host modules detected")),
-                    "expected multiple host configuration error, got {errors:?}"
-                );
-            }
-            other => panic!("unexpected error: {other:?}"),
-        }
+        let public_calls = Arc::new(Mutex::new(Vec::new()));
+        let admin_calls = Arc::new(Mutex::new(Vec::new()));
+        register_host(&mut builder, "api_ingress", public_calls.clone());
+        register_host(&mut builder, "admin_ingress", admin_calls.clone());
+        register_rest(&mut builder, "public");
+        register_rest(&mut builder, "admin");
+        builder.register_rest_binding_with_meta("admin", "admin_ingress");
+
+        let registry = builder.build_topo_sorted().expect("registry should build");
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let router = registry
+            .run_rest_phase(&ctx, Router::new())
+            .await
+            .expect("rest phase should succeed");
+
+        // Both hosts ran their own prepare/finalize
+        assert_eq!(*public_calls.lock().unwrap(), vec!["prepare", "finalize"]);
+        assert_eq!(*admin_calls.lock().unwrap(), vec!["prepare", "finalize"]);
+
+        // The returned router is the default host's: unbound modules only
+        assert_eq!(status_of(&router, "/public").await, 200);
+        assert_eq!(status_of(&router, "/admin").await, 404);
+    }
+
+    #[tokio::test]
+    async fn test_unbound_rest_module_without_default_host_fails() {
+        let mut builder = RegistryBuilder::default();
+        register_host(&mut builder, "host1", Arc::new(Mutex::new(Vec::new())));
+        register_host(&mut builder, "host2", Arc::new(Mutex::new(Vec::new())));
+        register_rest(&mut builder, "test_rest");
+
+        let registry = builder.build_topo_sorted().expect("registry should build");
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let err = registry
+            .run_rest_phase(&ctx, Router::new())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                RegistryError::AmbiguousRestHost {
+                    module: "test_rest",
+                    ..
+                }
+            ),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rest_binding_to_unknown_host_fails() {
+        let mut builder = RegistryBuilder::default();
+        register_host(
+            &mut builder,
+            "api_ingress",
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        register_rest(&mut builder, "test_rest");
+        builder.register_rest_binding_with_meta("test_rest", "admin_ingress");
+
+        let registry = builder.build_topo_sorted().expect("registry should build");
+        let ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let err = registry
+            .run_rest_phase(&ctx, Router::new())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                RegistryError::UnknownRestHost {
+                    module: "test_rest",
+                    host: "admin_ingress"
+                }
+            ),
+            "unexpected error: {err:?}"
+        );
     }
 
     #[test]
@@ -270,8 +343,8 @@ mod module_tests {
         }
     }
 
-    #[test]
-    fn test_rest_host_no_host_no_rest_modules_succeeds() {
+    #[tokio::test]
+    async fn test_rest_host_no_host_no_rest_modules_succeeds() {
         let mut builder = RegistryBuilder::default();
 
         // Register a module that's neither REST nor REST host (e.g., just core)
@@ -285,12 +358,12 @@ mod module_tests {
 
         let router = Router::new();
         // Should succeed and return router unchanged
-        let result = registry.run_rest_phase(&ctx, router);
+        let result = registry.run_rest_phase(&ctx, router).await;
         assert!(result.is_ok());
     }
 
-    #[test]
-    fn test_rest_host_prepare_finalize_call_order() {
+    #[tokio::test]
+    async fn test_rest_host_prepare_finalize_call_order() {
         let mut builder = RegistryBuilder::default();
         let call_tracker = Arc::new(Mutex::new(Vec::new()));
 
@@ -317,7 +390,7 @@ mod module_tests {
 
         let router = Router::new();
         // Run REST phase
-        let result = registry.run_rest_phase(&ctx, router);
+        let result = registry.run_rest_phase(&ctx, router).await;
         assert!(result.is_ok());
 
         // Verify the call order: prepare first, then finalize last
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use modkit::client_hub::ClientHub;
+use modkit::telemetry::render_prometheus;
+
+#[modkit::instrumented_client]
+#[async_trait::async_trait]
+pub trait Pricing: Send + Sync {
+    async fn quote(&self, sku: String) -> anyhow::Result<u64>;
+    async fn currency(&self) -> String;
+    fn region(&self) -> &'static str;
+}
+
+struct Fixed;
+
+#[async_trait::async_trait]
+impl Pricing for Fixed {
+    async fn quote(&self, sku: String) -> anyhow::Result<u64> {
+        match sku.as_str() {
+            "known" => Ok(42),
+            _ => anyhow::bail!("unknown sku {sku}"),
+        }
+    }
+    async fn currency(&self) -> String {
+        "EUR".to_string()
+    }
+    fn region(&self) -> &'static str {
+        "eu"
+    }
+}
+
+fn series(metrics: &str, name: &str, labels: &str) -> Option<String> {
+    metrics
+        .lines()
+        .find(|l| l.starts_with(name) && l.contains(labels))
+        .map(str::to_string)
+}
+
+#[tokio::test]
+async fn instrumented_hub_counts_calls_by_contract_and_method() -> anyhow::Result<()> {
+    let hub = ClientHub::instrumented();
+    hub.register::<dyn Pricing>(Arc::new(Fixed));
+    let client = hub.get::<dyn Pricing>()?;
+
+    assert_eq!(client.quote("known".into()).await?, 42);
+    assert!(client.quote("other".into()).await.is_err());
+    assert_eq!(client.currency().await, "EUR");
+    assert_eq!(client.region(), "eu");
+
+    let metrics = render_prometheus();
+    let ok = r#"contract="Pricing",method="quote",outcome="ok""#;
+    let err = r#"contract="Pricing",method="quote",outcome="error""#;
+    assert!(
+        series(&metrics, "client_calls_total", ok).is_some(),
+        "{metrics}"
+    );
+    assert!(
+        series(&metrics, "client_calls_total", err).is_some(),
+        "{metrics}"
+    );
+    assert!(series(
+        &metrics,
+        "client_call_duration_seconds_count",
+        r#"contract="Pricing",method="currency""#
+    )
+    .is_some());
+    assert!(series(&metrics, "client_calls_total", r#"method="region""#).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn plain_hub_leaves_clients_alone() -> anyhow::Result<()> {
+    let hub = ClientHub::new();
+    let client: Arc<dyn Pricing> = Arc::new(Fixed);
+    hub.register::<dyn Pricing>(client.clone());
+    assert!(Arc::ptr_eq(&hub.get::<dyn Pricing>()?, &client));
+
+    let instrumented = ClientHub::instrumented();
+    instrumented.register::<dyn Pricing>(client.clone());
+    assert!(!Arc::ptr_eq(&instrumented.get::<dyn Pricing>()?, &client));
+    Ok(())
+}
@@ -32,6 +32,66 @@ impl AutoNotify {
     }
 }
 
+#[derive(Default)]
+#[modkit::module(
+    name = "multi_task",
+    capabilities = [stateful],
+    lifecycle(entry = "serve", stop_timeout = "200ms", tasks(janitor, pump))
+)]
+struct MultiTask;
+
+#[async_trait::async_trait]
+impl modkit::Module for MultiTask {
+    async fn init(&self, _ctx: &modkit::context::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl MultiTask {
+    async fn serve(&self, cancel: CancellationToken) -> Result<()> {
+        cancel.cancelled().await;
+        Ok(())
+    }
+
+    async fn janitor(&self, cancel: CancellationToken) -> Result<()> {
+        cancel.cancelled().await;
+        Ok(())
+    }
+
+    async fn pump(&self, _cancel: CancellationToken) -> Result<()> {
+        anyhow::bail!("metrics sink unreachable")
+    }
+}
+
+#[tokio::test]
+async fn named_tasks_report_status_and_stop_together() {
+    let m = MultiTask.into_module();
+    m.start(CancellationToken::new()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert_eq!(m.task_status("janitor"), Some(Status::Running));
+    assert_eq!(m.task_status("pump"), Some(Status::Failed));
+    assert_eq!(m.task_status("missing"), None);
+    // The main task runs on, but the module reports the failure.
+    assert_eq!(m.status(), Status::Running);
+    assert_eq!(StatefulModule::status(&m), Some(Status::Failed));
+    assert_eq!(
+        m.last_error().as_deref(),
+        Some("pump: metrics sink unreachable")
+    );
+
+    m.stop(CancellationToken::new()).await.unwrap();
+    assert_eq!(m.status(), Status::Stopped);
+    assert_eq!(
+        m.task_statuses(),
+        vec![("janitor", Status::Stopped), ("pump", Status::Failed)]
+    );
+    assert_eq!(m.last_stop_reason(), Some(StopReason::Cancelled));
+}
+
 #[tokio::test]
 async fn stays_starting_until_ready_signal() {
     let m = ReadyAware.into_module();
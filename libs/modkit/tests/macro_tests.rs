@@ -34,7 +34,7 @@ impl OpenApiRegistry for TestOpenApiRegistry {
 // ---------- Test modules (must be at module scope for `inventory`) ----------
 
 #[derive(Default)]
-#[module(name = "basic")]
+#[module(name = "basic", version = "1.2.0")]
 struct BasicModule;
 
 #[async_trait]
@@ -66,8 +66,9 @@ impl DbModule for FullFeaturedModule {
         Ok(())
     }
 }
+#[async_trait]
 impl RestfulModule for FullFeaturedModule {
-    fn register_rest(
+    async fn register_rest(
         &self,
         _ctx: &modkit::context::ModuleCtx,
         router: axum::Router,
@@ -87,7 +88,7 @@ impl StatefulModule for FullFeaturedModule {
 }
 
 #[derive(Default)]
-#[module(name = "dependent", deps = ["basic", "full_featured"])]
+#[module(name = "dependent", deps = ["basic@^1.2", "full_featured"])]
 struct DependentModule;
 
 #[async_trait]
@@ -142,7 +143,7 @@ impl DbModule for DbOnlyModule {
 }
 
 #[derive(Default)]
-#[module(name = "rest_only", capabilities = [rest])]
+#[module(name = "rest_only", capabilities = [rest], host = "rest_host")]
 struct RestOnlyModule;
 #[async_trait]
 impl Module for RestOnlyModule {
@@ -153,8 +154,9 @@ impl Module for RestOnlyModule {
         self
     }
 }
+#[async_trait]
 impl RestfulModule for RestOnlyModule {
-    fn register_rest(
+    async fn register_rest(
         &self,
         _ctx: &modkit::context::ModuleCtx,
         router: axum::Router,
@@ -180,8 +182,9 @@ impl Module for TestRestHostModule {
     }
 }
 
+#[async_trait]
 impl RestHostModule for TestRestHostModule {
-    fn rest_prepare(
+    async fn rest_prepare(
         &self,
         _ctx: &modkit::context::ModuleCtx,
         router: axum::Router,
@@ -189,7 +192,7 @@ impl RestHostModule for TestRestHostModule {
         Ok(router)
     }
 
-    fn rest_finalize(
+    async fn rest_finalize(
         &self,
         _ctx: &modkit::context::ModuleCtx,
         router: axum::Router,
@@ -224,6 +227,32 @@ impl StatefulModule for StatefulOnlyModule {
     }
 }
 
+#[module(
+    name = "scheduled",
+    jobs(tick = "every 1h", report(spec = "0 0 * * * *", jitter = "30s"))
+)]
+#[derive(Default)]
+struct ScheduledModule;
+
+impl ScheduledModule {
+    async fn tick(&self, _cancel: CancellationToken) -> Result<()> {
+        Ok(())
+    }
+    async fn report(&self, _cancel: CancellationToken) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Module for ScheduledModule {
+    async fn init(&self, _ctx: &modkit::context::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
 // ---------- Tests ----------
 
 #[tokio::test]
@@ -247,11 +276,12 @@ async fn test_full_capabilities() {
     let ctx = ModuleCtxBuilder::new(CancellationToken::new()).build();
     FullFeaturedModule.init(&ctx).await.unwrap();
 
-    // REST sync phase
+    // REST phase
     let router = axum::Router::new();
     let oas = TestOpenApiRegistry;
     let _router = FullFeaturedModule
         .register_rest(&ctx, router, &oas)
+        .await
         .unwrap();
 
     // Stateful
@@ -272,7 +302,10 @@ async fn test_registry_discovery_and_phases() {
     // init → REST → start → stop
     registry.run_init_phase(&ctx).await.unwrap();
 
-    let app = registry.run_rest_phase(&ctx, axum::Router::new()).unwrap();
+    let app = registry
+        .run_rest_phase(&ctx, axum::Router::new())
+        .await
+        .unwrap();
 
     // app is a Router; just ensure type compiles
     let _ = app;
@@ -281,6 +314,108 @@ async fn test_registry_discovery_and_phases() {
     registry.run_stop_phase(cancel).await.unwrap();
 }
 
+#[test]
+fn test_jobs_are_registered() {
+    let registry = ModuleRegistry::discover_and_build().expect("registry builds");
+    let entry = registry
+        .modules()
+        .iter()
+        .find(|e| e.name == "scheduled")
+        .unwrap();
+    let names: Vec<_> = entry.jobs.iter().map(|j| j.name.as_str()).collect();
+    assert_eq!(names, ["tick", "report"]);
+}
+
+#[test]
+fn test_versions_and_requirements_are_registered() {
+    let registry = ModuleRegistry::discover_and_build().expect("registry builds");
+    let entry = |name| registry.modules().iter().find(|e| e.name == name).unwrap();
+    assert_eq!(entry("basic").version, Some("1.2.0"));
+    assert_eq!(entry("dependent").deps, ["basic@^1.2", "full_featured"]);
+}
+
+#[test]
+fn test_rest_host_binding_is_registered() {
+    let registry = ModuleRegistry::discover_and_build().expect("registry builds");
+    let bind = |name| {
+        registry
+            .modules()
+            .iter()
+            .find(|e| e.name == name)
+            .unwrap()
+            .rest_bind
+    };
+    assert_eq!(bind("rest_only"), Some("rest_host"));
+    assert_eq!(bind("full_featured"), None);
+}
+
+#[test]
+fn test_typed_lookup_and_capability_iteration() {
+    let registry = ModuleRegistry::discover_and_build().expect("registry builds");
+    assert!(registry.get_module_as::<BasicModule>().is_some());
+    assert!(registry.get_module_as::<DependentModule>().is_some());
+
+    let stateful: Vec<_> = registry
+        .modules_with_capability::<dyn StatefulModule>()
+        .map(|(name, _)| name)
+        .collect();
+    assert!(stateful.contains(&"full_featured"));
+    assert!(!stateful.contains(&"basic"));
+    let dbs = registry.modules_with_capability::<dyn DbModule>().count();
+    assert_eq!(
+        dbs,
+        registry.modules().iter().filter(|e| e.db.is_some()).count()
+    );
+}
+
+// Takes a constructor argument; `ctor` supplies one when discovered.
+#[module(name = "greeter", deps = ["basic"], ctor = Greeter::new("hello"))]
+struct Greeter {
+    greeting: String,
+}
+
+impl Greeter {
+    fn new(greeting: &str) -> Self {
+        Self {
+            greeting: greeting.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Module for Greeter {
+    async fn init(&self, _ctx: &modkit::context::ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[test]
+fn test_explicit_registration() {
+    use modkit::registry::{ModuleRegistration, RegistryBuilder};
+
+    assert_eq!(Greeter::NAME, "greeter");
+    let mut b = RegistryBuilder::new().with_module(BasicModule);
+    b.register(Greeter::new("hi"));
+    let registry = b.build_topo_sorted().expect("registry builds");
+
+    // Only what was registered, in dependency order, whatever else is linked in.
+    let names: Vec<_> = registry.modules().iter().map(|e| e.name).collect();
+    assert_eq!(names, ["basic", "greeter"]);
+    assert_eq!(registry.modules()[0].version, Some("1.2.0"));
+    let greeter = registry.get_module_as::<Greeter>().unwrap();
+    assert_eq!(greeter.greeting, "hi");
+
+    // Dependencies are still checked.
+    let err = RegistryBuilder::new()
+        .with_module(Greeter::new("hi"))
+        .build_topo_sorted()
+        .unwrap_err();
+    assert!(err.to_string().contains("basic"), "{err}");
+}
+
 #[test]
 fn test_capability_trait_markers() {
     fn assert_module<T: Module>(_: &T) {}
@@ -301,3 +436,51 @@ fn test_capability_trait_markers() {
     assert_stateful(&FullFeaturedModule);
     assert_stateful(&StatefulOnlyModule);
 }
+
+#[derive(serde::Deserialize, modkit::ConfigSchema)]
+struct RetrySchema {
+    #[config(min = 1, max = 10)]
+    attempts: u32,
+}
+
+#[derive(serde::Deserialize, modkit::ConfigSchema)]
+struct CacheSchema {
+    #[config(min = 1, max = 10_000)]
+    capacity: u32,
+    #[config(min = -1.5)]
+    offset: Option<f64>,
+    #[config(one_of = ["lru", "lfu"])]
+    policy: String,
+    #[config(required_with = "tls_cert")]
+    tls_key: Option<String>,
+    tls_cert: Option<String>,
+    #[config(nested)]
+    retry: RetrySchema,
+}
+
+#[test]
+fn test_config_schema_derive_reports_every_violation() {
+    use modkit::ConfigSchema;
+
+    let cfg: CacheSchema = serde_json::from_value(serde_json::json!({
+        "capacity": 0,
+        "offset": -2.0,
+        "policy": "fifo",
+        "tls_cert": "cert.pem",
+        "retry": { "attempts": 11 },
+    }))
+    .unwrap();
+    let fields: Vec<_> = cfg.violations().into_iter().map(|v| v.field).collect();
+    assert_eq!(
+        fields,
+        ["capacity", "offset", "policy", "tls_key", "retry.attempts"]
+    );
+
+    let ok: CacheSchema = serde_json::from_value(serde_json::json!({
+        "capacity": 5,
+        "policy": "lru",
+        "retry": { "attempts": 3 },
+    }))
+    .unwrap();
+    assert!(ok.violations().is_empty());
+}
@@ -15,7 +15,10 @@ use modkit::{
     context::{ConfigProvider, ModuleCtx},
     contracts::{DbModule, Module, OpenApiRegistry, RestfulModule, StatefulModule},
     registry::{ModuleRegistry, RegistryBuilder},
-    runtime::{run, DbOptions, RunOptions, ShutdownOptions},
+    runtime::{
+        run, ConfigReload, DbOptions, PhaseHooks, RunOptions, ShutdownHandle, ShutdownOptions,
+    },
+    secrets::SecretProvider,
 };
 
 // Test tracking infrastructure
@@ -165,8 +168,9 @@ impl DbModule for TestModule {
     }
 }
 
+#[async_trait::async_trait]
 impl RestfulModule for TestModule {
-    fn register_rest(
+    async fn register_rest(
         &self,
         _ctx: &ModuleCtx,
         router: axum::Router,
@@ -257,11 +261,10 @@ async fn test_db_options_none() {
     let cancel = CancellationToken::new();
     cancel.cancel(); // Immediate shutdown for test
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(MockConfigProvider::new()),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel),
-    };
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(cancel))
+        .build();
 
     // This test requires registry discovery to work, which won't work in isolation
     // For now, let's test the individual components we can test
@@ -282,8 +285,8 @@ async fn test_db_options_manager() {
         cancel_clone.cancel();
     });
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(MockConfigProvider::new().with_config(
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new().with_config(
             "test_module",
             serde_json::json!({
                 "database": {
@@ -291,10 +294,10 @@ async fn test_db_options_manager() {
                 },
                 "config": {}
             }),
-        )),
-        db: DbOptions::Manager(create_mock_db_manager()),
-        shutdown: ShutdownOptions::Token(cancel),
-    };
+        )))
+        .db(DbOptions::Manager(create_mock_db_manager()))
+        .shutdown(ShutdownOptions::Token(cancel))
+        .build();
 
     let result = timeout(Duration::from_millis(1000), run(opts)).await;
     assert!(result.is_ok());
@@ -303,15 +306,78 @@ async fn test_db_options_manager() {
     assert!(run_result.is_ok());
 }
 
+/// Keeps the handle `ctx.db_async()` gives it during init.
+#[derive(Default)]
+struct DbProbe {
+    handle: Mutex<Option<Arc<modkit_db::DbHandle>>>,
+}
+
+#[async_trait::async_trait]
+impl Module for DbProbe {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        *self.handle.lock().unwrap() = ctx.db_async().await?;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn test_db_options_manager_with_fallback() {
+    use figment::{providers::Serialized, Figment};
+
+    let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
+        "modules": { "own": { "database": { "dsn": "sqlite::memory:" } } }
+    })));
+    let manager = Arc::new(
+        modkit_db::DbManager::from_figment(figment, std::path::PathBuf::from("/tmp/test")).unwrap(),
+    );
+    let fallback = Arc::new(
+        modkit_db::DbHandle::connect("sqlite::memory:", modkit_db::ConnectOpts::default())
+            .await
+            .unwrap(),
+    );
+
+    let own = Arc::new(DbProbe::default());
+    let legacy = Arc::new(DbProbe::default());
+    let mut builder = RegistryBuilder::default();
+    builder.register_core_with_meta("own", &[], own.clone() as Arc<dyn Module>);
+    builder.register_core_with_meta("legacy", &[], legacy.clone() as Arc<dyn Module>);
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let opts = RunOptions::builder()
+        .db(DbOptions::ManagerWithFallback {
+            manager,
+            fallback: fallback.clone(),
+        })
+        .shutdown(ShutdownOptions::Token(cancel))
+        .registry(builder)
+        .build();
+    run(opts).await.unwrap();
+
+    // `own` has a `database` section and gets its own handle; `legacy` shares the fallback
+    let own_handle = own.handle.lock().unwrap().clone().expect("own handle");
+    let legacy_handle = legacy
+        .handle
+        .lock()
+        .unwrap()
+        .clone()
+        .expect("legacy handle");
+    assert!(!Arc::ptr_eq(&own_handle, &fallback));
+    assert!(Arc::ptr_eq(&legacy_handle, &fallback));
+}
+
 #[tokio::test]
 async fn test_shutdown_options_token() {
     let cancel = CancellationToken::new();
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(MockConfigProvider::new()),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel.clone()),
-    };
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(cancel.clone()))
+        .build();
 
     // Start the runner in a background task
     let runner_handle = tokio::spawn(run(opts));
@@ -333,13 +399,12 @@ async fn test_shutdown_options_token() {
 async fn test_shutdown_options_future() {
     let (tx, rx) = tokio::sync::oneshot::channel();
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(MockConfigProvider::new()),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Future(Box::pin(async move {
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Future(Box::pin(async move {
             let _ = rx.await;
-        })),
-    };
+        })))
+        .build();
 
     // Start the runner in a background task
     let runner_handle = tokio::spawn(run(opts));
@@ -357,6 +422,257 @@ async fn test_shutdown_options_future() {
     assert!(run_result.is_ok());
 }
 
+#[tokio::test]
+async fn test_shutdown_handle() {
+    let handle = ShutdownHandle::new();
+
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(handle.options())
+        .build();
+
+    let runner_handle = tokio::spawn(run(opts));
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!runner_handle.is_finished());
+
+    handle.shutdown();
+    assert!(handle.is_shutdown_requested());
+
+    let result = timeout(Duration::from_millis(100), runner_handle).await;
+    assert!(result.is_ok());
+    let run_result = result.unwrap().unwrap();
+    assert!(run_result.is_ok());
+}
+
+#[tokio::test]
+async fn test_run_options_builder_with_injected_registry() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let module = Arc::new(TestModule::new("curated", calls.clone()));
+    let mut builder = RegistryBuilder::default();
+    builder.register_core_with_meta("curated", &[], module.clone() as Arc<dyn Module>);
+    builder.register_stateful_with_meta("curated", module as Arc<dyn StatefulModule>);
+
+    let handle = ShutdownHandle::new();
+    let opts = RunOptions::builder()
+        .shutdown(handle.options())
+        .registry(builder)
+        .build();
+
+    let runner_handle = tokio::spawn(run(opts));
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    handle.shutdown();
+
+    let result = timeout(Duration::from_millis(100), runner_handle).await;
+    result.unwrap().unwrap().unwrap();
+    assert_eq!(
+        *calls.lock().unwrap(),
+        ["curated.init", "curated.start", "curated.stop"]
+    );
+}
+
+trait Directory: Send + Sync {
+    fn base_url(&self) -> String;
+}
+
+struct RemoteDirectory(String);
+
+impl Directory for RemoteDirectory {
+    fn base_url(&self) -> String {
+        self.0.clone()
+    }
+}
+
+modkit::inventory::submit! {
+    modkit::remote::RemoteClient {
+        module: "directory",
+        register: |hub, endpoint| {
+            hub.register::<dyn Directory>(Arc::new(RemoteDirectory(endpoint.url.clone())));
+            Ok(())
+        },
+    }
+}
+
+/// Resolves `dyn Directory` during init.
+#[derive(Default)]
+struct DirectoryConsumer {
+    seen: Mutex<Option<String>>,
+}
+
+#[async_trait::async_trait]
+impl Module for DirectoryConsumer {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        *self.seen.lock().unwrap() = Some(ctx.client::<dyn Directory>()?.base_url());
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn test_remote_module_is_replaced_by_its_client() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let directory = Arc::new(TestModule::new("directory", calls.clone()));
+    let consumer = Arc::new(DirectoryConsumer::default());
+    let mut builder = RegistryBuilder::default();
+    builder.register_core_with_meta("directory", &[], directory as Arc<dyn Module>);
+    builder.register_core_with_meta(
+        "consumer",
+        &["directory"],
+        consumer.clone() as Arc<dyn Module>,
+    );
+
+    let cfg = MockConfigProvider::new().with_config(
+        "directory",
+        serde_json::json!({ "remote": { "url": "http://directory:8080" } }),
+    );
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(cfg))
+        .shutdown(ShutdownOptions::Token(cancel))
+        .registry(builder)
+        .build();
+
+    timeout(Duration::from_secs(1), run(opts))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(calls.lock().unwrap().is_empty());
+    assert_eq!(
+        consumer.seen.lock().unwrap().as_deref(),
+        Some("http://directory:8080")
+    );
+}
+
+/// Reads the shared `String` resource during init.
+#[derive(Default)]
+struct ResourceProbe {
+    seen: Mutex<Option<String>>,
+}
+
+#[async_trait::async_trait]
+impl Module for ResourceProbe {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        *self.seen.lock().unwrap() = Some(ctx.resource_required::<String>()?.to_string());
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[tokio::test]
+async fn test_resources_reach_every_module() {
+    let probe = Arc::new(ResourceProbe::default());
+    let mut builder = RegistryBuilder::default();
+    builder.register_core_with_meta("probe", &[], probe.clone() as Arc<dyn Module>);
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let opts = RunOptions::builder()
+        .shutdown(ShutdownOptions::Token(cancel))
+        .registry(builder)
+        .resource(Arc::new("redis://cache".to_string()))
+        .build();
+
+    timeout(Duration::from_secs(1), run(opts))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(probe.seen.lock().unwrap().as_deref(), Some("redis://cache"));
+}
+
+/// Reads `token` from its config section during init.
+#[derive(Default)]
+struct SecretProbe {
+    seen: Mutex<Option<String>>,
+}
+
+#[async_trait::async_trait]
+impl Module for SecretProbe {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        let cfg: serde_json::Value = ctx.config()?;
+        *self.seen.lock().unwrap() = cfg["token"].as_str().map(str::to_string);
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+struct StaticSecrets;
+
+impl SecretProvider for StaticSecrets {
+    fn get(&self, key: &str) -> anyhow::Result<String> {
+        match key {
+            "billing/token" => Ok("s3cret".to_string()),
+            _ => anyhow::bail!("no such secret"),
+        }
+    }
+}
+
+fn secret_run_options(reference: &str) -> (Arc<SecretProbe>, RunOptions) {
+    let probe = Arc::new(SecretProbe::default());
+    let mut builder = RegistryBuilder::default();
+    builder.register_core_with_meta("billing", &[], probe.clone() as Arc<dyn Module>);
+    let cfg = MockConfigProvider::new().with_config(
+        "billing",
+        serde_json::json!({ "config": { "token": format!("Bearer {reference}") } }),
+    );
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(cfg))
+        .shutdown(ShutdownOptions::Token(cancel))
+        .registry(builder)
+        .secret_provider("static", Arc::new(StaticSecrets))
+        .build();
+    (probe, opts)
+}
+
+#[tokio::test]
+async fn test_secret_references_are_resolved_before_init() {
+    let (probe, opts) = secret_run_options("${secret:static:billing/token}");
+
+    timeout(Duration::from_secs(1), run(opts))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(probe.seen.lock().unwrap().as_deref(), Some("Bearer s3cret"));
+}
+
+#[tokio::test]
+async fn test_unresolved_secret_stops_startup() {
+    let (probe, opts) = secret_run_options("${secret:static:other}");
+
+    let err = run(opts).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("module 'billing' config.token: ${secret:static:other}: no such secret"),
+        "unexpected error: {err}"
+    );
+    assert!(probe.seen.lock().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_run_options_builder_rejects_unknown_module() {
+    let opts = RunOptions::builder()
+        .shutdown(ShutdownOptions::Token(CancellationToken::new()))
+        .modules(&["not_compiled_in"])
+        .build();
+
+    let err = run(opts).await.unwrap_err();
+    assert!(
+        format!("{err:?}").contains("Module 'not_compiled_in' is not compiled in"),
+        "unexpected error: {err:?}"
+    );
+}
+
 #[tokio::test]
 async fn test_runner_with_config_provider() {
     let cancel = CancellationToken::new();
@@ -370,11 +686,10 @@ async fn test_runner_with_config_provider() {
         }),
     );
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(config_provider),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel),
-    };
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(config_provider))
+        .shutdown(ShutdownOptions::Token(cancel))
+        .build();
 
     let result = timeout(Duration::from_millis(100), run(opts)).await;
     assert!(result.is_ok());
@@ -388,25 +703,140 @@ async fn test_complete_lifecycle_success() {
     let cancel = CancellationToken::new();
     cancel.cancel(); // Immediate shutdown
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(MockConfigProvider::new()),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel),
-    };
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(cancel))
+        .build();
 
     let result = run(opts).await;
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_phase_hooks_wrap_every_phase() {
+    use modkit::registry::Phase;
+
+    let cancel = CancellationToken::new();
+    cancel.cancel(); // Immediate shutdown
+
+    let events = Arc::new(Mutex::new(Vec::<String>::new()));
+    let mut hooks = PhaseHooks::new();
+    for phase in [
+        Phase::Init,
+        Phase::DbMigrate,
+        Phase::Rest,
+        Phase::Start,
+        Phase::Stop,
+    ] {
+        let (before, after) = (events.clone(), events.clone());
+        hooks = hooks
+            .before(phase, move |_| {
+                before.lock().unwrap().push(format!("before {phase}"));
+                async { Ok(()) }
+            })
+            .after(phase, move |_| {
+                after.lock().unwrap().push(format!("after {phase}"));
+                async { Ok(()) }
+            });
+    }
+
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(cancel))
+        .hooks(hooks)
+        .build();
+    run(opts).await.unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(
+        *events,
+        [
+            "before init",
+            "after init",
+            "before db migration",
+            "after db migration",
+            "before rest",
+            "after rest",
+            "before start",
+            "after start",
+            "before stop",
+            "after stop",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_failing_hook_aborts_run() {
+    use modkit::registry::Phase;
+
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(CancellationToken::new()))
+        .hooks(
+            PhaseHooks::new().before(Phase::Init, |_| async { anyhow::bail!("license expired") }),
+        )
+        .build();
+
+    let err = timeout(Duration::from_secs(1), run(opts))
+        .await
+        .expect("run should fail fast")
+        .unwrap_err();
+    assert_eq!(err.to_string(), "hook before init failed");
+    assert_eq!(err.root_cause().to_string(), "license expired");
+}
+
+#[tokio::test]
+async fn test_config_file_change_triggers_reload() {
+    let path = std::env::temp_dir().join(format!("modkit-reload-{}.json", std::process::id()));
+    std::fs::write(&path, "{}").unwrap();
+
+    let loads = Arc::new(Mutex::new(0));
+    let reload = ConfigReload::new({
+        let loads = loads.clone();
+        move || {
+            *loads.lock().unwrap() += 1;
+            Ok(Arc::new(MockConfigProvider::new()) as Arc<dyn ConfigProvider>)
+        }
+    })
+    .watch_file(&path)
+    .poll_interval(Duration::from_millis(20));
+
+    let cancel = CancellationToken::new();
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(cancel.clone()))
+        .config_reload(reload)
+        .build();
+    let handle = tokio::spawn(run(opts));
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(*loads.lock().unwrap(), 0, "unchanged file must not reload");
+
+    // Bump the mtime explicitly: rewrites within the same clock tick look unchanged.
+    let file = std::fs::File::options().write(true).open(&path).unwrap();
+    file.set_modified(std::time::SystemTime::now() + Duration::from_secs(5))
+        .unwrap();
+    timeout(Duration::from_secs(2), async {
+        while *loads.lock().unwrap() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("changed file should be reloaded");
+
+    cancel.cancel();
+    handle.await.unwrap().unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
 #[test]
 fn test_run_options_construction() {
     let cancel = CancellationToken::new();
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(MockConfigProvider::new()),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel),
-    };
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(cancel))
+        .build();
 
     // Test that we can construct RunOptions with all variants
     match opts.db {
@@ -424,11 +854,10 @@ fn test_run_options_construction() {
 async fn test_cancellation_during_startup() {
     let cancel = CancellationToken::new();
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(MockConfigProvider::new()),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel.clone()),
-    };
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(cancel.clone()))
+        .build();
 
     // Start the runner in a background task
     let runner_handle = tokio::spawn(run(opts));
@@ -457,11 +886,10 @@ async fn test_multiple_config_provider_scenarios() {
 
     // Test with empty config
     let empty_config = MockConfigProvider::new();
-    let opts = RunOptions {
-        modules_cfg: Arc::new(empty_config),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel.clone()),
-    };
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(empty_config))
+        .shutdown(ShutdownOptions::Token(cancel.clone()))
+        .build();
 
     let result = run(opts).await;
     assert!(result.is_ok(), "Should handle empty config");
@@ -489,11 +917,10 @@ async fn test_multiple_config_provider_scenarios() {
     let cancel2 = CancellationToken::new();
     cancel2.cancel();
 
-    let opts2 = RunOptions {
-        modules_cfg: Arc::new(complex_config),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel2),
-    };
+    let opts2 = RunOptions::builder()
+        .modules_cfg(Arc::new(complex_config))
+        .shutdown(ShutdownOptions::Token(cancel2))
+        .build();
 
     let result2 = run(opts2).await;
     assert!(result2.is_ok(), "Should handle complex config");
@@ -504,11 +931,10 @@ async fn test_runner_timeout_scenarios() {
     // Test that runner doesn't hang indefinitely
     let cancel = CancellationToken::new();
 
-    let opts = RunOptions {
-        modules_cfg: Arc::new(MockConfigProvider::new()),
-        db: DbOptions::None,
-        shutdown: ShutdownOptions::Token(cancel.clone()),
-    };
+    let opts = RunOptions::builder()
+        .modules_cfg(Arc::new(MockConfigProvider::new()))
+        .shutdown(ShutdownOptions::Token(cancel.clone()))
+        .build();
 
     let runner_handle = tokio::spawn(run(opts));
 
@@ -17,10 +17,20 @@ use crate::paths::home_dir::resolve_home_dir;
 pub struct ModuleEntry {
     #[serde(default)]
     pub database: Option<DbConnConfig>,
+    /// Path prefix the module's REST routes are mounted under (e.g. `/api/users-info`).
+    #[serde(default)]
+    pub rest_prefix: Option<String>,
+    /// `false` drops the module from the registry at startup.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
     #[serde(default)]
     pub config: serde_json::Value,
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
 /// Main application configuration with strongly-typed global sections
 /// and a flexible per-module configuration bag.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -32,12 +42,25 @@ pub struct AppConfig {
     pub database: Option<GlobalDatabaseConfig>,
     /// Logging configuration (optional, uses defaults if None).
     pub logging: Option<LoggingConfig>,
+    /// Metrics and tracing export settings (optional).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<TelemetryConfig>,
     /// Directory containing per-module YAML files (optional).
     #[serde(default)]
     pub modules_dir: Option<String>,
+    /// Directory of WASM plugin manifests loaded at startup (optional).
+    #[serde(default)]
+    pub plugins_dir: Option<String>,
     /// Per-module configuration bag: module_name → arbitrary JSON/YAML value.
     #[serde(default)]
     pub modules: HashMap<String, serde_json::Value>,
+    /// Profile (`dev`, `staging`, `prod`, ...) whose overlay file was merged;
+    /// after loading, the effective one (see [`PROFILE_ENV`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Files merged right after the main one (see [`include_paths`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -50,6 +73,68 @@ pub struct ServerConfig {
     pub timeout_sec: u64,
 }
 
+/// `telemetry` section of the app config.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryConfig {
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    /// OTLP trace export; off when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracing: Option<TracingConfig>,
+}
+
+/// Where and how spans are shipped to an OpenTelemetry collector.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TracingConfig {
+    /// Collector URL; empty means `localhost` on the protocol's default port.
+    #[serde(default)]
+    pub endpoint: String,
+    /// `grpc` (default) or `http`.
+    #[serde(default = "default_otlp_protocol")]
+    pub protocol: String,
+    /// Sent with every export, e.g. `authorization: "Bearer ..."`.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Resource attributes; `service.name` defaults to `hyperspot`.
+    #[serde(default)]
+    pub resource: HashMap<String, String>,
+    /// OpenTelemetry sampler name, e.g. `parentbased_traceidratio`.
+    #[serde(default = "default_sampler")]
+    pub sampler: String,
+    /// Ratio for the `traceidratio` samplers.
+    #[serde(default)]
+    pub sampler_arg: Option<f64>,
+    /// Per-export timeout.
+    #[serde(default = "default_otlp_timeout", with = "humantime_serde")]
+    pub timeout: std::time::Duration,
+}
+
+fn default_otlp_protocol() -> String {
+    "grpc".to_string()
+}
+
+fn default_sampler() -> String {
+    "parentbased_always_on".to_string()
+}
+
+fn default_otlp_timeout() -> std::time::Duration {
+    std::time::Duration::from_secs(10)
+}
+
+/// Global settings of the Prometheus registry.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// Prepended to every metric name, e.g. `hyperspot`.
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Labels added to every series, e.g. `env: prod`.
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
 /// Logging configuration - maps subsystem names to their logging settings.
 /// Key "default" is the catch-all for logs that don't match explicit subsystems.
 pub type LoggingConfig = HashMap<String, Section>;
@@ -108,40 +193,92 @@ impl Default for AppConfig {
                 auto_provision: None,
             }),
             logging: Some(default_logging_config()),
+            telemetry: None,
             modules_dir: None,
+            plugins_dir: None,
             modules: HashMap::new(),
+            profile: None,
+            include: Vec::new(),
         }
     }
 }
 
 impl AppConfig {
-    /// Load configuration with layered loading: defaults → YAML file → environment variables.
+    /// Load configuration with layered loading, later layers winning:
+    /// defaults → YAML file → `include` files → profile overlay → `APP__` env →
+    /// `modules_dir` files → `HYPERSPOT__` module env.
     /// Also normalizes `server.home_dir` into an absolute path and creates the directory.
     pub fn load_layered<P: AsRef<Path>>(config_path: P) -> Result<Self> {
+        Self::load_layered_with_profile(config_path, std::env::var(PROFILE_ENV).ok())
+    }
+
+    /// [`AppConfig::load_layered`] with the profile from the environment given
+    /// explicitly (`None` falls back to the file's `profile` key).
+    pub fn load_layered_with_profile<P: AsRef<Path>>(
+        config_path: P,
+        env_profile: Option<String>,
+    ) -> Result<Self> {
         use figment::{
             providers::{Env, Format, Serialized, Yaml},
             Figment,
         };
 
+        let config_path = config_path.as_ref();
+        let profile = match env_profile.filter(|p| !p.trim().is_empty()) {
+            Some(p) => Some(p),
+            None => Figment::from(Yaml::file(config_path))
+                .extract_inner::<Option<String>>("profile")
+                .unwrap_or(None),
+        };
+        let overlay = profile
+            .as_deref()
+            .map(|p| profile_overlay_path(config_path, p))
+            .transpose()?;
+        let include = Figment::from(Yaml::file(config_path))
+            .extract_inner::<Vec<String>>("include")
+            .unwrap_or_default();
+        let included = include_paths(config_path, &include)?;
+
         // For layered loading, start from a minimal base where optional sections are None,
         // so they remain None unless explicitly provided by YAML/ENV.
         let base = AppConfig {
             server: ServerConfig::default(),
             database: None,
             logging: None,
+            telemetry: None,
             modules_dir: None,
+            plugins_dir: None,
             modules: HashMap::new(),
+            profile: None,
+            include: Vec::new(),
         };
 
-        let figment = Figment::new()
+        let mut figment = Figment::new()
             .merge(Serialized::defaults(base))
-            .merge(Yaml::file(config_path.as_ref()))
-            // Example: APP__SERVER__PORT=8087 maps to server.port
-            .merge(Env::prefixed("APP__").split("__"));
+            .merge(Yaml::file(config_path));
+        for path in &included {
+            let nested = Figment::from(Yaml::file(path))
+                .find_value("include")
+                .is_ok();
+            if nested {
+                anyhow::bail!(
+                    "included config {} has its own `include`; nested includes are not supported",
+                    path.display()
+                );
+            }
+            figment = figment.merge(Yaml::file(path));
+        }
+        if let Some(overlay) = &overlay {
+            figment = figment.merge(Yaml::file(overlay));
+        }
+        // Example: APP__SERVER__PORT=8087 maps to server.port
+        figment = figment.merge(Env::prefixed("APP__").split("__"));
 
         let mut config: AppConfig = figment
             .extract()
             .with_context(|| "Failed to extract config from figment".to_string())?;
+        config.profile = profile;
+        config.include = include;
 
         // Normalize + create home_dir immediately.
         normalize_home_dir_inplace(&mut config.server)
@@ -152,6 +289,11 @@ impl AppConfig {
             merge_module_files(&mut config.modules, dir)?;
         }
 
+        // Per-module env overrides win over both the main file and module files.
+        apply_module_env_overrides(&mut config.modules, std::env::vars());
+
+        expand_env_in_modules(&mut config.modules, |name| std::env::var(name).ok())?;
+
         Ok(config)
     }
 
@@ -164,6 +306,8 @@ impl AppConfig {
                 let mut c = Self::default();
                 normalize_home_dir_inplace(&mut c.server)
                     .context("Failed to resolve server.home_dir (defaults)")?;
+                apply_module_env_overrides(&mut c.modules, std::env::vars());
+                expand_env_in_modules(&mut c.modules, |name| std::env::var(name).ok())?;
                 Ok(c)
             }
         }
@@ -174,8 +318,9 @@ impl AppConfig {
         serde_yaml::to_string(self).context("Failed to serialize config to YAML")
     }
 
-    /// Apply overrides from command line arguments.
-    pub fn apply_cli_overrides(&mut self, args: &CliArgs) {
+    /// Apply overrides from command line arguments; `--set` ones last, so they
+    /// win over every other layer.
+    pub fn apply_cli_overrides(&mut self, args: &CliArgs) -> Result<()> {
         if let Some(port) = args.port {
             self.server.port = port;
         }
@@ -189,9 +334,119 @@ impl AppConfig {
                 _ => "trace".to_string(),
             };
         }
+
+        self.apply_set_overrides(&args.set)
+    }
+
+    /// Apply `key.path=value` overrides (`--set`), e.g.
+    /// `modules.api_ingress.config.cors_enabled=false`.
+    ///
+    /// Values are parsed like `HYPERSPOT__` overrides. The result must still
+    /// be a valid config, so misspelled top-level keys are rejected.
+    pub fn apply_set_overrides(&mut self, sets: &[String]) -> Result<()> {
+        if sets.is_empty() {
+            return Ok(());
+        }
+        let mut root = serde_json::to_value(&*self).context("Failed to serialize config")?;
+        for set in sets {
+            let (path, raw) = set
+                .split_once('=')
+                .with_context(|| format!("--set '{set}': expected KEY=VALUE"))?;
+            let keys: Vec<String> = path.trim().split('.').map(str::to_string).collect();
+            if keys.iter().any(String::is_empty) {
+                anyhow::bail!("--set '{set}': empty key in '{path}'");
+            }
+            set_path(&mut root, &keys, parse_override_value(raw));
+        }
+        *self = serde_json::from_value(root).context("Invalid --set override")?;
+        Ok(())
     }
 }
 
+/// Environment variable selecting the config profile; wins over the `profile` key.
+pub const PROFILE_ENV: &str = "HYPERSPOT_PROFILE";
+
+/// Overlay file of `profile` next to `config_path`: `config.yaml` → `config.dev.yaml`.
+///
+/// The overlay must exist, so a misspelled profile fails instead of silently
+/// running with the base config.
+pub fn profile_overlay_path(config_path: &Path, profile: &str) -> Result<PathBuf> {
+    if !profile
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("invalid config profile '{profile}': use letters, digits, '-' and '_'");
+    }
+    let stem = config_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("config");
+    let ext = config_path
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("yaml");
+    let overlay = config_path.with_file_name(format!("{stem}.{profile}.{ext}"));
+    if !overlay.is_file() {
+        anyhow::bail!(
+            "config profile '{profile}' has no overlay file {}",
+            overlay.display()
+        );
+    }
+    Ok(overlay)
+}
+
+/// Files for the `include` entries of `config_path`, in merge order.
+///
+/// Relative entries are resolved against the directory of `config_path`. An
+/// entry is a file, a directory (conf.d style: its `*.yaml` / `*.yml` files by
+/// name), or a pattern with one `*` in the file name (`teams/*.yaml`). Missing
+/// files and directories are errors; a pattern may match nothing.
+pub fn include_paths(config_path: &Path, include: &[String]) -> Result<Vec<PathBuf>> {
+    let base = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut paths = Vec::new();
+    for entry in include {
+        let path = base.join(entry);
+        let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        let (dir, pattern) = if let Some((prefix, suffix)) = file_name.split_once('*') {
+            (
+                path.parent().unwrap_or(base).to_path_buf(),
+                Some((prefix, suffix)),
+            )
+        } else if path.is_dir() {
+            (path.clone(), None)
+        } else if path.is_file() {
+            paths.push(path);
+            continue;
+        } else {
+            anyhow::bail!("included config {} does not exist", path.display());
+        };
+
+        let mut matched = Vec::new();
+        let read = std::fs::read_dir(&dir)
+            .with_context(|| format!("failed to read include directory {}", dir.display()))?;
+        for item in read {
+            let item = item?.path();
+            let Some(name) = item.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let selected = match pattern {
+                Some((prefix, suffix)) => {
+                    name.len() >= prefix.len() + suffix.len()
+                        && name.starts_with(prefix)
+                        && name.ends_with(suffix)
+                }
+                None => name.ends_with(".yaml") || name.ends_with(".yml"),
+            };
+            if selected && item.is_file() {
+                matched.push(item);
+            }
+        }
+        matched.sort();
+        paths.extend(matched);
+    }
+    Ok(paths)
+}
+
 /// Command line arguments structure.
 #[derive(Debug, Clone)]
 pub struct CliArgs {
@@ -200,6 +455,8 @@ pub struct CliArgs {
     pub print_config: bool,
     pub verbose: u8,
     pub mock: bool,
+    /// `key.path=value` overrides, applied after every other layer.
+    pub set: Vec<String>,
 }
 
 // TODO: should be pass from outside
@@ -259,6 +516,160 @@ fn merge_module_files(
     Ok(())
 }
 
+/// Prefix of per-module overrides: `HYPERSPOT__<MODULE>__<KEY>[__<KEY>...]`.
+pub const MODULE_ENV_PREFIX: &str = "HYPERSPOT__";
+
+/// Apply `HYPERSPOT__<MODULE>__<KEY>` variables to the module sections.
+///
+/// Names are lowercased and split on `__`, so
+/// `HYPERSPOT__USERS_INFO__CONFIG__DEFAULT_PAGE_SIZE=50` sets
+/// `modules.users_info.config.default_page_size` to `50`. Values that parse as
+/// JSON (numbers, booleans, arrays) keep their type; anything else is a string.
+/// Missing modules and intermediate objects are created.
+pub fn apply_module_env_overrides(
+    modules: &mut HashMap<String, serde_json::Value>,
+    vars: impl IntoIterator<Item = (String, String)>,
+) {
+    let mut overrides: Vec<(Vec<String>, String)> = vars
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let rest = name.strip_prefix(MODULE_ENV_PREFIX)?;
+            let path: Vec<String> = rest.split("__").map(str::to_ascii_lowercase).collect();
+            // Need a module and at least one key, none of them empty.
+            (path.len() >= 2 && path.iter().all(|s| !s.is_empty())).then_some((path, value))
+        })
+        .collect();
+    // Deterministic order: a parent set after its children would erase them.
+    overrides.sort();
+
+    for (path, raw) in overrides {
+        let (module, keys) = path.split_first().expect("at least two segments");
+        let node = modules
+            .entry(module.clone())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        set_path(node, keys, parse_override_value(&raw));
+    }
+}
+
+/// JSON when it parses (numbers, booleans, arrays), else the raw string.
+fn parse_override_value(raw: &str) -> serde_json::Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| serde_json::Value::String(raw.to_string()))
+}
+
+/// Set `root.<keys...>` to `value`, creating (or replacing non-object)
+/// intermediate nodes.
+fn set_path(root: &mut serde_json::Value, keys: &[String], value: serde_json::Value) {
+    let mut node = root;
+    for key in keys {
+        if !node.is_object() {
+            *node = serde_json::Value::Object(Default::default());
+        }
+        node = node
+            .as_object_mut()
+            .expect("just made an object")
+            .entry(key.clone())
+            .or_insert(serde_json::Value::Null);
+    }
+    *node = value;
+}
+
+/// A `${VAR}` placeholder with no value and no default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedEnvVar {
+    pub module: String,
+    /// Dotted path of the string within the module section.
+    pub path: String,
+    pub var: String,
+}
+
+impl std::fmt::Display for UnresolvedEnvVar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "module '{}' {}: ${{{}}}",
+            self.module, self.path, self.var
+        )
+    }
+}
+
+/// Every placeholder of the module sections that could not be expanded.
+#[derive(Debug, thiserror::Error)]
+#[error("unresolved environment variables: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+pub struct UnresolvedEnvVars(pub Vec<UnresolvedEnvVar>);
+
+/// Expand `${VAR}` and `${VAR:-default}` in every string of every module
+/// section, looking variables up with `lookup`. The default applies when the
+/// variable is unset or empty. `${secret:...}` references are left for the
+/// runner. Nothing is changed unless every placeholder resolves.
+pub fn expand_env_in_modules(
+    modules: &mut HashMap<String, serde_json::Value>,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<(), UnresolvedEnvVars> {
+    let re = regex::Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}").unwrap();
+    let mut expanded = modules.clone();
+    let mut errors = Vec::new();
+    let mut names: Vec<&String> = modules.keys().collect();
+    names.sort();
+    for name in names {
+        let section = expanded.get_mut(name).expect("cloned from modules");
+        expand_env_in_value(&re, &lookup, name, "", section, &mut errors);
+    }
+    if !errors.is_empty() {
+        return Err(UnresolvedEnvVars(errors));
+    }
+    *modules = expanded;
+    Ok(())
+}
+
+fn expand_env_in_value(
+    re: &regex::Regex,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    module: &str,
+    path: &str,
+    value: &mut serde_json::Value,
+    errors: &mut Vec<UnresolvedEnvVar>,
+) {
+    let child = |key: &dyn std::fmt::Display| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    match value {
+        serde_json::Value::String(s) if s.contains("${") => {
+            let out = re.replace_all(s, |cap: &regex::Captures| {
+                match (lookup(&cap[1]), cap.get(2)) {
+                    // Like the shell, `:-` also replaces an empty value.
+                    (Some(v), Some(default)) if v.is_empty() => default.as_str().to_string(),
+                    (Some(v), _) => v,
+                    (None, Some(default)) => default.as_str().to_string(),
+                    (None, None) => {
+                        errors.push(UnresolvedEnvVar {
+                            module: module.to_string(),
+                            path: path.to_string(),
+                            var: cap[1].to_string(),
+                        });
+                        String::new()
+                    }
+                }
+            });
+            *s = out.into_owned();
+        }
+        serde_json::Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                expand_env_in_value(re, lookup, module, &child(&i), item, errors);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                expand_env_in_value(re, lookup, module, &child(key), item, errors);
+            }
+        }
+        _ => {}
+    }
+}
+
 // ---- New ModKit DB Handling Functions ----
 
 /// Expands environment variables in a DSN string.
@@ -984,9 +1395,10 @@ server:
             print_config: false,
             verbose: 2, // trace
             mock: false,
+            set: Vec::new(),
         };
 
-        config.apply_cli_overrides(&args);
+        config.apply_cli_overrides(&args).unwrap();
 
         // Port override
         assert_eq!(config.server.port, 3000);
@@ -997,6 +1409,48 @@ server:
         assert_eq!(default_section.console_level, "trace");
     }
 
+    #[test]
+    fn test_set_overrides_win_over_other_layers() {
+        let mut config = AppConfig::default();
+        config.modules.insert(
+            "api_ingress".to_string(),
+            serde_json::json!({ "config": { "cors_enabled": true, "bind_addr": "0.0.0.0:1" } }),
+        );
+        let args = super::CliArgs {
+            config: None,
+            port: Some(3000),
+            print_config: false,
+            verbose: 0,
+            mock: false,
+            set: [
+                "modules.api_ingress.config.cors_enabled=false",
+                "modules.billing.config.currency=EUR",
+                "server.port=4000",
+            ]
+            .map(String::from)
+            .to_vec(),
+        };
+
+        config.apply_cli_overrides(&args).unwrap();
+
+        assert_eq!(config.server.port, 4000);
+        let ingress = &config.modules["api_ingress"]["config"];
+        assert_eq!(ingress["cors_enabled"], false);
+        assert_eq!(ingress["bind_addr"], "0.0.0.0:1");
+        assert_eq!(config.modules["billing"]["config"]["currency"], "EUR");
+
+        for bad in [
+            "server.port",
+            "server..port=1",
+            "server.port=abc",
+            "servr.port=1",
+        ] {
+            let err = config.apply_set_overrides(&[bad.to_string()]);
+            assert!(err.is_err(), "{bad} should be rejected");
+        }
+        assert_eq!(config.server.port, 4000);
+    }
+
     #[test]
     fn test_cli_verbose_levels_matrix() {
         for (verbose_level, expected_log_level) in [
@@ -1012,9 +1466,10 @@ server:
                 print_config: false,
                 verbose: verbose_level,
                 mock: false,
+                set: Vec::new(),
             };
 
-            config.apply_cli_overrides(&args);
+            config.apply_cli_overrides(&args).unwrap();
 
             let logging = config.logging.as_ref().unwrap();
             let default_section = &logging["default"];
@@ -1078,6 +1533,215 @@ modules:
         assert_eq!(test_module["setting2"], 42);
     }
 
+    #[test]
+    fn test_module_env_overrides() {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "users_info".to_string(),
+            serde_json::json!({
+                "enabled": true,
+                "config": { "default_page_size": 10, "name": "users" }
+            }),
+        );
+        let vars = [
+            ("HYPERSPOT__USERS_INFO__CONFIG__DEFAULT_PAGE_SIZE", "50"),
+            ("HYPERSPOT__USERS_INFO__ENABLED", "false"),
+            ("HYPERSPOT__USERS_INFO__CONFIG__TAGS", r#"["a","b"]"#),
+            ("HYPERSPOT__USERS_INFO__REMOTE__URL", "http://users:8080"),
+            ("HYPERSPOT__BILLING__CONFIG__CURRENCY", "EUR"),
+            ("HYPERSPOT__NOKEY", "ignored"),
+            ("APP__SERVER__PORT", "1"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+
+        apply_module_env_overrides(&mut modules, vars);
+
+        let users = &modules["users_info"];
+        assert_eq!(users["enabled"], false);
+        assert_eq!(users["config"]["default_page_size"], 50);
+        assert_eq!(users["config"]["name"], "users");
+        assert_eq!(users["config"]["tags"], serde_json::json!(["a", "b"]));
+        assert_eq!(users["remote"]["url"], "http://users:8080");
+        assert_eq!(modules["billing"]["config"]["currency"], "EUR");
+        assert!(!modules.contains_key("nokey"));
+        assert_eq!(modules.len(), 2);
+    }
+
+    #[test]
+    fn test_env_placeholders_expand_in_every_module_section() {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "billing".to_string(),
+            serde_json::json!({
+                "config": {
+                    "url": "https://${BILLING_HOST}:${BILLING_PORT:-443}/v1",
+                    "hosts": ["${BILLING_HOST}", "${REGION:-eu}"],
+                    "api_key": "${secret:env:BILLING_KEY}",
+                    "retries": 3
+                }
+            }),
+        );
+        let lookup = |name: &str| (name == "BILLING_HOST").then(|| "pay.internal".to_string());
+
+        expand_env_in_modules(&mut modules, lookup).unwrap();
+
+        let cfg = &modules["billing"]["config"];
+        assert_eq!(cfg["url"], "https://pay.internal:443/v1");
+        assert_eq!(cfg["hosts"], serde_json::json!(["pay.internal", "eu"]));
+        assert_eq!(cfg["api_key"], "${secret:env:BILLING_KEY}");
+        assert_eq!(cfg["retries"], 3);
+
+        modules.insert(
+            "users".to_string(),
+            serde_json::json!({ "config": { "a": "${MISSING_A}", "b": ["${MISSING_B}"] } }),
+        );
+        let before = modules.clone();
+        let err = expand_env_in_modules(&mut modules, lookup).unwrap_err();
+        let found: Vec<_> = err
+            .0
+            .iter()
+            .map(|e| (e.module.as_str(), e.path.as_str(), e.var.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("users", "config.a", "MISSING_A"),
+                ("users", "config.b.0", "MISSING_B")
+            ]
+        );
+        assert_eq!(modules, before);
+    }
+
+    #[test]
+    fn test_load_layered_applies_module_env_overrides() {
+        let tmp = tempdir().unwrap();
+        let cfg_path = tmp.path().join("env_override.yaml");
+        let home = tmp.path().to_string_lossy().replace('\\', "/");
+        fs::write(
+            &cfg_path,
+            format!(
+                r#"
+server:
+  home_dir: "{home}/home"
+modules:
+  env_probe:
+    config:
+      level: 1
+"#
+            ),
+        )
+        .unwrap();
+
+        env::set_var("HYPERSPOT__ENV_PROBE__CONFIG__LEVEL", "7");
+        let config = AppConfig::load_layered(&cfg_path).unwrap();
+        env::remove_var("HYPERSPOT__ENV_PROBE__CONFIG__LEVEL");
+
+        assert_eq!(config.modules["env_probe"]["config"]["level"], 7);
+    }
+
+    #[test]
+    fn test_include_files_and_directories_merge_in_order() {
+        let tmp = tempdir().unwrap();
+        let cfg_path = tmp.path().join("config.yaml");
+        let home = tmp.path().to_string_lossy().replace('\\', "/");
+        fs::write(
+            &cfg_path,
+            format!(
+                r#"
+server:
+  home_dir: "{home}/home"
+  port: 8087
+include:
+  - server.yaml
+  - conf.d
+modules:
+  billing:
+    config:
+      currency: USD
+"#
+            ),
+        )
+        .unwrap();
+        fs::write(tmp.path().join("server.yaml"), "server:\n  port: 9000\n").unwrap();
+        let conf_d = tmp.path().join("conf.d");
+        fs::create_dir(&conf_d).unwrap();
+        fs::write(
+            conf_d.join("10-billing.yaml"),
+            "modules:\n  billing:\n    config:\n      currency: EUR\n      region: eu\n",
+        )
+        .unwrap();
+        fs::write(
+            conf_d.join("20-billing.yml"),
+            "modules:\n  billing:\n    config:\n      region: us\n",
+        )
+        .unwrap();
+        fs::write(conf_d.join("README.md"), "not config").unwrap();
+
+        let config = AppConfig::load_layered_with_profile(&cfg_path, None).unwrap();
+        assert_eq!(config.server.port, 9000);
+        let billing = &config.modules["billing"]["config"];
+        assert_eq!(billing["currency"], "EUR");
+        assert_eq!(billing["region"], "us");
+        assert_eq!(config.include, ["server.yaml", "conf.d"]);
+
+        let found = include_paths(&cfg_path, &["conf.d/*-billing.yml".to_string()]).unwrap();
+        assert_eq!(found, [conf_d.join("20-billing.yml")]);
+        assert!(include_paths(&cfg_path, &["missing.yaml".to_string()]).is_err());
+
+        fs::write(conf_d.join("30-nested.yaml"), "include: [other.yaml]\n").unwrap();
+        let err = AppConfig::load_layered_with_profile(&cfg_path, None).unwrap_err();
+        assert!(format!("{err:#}").contains("nested includes"), "{err:#}");
+    }
+
+    #[test]
+    fn test_profile_overlay_merges_over_base() {
+        let tmp = tempdir().unwrap();
+        let cfg_path = tmp.path().join("config.yaml");
+        let home = tmp.path().to_string_lossy().replace('\\', "/");
+        fs::write(
+            &cfg_path,
+            format!(
+                r#"
+profile: dev
+server:
+  home_dir: "{home}/home"
+  port: 8087
+modules:
+  users_info:
+    config:
+      default_page_size: 10
+      max_page_size: 100
+"#
+            ),
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("config.dev.yaml"),
+            "server:\n  port: 9000\nmodules:\n  users_info:\n    config:\n      default_page_size: 5\n",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("config.prod.yaml"), "server:\n  port: 80\n").unwrap();
+
+        // The file's `profile` key selects the overlay...
+        let config = AppConfig::load_layered_with_profile(&cfg_path, None).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("dev"));
+        assert_eq!(config.server.port, 9000);
+        let users = &config.modules["users_info"]["config"];
+        assert_eq!(users["default_page_size"], 5);
+        assert_eq!(users["max_page_size"], 100);
+
+        // ...unless the environment picks another one.
+        let config =
+            AppConfig::load_layered_with_profile(&cfg_path, Some("prod".to_string())).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("prod"));
+        assert_eq!(config.server.port, 80);
+
+        let err = AppConfig::load_layered_with_profile(&cfg_path, Some("stagin".to_string()))
+            .unwrap_err();
+        assert!(err.to_string().contains("config.stagin.yaml"), "{err}");
+        assert!(profile_overlay_path(&cfg_path, "../etc").is_err());
+    }
+
     #[test]
     fn test_to_yaml_roundtrip_basic() {
         let config = AppConfig::default();
@@ -5,8 +5,11 @@ use std::{
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
+use tracing::level_filters::LevelFilter;
+use tracing::subscriber::Interest;
 use tracing::Level;
-use tracing_subscriber::{filter::FilterFn, fmt};
+use tracing_subscriber::layer::{Context, Filter};
+use tracing_subscriber::{filter::FilterFn, fmt, reload};
 
 use file_rotate::{
     compression::Compression,
@@ -55,6 +58,158 @@ fn matches_crate_prefix(target: &str, crate_name: &str) -> bool {
         || (target.starts_with(crate_name) && target[crate_name.len()..].starts_with("::"))
 }
 
+// -------- runtime level overrides --------
+
+/// Levels set at runtime with [`set_filter_directives`], winning over the config.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Overrides {
+    default: Option<LevelFilter>,
+    /// Longest target first, so the most specific directive wins.
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Overrides {
+    /// Parse `info,api_ingress=debug,sqlx=off`.
+    fn parse(directives: &str) -> anyhow::Result<Self> {
+        let mut overrides = Overrides::default();
+        for directive in directives
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+        {
+            let (target, level) = match directive.split_once('=') {
+                Some((target, level)) => (Some(target.trim()), level.trim()),
+                None => (None, directive),
+            };
+            let level: LevelFilter = level
+                .parse()
+                .map_err(|_| anyhow::anyhow!("invalid level in filter directive '{directive}'"))?;
+            match target {
+                Some("") => anyhow::bail!("empty target in filter directive '{directive}'"),
+                Some(target) => {
+                    overrides.targets.retain(|(t, _)| t != target);
+                    overrides.targets.push((target.to_string(), level));
+                }
+                None => overrides.default = Some(level),
+            }
+        }
+        overrides
+            .targets
+            .sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        Ok(overrides)
+    }
+
+    fn level_for(&self, target: &str) -> Option<LevelFilter> {
+        self.targets
+            .iter()
+            .find(|(t, _)| matches_crate_prefix(target, t))
+            .map(|(_, level)| *level)
+            .or(self.default)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.default.is_none() && self.targets.is_empty()
+    }
+}
+
+/// Per-layer filter: `base` unless an override covers the target. Only the
+/// targets the layer already handles are affected (crates with their own
+/// config section for the explicit layers, everything else for the default
+/// ones), so an override never duplicates output.
+#[derive(Clone)]
+struct Overridable<F> {
+    base: F,
+    crates: Arc<[String]>,
+    listed: bool,
+    overrides: Overrides,
+}
+
+impl<F> Overridable<F> {
+    fn owns(&self, target: &str) -> bool {
+        self.crates.iter().any(|c| matches_crate_prefix(target, c)) == self.listed
+    }
+}
+
+impl<S, F> Filter<S> for Overridable<F>
+where
+    F: Filter<S>,
+{
+    fn enabled(&self, meta: &tracing::Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        if !self.owns(meta.target()) {
+            return false;
+        }
+        match self.overrides.level_for(meta.target()) {
+            Some(level) => meta.level() <= &level,
+            None => self.base.enabled(meta, cx),
+        }
+    }
+
+    fn callsite_enabled(&self, meta: &'static tracing::Metadata<'static>) -> Interest {
+        if !self.owns(meta.target()) {
+            Interest::never()
+        } else if self.overrides.is_empty() {
+            self.base.callsite_enabled(meta)
+        } else {
+            Interest::sometimes()
+        }
+    }
+}
+
+type Reloader = Box<dyn Fn(&Overrides) -> Result<(), reload::Error> + Send + Sync>;
+
+/// Reload handles of the installed subscriber's filters, and the directives in force.
+static RELOADERS: Mutex<Vec<Reloader>> = Mutex::new(Vec::new());
+static DIRECTIVES: Mutex<String> = Mutex::new(String::new());
+
+/// Wrap `base` so [`set_filter_directives`] can change it at runtime.
+fn overridable<S, F>(
+    base: F,
+    crates: &[String],
+    listed: bool,
+    reloaders: &mut Vec<Reloader>,
+) -> reload::Layer<Overridable<F>, S>
+where
+    S: tracing::Subscriber,
+    F: Send + Sync + 'static,
+{
+    let current = Overrides::parse(&filter_directives()).unwrap_or_default();
+    let (filter, handle) = reload::Layer::new(Overridable {
+        base,
+        crates: crates.into(),
+        listed,
+        overrides: current,
+    });
+    reloaders.push(Box::new(move |overrides| {
+        handle.modify(|f| f.overrides = overrides.clone())
+    }));
+    filter
+}
+
+/// Keep `reloaders` when their subscriber became the global one.
+fn install<E>(result: Result<(), E>, reloaders: Vec<Reloader>) {
+    if result.is_ok() {
+        *RELOADERS.lock().unwrap_or_else(|e| e.into_inner()) = reloaders;
+    }
+}
+
+/// Filter directives set with [`set_filter_directives`] (empty: config levels).
+pub fn filter_directives() -> String {
+    DIRECTIVES.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Override log levels without a restart, e.g. `api_ingress=debug,sqlx=off`;
+/// a bare level (`debug`) applies to every target. They replace the previous
+/// overrides; an empty string goes back to the configured levels.
+pub fn set_filter_directives(directives: &str) -> anyhow::Result<()> {
+    let overrides = Overrides::parse(directives)?;
+    let mut current = DIRECTIVES.lock().unwrap_or_else(|e| e.into_inner());
+    for reload in RELOADERS.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+        reload(&overrides)?;
+    }
+    *current = directives.trim().to_string();
+    Ok(())
+}
+
 // -------- rotating writer for files --------
 #[derive(Clone)]
 struct RotWriter(Arc<Mutex<FileRotate<AppendTimestamp>>>);
@@ -201,15 +356,25 @@ fn create_rotating_writer_at_path(
 
 // -------- public init --------
 
+/// Extra layer installed next to the logging ones (e.g. an OTLP span exporter).
+pub type ExtraLayer =
+    Box<dyn tracing_subscriber::Layer<tracing_subscriber::Registry> + Send + Sync + 'static>;
+
 /// Initialize logging from a configuration.
 /// - `cfg`: LoggingConfig containing the logging sections
 /// - `base_dir`: base directory used to resolve relative log file paths (usually server.home_dir)
 pub fn init_logging_from_config(cfg: &LoggingConfig, base_dir: &Path) {
+    init_logging_with_layer(cfg, base_dir, None);
+}
+
+/// [`init_logging_from_config`] plus `extra`, which sees every span and event
+/// regardless of the log levels.
+pub fn init_logging_with_layer(cfg: &LoggingConfig, base_dir: &Path, extra: Option<ExtraLayer>) {
     // Bridge `log` → `tracing` *before* installing the subscriber
     let _ = tracing_log::LogTracer::init();
 
     if cfg.is_empty() {
-        init_default_logging();
+        init_default_logging(extra);
         return;
     }
 
@@ -218,15 +383,29 @@ pub fn init_logging_from_config(cfg: &LoggingConfig, base_dir: &Path) {
     let file_router = build_file_router(&config_data, base_dir);
     let file_targets = build_file_targets(&config_data, file_router.default.is_some());
 
-    build_logging_layers(config_data, console_targets, file_targets, file_router);
+    build_logging_layers(
+        config_data,
+        console_targets,
+        file_targets,
+        file_router,
+        extra,
+    );
 }
 
-fn init_default_logging() {
-    use tracing_subscriber::fmt;
-    let _ = fmt()
-        .with_target(true)
-        .with_timer(fmt::time::UtcTime::rfc_3339())
+fn init_default_logging(extra: Option<ExtraLayer>) {
+    use tracing_subscriber::{fmt, prelude::*, Registry};
+    let mut reloaders = Vec::new();
+    let filter = overridable(LevelFilter::INFO, &[], false, &mut reloaders);
+    let result = Registry::default()
+        .with(extra)
+        .with(
+            fmt::layer()
+                .with_target(true)
+                .with_timer(fmt::time::UtcTime::rfc_3339())
+                .with_filter(filter),
+        )
         .try_init();
+    install(result, reloaders);
 }
 
 fn build_console_targets(config: &ConfigData) -> tracing_subscriber::filter::Targets {
@@ -341,20 +520,27 @@ fn build_logging_layers(
     console_targets: tracing_subscriber::filter::Targets,
     file_targets: tracing_subscriber::filter::Targets,
     file_router: MultiFileRouter,
+    extra: Option<ExtraLayer>,
 ) {
     use tracing_subscriber::{fmt, layer::SubscriberExt, prelude::*, Registry};
 
     let ansi = std::io::stdout().is_terminal();
+    let crates = &config.crate_names;
+    let mut reloaders = Vec::new();
 
     let console_layer = fmt::layer()
         .with_ansi(ansi)
         .with_target(true)
         .with_level(true)
         .with_timer(fmt::time::UtcTime::rfc_3339())
-        .with_filter(console_targets);
+        .with_filter(overridable(console_targets, crates, true, &mut reloaders));
 
     if file_router.is_empty() {
-        let _ = Registry::default().with(console_layer).try_init();
+        let result = Registry::default()
+            .with(extra)
+            .with(console_layer)
+            .try_init();
+        install(result, reloaders);
         return;
     }
 
@@ -370,7 +556,7 @@ fn build_logging_layers(
         .with_level(true)
         .with_timer(fmt::time::UtcTime::rfc_3339())
         .with_writer(router_for_explicit)
-        .with_filter(file_targets);
+        .with_filter(overridable(file_targets, crates, true, &mut reloaders));
 
     // Add default layers if configured
     if let Some(default_section) = config.default_section {
@@ -381,9 +567,11 @@ fn build_logging_layers(
                 .with_target(true)
                 .with_level(true)
                 .with_timer(fmt::time::UtcTime::rfc_3339())
-                .with_filter(create_default_filter_for_crates(
-                    &config.crate_names,
-                    console_level,
+                .with_filter(overridable(
+                    create_default_filter_for_crates(crates, console_level),
+                    crates,
+                    false,
+                    &mut reloaders,
                 ));
 
             // File default layer (if file is configured)
@@ -396,34 +584,42 @@ fn build_logging_layers(
                         .with_level(true)
                         .with_timer(fmt::time::UtcTime::rfc_3339())
                         .with_writer(file_router)
-                        .with_filter(create_default_filter_for_crates(
-                            &config.crate_names,
-                            file_level,
+                        .with_filter(overridable(
+                            create_default_filter_for_crates(crates, file_level),
+                            crates,
+                            false,
+                            &mut reloaders,
                         ));
 
-                    let _ = Registry::default()
+                    let result = Registry::default()
+                        .with(extra)
                         .with(console_layer)
                         .with(explicit_file_layer)
                         .with(console_default)
                         .with(file_default)
                         .try_init();
+                    install(result, reloaders);
                     return;
                 }
             }
 
-            let _ = Registry::default()
+            let result = Registry::default()
+                .with(extra)
                 .with(console_layer)
                 .with(explicit_file_layer)
                 .with(console_default)
                 .try_init();
+            install(result, reloaders);
             return;
         }
     }
 
-    let _ = Registry::default()
+    let result = Registry::default()
+        .with(extra)
         .with(console_layer)
         .with(explicit_file_layer)
         .try_init();
+    install(result, reloaders);
 }
 
 // =================== tests ===================
@@ -435,6 +631,65 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_filter_directives_override_levels_at_runtime() {
+        use tracing_subscriber::{filter::Targets, prelude::*, Registry};
+
+        struct Capture(Arc<Mutex<Vec<String>>>);
+
+        impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for Capture {
+            fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+                let meta = event.metadata();
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}:{}", meta.target(), meta.level()));
+            }
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let crates = vec!["api_ingress".to_string()];
+        let mut reloaders = Vec::new();
+        let listed = Capture(seen.clone()).with_filter(overridable(
+            Targets::new().with_target("api_ingress", LevelFilter::INFO),
+            &crates,
+            true,
+            &mut reloaders,
+        ));
+        let default = Capture(seen.clone()).with_filter(overridable(
+            create_default_filter_for_crates(&crates, Level::WARN),
+            &crates,
+            false,
+            &mut reloaders,
+        ));
+        let subscriber = Registry::default().with(listed).with(default);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let emit = || {
+                tracing::debug!(target: "api_ingress::routes", "routes");
+                tracing::info!(target: "sqlx", "query");
+                tracing::warn!(target: "sqlx", "slow query");
+            };
+            emit();
+            let overrides = Overrides::parse("api_ingress=debug, sqlx=error").unwrap();
+            for reload in &reloaders {
+                reload(&overrides).unwrap();
+            }
+            emit();
+        });
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["sqlx:WARN", "api_ingress::routes:DEBUG"]
+        );
+        assert!(Overrides::parse("api_ingress=loud").is_err());
+        assert!(Overrides::parse("=debug").is_err());
+        assert_eq!(
+            Overrides::parse("warn").unwrap().level_for("anything"),
+            Some(LevelFilter::WARN)
+        );
+    }
+
     #[test]
     fn test_logging_level_parsing() {
         assert_eq!(parse_tracing_level("trace"), Some(Level::TRACE));
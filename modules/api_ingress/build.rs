@@ -3,42 +3,92 @@ use std::fs;
 use std::io::Write;
 use std::path::Path;
 
+/// Docs UI asset bundle fetched when its embed feature is enabled.
+struct Bundle {
+    feature: &'static str,
+    name: &'static str,
+    dir: &'static str,
+    /// (download URL, file name)
+    files: &'static [(&'static str, &'static str)],
+}
+
+const BUNDLES: &[Bundle] = &[
+    Bundle {
+        feature: "CARGO_FEATURE_EMBED_ELEMENTS",
+        name: "Stoplight Elements",
+        dir: "elements",
+        files: &[
+            (
+                "https://unpkg.com/@stoplight/elements@latest/web-components.min.js",
+                "web-components.min.js",
+            ),
+            (
+                "https://unpkg.com/@stoplight/elements@latest/styles.min.css",
+                "styles.min.css",
+            ),
+        ],
+    },
+    Bundle {
+        feature: "CARGO_FEATURE_EMBED_SWAGGER_UI",
+        name: "Swagger UI",
+        dir: "swagger-ui",
+        files: &[
+            (
+                "https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js",
+                "swagger-ui-bundle.js",
+            ),
+            (
+                "https://unpkg.com/swagger-ui-dist@5/swagger-ui.css",
+                "swagger-ui.css",
+            ),
+        ],
+    },
+    Bundle {
+        feature: "CARGO_FEATURE_EMBED_REDOC",
+        name: "Redoc",
+        dir: "redoc",
+        files: &[(
+            "https://cdn.redoc.ly/redoc/latest/bundles/redoc.standalone.js",
+            "redoc.standalone.js",
+        )],
+    },
+];
+
 fn main() {
-    // Only run when the embed_elements feature is enabled
-    let embed_enabled = env::var("CARGO_FEATURE_EMBED_ELEMENTS").is_ok();
-    if !embed_enabled {
-        return;
+    for bundle in BUNDLES {
+        println!("cargo:rerun-if-env-changed={}", bundle.feature);
+        // Only fetch assets for the embed features that are enabled
+        if env::var(bundle.feature).is_ok() {
+            fetch_bundle(bundle);
+        }
     }
+}
 
-    println!("cargo:rerun-if-env-changed=CARGO_FEATURE_EMBED_ELEMENTS");
-
-    let out_dir = Path::new("assets").join("elements");
+fn fetch_bundle(bundle: &Bundle) {
+    let Bundle {
+        name, dir, files, ..
+    } = bundle;
+    let out_dir = Path::new("assets").join(dir);
     if let Err(e) = fs::create_dir_all(&out_dir) {
-        println!("cargo:warning=Failed to create assets/elements directory: {e}");
+        println!("cargo:warning=Failed to create assets/{dir} directory: {e}");
         panic!(
-            "Failed to create assets directory for embedded Elements. Build with --no-default-features or without --features embed_elements, or vendor assets manually."
+            "Failed to create assets directory for embedded {name}. Build without the embed feature, or vendor assets manually."
         );
     }
 
-    let files = [
-        (
-            "https://unpkg.com/@stoplight/elements@latest/web-components.min.js",
-            out_dir.join("web-components.min.js"),
-        ),
-        (
-            "https://unpkg.com/@stoplight/elements@latest/styles.min.css",
-            out_dir.join("styles.min.css"),
-        ),
-    ];
-
-    for (url, dest) in files.iter() {
-        if let Err(e) = download_to(url, dest) {
+    for (url, file) in files.iter() {
+        let dest = out_dir.join(file);
+        // Vendored files are kept as-is
+        if dest.exists() {
+            println!("cargo:rerun-if-changed={}", dest.display());
+            continue;
+        }
+        if let Err(e) = download_to(url, &dest) {
             println!("cargo:warning=Failed to download {url} -> {dest:?}: {e}");
             panic!(
-                "Failed to download Stoplight Elements assets.\n\
-                 To proceed: either build without --features embed_elements (external mode),\n\
-                 or pin a specific version and vendor files manually into modules/api_ingress/assets/elements/.\n\
-                 Example pinned URL: https://unpkg.com/@stoplight/elements@7.18.0/web-components.min.js"
+                "Failed to download {name} assets.\n\
+                 To proceed: either build without the embed feature (external mode),\n\
+                 or pin a specific version and vendor files manually into modules/api_ingress/assets/{dir}/."
             );
         }
     }
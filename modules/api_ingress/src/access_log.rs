@@ -0,0 +1,243 @@
+//! Structured access log.
+//!
+//! One event per request on the `api_ingress::access_log` target (written as a
+//! JSON line by the file logger) with request id, route template, status,
+//! latency and byte counts. A configurable fraction of requests additionally
+//! captures request/response bodies, limited by size and content type.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{MatchedPath, State},
+    http::{header, HeaderMap, Request},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::request_id::XRequestId;
+
+/// Access log section of the ingress configuration.
+#[derive(Debug, Clone, Deserialize, Serialize, modkit::ConfigSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccessLogConfig {
+    /// Emit one access log event per request.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Fraction of requests (0.0..=1.0) whose bodies are captured.
+    #[serde(default)]
+    #[config(min = 0.0, max = 1.0)]
+    pub body_sample_rate: f64,
+    /// Bodies larger than this are never captured.
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// Content types eligible for body capture (prefix match).
+    #[serde(default = "default_body_content_types")]
+    pub body_content_types: Vec<String>,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            body_sample_rate: 0.0,
+            max_body_bytes: default_max_body_bytes(),
+            body_content_types: default_body_content_types(),
+        }
+    }
+}
+
+fn default_max_body_bytes() -> usize {
+    4096
+}
+
+fn default_body_content_types() -> Vec<String> {
+    vec![
+        "application/json".to_string(),
+        "application/problem+json".to_string(),
+        "text/plain".to_string(),
+    ]
+}
+
+/// Shared state of the access log middleware.
+#[derive(Clone)]
+pub struct AccessLogState {
+    cfg: Arc<AccessLogConfig>,
+    counter: Arc<AtomicU64>,
+}
+
+impl AccessLogState {
+    pub fn new(cfg: AccessLogConfig) -> Self {
+        Self {
+            cfg: Arc::new(cfg),
+            counter: Arc::default(),
+        }
+    }
+
+    /// Deterministic sampling: spreads `rate * N` samples evenly over N requests.
+    fn should_sample(&self) -> bool {
+        let rate = self.cfg.body_sample_rate.clamp(0.0, 1.0);
+        if rate <= 0.0 {
+            return false;
+        }
+        let n = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
+        (n as f64 * rate).floor() > ((n - 1) as f64 * rate).floor()
+    }
+
+    fn capturable(&self, headers: &HeaderMap, len: Option<u64>) -> bool {
+        let fits = len.is_some_and(|l| l <= self.cfg.max_body_bytes as u64);
+        let ct = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        fits && self
+            .cfg
+            .body_content_types
+            .iter()
+            .any(|allowed| ct.starts_with(allowed.as_str()))
+    }
+}
+
+/// Buffer a body known to be small, returning the bytes and a rebuilt body.
+async fn capture(body: Body, limit: usize) -> (Option<Bytes>, Body) {
+    match axum::body::to_bytes(body, limit).await {
+        Ok(bytes) => (Some(bytes.clone()), Body::from(bytes)),
+        Err(e) => {
+            tracing::debug!(error = %e, "access log: failed to buffer body");
+            (None, Body::empty())
+        }
+    }
+}
+
+fn body_text(bytes: &Option<Bytes>) -> Option<String> {
+    bytes
+        .as_ref()
+        .map(|b| String::from_utf8_lossy(b).into_owned())
+}
+
+/// Middleware emitting the access log event.
+pub async fn access_log_middleware(
+    State(state): State<AccessLogState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let method = req.method().clone();
+    let path = req.uri().path().to_owned();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_owned());
+    let request_id = req
+        .extensions()
+        .get::<XRequestId>()
+        .map(|r| r.0.clone())
+        .or_else(|| {
+            req.headers()
+                .get(crate::request_id::header())
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_owned)
+        });
+    let bytes_in = req.body().size_hint().exact();
+    let sampled = state.should_sample();
+
+    let (req_body, req) = if sampled && state.capturable(req.headers(), bytes_in) {
+        let (parts, body) = req.into_parts();
+        let (bytes, body) = capture(body, state.cfg.max_body_bytes).await;
+        (bytes, Request::from_parts(parts, body))
+    } else {
+        (None, req)
+    };
+
+    let resp = next.run(req).await;
+
+    let status = resp.status().as_u16();
+    let bytes_out = resp.body().size_hint().exact();
+    let (resp_body, resp) = if sampled && state.capturable(resp.headers(), bytes_out) {
+        let (parts, body) = resp.into_parts();
+        let (bytes, body) = capture(body, state.cfg.max_body_bytes).await;
+        (bytes, Response::from_parts(parts, body))
+    } else {
+        (None, resp)
+    };
+
+    let request_body = body_text(&req_body);
+    let response_body = body_text(&resp_body);
+    tracing::info!(
+        target: "api_ingress::access_log",
+        request_id = request_id.as_deref().unwrap_or("n/a"),
+        method = %method,
+        path = %path,
+        route = route.as_deref().unwrap_or("unmatched"),
+        status,
+        latency_ms = start.elapsed().as_secs_f64() * 1000.0,
+        bytes_in,
+        bytes_out,
+        request_body = request_body.as_deref(),
+        response_body = response_body.as_deref(),
+        "access"
+    );
+
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn_with_state, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn state(rate: f64) -> AccessLogState {
+        AccessLogState::new(AccessLogConfig {
+            enabled: true,
+            body_sample_rate: rate,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn sampling_spreads_evenly() {
+        let s = state(0.25);
+        let hits = (0..100).filter(|_| s.should_sample()).count();
+        assert_eq!(hits, 25);
+        assert!(!(0..10).any(|_| state(0.0).should_sample()));
+        assert!((0..10).all(|_| state(1.0).should_sample()));
+    }
+
+    #[test]
+    fn capture_respects_size_and_content_type() {
+        let s = state(1.0);
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
+        assert!(s.capturable(&headers, Some(10)));
+        assert!(!s.capturable(&headers, Some(1_000_000)));
+        assert!(!s.capturable(&headers, None));
+
+        headers.insert(header::CONTENT_TYPE, "image/png".parse().unwrap());
+        assert!(!s.capturable(&headers, Some(10)));
+    }
+
+    #[tokio::test]
+    async fn sampled_bodies_are_passed_through_intact() {
+        let app = Router::new()
+            .route("/echo", post(|body: String| async move { body }))
+            .layer(from_fn_with_state(state(1.0), access_log_middleware));
+
+        let resp = app
+            .oneshot(
+                Request::post("/echo")
+                    .header(header::CONTENT_TYPE, "text/plain")
+                    .body(Body::from("hello"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"hello");
+    }
+}
@@ -0,0 +1,335 @@
+//! API key authentication for service-to-service callers.
+//!
+//! Keys are looked up through an [`ApiKeyStore`]; the default store is built
+//! from `ApiIngressConfig.api_keys` and accepts plaintext or SHA-256 hashed
+//! keys. Each key carries a policy: allowed path prefixes, optional rate limit
+//! and the scopes it grants. A valid key becomes an [`AuthContext`]; operations
+//! accept it when they declare `OperationBuilder::api_key_auth`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderName, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use modkit::api::{problem, AuthContext, Problem, ProblemResponse};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::rate_limit::{retry_after_secs, TokenBucket};
+
+/// API key section of the ingress configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// Turn on API key validation.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Request header carrying the key.
+    #[serde(default = "default_header")]
+    pub header: String,
+    /// Known keys and their policies.
+    #[serde(default)]
+    pub keys: Vec<ApiKeyEntry>,
+}
+
+impl Default for ApiKeyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            header: default_header(),
+            keys: Vec::new(),
+        }
+    }
+}
+
+fn default_header() -> String {
+    "x-api-key".to_string()
+}
+
+/// A single API key and its policy.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyEntry {
+    /// Stable identifier used as the caller subject and in logs.
+    pub id: String,
+    /// Plaintext key (prefer `key_sha256` outside of development).
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Hex-encoded SHA-256 of the key.
+    #[serde(default)]
+    pub key_sha256: Option<String>,
+    /// Path prefixes this key may access; empty means all paths.
+    #[serde(default)]
+    pub allowed_prefixes: Vec<String>,
+    /// Scopes granted to the caller.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Per-key request rate limit.
+    #[serde(default)]
+    pub rate_limit: Option<KeyRateLimit>,
+}
+
+/// Token-bucket limit for one key.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyRateLimit {
+    /// Sustained requests per second.
+    pub rps: u32,
+    /// Maximum burst size.
+    pub burst: u32,
+}
+
+/// Resolved policy for a presented key.
+#[derive(Debug, Clone)]
+pub struct ApiKeyPolicy {
+    pub id: String,
+    pub allowed_prefixes: Vec<String>,
+    pub scopes: Vec<String>,
+    pub rate_limit: Option<KeyRateLimit>,
+}
+
+impl ApiKeyPolicy {
+    /// Check whether the key may access `path` (prefixes match whole segments).
+    pub fn allows_path(&self, path: &str) -> bool {
+        self.allowed_prefixes.is_empty()
+            || self.allowed_prefixes.iter().any(|prefix| {
+                path.strip_prefix(prefix.as_str()).is_some_and(|rest| {
+                    rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+                })
+            })
+    }
+}
+
+/// Source of API key policies. Implement to back keys by a database or vault.
+pub trait ApiKeyStore: Send + Sync {
+    /// Resolve the policy for a presented key, `None` if the key is unknown.
+    fn lookup(&self, presented_key: &str) -> Option<ApiKeyPolicy>;
+}
+
+/// Store backed by the static configuration; keys are indexed by SHA-256.
+#[derive(Debug, Default)]
+pub struct StaticApiKeyStore {
+    by_hash: HashMap<String, ApiKeyPolicy>,
+}
+
+impl StaticApiKeyStore {
+    pub fn from_config(cfg: &ApiKeyConfig) -> anyhow::Result<Self> {
+        let mut by_hash = HashMap::new();
+        for entry in &cfg.keys {
+            let hash = match (&entry.key, &entry.key_sha256) {
+                (Some(plain), None) => sha256_hex(plain),
+                (None, Some(hash)) => hash.to_ascii_lowercase(),
+                _ => anyhow::bail!(
+                    "API key '{}' must set exactly one of 'key' or 'key_sha256'",
+                    entry.id
+                ),
+            };
+            let policy = ApiKeyPolicy {
+                id: entry.id.clone(),
+                allowed_prefixes: entry.allowed_prefixes.clone(),
+                scopes: entry.scopes.clone(),
+                rate_limit: entry.rate_limit,
+            };
+            if by_hash.insert(hash, policy).is_some() {
+                anyhow::bail!("API key '{}' duplicates another configured key", entry.id);
+            }
+        }
+        Ok(Self { by_hash })
+    }
+}
+
+impl ApiKeyStore for StaticApiKeyStore {
+    fn lookup(&self, presented_key: &str) -> Option<ApiKeyPolicy> {
+        self.by_hash.get(&sha256_hex(presented_key)).cloned()
+    }
+}
+
+fn sha256_hex(input: &str) -> String {
+    hex::encode(Sha256::digest(input.as_bytes()))
+}
+
+/// Shared state of the API key middleware.
+#[derive(Clone)]
+pub struct ApiKeyState {
+    store: Arc<dyn ApiKeyStore>,
+    header: HeaderName,
+    limiters: Arc<parking_lot::Mutex<HashMap<String, Arc<TokenBucket>>>>,
+}
+
+impl ApiKeyState {
+    pub fn new(store: Arc<dyn ApiKeyStore>, header: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            store,
+            header: HeaderName::try_from(header)?,
+            limiters: Arc::default(),
+        })
+    }
+
+    fn limiter(&self, policy: &ApiKeyPolicy) -> Option<Arc<TokenBucket>> {
+        let limit = policy.rate_limit?;
+        Some(
+            self.limiters
+                .lock()
+                .entry(policy.id.clone())
+                .or_insert_with(|| Arc::new(TokenBucket::new(limit.rps, limit.burst)))
+                .clone(),
+        )
+    }
+}
+
+/// Middleware: validate the API key header (if present), apply the key
+/// policy and expose the caller as an [`AuthContext`]. Requests without the
+/// header pass through untouched so other authenticators can handle them.
+pub async fn api_key_middleware(
+    State(state): State<ApiKeyState>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(presented) = req.headers().get(&state.header) else {
+        return next.run(req).await;
+    };
+    let Some(policy) = presented.to_str().ok().and_then(|k| state.store.lookup(k)) else {
+        return problem::unauthorized("Invalid API key").into_response();
+    };
+
+    if !policy.allows_path(req.uri().path()) {
+        tracing::debug!(key_id = %policy.id, path = %req.uri().path(), "API key not allowed for path");
+        return problem::forbidden("API key is not allowed to access this path").into_response();
+    }
+
+    if let Some(limiter) = state.limiter(&policy) {
+        if let Err(wait) = limiter.try_acquire() {
+            let mut resp = ProblemResponse::from(Problem::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too Many Requests",
+                "API key rate limit exceeded",
+            ))
+            .into_response();
+            resp.headers_mut().insert(
+                header::RETRY_AFTER,
+                HeaderValue::from(retry_after_secs(wait)),
+            );
+            return resp;
+        }
+    }
+
+    req.extensions_mut().insert(AuthContext {
+        subject: format!("apikey:{}", policy.id),
+        scopes: policy.scopes,
+        tenant: None,
+    });
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn_with_state, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn store() -> Arc<dyn ApiKeyStore> {
+        let cfg = ApiKeyConfig {
+            enabled: true,
+            keys: vec![
+                ApiKeyEntry {
+                    id: "billing".into(),
+                    key: Some("plain-secret".into()),
+                    allowed_prefixes: vec!["/billing".into()],
+                    rate_limit: Some(KeyRateLimit { rps: 1, burst: 1 }),
+                    ..Default::default()
+                },
+                ApiKeyEntry {
+                    id: "ops".into(),
+                    key_sha256: Some(sha256_hex("hashed-secret")),
+                    scopes: vec!["ops".into()],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        Arc::new(StaticApiKeyStore::from_config(&cfg).unwrap())
+    }
+
+    fn app() -> Router {
+        let state = ApiKeyState::new(store(), "x-api-key").unwrap();
+        Router::new()
+            .route(
+                "/billing/invoices",
+                get(|ctx: AuthContext| async move { ctx.subject }),
+            )
+            .route("/admin", get(|| async { "admin" }))
+            .layer(from_fn_with_state(state, api_key_middleware))
+    }
+
+    async fn call(app: Router, path: &str, key: Option<&str>) -> StatusCode {
+        let mut req = Request::builder().uri(path);
+        if let Some(k) = key {
+            req = req.header("x-api-key", k);
+        }
+        app.oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[test]
+    fn config_requires_exactly_one_key_form() {
+        let cfg = ApiKeyConfig {
+            keys: vec![ApiKeyEntry {
+                id: "broken".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(StaticApiKeyStore::from_config(&cfg).is_err());
+    }
+
+    #[test]
+    fn store_resolves_plain_and_hashed_keys() {
+        let store = store();
+        assert_eq!(store.lookup("plain-secret").unwrap().id, "billing");
+        assert_eq!(store.lookup("hashed-secret").unwrap().id, "ops");
+        assert!(store.lookup("nope").is_none());
+    }
+
+    #[test]
+    fn prefixes_match_whole_segments() {
+        let policy = ApiKeyPolicy {
+            id: "k".into(),
+            allowed_prefixes: vec!["/billing".into()],
+            scopes: vec![],
+            rate_limit: None,
+        };
+        assert!(policy.allows_path("/billing"));
+        assert!(policy.allows_path("/billing/invoices"));
+        assert!(!policy.allows_path("/billing-admin"));
+    }
+
+    #[tokio::test]
+    async fn middleware_applies_key_policy() {
+        let app = app();
+        assert_eq!(call(app.clone(), "/admin", None).await, StatusCode::OK);
+        assert_eq!(
+            call(app.clone(), "/admin", Some("wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call(app.clone(), "/admin", Some("plain-secret")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(app.clone(), "/billing/invoices", Some("plain-secret")).await,
+            StatusCode::OK
+        );
+        // burst of 1 is exhausted by the previous call
+        assert_eq!(
+            call(app, "/billing/invoices", Some("plain-secret")).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+}
@@ -2,7 +2,11 @@
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 
-#[cfg(feature = "embed_elements")]
+#[cfg(any(
+    feature = "embed_elements",
+    feature = "embed_swagger_ui",
+    feature = "embed_redoc"
+))]
 use rust_embed::RustEmbed;
 
 #[cfg(feature = "embed_elements")]
@@ -10,24 +14,61 @@ use rust_embed::RustEmbed;
 #[folder = "assets/elements/"]
 pub struct ElementsAssets;
 
+#[cfg(feature = "embed_swagger_ui")]
+#[derive(RustEmbed)]
+#[folder = "assets/swagger-ui/"]
+pub struct SwaggerUiAssets;
+
+#[cfg(feature = "embed_redoc")]
+#[derive(RustEmbed)]
+#[folder = "assets/redoc/"]
+pub struct RedocAssets;
+
 #[cfg(feature = "embed_elements")]
 pub async fn serve_elements_asset(
     axum::extract::Path(file): axum::extract::Path<String>,
 ) -> Result<impl IntoResponse, StatusCode> {
-    match ElementsAssets::get(&file) {
+    serve_embedded::<ElementsAssets>("Elements", &file)
+}
+
+#[cfg(feature = "embed_swagger_ui")]
+pub async fn serve_swagger_ui_asset(
+    axum::extract::Path(file): axum::extract::Path<String>,
+) -> Result<impl IntoResponse, StatusCode> {
+    serve_embedded::<SwaggerUiAssets>("Swagger UI", &file)
+}
+
+#[cfg(feature = "embed_redoc")]
+pub async fn serve_redoc_asset(
+    axum::extract::Path(file): axum::extract::Path<String>,
+) -> Result<impl IntoResponse, StatusCode> {
+    serve_embedded::<RedocAssets>("Redoc", &file)
+}
+
+#[cfg(any(
+    feature = "embed_elements",
+    feature = "embed_swagger_ui",
+    feature = "embed_redoc"
+))]
+fn serve_embedded<A: RustEmbed>(ui: &str, file: &str) -> Result<impl IntoResponse, StatusCode> {
+    match A::get(file) {
         Some(content) => {
-            let mime_type = content_type_for(&file);
+            let mime_type = content_type_for(file);
             let body = content.data.into_owned();
             Ok(([(axum::http::header::CONTENT_TYPE, mime_type)], body))
         }
         None => {
-            tracing::warn!("Elements asset not found: {}", file);
+            tracing::warn!("{} asset not found: {}", ui, file);
             Err(StatusCode::NOT_FOUND)
         }
     }
 }
 
-#[cfg(feature = "embed_elements")]
+#[cfg(any(
+    feature = "embed_elements",
+    feature = "embed_swagger_ui",
+    feature = "embed_redoc"
+))]
 fn content_type_for(file: &str) -> &'static str {
     match file.rsplit('.').next().unwrap_or("") {
         "css" => "text/css; charset=utf-8",
@@ -0,0 +1,820 @@
+//! Bearer token (JWT/OIDC) authentication for the ingress.
+//!
+//! Tokens are verified against the JWKS of the configured issuers. Keys are
+//! fetched lazily and refreshed when an unknown `kid` shows up. Refreshes run
+//! one at a time per issuer and at most once per `jwks_refresh_secs`, failed
+//! ones included, so an unreachable IdP is not hammered. On success an [`AuthContext`] is inserted into the
+//! request extensions. Routes that declared security requirements through
+//! `OperationBuilder::bearer_auth` / `api_key_auth` only accept the declared
+//! schemes, and requests that fail them get 401/403 Problem responses.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{header, HeaderValue, Method, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::Engine;
+use jsonwebtoken::{
+    jwk::{Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use modkit::api::{
+    problem, AuthContext, OperationSpec, SecurityRequirement, API_KEY_AUTH_SCHEME,
+    BEARER_AUTH_SCHEME,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
+
+/// Auth section of the ingress configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    /// Turn on bearer token validation.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Require a valid token on every route, not only on routes that declare scopes.
+    #[serde(default)]
+    pub require_by_default: bool,
+    /// Trusted token issuers.
+    #[serde(default)]
+    pub issuers: Vec<IssuerConfig>,
+    /// Accepted signing algorithms (JWA names, e.g. `RS256`).
+    #[serde(
+        default = "default_algorithms",
+        deserialize_with = "deserialize_algorithms"
+    )]
+    pub algorithms: Vec<Algorithm>,
+    /// Clock skew tolerance for `exp`/`nbf`.
+    #[serde(default = "default_leeway_secs")]
+    pub leeway_secs: u64,
+    /// Minimum interval between JWKS fetches for unknown key ids; failed fetches count too.
+    #[serde(default = "default_jwks_refresh_secs")]
+    pub jwks_refresh_secs: u64,
+    /// Connect and request timeout for JWKS fetches.
+    #[serde(default = "default_jwks_timeout_secs")]
+    pub jwks_timeout_secs: u64,
+    /// Claim holding granted scopes (space-delimited string or array).
+    #[serde(default = "default_scope_claim")]
+    pub scope_claim: String,
+    /// Claim holding the tenant id.
+    #[serde(default = "default_tenant_claim")]
+    pub tenant_claim: String,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            require_by_default: false,
+            issuers: Vec::new(),
+            algorithms: default_algorithms(),
+            leeway_secs: default_leeway_secs(),
+            jwks_refresh_secs: default_jwks_refresh_secs(),
+            jwks_timeout_secs: default_jwks_timeout_secs(),
+            scope_claim: default_scope_claim(),
+            tenant_claim: default_tenant_claim(),
+        }
+    }
+}
+
+/// A trusted issuer and where to fetch its signing keys.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IssuerConfig {
+    /// Expected `iss` claim.
+    pub issuer: String,
+    /// JWKS endpoint of the issuer.
+    pub jwks_uri: String,
+    /// Accepted `aud` values; empty disables the audience check.
+    #[serde(default)]
+    pub audiences: Vec<String>,
+}
+
+fn default_algorithms() -> Vec<Algorithm> {
+    vec![
+        Algorithm::RS256,
+        Algorithm::RS384,
+        Algorithm::RS512,
+        Algorithm::PS256,
+        Algorithm::ES256,
+        Algorithm::ES384,
+        Algorithm::EdDSA,
+    ]
+}
+
+fn deserialize_algorithms<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Algorithm>, D::Error> {
+    Vec::<String>::deserialize(d)?
+        .iter()
+        .map(|name| {
+            Algorithm::from_str(name).map_err(|_| {
+                serde::de::Error::custom(format!("unknown signing algorithm '{name}'"))
+            })
+        })
+        .collect()
+}
+
+fn default_leeway_secs() -> u64 {
+    60
+}
+
+fn default_jwks_refresh_secs() -> u64 {
+    300
+}
+
+fn default_jwks_timeout_secs() -> u64 {
+    10
+}
+
+fn default_scope_claim() -> String {
+    "scope".to_string()
+}
+
+fn default_tenant_claim() -> String {
+    "tenant_id".to_string()
+}
+
+/// Reasons a request fails authentication or authorization.
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("missing bearer token")]
+    MissingToken,
+    #[error("malformed token: {0}")]
+    Malformed(String),
+    #[error("untrusted issuer '{0}'")]
+    UnknownIssuer(String),
+    #[error("signing algorithm '{0:?}' is not accepted")]
+    AlgorithmNotAllowed(Algorithm),
+    #[error("no signing key matches the token")]
+    UnknownKey,
+    #[error("failed to fetch JWKS from '{uri}': {reason}")]
+    Jwks { uri: String, reason: String },
+    #[error("invalid token: {0}")]
+    InvalidToken(#[from] jsonwebtoken::errors::Error),
+    #[error("missing required scopes: {}", .0.join(" "))]
+    InsufficientScope(Vec<String>),
+    #[error("credentials required: {}", .0.join(" or "))]
+    MissingCredentials(Vec<String>),
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let (mut resp, challenge) = match &self {
+            AuthError::InsufficientScope(missing) => (
+                problem::forbidden(self.to_string()).into_response(),
+                format!(
+                    "Bearer error=\"insufficient_scope\", scope=\"{}\"",
+                    missing.join(" ")
+                ),
+            ),
+            AuthError::MissingCredentials(schemes) => (
+                problem::unauthorized(self.to_string()).into_response(),
+                if schemes.iter().any(|s| s == BEARER_AUTH_SCHEME) {
+                    "Bearer".to_string()
+                } else {
+                    String::new()
+                },
+            ),
+            AuthError::MissingToken => (
+                problem::unauthorized(self.to_string()).into_response(),
+                "Bearer".to_string(),
+            ),
+            AuthError::Jwks { .. } => {
+                tracing::error!(error = %self, "JWKS unavailable");
+                (
+                    problem::unauthorized("Unable to verify token").into_response(),
+                    "Bearer error=\"invalid_token\"".to_string(),
+                )
+            }
+            _ => (
+                problem::unauthorized(self.to_string()).into_response(),
+                "Bearer error=\"invalid_token\"".to_string(),
+            ),
+        };
+        if challenge.is_empty() {
+            return resp;
+        }
+        if let Ok(v) = HeaderValue::from_str(&challenge) {
+            resp.headers_mut().insert(header::WWW_AUTHENTICATE, v);
+        }
+        resp
+    }
+}
+
+#[derive(Default)]
+struct IssuerKeys {
+    keys: Vec<Jwk>,
+    /// Last fetch attempt, successful or not.
+    fetched_at: Option<Instant>,
+    /// Why the last attempt failed; replayed until the next one is due.
+    failure: Option<String>,
+}
+
+/// Verifies bearer tokens against issuer JWKS and builds [`AuthContext`]s.
+pub struct JwtValidator {
+    cfg: AuthConfig,
+    http: reqwest::Client,
+    keys: RwLock<HashMap<String, IssuerKeys>>,
+    /// One in-flight JWKS refresh per issuer.
+    refreshing: HashMap<String, Mutex<()>>,
+}
+
+impl JwtValidator {
+    pub fn new(cfg: AuthConfig) -> anyhow::Result<Self> {
+        // A slow IdP must not stall authenticated requests indefinitely.
+        let timeout = Duration::from_secs(cfg.jwks_timeout_secs);
+        let http = reqwest::Client::builder()
+            .connect_timeout(timeout)
+            .timeout(timeout)
+            .build()?;
+        let refreshing = cfg
+            .issuers
+            .iter()
+            .map(|i| (i.issuer.clone(), Mutex::new(())))
+            .collect();
+        Ok(Self {
+            cfg,
+            http,
+            keys: RwLock::new(HashMap::new()),
+            refreshing,
+        })
+    }
+
+    /// Seed the key cache for an issuer (skips the network fetch).
+    pub async fn insert_jwks(&self, issuer: &str, jwks: JwkSet) {
+        self.keys.write().await.insert(
+            issuer.to_string(),
+            IssuerKeys {
+                keys: jwks.keys,
+                fetched_at: Some(Instant::now()),
+                failure: None,
+            },
+        );
+    }
+
+    /// Validate a compact JWT and extract the caller identity.
+    pub async fn validate(&self, token: &str) -> Result<AuthContext, AuthError> {
+        let header = jsonwebtoken::decode_header(token)?;
+        if !self.cfg.algorithms.contains(&header.alg) {
+            return Err(AuthError::AlgorithmNotAllowed(header.alg));
+        }
+
+        let iss = unverified_issuer(token)?;
+        let issuer = self
+            .cfg
+            .issuers
+            .iter()
+            .find(|i| i.issuer == iss)
+            .ok_or_else(|| AuthError::UnknownIssuer(iss.clone()))?;
+
+        let key = self.decoding_key(issuer, header.kid.as_deref()).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = self.cfg.leeway_secs;
+        validation.set_issuer(&[&issuer.issuer]);
+        if issuer.audiences.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&issuer.audiences);
+        }
+
+        let data =
+            jsonwebtoken::decode::<serde_json::Map<String, Value>>(token, &key, &validation)?;
+        Ok(self.context_from_claims(&data.claims))
+    }
+
+    fn context_from_claims(&self, claims: &serde_json::Map<String, Value>) -> AuthContext {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let scopes = match claims.get(&self.cfg.scope_claim) {
+            Some(Value::String(s)) => s.split_whitespace().map(String::from).collect(),
+            Some(Value::Array(a)) => a
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let tenant = claims
+            .get(&self.cfg.tenant_claim)
+            .and_then(Value::as_str)
+            .map(String::from);
+        AuthContext {
+            subject,
+            scopes,
+            tenant,
+        }
+    }
+
+    async fn decoding_key(
+        &self,
+        issuer: &IssuerConfig,
+        kid: Option<&str>,
+    ) -> Result<DecodingKey, AuthError> {
+        if let Some(key) = self.lookup_key(&issuer.issuer, kid).await? {
+            return Ok(key);
+        }
+
+        // Concurrent misses wait for one refresh instead of each fetching.
+        let _refresh = match self.refreshing.get(&issuer.issuer) {
+            Some(lock) => Some(lock.lock().await),
+            None => None,
+        };
+        if let Some(key) = self.lookup_key(&issuer.issuer, kid).await? {
+            return Ok(key);
+        }
+
+        // Unknown kid: refetch unless we tried recently (key rotation vs. garbage
+        // kids, or an IdP that is down).
+        let refresh_after = Duration::from_secs(self.cfg.jwks_refresh_secs);
+        if let Some(entry) = self.keys.read().await.get(&issuer.issuer) {
+            if entry
+                .fetched_at
+                .is_some_and(|at| at.elapsed() < refresh_after)
+            {
+                return Err(match &entry.failure {
+                    Some(reason) => AuthError::Jwks {
+                        uri: issuer.jwks_uri.clone(),
+                        reason: reason.clone(),
+                    },
+                    None => AuthError::UnknownKey,
+                });
+            }
+        }
+
+        match self.fetch_jwks(&issuer.jwks_uri).await {
+            Ok(jwks) => self.insert_jwks(&issuer.issuer, jwks).await,
+            Err(e) => {
+                let mut cache = self.keys.write().await;
+                let entry = cache.entry(issuer.issuer.clone()).or_default();
+                entry.fetched_at = Some(Instant::now());
+                entry.failure = Some(match &e {
+                    AuthError::Jwks { reason, .. } => reason.clone(),
+                    other => other.to_string(),
+                });
+                return Err(e);
+            }
+        }
+        self.lookup_key(&issuer.issuer, kid)
+            .await?
+            .ok_or(AuthError::UnknownKey)
+    }
+
+    async fn lookup_key(
+        &self,
+        issuer: &str,
+        kid: Option<&str>,
+    ) -> Result<Option<DecodingKey>, AuthError> {
+        let cache = self.keys.read().await;
+        let Some(entry) = cache.get(issuer) else {
+            return Ok(None);
+        };
+        let jwk = match kid {
+            Some(kid) => entry
+                .keys
+                .iter()
+                .find(|k| k.common.key_id.as_deref() == Some(kid)),
+            // Without a kid we only accept an unambiguous key set.
+            None if entry.keys.len() == 1 => entry.keys.first(),
+            None => None,
+        };
+        jwk.map(DecodingKey::from_jwk)
+            .transpose()
+            .map_err(AuthError::from)
+    }
+
+    async fn fetch_jwks(&self, uri: &str) -> Result<JwkSet, AuthError> {
+        tracing::debug!(%uri, "Fetching JWKS");
+        let err = |e: reqwest::Error| AuthError::Jwks {
+            uri: uri.to_string(),
+            reason: e.to_string(),
+        };
+        self.http
+            .get(uri)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(err)?
+            .json::<JwkSet>()
+            .await
+            .map_err(err)
+    }
+}
+
+/// Read `iss` from the payload before verification, to pick the issuer's keys.
+fn unverified_issuer(token: &str) -> Result<String, AuthError> {
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| AuthError::Malformed("expected three segments".into()))?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|e| AuthError::Malformed(e.to_string()))?;
+    let claims: Value =
+        serde_json::from_slice(&bytes).map_err(|e| AuthError::Malformed(e.to_string()))?;
+    claims
+        .get("iss")
+        .and_then(Value::as_str)
+        .map(String::from)
+        .ok_or_else(|| AuthError::Malformed("missing 'iss' claim".into()))
+}
+
+/// Security requirements per `(method, route template)`, derived from operation specs.
+#[derive(Debug, Default)]
+pub struct RoutePolicies {
+    required: HashMap<(Method, String), Vec<SecurityRequirement>>,
+}
+
+impl RoutePolicies {
+    /// Collect the requirements on `schemes`; requirements on other (disabled)
+    /// schemes are not enforced.
+    pub fn from_specs<'a>(
+        specs: impl IntoIterator<Item = &'a OperationSpec>,
+        schemes: &[&str],
+    ) -> Self {
+        let required = specs
+            .into_iter()
+            .filter_map(|spec| {
+                let reqs: Vec<_> = spec
+                    .security
+                    .iter()
+                    .filter(|r| schemes.contains(&r.scheme.as_str()))
+                    .cloned()
+                    .collect();
+                (!reqs.is_empty()).then(|| ((spec.method.clone(), spec.path.clone()), reqs))
+            })
+            .collect();
+        Self { required }
+    }
+
+    /// Accepted requirements for the route (alternatives), `None` when the route is public.
+    pub fn requirements(&self, method: &Method, path: &str) -> Option<&[SecurityRequirement]> {
+        self.required
+            .get(&(method.clone(), path.to_string()))
+            .map(Vec::as_slice)
+    }
+}
+
+/// Shared state of the auth middleware.
+#[derive(Clone)]
+pub struct AuthState {
+    validator: Arc<JwtValidator>,
+    policies: Arc<RoutePolicies>,
+    require_by_default: bool,
+}
+
+impl AuthState {
+    pub fn new(validator: Arc<JwtValidator>, policies: RoutePolicies) -> Self {
+        let require_by_default = validator.cfg.enabled && validator.cfg.require_by_default;
+        Self {
+            validator,
+            policies: Arc::new(policies),
+            require_by_default,
+        }
+    }
+}
+
+/// Middleware: validate the bearer token (if any), enforce the route's security
+/// requirements and expose the resulting [`AuthContext`] to handlers.
+///
+/// A caller identified by the API key layer counts as `apiKeyAuth`, a valid
+/// token as `bearerAuth`; the credential must match one of the declared schemes.
+pub async fn auth_middleware(
+    State(state): State<AuthState>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string());
+    let requirements = route
+        .as_deref()
+        .and_then(|p| state.policies.requirements(req.method(), p));
+    let must_authenticate = requirements.is_some() || state.require_by_default;
+    let accepts =
+        |scheme: &str| requirements.is_none_or(|reqs| reqs.iter().any(|r| r.scheme == scheme));
+
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| {
+            v.strip_prefix("Bearer ")
+                .or_else(|| v.strip_prefix("bearer "))
+        })
+        .map(str::trim);
+
+    // An outer authenticator (API keys) may already have identified the caller.
+    let existing = req
+        .extensions()
+        .get::<AuthContext>()
+        .cloned()
+        .filter(|_| accepts(API_KEY_AUTH_SCHEME));
+
+    let (ctx, scheme) = match (existing, token) {
+        (Some(ctx), _) => (Some(ctx), API_KEY_AUTH_SCHEME),
+        (None, Some(token)) => match state.validator.validate(token).await {
+            Ok(ctx) => (Some(ctx), BEARER_AUTH_SCHEME),
+            Err(e) if must_authenticate => return e.into_response(),
+            Err(e) => {
+                tracing::debug!(error = %e, "Ignoring invalid token on public route");
+                (None, BEARER_AUTH_SCHEME)
+            }
+        },
+        (None, None) if must_authenticate && requirements.is_none() => {
+            return AuthError::MissingToken.into_response()
+        }
+        (None, None) => (None, BEARER_AUTH_SCHEME),
+    };
+
+    if let Some(requirements) = requirements {
+        let matched = requirements.iter().find(|r| r.scheme == scheme);
+        let (Some(ctx), Some(requirement)) = (&ctx, matched) else {
+            let schemes = requirements.iter().map(|r| r.scheme.clone()).collect();
+            return AuthError::MissingCredentials(schemes).into_response();
+        };
+        let missing: Vec<String> = requirement
+            .scopes
+            .iter()
+            .filter(|s| !ctx.has_scope(s))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            return AuthError::InsufficientScope(missing).into_response();
+        }
+    }
+
+    if let Some(ctx) = ctx {
+        req.extensions_mut().insert(ctx);
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, middleware::from_fn_with_state, routing::get, Router};
+    use jsonwebtoken::{EncodingKey, Header};
+    use tower::ServiceExt;
+
+    const SECRET: &[u8] = b"test-secret-that-is-long-enough!";
+    const ISSUER: &str = "https://issuer.test";
+
+    fn test_config() -> AuthConfig {
+        AuthConfig {
+            enabled: true,
+            issuers: vec![IssuerConfig {
+                issuer: ISSUER.into(),
+                jwks_uri: "http://127.0.0.1:9/jwks".into(),
+                audiences: vec!["hyperspot".into()],
+            }],
+            algorithms: vec![Algorithm::HS256],
+            ..Default::default()
+        }
+    }
+
+    async fn test_validator() -> Arc<JwtValidator> {
+        let validator = JwtValidator::new(test_config()).unwrap();
+        let jwks: JwkSet = serde_json::from_value(serde_json::json!({
+            "keys": [{
+                "kty": "oct",
+                "kid": "k1",
+                "alg": "HS256",
+                "k": base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(SECRET),
+            }]
+        }))
+        .unwrap();
+        validator.insert_jwks(ISSUER, jwks).await;
+        Arc::new(validator)
+    }
+
+    fn token(claims: Value) -> String {
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("k1".into());
+        jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    fn claims(scope: &str) -> Value {
+        serde_json::json!({
+            "iss": ISSUER,
+            "aud": "hyperspot",
+            "sub": "user-42",
+            "scope": scope,
+            "tenant_id": "acme",
+            "exp": 4_102_444_800u64,
+        })
+    }
+
+    #[tokio::test]
+    async fn validates_token_and_builds_context() {
+        let validator = test_validator().await;
+        let ctx = validator
+            .validate(&token(claims("users:read users:write")))
+            .await
+            .unwrap();
+        assert_eq!(ctx.subject, "user-42");
+        assert_eq!(ctx.scopes, vec!["users:read", "users:write"]);
+        assert_eq!(ctx.tenant.as_deref(), Some("acme"));
+    }
+
+    #[tokio::test]
+    async fn rejects_untrusted_issuer_and_wrong_audience() {
+        let validator = test_validator().await;
+
+        let mut c = claims("");
+        c["iss"] = "https://evil.test".into();
+        assert!(matches!(
+            validator.validate(&token(c)).await,
+            Err(AuthError::UnknownIssuer(_))
+        ));
+
+        let mut c = claims("");
+        c["aud"] = "someone-else".into();
+        assert!(matches!(
+            validator.validate(&token(c)).await,
+            Err(AuthError::InvalidToken(_))
+        ));
+    }
+
+    #[test]
+    fn algorithms_are_parsed_when_loading_config() {
+        let cfg: AuthConfig =
+            serde_json::from_value(serde_json::json!({ "algorithms": ["HS256", "ES384"] }))
+                .unwrap();
+        assert_eq!(cfg.algorithms, [Algorithm::HS256, Algorithm::ES384]);
+
+        let err =
+            serde_json::from_value::<AuthConfig>(serde_json::json!({ "algorithms": ["none"] }))
+                .unwrap_err();
+        assert!(err.to_string().contains("unknown signing algorithm 'none'"));
+    }
+
+    #[tokio::test]
+    async fn rejects_algorithms_outside_the_allow_list() {
+        let validator = JwtValidator::new(AuthConfig {
+            algorithms: vec![Algorithm::RS256],
+            ..test_config()
+        })
+        .unwrap();
+        assert!(matches!(
+            validator.validate(&token(claims(""))).await,
+            Err(AuthError::AlgorithmNotAllowed(Algorithm::HS256))
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_jwks_fetches_are_shared_and_cached() {
+        let hits = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = hits.clone();
+        let idp = Router::new().route(
+            "/jwks",
+            get(move || {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    StatusCode::SERVICE_UNAVAILABLE
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, idp).await.unwrap() });
+
+        let mut cfg = test_config();
+        cfg.issuers[0].jwks_uri = format!("http://{addr}/jwks");
+        let validator = Arc::new(JwtValidator::new(cfg).unwrap());
+        let token = token(claims(""));
+
+        let results = futures::future::join_all((0..8).map(|_| {
+            let validator = validator.clone();
+            let token = token.clone();
+            async move { validator.validate(&token).await }
+        }))
+        .await;
+        assert!(results
+            .iter()
+            .all(|r| matches!(r, Err(AuthError::Jwks { .. }))));
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Still inside `jwks_refresh_secs`: the failure is replayed.
+        assert!(matches!(
+            validator.validate(&token).await,
+            Err(AuthError::Jwks { .. })
+        ));
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    fn secured_app(validator: Arc<JwtValidator>) -> Router {
+        let spec = modkit::api::OperationBuilder::<_, _, ()>::get("/secure")
+            .require_scopes(["users:read"])
+            .spec()
+            .clone();
+        let state = AuthState::new(
+            validator,
+            RoutePolicies::from_specs([&spec], &[BEARER_AUTH_SCHEME, API_KEY_AUTH_SCHEME]),
+        );
+        Router::new()
+            .route(
+                "/secure",
+                get(|ctx: AuthContext| async move { ctx.subject }),
+            )
+            .route("/public", get(|| async { "ok" }))
+            .layer(from_fn_with_state(state, auth_middleware))
+    }
+
+    async fn call(app: Router, path: &str, bearer: Option<String>) -> Response {
+        let mut req = Request::builder().uri(path);
+        if let Some(t) = bearer {
+            req = req.header(header::AUTHORIZATION, format!("Bearer {t}"));
+        }
+        app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn middleware_enforces_declared_scopes() {
+        let app = secured_app(test_validator().await);
+
+        let resp = call(app.clone(), "/secure", None).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(resp.headers().contains_key(header::WWW_AUTHENTICATE));
+
+        let resp = call(app.clone(), "/secure", Some(token(claims("other")))).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = call(app.clone(), "/secure", Some(token(claims("users:read")))).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = call(app, "/public", None).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn credentials_must_match_declared_schemes() {
+        let key_only = modkit::api::OperationBuilder::<_, _, ()>::get("/internal")
+            .api_key_auth()
+            .spec()
+            .clone();
+        let bearer_only = modkit::api::OperationBuilder::<_, _, ()>::get("/secure")
+            .bearer_auth(["users:read"])
+            .spec()
+            .clone();
+        let state = AuthState::new(
+            test_validator().await,
+            RoutePolicies::from_specs(
+                [&key_only, &bearer_only],
+                &[BEARER_AUTH_SCHEME, API_KEY_AUTH_SCHEME],
+            ),
+        );
+        // Stand-in for the API key layer: any `x-api-key` identifies a caller.
+        let api_key = |mut req: Request<Body>, next: Next| async move {
+            if req.headers().contains_key("x-api-key") {
+                req.extensions_mut().insert(AuthContext {
+                    subject: "apikey:svc".into(),
+                    scopes: vec!["users:read".into()],
+                    tenant: None,
+                });
+            }
+            next.run(req).await
+        };
+        let app = Router::new()
+            .route(
+                "/internal",
+                get(|ctx: AuthContext| async move { ctx.subject }),
+            )
+            .route(
+                "/secure",
+                get(|ctx: AuthContext| async move { ctx.subject }),
+            )
+            .layer(from_fn_with_state(state, auth_middleware))
+            .layer(axum::middleware::from_fn(api_key));
+        let with_key = |path: &str| {
+            Request::get(path)
+                .header("x-api-key", "k")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let resp = call(app.clone(), "/internal", Some(token(claims("users:read")))).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(!resp.headers().contains_key(header::WWW_AUTHENTICATE));
+
+        let resp = app.clone().oneshot(with_key("/internal")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = app.oneshot(with_key("/secure")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(resp.headers().contains_key(header::WWW_AUTHENTICATE));
+    }
+}
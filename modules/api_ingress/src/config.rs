@@ -1,11 +1,77 @@
 use serde::{Deserialize, Serialize};
+
+use crate::access_log::AccessLogConfig;
+use crate::api_key::ApiKeyConfig;
+use crate::auth::AuthConfig;
+use crate::cors::CorsConfig;
+use crate::load_shed::ConcurrencyConfig;
+use crate::metrics::MetricsConfig;
+use crate::openapi_meta::OpenApiMetaConfig;
+use crate::path_normalize::PathNormalizationConfig;
+use crate::proxy::ProxyRouteConfig;
+use crate::ready_gate::ReadyGateConfig;
+use crate::static_sites::StaticSiteConfig;
+use crate::web::DocsUi;
+
 /// API ingress configuration - reused from api_ingress module
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, modkit::ConfigSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ApiIngressConfig {
     pub bind_addr: String,
     #[serde(default)]
     pub enable_docs: bool,
+    /// UI rendered at `/docs`: `elements`, `swagger_ui` or `redoc`.
+    #[serde(default)]
+    pub docs_ui: DocsUi,
+    /// Expose `/__debug/routes` listing every registered operation.
+    #[serde(default)]
+    pub debug_routes: bool,
+    /// Expose `/__debug/clients` listing the clients wired into the ClientHub.
+    #[serde(default)]
+    pub debug_clients: bool,
+    /// Expose `/__debug/telemetry` to change log levels and trace sampling at runtime.
+    /// Changes need a bearer token with the `admin:telemetry` scope; without auth it is read-only.
+    #[serde(default)]
+    pub debug_telemetry: bool,
+    /// Trailing-slash handling and case-insensitive path matching.
+    #[serde(default)]
+    pub path_normalization: PathNormalizationConfig,
+    /// Document info, servers and tag metadata of the generated OpenAPI spec.
+    #[serde(default)]
+    pub openapi: OpenApiMetaConfig,
     #[serde(default)]
     pub cors_enabled: bool,
+    /// CORS policy applied when `cors_enabled` is set.
+    #[serde(default)]
+    pub cors: CorsConfig,
+    /// Bearer token (JWT/OIDC) authentication.
+    #[serde(default)]
+    pub auth: AuthConfig,
+    /// Static API keys for service-to-service callers.
+    #[serde(default)]
+    pub api_keys: ApiKeyConfig,
+    /// Prometheus metrics endpoint and HTTP request metrics.
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    /// In-flight request limits; excess requests get a 503 with `Retry-After`.
+    #[serde(default)]
+    #[config(nested)]
+    pub concurrency: ConcurrencyConfig,
+    /// Answer 503 on application routes until every module is running (on by default).
+    #[serde(default)]
+    pub ready_gate: ReadyGateConfig,
+    /// Structured access log with optional body sampling.
+    #[serde(default)]
+    #[config(nested)]
+    pub access_log: AccessLogConfig,
+    /// Validate request parameters and JSON bodies against the registered
+    /// OpenAPI schemas (422 on mismatch). Intended for dev/staging.
+    #[serde(default)]
+    pub request_validation: bool,
+    /// Static directories (e.g. dashboards) served next to the API.
+    #[serde(default)]
+    pub static_sites: Vec<StaticSiteConfig>,
+    /// Path prefixes forwarded to upstream services.
+    #[serde(default)]
+    pub proxy: Vec<ProxyRouteConfig>,
 }
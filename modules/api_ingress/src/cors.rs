@@ -0,0 +1,254 @@
+//! Typed CORS policy.
+//!
+//! Origins are matched exactly, by `*` (any origin) or by a single leading
+//! subdomain wildcard such as `https://*.example.com`. The policy is validated
+//! when the router is built so a bad config fails startup instead of silently
+//! allowing (or blocking) browsers.
+
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{AllowHeaders, AllowOrigin, CorsLayer, ExposeHeaders};
+
+/// CORS section of the ingress configuration (applied when `cors_enabled`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CorsConfig {
+    /// Allowed origins: exact values, `*`, or `scheme://*.domain` wildcards.
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    #[serde(default = "default_methods")]
+    pub allowed_methods: Vec<String>,
+    /// Allowed request headers; `*` allows any header.
+    #[serde(default = "default_headers")]
+    pub allowed_headers: Vec<String>,
+    /// Response headers readable by scripts.
+    #[serde(default = "default_expose_headers")]
+    pub expose_headers: Vec<String>,
+    #[serde(default)]
+    pub allow_credentials: bool,
+    /// How long browsers may cache preflight results.
+    #[serde(default = "default_max_age_secs")]
+    pub max_age_secs: Option<u64>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: default_methods(),
+            allowed_headers: default_headers(),
+            expose_headers: default_expose_headers(),
+            allow_credentials: false,
+            max_age_secs: default_max_age_secs(),
+        }
+    }
+}
+
+fn default_methods() -> Vec<String> {
+    ["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"]
+        .map(String::from)
+        .to_vec()
+}
+
+fn default_headers() -> Vec<String> {
+    ["content-type", "authorization", "x-request-id"]
+        .map(String::from)
+        .to_vec()
+}
+
+fn default_expose_headers() -> Vec<String> {
+    vec!["x-request-id".to_string()]
+}
+
+fn default_max_age_secs() -> Option<u64> {
+    Some(600)
+}
+
+/// Parsed origin rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OriginRule {
+    Any,
+    Exact(String),
+    /// `scheme://` prefix and `.domain` suffix of a subdomain wildcard.
+    Wildcard {
+        scheme: String,
+        suffix: String,
+    },
+}
+
+impl OriginRule {
+    fn parse(origin: &str) -> anyhow::Result<Self> {
+        if origin == "*" {
+            return Ok(Self::Any);
+        }
+        let Some((scheme, host)) = origin.split_once("://") else {
+            anyhow::bail!("CORS origin '{origin}' must include a scheme, e.g. https://");
+        };
+        if origin.ends_with('/') {
+            anyhow::bail!("CORS origin '{origin}' must not end with '/'");
+        }
+        match host.strip_prefix('*') {
+            Some(suffix) if suffix.starts_with('.') && !suffix.contains('*') => {
+                Ok(Self::Wildcard {
+                    scheme: format!("{scheme}://"),
+                    suffix: suffix.to_string(),
+                })
+            }
+            Some(_) => anyhow::bail!(
+                "CORS origin '{origin}' may only use a leading '*.' subdomain wildcard"
+            ),
+            None if host.contains('*') => anyhow::bail!(
+                "CORS origin '{origin}' may only use a leading '*.' subdomain wildcard"
+            ),
+            None => Ok(Self::Exact(origin.to_string())),
+        }
+    }
+
+    fn matches(&self, origin: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Exact(o) => o.eq_ignore_ascii_case(origin),
+            Self::Wildcard { scheme, suffix } => origin
+                .strip_prefix(scheme.as_str())
+                .and_then(|host| host.strip_suffix(suffix.as_str()))
+                .is_some_and(|sub| !sub.is_empty() && !sub.contains('/')),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Validate the policy and build the tower-http layer.
+    pub fn build_layer(&self) -> anyhow::Result<CorsLayer> {
+        if self.allowed_origins.is_empty() {
+            anyhow::bail!("cors_enabled requires at least one entry in cors.allowed_origins");
+        }
+        let rules = self
+            .allowed_origins
+            .iter()
+            .map(|o| OriginRule::parse(o))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let any_origin = rules.contains(&OriginRule::Any);
+        let any_header = self.allowed_headers.iter().any(|h| h == "*");
+        if self.allow_credentials && (any_origin || any_header) {
+            anyhow::bail!("CORS allow_credentials cannot be combined with '*' origins or headers");
+        }
+
+        let methods = self
+            .allowed_methods
+            .iter()
+            .map(|m| Method::from_bytes(m.to_ascii_uppercase().as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let origin = if any_origin {
+            AllowOrigin::any()
+        } else if rules.iter().all(|r| matches!(r, OriginRule::Exact(_))) {
+            AllowOrigin::list(
+                self.allowed_origins
+                    .iter()
+                    .map(|o| HeaderValue::from_str(o))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+        } else {
+            AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+                origin
+                    .to_str()
+                    .is_ok_and(|o| rules.iter().any(|r| r.matches(o)))
+            })
+        };
+
+        let headers = if any_header {
+            AllowHeaders::any()
+        } else {
+            AllowHeaders::list(parse_headers(&self.allowed_headers)?)
+        };
+
+        let mut layer = CorsLayer::new()
+            .allow_origin(origin)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .expose_headers(ExposeHeaders::list(parse_headers(&self.expose_headers)?))
+            .allow_credentials(self.allow_credentials);
+        if let Some(secs) = self.max_age_secs {
+            layer = layer.max_age(Duration::from_secs(secs));
+        }
+        Ok(layer)
+    }
+}
+
+fn parse_headers(names: &[String]) -> anyhow::Result<Vec<HeaderName>> {
+    names
+        .iter()
+        .map(|h| HeaderName::try_from(h.as_str()).map_err(Into::into))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn cfg(origins: &[&str]) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: origins.iter().map(|o| o.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wildcard_origins_match_subdomains_only() {
+        let rule = OriginRule::parse("https://*.example.com").unwrap();
+        assert!(rule.matches("https://app.example.com"));
+        assert!(rule.matches("https://a.b.example.com"));
+        assert!(!rule.matches("https://example.com"));
+        assert!(!rule.matches("http://app.example.com"));
+        assert!(!rule.matches("https://app.example.com.evil.io"));
+    }
+
+    #[test]
+    fn invalid_policies_are_rejected() {
+        assert!(cfg(&[]).build_layer().is_err());
+        assert!(cfg(&["example.com"]).build_layer().is_err());
+        assert!(cfg(&["https://app.*.com"]).build_layer().is_err());
+        let mut creds = cfg(&["*"]);
+        creds.allow_credentials = true;
+        assert!(creds.build_layer().is_err());
+        assert!(cfg(&["https://*.example.com", "http://localhost:3000"])
+            .build_layer()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn only_allowed_origins_get_cors_headers() {
+        let layer = cfg(&["https://*.example.com"]).build_layer().unwrap();
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(layer);
+
+        let allow_origin = |origin: &'static str| {
+            let app = app.clone();
+            async move {
+                let resp = app
+                    .oneshot(
+                        Request::get("/")
+                            .header("origin", origin)
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                resp.headers()
+                    .get("access-control-allow-origin")
+                    .map(|v| v.to_str().unwrap().to_owned())
+            }
+        };
+
+        assert_eq!(
+            allow_origin("https://app.example.com").await.as_deref(),
+            Some("https://app.example.com")
+        );
+        assert_eq!(allow_origin("https://evil.io").await, None);
+    }
+}
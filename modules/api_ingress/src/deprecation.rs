@@ -0,0 +1,157 @@
+//! `Deprecation` / `Sunset` response headers for deprecated operations.
+//!
+//! Operations marked with `OperationBuilder::deprecated` get `Deprecation: true`
+//! on every response; those with a sunset date also get `Sunset` (RFC 8594) as
+//! an HTTP-date, so clients learn about the removal without reading the docs.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{HeaderName, HeaderValue, Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use modkit::api::OperationSpec;
+
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Parse a sunset date given as RFC 3339, `YYYY-MM-DD` (midnight UTC) or HTTP-date.
+pub fn parse_sunset(raw: &str) -> anyhow::Result<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return Ok(date.and_time(Default::default()).and_utc());
+    }
+    DateTime::parse_from_rfc2822(raw)
+        .map(|ts| ts.with_timezone(&Utc))
+        .map_err(|_| anyhow::anyhow!("invalid sunset date '{raw}'"))
+}
+
+/// IMF-fixdate, the preferred HTTP-date format (RFC 9110 §5.6.7).
+fn http_date(ts: DateTime<Utc>) -> String {
+    ts.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Deprecated routes keyed by `(method, route template)`.
+#[derive(Clone, Default)]
+pub struct DeprecationState {
+    routes: Arc<HashMap<(Method, String), Option<HeaderValue>>>,
+}
+
+impl DeprecationState {
+    pub fn from_specs<'a>(
+        specs: impl IntoIterator<Item = &'a OperationSpec>,
+    ) -> anyhow::Result<Self> {
+        let mut routes = HashMap::new();
+        for spec in specs.into_iter().filter(|s| s.deprecated) {
+            let sunset = match &spec.sunset {
+                Some(raw) => {
+                    let date = parse_sunset(raw)
+                        .map_err(|e| anyhow::anyhow!("{} {}: {e}", spec.method, spec.path))?;
+                    Some(HeaderValue::try_from(http_date(date))?)
+                }
+                None => None,
+            };
+            routes.insert((spec.method.clone(), spec.path.clone()), sunset);
+        }
+        Ok(Self {
+            routes: Arc::new(routes),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+/// Middleware adding `Deprecation` and `Sunset` to responses of deprecated routes.
+pub async fn deprecation_middleware(
+    State(state): State<DeprecationState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let key = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| (req.method().clone(), p.as_str().to_string()));
+    let entry = key.and_then(|k| state.routes.get(&k).cloned());
+    let mut resp = next.run(req).await;
+    if let Some(sunset) = entry {
+        let headers = resp.headers_mut();
+        headers.insert(DEPRECATION, HeaderValue::from_static("true"));
+        if let Some(sunset) = sunset {
+            headers.insert(SUNSET, sunset);
+        }
+    }
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn_with_state, routing::get, Router};
+    use modkit::api::{Missing, OperationBuilder};
+    use tower::ServiceExt;
+
+    #[test]
+    fn sunset_formats() {
+        let expected = "Thu, 31 Dec 2026 00:00:00 GMT";
+        for raw in [
+            "2026-12-31",
+            "2026-12-31T00:00:00Z",
+            "Thu, 31 Dec 2026 00:00:00 GMT",
+        ] {
+            assert_eq!(http_date(parse_sunset(raw).unwrap()), expected);
+        }
+        assert!(parse_sunset("next year").is_err());
+    }
+
+    #[tokio::test]
+    async fn deprecated_routes_get_headers() {
+        let old = OperationBuilder::<Missing, Missing, ()>::get("/v1/users")
+            .deprecated_with_sunset("2026-12-31")
+            .spec()
+            .clone();
+        let state = DeprecationState::from_specs([&old]).unwrap();
+        let app = Router::new()
+            .route("/v1/users", get(|| async { "old" }))
+            .route("/v2/users", get(|| async { "new" }))
+            .layer(from_fn_with_state(state, deprecation_middleware));
+
+        let resp = app
+            .clone()
+            .oneshot(Request::get("/v1/users").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.headers()["deprecation"], "true");
+        assert_eq!(resp.headers()["sunset"], "Thu, 31 Dec 2026 00:00:00 GMT");
+
+        let resp = app
+            .oneshot(Request::get("/v2/users").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert!(!resp.headers().contains_key("deprecation"));
+    }
+
+    #[test]
+    fn openapi_marks_operation_deprecated() {
+        let api = crate::ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/v1/users")
+            .deprecated()
+            .handler(|| async { "old" })
+            .json_response(200, "Users")
+            .register(Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        assert_eq!(
+            v.pointer("/paths/~1v1~1users/get/deprecated"),
+            Some(&true.into())
+        );
+    }
+}
@@ -88,8 +88,9 @@ impl Module for UserModule {
     }
 }
 
+#[async_trait]
 impl RestfulModule for UserModule {
-    fn register_rest(
+    async fn register_rest(
         &self,
         _ctx: &modkit::context::ModuleCtx,
         router: Router,
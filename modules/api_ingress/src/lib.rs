@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use arc_swap::ArcSwap;
@@ -6,7 +7,11 @@ use dashmap::DashMap;
 
 use anyhow::Result;
 use axum::http::Method;
-use axum::{middleware::from_fn, routing::get, Router};
+use axum::{
+    middleware::{from_fn, from_fn_with_state},
+    routing::get,
+    Router,
+};
 use modkit::api::problem;
 use modkit::api::OpenApiRegistry;
 use modkit::lifecycle::ReadySignal;
@@ -15,35 +20,59 @@ use std::net::SocketAddr;
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tower_http::{
-    cors::CorsLayer,
     limit::RequestBodyLimitLayer,
     request_id::{PropagateRequestIdLayer, SetRequestIdLayer},
     timeout::TimeoutLayer,
 };
 use utoipa::openapi::{
     content::ContentBuilder,
-    info::InfoBuilder,
+    example::ExampleBuilder,
+    extensions::Extensions,
+    external_docs::ExternalDocsBuilder,
+    header::HeaderBuilder,
     path::{
         HttpMethod, OperationBuilder as UOperationBuilder, ParameterBuilder, ParameterIn,
-        PathItemBuilder, PathsBuilder,
+        ParameterStyle, PathItemBuilder, PathsBuilder,
     },
     request_body::RequestBodyBuilder,
     response::{ResponseBuilder, ResponsesBuilder},
-    schema::{ComponentsBuilder, ObjectBuilder, Schema, SchemaFormat, SchemaType},
+    schema::{ArrayBuilder, ComponentsBuilder, ObjectBuilder, Schema, SchemaFormat, SchemaType},
+    security::{
+        ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme,
+    },
     OpenApi, OpenApiBuilder, Ref, RefOr, Required,
 };
 
+pub mod access_log;
+pub mod api_key;
 mod assets;
 
+pub mod auth;
 mod config;
+pub mod cors;
+pub mod deprecation;
 pub mod error;
+pub mod load_shed;
+pub mod metrics;
 mod model;
+mod openapi_export;
+pub mod openapi_meta;
+pub mod path_normalize;
+pub mod proxy;
+mod rate_limit;
+mod readiness;
+pub mod ready_gate;
 pub mod request_id;
+pub mod route_table;
 mod router_cache;
+pub mod static_sites;
+pub mod validation;
 mod web;
 
 pub use config::ApiIngressConfig;
+pub use openapi_export::OpenApiFormat;
 use router_cache::RouterCache;
+pub use web::DocsUi;
 
 #[cfg(test)]
 pub mod example_user_module;
@@ -55,6 +84,7 @@ use model::ComponentsRegistry;
 #[modkit::module(
     name = "api_ingress",
     capabilities = [rest_host, rest, stateful],
+    config = crate::config::ApiIngressConfig,
     lifecycle(entry = "serve", stop_timeout = "30s", await_ready)
 )]
 pub struct ApiIngress {
@@ -73,6 +103,18 @@ pub struct ApiIngress {
 
     // Store operation specs for OpenAPI generation
     operation_specs: DashMap<String, modkit::api::OperationSpec>,
+
+    // Custom API key store; defaults to the keys from config
+    api_key_store: Mutex<Option<Arc<dyn api_key::ApiKeyStore>>>,
+
+    // Hub from init, listed by `/__debug/clients`
+    client_hub: Mutex<Option<Arc<modkit::ClientHub>>>,
+
+    // Log level control shared by the embedder, used by `/__debug/telemetry`
+    log_levels: Mutex<Option<Arc<dyn modkit::telemetry::LogLevels>>>,
+
+    // Mappers for foreign errors, registered by modules during the REST phase
+    error_mappers: Mutex<Vec<Arc<modkit::api::ErrorMapper>>>,
 }
 
 impl Default for ApiIngress {
@@ -86,6 +128,10 @@ impl Default for ApiIngress {
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
             operation_specs: DashMap::new(),
+            api_key_store: Mutex::new(None),
+            client_hub: Mutex::new(None),
+            log_levels: Mutex::new(None),
+            error_mappers: Mutex::new(Vec::new()),
         }
     }
 }
@@ -113,6 +159,11 @@ impl ApiIngress {
         (**self.config.load()).clone()
     }
 
+    /// Replace the config-backed API key store (must be called before the REST phase)
+    pub fn set_api_key_store(&self, store: Arc<dyn api_key::ApiKeyStore>) {
+        *self.api_key_store.lock() = Some(store);
+    }
+
     /// Get the cached router without rebuilding (useful for performance-critical paths)
     pub fn get_cached_router(&self) -> Arc<Router> {
         self.router_cache.load()
@@ -136,12 +187,32 @@ impl ApiIngress {
         }
 
         tracing::debug!("Building new router");
+        let config = self.get_cached_config();
         let mut router = Router::new().route("/health", get(web::health_check));
+        if config.metrics.enabled {
+            router = router.route(&config.metrics.path, get(metrics::metrics_handler));
+        }
 
         // Correct middleware order (outermost to innermost):
-        // PropagateRequestId -> SetRequestId -> push_req_id_to_extensions -> Trace -> Timeout -> CORS -> BodyLimit
+        // PropagateRequestId -> SetRequestId -> push_req_id_to_extensions -> Trace -> Timeout -> CORS -> BodyLimit -> Metrics
         let x_request_id = crate::request_id::header();
 
+        // 0. Request metrics (innermost, so it sees the matched route)
+        if config.metrics.enabled {
+            router = router.layer(from_fn(metrics::http_metrics_middleware));
+        }
+
+        // 0a. Conditional GET (304 for matching ETags)
+        router = router.layer(from_fn(modkit::api::response::conditional_get_middleware));
+
+        // 0b. Access log (inside the request-id layers so it can read XRequestId)
+        if config.access_log.enabled {
+            router = router.layer(from_fn_with_state(
+                access_log::AccessLogState::new(config.access_log.clone()),
+                access_log::access_log_middleware,
+            ));
+        }
+
         // 1. If client sent x-request-id, propagate it; otherwise we will set it
         router = router.layer(PropagateRequestIdLayer::new(x_request_id.clone()));
 
@@ -161,14 +232,16 @@ impl ApiIngress {
         router = router.layer(TimeoutLayer::new(Duration::from_secs(30)));
 
         // 6. CORS layer (if enabled)
-        let config = self.get_cached_config();
         if config.cors_enabled {
-            router = router.layer(CorsLayer::permissive());
+            router = router.layer(config.cors.build_layer()?);
         }
 
         // 7. Body limit layer - 16MB default limit
         router = router.layer(RequestBodyLimitLayer::new(16 * 1024 * 1024));
 
+        // 8. Path normalization wraps the whole router (must run before routing)
+        router = self.normalize_paths(router, &config.path_normalization);
+
         // Cache the built router for future use
         self.router_cache.store(router.clone());
 
@@ -177,14 +250,64 @@ impl ApiIngress {
 
     /// Build OpenAPI specification from registered routes and components using utoipa.
     pub fn build_openapi(&self) -> Result<OpenApi> {
+        self.build_openapi_filtered(|_| true)
+    }
+
+    /// Build the OpenAPI document of the operations registered under `version`.
+    pub fn build_openapi_for_version(&self, version: &str) -> Result<OpenApi> {
+        self.build_openapi_filtered(|spec| spec.version.as_deref() == Some(version))
+    }
+
+    /// Wrap `router` with path normalization over the registered operation paths.
+    fn normalize_paths(
+        &self,
+        router: Router,
+        cfg: &path_normalize::PathNormalizationConfig,
+    ) -> Router {
+        let templates: Vec<String> = self
+            .operation_specs
+            .iter()
+            .map(|e| e.value().path.clone())
+            .collect();
+        path_normalize::wrap(router, cfg, templates.iter().map(String::as_str))
+    }
+
+    /// Validator over the registered component schemas.
+    pub fn schema_validator(&self) -> Result<validation::SchemaValidator> {
+        let mut components = serde_json::Map::new();
+        for (name, schema) in self.components_registry.load().iter() {
+            components.insert(name.clone(), serde_json::to_value(schema)?);
+        }
+        Ok(validation::SchemaValidator::new(components))
+    }
+
+    /// API versions used by registered operations, sorted.
+    pub fn api_versions(&self) -> BTreeSet<String> {
+        self.operation_specs
+            .iter()
+            .filter_map(|e| e.value().version.clone())
+            .collect()
+    }
+
+    fn build_openapi_filtered(
+        &self,
+        include: impl Fn(&modkit::api::OperationSpec) -> bool,
+    ) -> Result<OpenApi> {
+        let specs: Vec<modkit::api::OperationSpec> = self
+            .operation_specs
+            .iter()
+            .map(|e| e.value().clone())
+            .filter(|spec| include(spec))
+            .collect();
+
         // Log operation count for visibility
-        let op_count = self.operation_specs.len();
+        let op_count = specs.len();
         tracing::info!("Building OpenAPI: found {op_count} registered operations");
 
         // 1) Paths
         let mut paths = PathsBuilder::new();
 
-        for spec in self.operation_specs.iter().map(|e| e.value().clone()) {
+        for spec in &specs {
             let mut op = UOperationBuilder::new()
                 .operation_id(spec.operation_id.clone().or(Some(spec.handler_id.clone())))
                 .summary(spec.summary.clone())
@@ -194,6 +317,43 @@ impl ApiIngress {
                 op = op.tag(tag.clone());
             }
 
+            if spec.deprecated {
+                op = op.deprecated(Some(utoipa::openapi::Deprecated::True));
+            }
+
+            let mut extensions: Extensions = spec
+                .extensions
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            if spec.websocket {
+                extensions.insert("x-websocket".to_string(), true.into());
+            }
+            if let Some(sunset) = &spec.sunset {
+                extensions.insert("x-sunset".to_string(), sunset.clone().into());
+            }
+            if let Some(limit) = spec.rate_limit {
+                extensions.insert(
+                    "x-rate-limit".to_string(),
+                    serde_json::json!({ "rps": limit.rps, "burst": limit.burst }),
+                );
+            }
+            // utoipa models `callbacks` as a bare string; the flattened extensions
+            // map serializes the object in the right place instead.
+            if !spec.callbacks.is_empty() {
+                extensions.insert("callbacks".to_string(), callbacks_json(&spec.callbacks));
+            }
+            if !extensions.is_empty() {
+                op = op.extensions(Some(extensions));
+            }
+
+            for req in &spec.security {
+                op = op.security(SecurityRequirement::new(
+                    req.scheme.clone(),
+                    req.scopes.clone(),
+                ));
+            }
+
             // Parameters
             for p in &spec.params {
                 let in_ = match p.location {
@@ -209,36 +369,69 @@ impl ApiIngress {
                         Required::False
                     };
 
-                let schema_type = match p.param_type.as_str() {
-                    "integer" => SchemaType::Type(utoipa::openapi::schema::Type::Integer),
-                    "number" => SchemaType::Type(utoipa::openapi::schema::Type::Number),
-                    "boolean" => SchemaType::Type(utoipa::openapi::schema::Type::Boolean),
-                    _ => SchemaType::Type(utoipa::openapi::schema::Type::String),
+                let scalar = |ty: &str, format: Option<String>| {
+                    let schema_type = match ty {
+                        "integer" => SchemaType::Type(utoipa::openapi::schema::Type::Integer),
+                        "number" => SchemaType::Type(utoipa::openapi::schema::Type::Number),
+                        "boolean" => SchemaType::Type(utoipa::openapi::schema::Type::Boolean),
+                        _ => SchemaType::Type(utoipa::openapi::schema::Type::String),
+                    };
+                    let mut schema = ObjectBuilder::new()
+                        .schema_type(schema_type)
+                        .format(format.map(SchemaFormat::Custom));
+                    if !p.enum_values.is_empty() {
+                        schema = schema.enum_values(Some(p.enum_values.clone()));
+                    }
+                    Schema::Object(schema.build())
                 };
-                let schema = Schema::Object(ObjectBuilder::new().schema_type(schema_type).build());
-
-                let param = ParameterBuilder::new()
+                let mut param = ParameterBuilder::new()
                     .name(&p.name)
                     .parameter_in(in_)
                     .required(required)
-                    .description(p.description.clone())
-                    .schema(Some(schema))
-                    .build();
+                    .description(p.description.clone());
+                param = match &p.items {
+                    Some(items) => param
+                        .schema(Some(Schema::Array(
+                            ArrayBuilder::new()
+                                .items(RefOr::T(scalar(&items.item_type, None)))
+                                .build(),
+                        )))
+                        .style(Some(ParameterStyle::Form))
+                        .explode(Some(
+                            items.collection == modkit::api::CollectionFormat::Multi,
+                        )),
+                    None => param.schema(Some(scalar(&p.param_type, p.format.clone()))),
+                };
+                let param = param.build();
 
                 op = op.parameter(param);
             }
 
             // Request body
             if let Some(rb) = &spec.request_body {
-                let content = if let Some(name) = &rb.schema_name {
+                let content = if !rb.multipart_fields.is_empty() {
+                    let mut form = ObjectBuilder::new()
+                        .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::Object));
+                    for field in &rb.multipart_fields {
+                        let mut prop = ObjectBuilder::new()
+                            .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::String))
+                            .description(field.description.clone());
+                        if field.file {
+                            prop = prop.format(Some(SchemaFormat::Custom("binary".into())));
+                        }
+                        form = form.property(&field.name, prop);
+                        if field.required {
+                            form = form.required(&field.name);
+                        }
+                    }
+                    ContentBuilder::new().schema(Some(Schema::Object(form.build())))
+                } else if let Some(name) = &rb.schema_name {
                     ContentBuilder::new()
                         .schema(Some(RefOr::Ref(Ref::from_schema_name(name.clone()))))
-                        .build()
                 } else {
-                    ContentBuilder::new()
-                        .schema(Some(Schema::Object(ObjectBuilder::new().build())))
-                        .build()
+                    ContentBuilder::new().schema(Some(Schema::Object(ObjectBuilder::new().build())))
                 };
+                let content = content.example(spec.request_example.clone()).build();
                 let mut rbld = RequestBodyBuilder::new()
                     .description(rb.description.clone())
                     .content(rb.content_type.to_string(), content);
@@ -248,50 +441,85 @@ impl ApiIngress {
                 op = op.request_body(Some(rbld.build()));
             }
 
-            // Responses
-            let mut responses = ResponsesBuilder::new();
+            // Responses; several specs with one status become one response with several media types
+            let mut by_status: Vec<(String, utoipa::openapi::Response)> = Vec::new();
             for r in &spec.responses {
                 let is_json_like = r.content_type == "application/json"
                     || r.content_type == problem::APPLICATION_PROBLEM_JSON
                     || r.content_type == "text/event-stream";
-                let resp = if is_json_like {
-                    if let Some(name) = &r.schema_name {
-                        // Manually build content to preserve the correct content type
-                        let content = ContentBuilder::new()
-                            .schema(Some(RefOr::Ref(Ref::new(format!(
-                                "#/components/schemas/{}",
-                                name
-                            )))))
-                            .build();
-                        ResponseBuilder::new()
-                            .description(&r.description)
-                            .content(r.content_type, content)
-                            .build()
-                    } else {
-                        let content = ContentBuilder::new()
-                            .schema(Some(Schema::Object(ObjectBuilder::new().build())))
-                            .build();
-                        ResponseBuilder::new()
-                            .description(&r.description)
-                            .content(r.content_type, content)
-                            .build()
-                    }
+                let mut resp = if r.content_type.is_empty() {
+                    // Body-less response, e.g. 304 Not Modified
+                    ResponseBuilder::new().description(&r.description)
+                } else if is_json_like {
+                    // Manually build content to preserve the correct content type
+                    let schema: RefOr<Schema> = match &r.schema_name {
+                        Some(name) => {
+                            RefOr::Ref(Ref::new(format!("#/components/schemas/{}", name)))
+                        }
+                        None => RefOr::T(Schema::Object(ObjectBuilder::new().build())),
+                    };
+                    let examples = spec
+                        .response_examples
+                        .iter()
+                        .filter(|e| r.status == modkit::api::ResponseStatus::Code(e.status))
+                        .map(|e| {
+                            let example = ExampleBuilder::new().value(Some(e.value.clone()));
+                            (e.name.clone(), example.build())
+                        });
+                    let content = ContentBuilder::new()
+                        .schema(Some(schema))
+                        .examples_from_iter(examples)
+                        .build();
+                    ResponseBuilder::new()
+                        .description(&r.description)
+                        .content(r.content_type, content)
                 } else {
+                    // Text bodies keep their media type as format; anything else is raw bytes
+                    let format = if r.content_type.starts_with("text/") {
+                        r.content_type
+                    } else {
+                        "binary"
+                    };
                     let schema = Schema::Object(
                         ObjectBuilder::new()
                             .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::String))
-                            .format(Some(SchemaFormat::Custom(r.content_type.into())))
+                            .format(Some(SchemaFormat::Custom(format.into())))
                             .build(),
                     );
                     let content = ContentBuilder::new().schema(Some(schema)).build();
                     ResponseBuilder::new()
                         .description(&r.description)
                         .content(r.content_type, content)
-                        .build()
                 };
-                responses = responses.response(r.status.to_string(), resp);
+                if r.status
+                    .code()
+                    .is_some_and(|code| (200..300).contains(&code))
+                {
+                    for h in &spec.response_headers {
+                        let schema = ObjectBuilder::new()
+                            .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::String))
+                            .build();
+                        resp = resp.header(
+                            &h.name,
+                            HeaderBuilder::new()
+                                .schema(schema)
+                                .description(h.description.clone())
+                                .build(),
+                        );
+                    }
+                }
+                let resp = resp.build();
+                let key = r.status.to_string();
+                match by_status.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, existing)) => existing.content.extend(resp.content),
+                    None => by_status.push((key, resp)),
+                }
             }
-            op = op.responses(responses.build());
+            op = op.responses(
+                ResponsesBuilder::new()
+                    .responses_from_iter(by_status)
+                    .build(),
+            );
 
             let method = match spec.method {
                 Method::GET => HttpMethod::Get,
@@ -302,7 +530,14 @@ impl ApiIngress {
                 _ => HttpMethod::Get,
             };
 
-            let item = PathItemBuilder::new().operation(method, op.build()).build();
+            let mut operation = op.build();
+            operation.external_docs = spec.external_docs.as_ref().map(|docs| {
+                ExternalDocsBuilder::new()
+                    .url(&docs.url)
+                    .description(docs.description.clone())
+                    .build()
+            });
+            let item = PathItemBuilder::new().operation(method, operation).build();
             paths = paths.path(spec.path.clone(), item);
         }
 
@@ -312,15 +547,48 @@ impl ApiIngress {
             components = components.schema(name.clone(), schema.clone());
         }
 
-        // 3) Info & final OpenAPI doc
-        let info = InfoBuilder::new()
-            .title("HyperSpot API")
-            .version("0.1.0")
-            .description(Some("HyperSpot Server API Documentation"))
-            .build();
+        // Security schemes: declared by auth config or referenced by operations
+        let config = self.config.load();
+        let used_schemes: BTreeSet<&str> = specs
+            .iter()
+            .flat_map(|spec| spec.security.iter().map(|r| r.scheme.as_str()))
+            .collect();
+        if config.auth.enabled || used_schemes.contains(modkit::api::BEARER_AUTH_SCHEME) {
+            components = components.security_scheme(
+                modkit::api::BEARER_AUTH_SCHEME,
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+        }
+        if config.api_keys.enabled || used_schemes.contains(modkit::api::API_KEY_AUTH_SCHEME) {
+            components = components.security_scheme(
+                modkit::api::API_KEY_AUTH_SCHEME,
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(
+                    config.api_keys.header.clone(),
+                ))),
+            );
+        }
+        for unknown in used_schemes.iter().filter(|s| {
+            **s != modkit::api::BEARER_AUTH_SCHEME && **s != modkit::api::API_KEY_AUTH_SCHEME
+        }) {
+            tracing::warn!(scheme = %unknown, "Operation references an undefined security scheme");
+        }
+
+        // 3) Info, servers, tags & final OpenAPI doc
+        let meta = &config.openapi;
+        let used_tags: BTreeSet<&str> = specs
+            .iter()
+            .flat_map(|spec| spec.tags.iter().map(String::as_str))
+            .collect();
 
         let openapi = OpenApiBuilder::new()
-            .info(info)
+            .info(meta.info())
+            .servers(meta.servers())
+            .tags(meta.tags(used_tags))
             .paths(paths.build())
             .components(Some(components.build()))
             .build();
@@ -381,6 +649,8 @@ impl modkit::Module for ApiIngress {
         tracing::debug!(module = "api_ingress", "Module initialized with context");
         let cfg = ctx.config::<crate::config::ApiIngressConfig>()?;
         self.config.store(Arc::new(cfg));
+        *self.client_hub.lock() = Some(ctx.client_hub());
+        *self.log_levels.lock() = ctx.resource::<dyn modkit::telemetry::LogLevels>();
         Ok(())
     }
 
@@ -448,60 +718,439 @@ mod tests {
         assert_eq!(info.get("title").unwrap(), "HyperSpot API");
         assert_eq!(info.get("version").unwrap(), "0.1.0");
     }
+
+    #[tokio::test]
+    async fn error_mappers_stay_with_their_ingress() {
+        use modkit::api::{AnyhowResponse, Missing, OperationBuilder, Problem};
+        use modkit::contracts::RestHostModule;
+        use tower::ServiceExt;
+
+        #[derive(Debug, thiserror::Error)]
+        #[error("storage unavailable")]
+        struct StorageDown;
+
+        async fn upload() -> Result<&'static str, AnyhowResponse> {
+            Err(StorageDown)?
+        }
+        let ctx = &modkit::context::ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let serve = |api: ApiIngress| async move {
+            let router = OperationBuilder::<Missing, Missing, ()>::post("/upload")
+                .handler(upload)
+                .json_response(200, "Stored")
+                .register(axum::Router::new(), &api);
+            let app = api.rest_finalize(ctx, router).await.unwrap();
+            let req = axum::http::Request::post("/upload")
+                .body(axum::body::Body::empty())
+                .unwrap();
+            app.oneshot(req).await.unwrap().status()
+        };
+
+        let mapped = ApiIngress::default();
+        let scoped = modkit::api::ModuleOpenApiRegistry::new(&mapped, "files");
+        modkit::api::register_error_mapper(&scoped, |e| {
+            e.downcast_ref::<StorageDown>().map(|_| {
+                Problem::new(
+                    axum::http::StatusCode::BAD_GATEWAY,
+                    "Bad Gateway",
+                    "Storage unavailable",
+                )
+            })
+        });
+        assert_eq!(serve(mapped).await, axum::http::StatusCode::BAD_GATEWAY);
+        assert_eq!(
+            serve(ApiIngress::default()).await,
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[tokio::test]
+    async fn debug_telemetry_is_read_only_without_auth_and_listed() {
+        use modkit::contracts::RestHostModule;
+        use tower::ServiceExt;
+
+        let api = ApiIngress::new(ApiIngressConfig {
+            debug_routes: true,
+            debug_telemetry: true,
+            ..Default::default()
+        });
+        let ctx = modkit::context::ModuleCtxBuilder::new(CancellationToken::new()).build();
+        let app = api.rest_finalize(&ctx, axum::Router::new()).await.unwrap();
+        let call = |method: Method, path: &str| {
+            axum::http::Request::builder()
+                .method(method)
+                .uri(path)
+                .header("content-type", "application/json")
+                .body(axum::body::Body::from("{}"))
+                .unwrap()
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(call(Method::PUT, route_table::DEBUG_TELEMETRY_PATH))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::METHOD_NOT_ALLOWED);
+
+        let resp = app
+            .oneshot(call(Method::GET, route_table::DEBUG_ROUTES_PATH))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let routes: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(routes
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["path"] == route_table::DEBUG_TELEMETRY_PATH && r["method"] == "GET"));
+    }
+}
+
+/// OpenAPI `callbacks` object: name → URL expression → method → operation.
+fn callbacks_json(callbacks: &[modkit::api::CallbackSpec]) -> serde_json::Value {
+    let mut out = serde_json::Map::new();
+    for cb in callbacks {
+        let op = &cb.operation;
+        let mut operation = serde_json::Map::new();
+        if let Some(summary) = &op.summary {
+            operation.insert("summary".into(), summary.clone().into());
+        }
+        if let Some(description) = &op.description {
+            operation.insert("description".into(), description.clone().into());
+        }
+        if let Some(rb) = &op.request_body {
+            let schema = match &rb.schema_name {
+                Some(name) => serde_json::json!({ "$ref": format!("#/components/schemas/{name}") }),
+                None => serde_json::json!({ "type": "object" }),
+            };
+            operation.insert(
+                "requestBody".into(),
+                serde_json::json!({
+                    "description": rb.description,
+                    "required": rb.required,
+                    "content": { rb.content_type: { "schema": schema } },
+                }),
+            );
+        }
+        let responses: serde_json::Map<_, _> = if op.responses.is_empty() {
+            [(
+                "200".to_string(),
+                serde_json::json!({ "description": "Callback received" }),
+            )]
+            .into_iter()
+            .collect()
+        } else {
+            op.responses
+                .iter()
+                .map(|r| {
+                    (
+                        r.status.to_string(),
+                        serde_json::json!({ "description": r.description }),
+                    )
+                })
+                .collect()
+        };
+        operation.insert("responses".into(), responses.into());
+
+        let path_item = out
+            .entry(cb.name.clone())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()))
+            .as_object_mut()
+            .and_then(|expressions| {
+                expressions
+                    .entry(cb.url_expression.clone())
+                    .or_insert_with(|| serde_json::Value::Object(Default::default()))
+                    .as_object_mut()
+            });
+        if let Some(path_item) = path_item {
+            path_item.insert(op.method.as_str().to_ascii_lowercase(), operation.into());
+        }
+    }
+    out.into()
+}
+
+/// Serve a pre-built OpenAPI document as static JSON (no per-request work).
+fn openapi_route(doc: OpenApi) -> axum::routing::MethodRouter {
+    use axum::{http::header, response::IntoResponse, Json};
+    let doc = Arc::new(doc);
+    get(move || async move {
+        ([(header::CACHE_CONTROL, "no-store")], Json(doc.as_ref())).into_response()
+    })
 }
 
 // REST host role: prepare/finalize the router, but do not start the server here.
+#[async_trait]
 impl modkit::contracts::RestHostModule for ApiIngress {
-    fn rest_prepare(
+    async fn rest_prepare(
         &self,
-        _ctx: &modkit::context::ModuleCtx,
+        ctx: &modkit::context::ModuleCtx,
         router: axum::Router,
     ) -> anyhow::Result<axum::Router> {
-        // Add basic health check endpoint and any global middlewares
-        let router = router.route("/healthz", get(|| async { "ok" }));
+        // Add basic health (liveness) and readiness endpoints
+        let router = router.route("/healthz", get(|| async { "ok" })).route(
+            "/readyz",
+            get(readiness::readiness_check).with_state(readiness::ReadinessState::from_ctx(ctx)),
+        );
 
         // You may attach global middlewares here (trace, compression, cors), but do not start server.
-        tracing::debug!("REST host prepared base router with health check");
+        tracing::debug!("REST host prepared base router with health and readiness checks");
         Ok(router)
     }
 
-    fn rest_finalize(
+    async fn rest_finalize(
         &self,
-        _ctx: &modkit::context::ModuleCtx,
+        ctx: &modkit::context::ModuleCtx,
         mut router: axum::Router,
     ) -> anyhow::Result<axum::Router> {
         let config = self.get_cached_config();
 
+        if config.debug_telemetry {
+            tracing::info!(
+                path = route_table::DEBUG_TELEMETRY_PATH,
+                "rest_finalize: exposing runtime telemetry control"
+            );
+            // Without bearer auth nothing enforces the admin scope, so stay read-only
+            if !config.auth.enabled {
+                tracing::warn!(
+                    "rest_finalize: auth is disabled, serving {} read-only",
+                    route_table::DEBUG_TELEMETRY_PATH
+                );
+            }
+            router = route_table::register_debug_telemetry(
+                router,
+                self,
+                self.log_levels.lock().clone(),
+                config.auth.enabled,
+            );
+        }
+
+        // Every operation is registered by now; the layers below share one snapshot
+        let specs: Vec<_> = self
+            .operation_specs
+            .iter()
+            .map(|e| e.value().clone())
+            .collect();
+
+        // A template typo or an undeclared path parameter would otherwise only
+        // show up as 404s or a broken spec
+        for spec in &specs {
+            spec.validate_path()
+                .map_err(|e| anyhow::anyhow!("{} {}: {e}", spec.method, spec.path))?;
+        }
+
         if config.enable_docs {
             // Build once, serve as static JSON (no per-request parsing)
-            let op_count = self.operation_specs.len();
             tracing::info!(
                 "rest_finalize: emitting OpenAPI with {} operations",
-                op_count
+                specs.len()
             );
 
-            let openapi_doc = Arc::new(self.build_openapi()?);
-
             router = router
-                .route(
-                    "/openapi.json",
-                    get({
-                        use axum::{http::header, response::IntoResponse, Json};
-                        let doc = openapi_doc.clone();
-                        move || async move {
-                            ([(header::CACHE_CONTROL, "no-store")], Json(doc.as_ref()))
-                                .into_response()
-                        }
-                    }),
-                )
-                .route("/docs", get(web::serve_docs));
+                .route("/openapi.json", openapi_route(self.build_openapi()?))
+                .route("/docs", web::serve_docs(config.docs_ui));
+
+            // One document per API version, next to the combined one
+            for version in self.api_versions() {
+                tracing::info!(%version, "rest_finalize: emitting versioned OpenAPI");
+                router = router.route(
+                    &format!("/openapi-{version}.json"),
+                    openapi_route(self.build_openapi_for_version(&version)?),
+                );
+            }
 
             #[cfg(feature = "embed_elements")]
             {
                 router = router.route("/docs/assets/{*file}", get(assets::serve_elements_asset));
             }
+            #[cfg(feature = "embed_swagger_ui")]
+            {
+                router = router.route(
+                    "/docs/swagger-ui/{*file}",
+                    get(assets::serve_swagger_ui_asset),
+                );
+            }
+            #[cfg(feature = "embed_redoc")]
+            {
+                router = router.route("/docs/redoc/{*file}", get(assets::serve_redoc_asset));
+            }
+        }
+
+        let routes = route_table::route_table(&specs);
+        tracing::debug!(
+            "rest_finalize: route table ({} operations)\n{}",
+            routes.len(),
+            route_table::render(&routes)
+        );
+        if config.debug_routes {
+            tracing::info!(
+                path = route_table::DEBUG_ROUTES_PATH,
+                "rest_finalize: exposing route introspection"
+            );
+            router = router.route(
+                route_table::DEBUG_ROUTES_PATH,
+                route_table::debug_routes_route(routes),
+            );
+        }
+        if config.debug_clients {
+            if let Some(hub) = self.client_hub.lock().clone() {
+                tracing::info!(
+                    path = route_table::DEBUG_CLIENTS_PATH,
+                    "rest_finalize: exposing client listing"
+                );
+                router = router.route(
+                    route_table::DEBUG_CLIENTS_PATH,
+                    route_table::debug_clients_route(hub),
+                );
+            }
+        }
+
+        if !config.static_sites.is_empty() {
+            router = static_sites::mount(router, &config.static_sites)?;
+        }
+
+        if !config.proxy.is_empty() {
+            router = proxy::mount(router, &config.proxy, modkit::http::TracedClient::default())?;
+        }
+
+        // Debug builds check handler output against the declared response schemas
+        #[cfg(debug_assertions)]
+        {
+            router = router.layer(from_fn_with_state(
+                validation::ResponseValidationState::new(
+                    Arc::new(self.schema_validator()?),
+                    &specs,
+                ),
+                validation::response_validation_middleware,
+            ));
+        }
+
+        // Answer If-None-Match with 304 for handlers that set an ETag
+        router = router.layer(from_fn(modkit::api::response::conditional_get_middleware));
+
+        // Render `AnyhowResponse` errors through registered error mappers, with the
+        // request path and trace id
+        router = router.layer(from_fn_with_state(
+            modkit::api::ErrorMappers::new(self.error_mappers.lock().clone()),
+            modkit::api::error_mapping_middleware,
+        ));
+
+        let deprecations = deprecation::DeprecationState::from_specs(&specs)?;
+        if !deprecations.is_empty() {
+            router = router.layer(from_fn_with_state(
+                deprecations,
+                deprecation::deprecation_middleware,
+            ));
+        }
+
+        let route_limits = rate_limit::RouteRateLimits::from_specs(&specs);
+        if !route_limits.is_empty() {
+            router = router.layer(from_fn_with_state(
+                route_limits,
+                rate_limit::route_rate_limit_middleware,
+            ));
+        }
+
+        // Contract validation runs after authentication so 401/403 win over 422
+        if config.request_validation {
+            tracing::info!(
+                operations = specs.len(),
+                "rest_finalize: request validation enabled"
+            );
+            router = router.layer(from_fn_with_state(
+                validation::RequestValidationState::new(Arc::new(self.schema_validator()?), &specs),
+                validation::request_validation_middleware,
+            ));
+        }
+
+        // Enforce declared security requirements for every enabled scheme
+        if config.auth.enabled || config.api_keys.enabled {
+            let validator = Arc::new(auth::JwtValidator::new(config.auth.clone())?);
+            let mut schemes = Vec::new();
+            if config.auth.enabled {
+                schemes.push(modkit::api::BEARER_AUTH_SCHEME);
+            }
+            if config.api_keys.enabled {
+                schemes.push(modkit::api::API_KEY_AUTH_SCHEME);
+            }
+            let policies = auth::RoutePolicies::from_specs(&specs, &schemes);
+            tracing::info!(
+                issuers = config.auth.issuers.len(),
+                ?schemes,
+                "rest_finalize: authentication enabled"
+            );
+            router = router.layer(from_fn_with_state(
+                auth::AuthState::new(validator, policies),
+                auth::auth_middleware,
+            ));
+        }
+
+        // Shed load before authentication work, but inside metrics so 503s are counted
+        if config.concurrency.enabled {
+            tracing::info!(
+                max_in_flight = ?config.concurrency.max_in_flight,
+                routes = config.concurrency.routes.len(),
+                "rest_finalize: concurrency limits enabled"
+            );
+            router = router.layer(from_fn_with_state(
+                load_shed::LoadShedState::new(&config.concurrency)?,
+                load_shed::load_shed_middleware,
+            ));
         }
 
+        // Hold application routes until every module runs; health, readiness and
+        // metrics stay reachable
+        if config.ready_gate.enabled {
+            let exempt = config.metrics.enabled.then(|| config.metrics.path.clone());
+            router = router.layer(from_fn_with_state(
+                ready_gate::ReadyGateState::new(
+                    ctx.module_registry().map(Arc::downgrade),
+                    &config.ready_gate,
+                    exempt,
+                ),
+                ready_gate::ready_gate_middleware,
+            ));
+        }
+
+        if config.metrics.enabled {
+            tracing::info!(path = %config.metrics.path, "rest_finalize: exposing Prometheus metrics");
+            router = router
+                .route(&config.metrics.path, get(metrics::metrics_handler))
+                .layer(from_fn(metrics::http_metrics_middleware));
+        }
+
+        if config.access_log.enabled {
+            router = router.layer(from_fn_with_state(
+                access_log::AccessLogState::new(config.access_log.clone()),
+                access_log::access_log_middleware,
+            ));
+        }
+
+        // API keys run before bearer auth so routes accepting `apiKeyAuth` see the caller
+        if config.api_keys.enabled {
+            let custom = self.api_key_store.lock().clone();
+            let store: Arc<dyn api_key::ApiKeyStore> = match custom {
+                Some(store) => store,
+                None => Arc::new(api_key::StaticApiKeyStore::from_config(&config.api_keys)?),
+            };
+            tracing::info!(
+                keys = config.api_keys.keys.len(),
+                "rest_finalize: API key authentication enabled"
+            );
+            router = router.layer(from_fn_with_state(
+                api_key::ApiKeyState::new(store, &config.api_keys.header)?,
+                api_key::api_key_middleware,
+            ));
+        }
+
+        // CORS outermost so preflight requests never hit authentication
+        if config.cors_enabled {
+            router = router.layer(config.cors.build_layer()?);
+        }
+
+        // Path normalization wraps the whole router (must run before routing)
+        router = self.normalize_paths(router, &config.path_normalization);
+
         // Keep the finalized router to be used by `serve()`
         *self.final_router.lock() = Some(router.clone());
 
@@ -514,8 +1163,9 @@ impl modkit::contracts::RestHostModule for ApiIngress {
     }
 }
 
+#[async_trait]
 impl modkit::contracts::RestfulModule for ApiIngress {
-    fn register_rest(
+    async fn register_rest(
         &self,
         _ctx: &modkit::context::ModuleCtx,
         router: axum::Router,
@@ -577,7 +1227,19 @@ impl OpenApiRegistry for ApiIngress {
         let current = self.components_registry.load();
         let mut reg = (**current).clone();
 
-        for (name, schema) in schemas {
+        for (name, mut schema) in schemas {
+            // Keep vendor extensions attached by an earlier registration
+            let previous = reg
+                .get(&name)
+                .cloned()
+                .and_then(|mut existing| modkit::api::schema_extensions_mut(&mut existing)?.take());
+            if let (Some(previous), Some(slot)) =
+                (previous, modkit::api::schema_extensions_mut(&mut schema))
+            {
+                if slot.is_none() {
+                    *slot = Some(previous);
+                }
+            }
             // Conflict policy: identical → no-op; different → warn & override
             if let Some(existing) = reg.get(&name) {
                 let a = serde_json::to_value(existing).ok();
@@ -598,6 +1260,10 @@ impl OpenApiRegistry for ApiIngress {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn add_error_mapper(&self, mapper: Arc<modkit::api::ErrorMapper>) {
+        self.error_mappers.lock().push(mapper);
+    }
 }
 
 #[cfg(test)]
@@ -662,6 +1328,495 @@ mod problem_openapi_tests {
     }
 }
 
+#[cfg(test)]
+mod etag_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[tokio::test]
+    async fn etag_header_and_304_are_documented() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/items/{id}")
+            .with_etag()
+            .handler(|| async { "ok" })
+            .json_response(200, "Item")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let op = v.pointer("/paths/~1items~1{id}/get").unwrap();
+        assert!(op.pointer("/responses/200/headers/ETag").is_some());
+        assert_eq!(op["responses"]["304"]["description"], "Not Modified");
+        assert!(op["responses"]["304"].get("content").is_none());
+        assert!(op["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["name"] == "If-None-Match" && p["in"] == "header"));
+    }
+}
+
+#[cfg(test)]
+mod default_response_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder, ResponseStatus};
+
+    #[tokio::test]
+    async fn default_problem_response_is_documented_under_default() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/items/{id}")
+            .handler(|| async { "ok" })
+            .json_response(200, "Item")
+            .default_problem_response(&api, "Unexpected error")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let op = v.pointer("/paths/~1items~1{id}/get").unwrap();
+        assert_eq!(
+            op.pointer("/responses/default/content/application~1problem+json/schema/$ref"),
+            Some(&serde_json::json!("#/components/schemas/Problem"))
+        );
+        assert_eq!(op["responses"]["200"]["description"], "Item");
+
+        let specs: Vec<_> = api
+            .operation_specs
+            .iter()
+            .map(|e| e.value().clone())
+            .collect();
+        let routes = route_table::route_table(&specs);
+        let statuses: Vec<_> = routes[0].responses.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [ResponseStatus::Code(200), ResponseStatus::Default]
+        );
+        assert_eq!(
+            serde_json::to_value(&routes[0].responses[1]).unwrap()["status"],
+            "default"
+        );
+    }
+}
+
+#[cfg(test)]
+mod examples_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn request_and_response_examples_are_emitted() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::post("/users")
+            .json_request_schema("CreateUser", "New user")
+            .request_example(json!({ "name": "Ada" }))
+            .handler(|| async { "ok" })
+            .json_response(201, "Created")
+            .response_example(201, "ada", json!({ "id": 1, "name": "Ada" }))
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let op = v.pointer("/paths/~1users/post").unwrap();
+        assert_eq!(
+            op.pointer("/requestBody/content/application~1json/example"),
+            Some(&json!({ "name": "Ada" }))
+        );
+        assert_eq!(
+            op.pointer("/responses/201/content/application~1json/examples/ada/value"),
+            Some(&json!({ "id": 1, "name": "Ada" }))
+        );
+    }
+}
+
+#[cfg(test)]
+mod extensions_openapi_tests {
+    use super::*;
+    use modkit::api::{ensure_schema, ensure_schema_with_extensions, Missing, OperationBuilder};
+    use serde_json::json;
+
+    #[derive(serde::Serialize, utoipa::ToSchema)]
+    struct AuditEvent {
+        id: u64,
+    }
+
+    #[tokio::test]
+    async fn operation_and_schema_extensions_are_emitted() {
+        let api = ApiIngress::default();
+        ensure_schema_with_extensions::<AuditEvent, _, _>(&api, [("x-internal", json!(true))]);
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/audit")
+            .extension("x-gateway-timeout", 5)
+            .external_docs("https://handbook.example.com/audit", "Audit log guide")
+            .handler(|| async { "ok" })
+            .json_response_with_schema::<AuditEvent>(&api, 200, "Events")
+            .register(axum::Router::new(), &api);
+        // Re-registering the plain schema keeps the extension
+        ensure_schema::<AuditEvent>(&api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        assert_eq!(
+            v.pointer("/paths/~1audit/get/x-gateway-timeout"),
+            Some(&json!(5))
+        );
+        assert_eq!(
+            v.pointer("/paths/~1audit/get/externalDocs/url"),
+            Some(&json!("https://handbook.example.com/audit"))
+        );
+        assert_eq!(
+            v.pointer("/components/schemas/AuditEvent/x-internal"),
+            Some(&json!(true))
+        );
+    }
+}
+
+#[cfg(test)]
+mod binary_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[tokio::test]
+    async fn binary_response_is_documented_as_bytes() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/exports/{id}")
+            .handler(|| async { "ok" })
+            .binary_response(200, "application/pdf", "Rendered report")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let schema = v
+            .pointer("/paths/~1exports~1{id}/get/responses/200/content/application~1pdf/schema")
+            .unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["format"], "binary");
+    }
+}
+
+#[cfg(test)]
+mod multipart_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, MultipartField, OperationBuilder};
+
+    #[tokio::test]
+    async fn multipart_fields_render_inline_schema() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::post("/uploads")
+            .multipart_request(
+                [
+                    MultipartField::file("file").description("CSV export"),
+                    MultipartField::text("title").optional(),
+                ],
+                "Upload",
+            )
+            .handler(|| async { "ok" })
+            .json_response(201, "Stored")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let schema = v
+            .pointer("/paths/~1uploads/post/requestBody/content/multipart~1form-data/schema")
+            .unwrap();
+        assert_eq!(schema["properties"]["file"]["format"], "binary");
+        assert_eq!(schema["properties"]["title"]["type"], "string");
+        assert_eq!(schema["required"], serde_json::json!(["file"]));
+    }
+}
+
+#[cfg(test)]
+mod content_negotiation_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[tokio::test]
+    async fn one_status_lists_every_declared_media_type() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/reports")
+            .handler(|| async { "ok" })
+            .json_response(200, "Report")
+            .binary_response(200, "text/csv", "Report as CSV")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let resp = v.pointer("/paths/~1reports/get/responses/200").unwrap();
+        assert_eq!(resp["description"], "Report");
+        let types: Vec<_> = resp["content"].as_object().unwrap().keys().collect();
+        assert_eq!(types, ["application/json", "text/csv"]);
+    }
+}
+
+#[cfg(test)]
+mod param_format_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[tokio::test]
+    async fn typed_path_params_render_type_and_format() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/orgs/{org}/items/{seq}")
+            .path_param_uuid("org", "Organization id")
+            .path_param_i64("seq", "Item sequence number")
+            .handler(|| async { "ok" })
+            .json_response(200, "Item")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let params = v
+            .pointer("/paths/~1orgs~1{org}~1items~1{seq}/get/parameters")
+            .unwrap();
+        assert_eq!(params[0]["schema"]["type"], "string");
+        assert_eq!(params[0]["schema"]["format"], "uuid");
+        assert_eq!(params[1]["schema"]["type"], "integer");
+        assert_eq!(params[1]["schema"]["format"], "int64");
+    }
+}
+
+#[cfg(test)]
+mod array_enum_param_openapi_tests {
+    use super::*;
+    use modkit::api::{CollectionFormat, Missing, OperationBuilder};
+
+    #[tokio::test]
+    async fn array_and_enum_params_render_schemas() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/tickets")
+            .query_param_enum("sort", false, "Sort order", ["asc", "desc"])
+            .query_param_array("status", false, "Statuses", "string", CollectionFormat::Csv)
+            .allowed_values(["active", "archived"])
+            .query_param_array("id", false, "Ids", "integer", CollectionFormat::Multi)
+            .handler(|| async { "ok" })
+            .json_response(200, "Tickets")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let params = v.pointer("/paths/~1tickets/get/parameters").unwrap();
+        assert_eq!(
+            params[0]["schema"]["enum"],
+            serde_json::json!(["asc", "desc"])
+        );
+        assert_eq!(params[1]["schema"]["type"], "array");
+        assert_eq!(
+            params[1]["schema"]["items"]["enum"],
+            serde_json::json!(["active", "archived"])
+        );
+        assert_eq!(params[1]["style"], "form");
+        assert_eq!(params[1]["explode"], false);
+        assert_eq!(params[2]["schema"]["items"]["type"], "integer");
+        assert_eq!(params[2]["explode"], true);
+    }
+}
+
+#[cfg(test)]
+mod generic_schema_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[derive(utoipa::ToSchema)]
+    #[allow(dead_code)]
+    struct UserDto {
+        id: String,
+    }
+
+    #[derive(utoipa::ToSchema)]
+    #[allow(dead_code)]
+    struct OrderDto {
+        total: i64,
+    }
+
+    #[tokio::test]
+    async fn page_instantiations_get_distinct_components() {
+        let api = ApiIngress::default();
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/users")
+            .handler(|| async { "ok" })
+            .paginated::<UserDto>(&api)
+            .register(axum::Router::new(), &api);
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/orders")
+            .handler(|| async { "ok" })
+            .paginated::<OrderDto>(&api)
+            .register(router, &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let schemas = &v["components"]["schemas"];
+        assert_eq!(
+            schemas["Page_UserDto"]["properties"]["items"]["items"]["$ref"],
+            "#/components/schemas/UserDto"
+        );
+        assert_eq!(
+            schemas["Page_OrderDto"]["properties"]["items"]["items"]["$ref"],
+            "#/components/schemas/OrderDto"
+        );
+        for name in ["UserDto", "OrderDto", "PageInfo"] {
+            assert!(schemas.get(name).is_some(), "missing component {name}");
+        }
+        assert!(schemas.get("Page").is_none());
+        assert_eq!(
+            v.pointer("/paths/~1orders/get/responses/200/content/application~1json/schema/$ref")
+                .unwrap(),
+            "#/components/schemas/Page_OrderDto"
+        );
+    }
+}
+
+#[cfg(test)]
+mod callback_openapi_tests {
+    use super::*;
+    use modkit::api::{CallbackOperation, Missing, OperationBuilder};
+
+    #[derive(utoipa::ToSchema)]
+    #[allow(dead_code)]
+    struct ShipmentEvent {
+        order_id: String,
+    }
+
+    #[tokio::test]
+    async fn callbacks_render_under_the_operation() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::post("/subscriptions")
+            .callback(
+                "orderShipped",
+                "{$request.body#/callback_url}",
+                CallbackOperation::post()
+                    .summary("Order shipped")
+                    .json_payload::<ShipmentEvent>(&api, "Shipment details")
+                    .response(204, "Delivery acknowledged"),
+            )
+            .handler(|| async { "ok" })
+            .json_response(201, "Subscribed")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let op = v
+            .pointer("/paths/~1subscriptions/post/callbacks/orderShipped/{$request.body#~1callback_url}/post")
+            .unwrap();
+        assert_eq!(op["summary"], "Order shipped");
+        assert_eq!(
+            op["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ShipmentEvent"
+        );
+        assert_eq!(
+            op["responses"]["204"]["description"],
+            "Delivery acknowledged"
+        );
+        assert!(v.pointer("/components/schemas/ShipmentEvent").is_some());
+    }
+}
+
+#[cfg(test)]
+mod rate_limit_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[tokio::test]
+    async fn rate_limit_renders_as_extension() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/search")
+            .rate_limit(10, 20)
+            .handler(|| async { "ok" })
+            .json_response(200, "Results")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        assert_eq!(
+            v.pointer("/paths/~1search/get/x-rate-limit").unwrap(),
+            &serde_json::json!({ "rps": 10, "burst": 20 })
+        );
+    }
+}
+
+#[cfg(test)]
+mod versioning_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[tokio::test]
+    async fn versions_get_their_own_documents() {
+        let api = ApiIngress::default();
+        let mut router = axum::Router::new();
+        for version in ["v1", "v2"] {
+            router = OperationBuilder::<Missing, Missing, ()>::get("/users")
+                .version(version)
+                .handler(move || async move { version })
+                .text_response(200, "OK")
+                .register(router, &api);
+        }
+        router = OperationBuilder::<Missing, Missing, ()>::get("/status")
+            .handler(|| async { "ok" })
+            .text_response(200, "OK")
+            .register(router, &api);
+        let _ = router;
+
+        assert_eq!(
+            api.api_versions().into_iter().collect::<Vec<_>>(),
+            ["v1", "v2"]
+        );
+
+        let v1 = serde_json::to_value(api.build_openapi_for_version("v1").unwrap()).unwrap();
+        let paths: Vec<_> = v1["paths"].as_object().unwrap().keys().cloned().collect();
+        assert_eq!(paths, ["/v1/users"]);
+
+        let all = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        assert_eq!(all["paths"].as_object().unwrap().len(), 3);
+    }
+}
+
+#[cfg(test)]
+mod security_openapi_tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder, API_KEY_AUTH_SCHEME};
+
+    #[tokio::test]
+    async fn openapi_renders_api_key_scheme_and_alternatives() {
+        let mut cfg = ApiIngressConfig::default();
+        cfg.api_keys.header = "x-service-key".into();
+        let api = ApiIngress::new(cfg);
+
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/either")
+            .security(API_KEY_AUTH_SCHEME, Vec::<String>::new())
+            .require_scopes(["ops"])
+            .handler(|| async { "ok" })
+            .text_response(200, "OK")
+            .register(axum::Router::new(), &api);
+
+        let v = serde_json::to_value(api.build_openapi().expect("openapi")).expect("json");
+        let scheme = v
+            .pointer("/components/securitySchemes/apiKeyAuth")
+            .expect("apiKeyAuth scheme missing");
+        assert_eq!(scheme["type"], "apiKey");
+        assert_eq!(scheme["in"], "header");
+        assert_eq!(scheme["name"], "x-service-key");
+
+        let security = v
+            .pointer("/paths/~1either/get/security")
+            .expect("operation security missing");
+        assert_eq!(
+            security,
+            &serde_json::json!([{ "apiKeyAuth": [] }, { "bearerAuth": ["ops"] }])
+        );
+    }
+
+    #[tokio::test]
+    async fn openapi_renders_bearer_security_with_scopes() {
+        let api = ApiIngress::default();
+        let router = axum::Router::new();
+
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/secure")
+            .require_scopes(["users:read"])
+            .handler(|| async { "ok" })
+            .text_response(200, "OK")
+            .register(router, &api);
+
+        let doc = api.build_openapi().expect("openapi");
+        let v = serde_json::to_value(&doc).expect("json");
+
+        let scheme = v
+            .pointer("/components/securitySchemes/bearerAuth")
+            .expect("bearerAuth scheme missing");
+        assert_eq!(
+            scheme.get("scheme").and_then(|s| s.as_str()),
+            Some("bearer")
+        );
+
+        let scopes = v
+            .pointer("/paths/~1secure/get/security/0/bearerAuth")
+            .expect("operation security missing");
+        assert_eq!(scopes, &serde_json::json!(["users:read"]));
+    }
+}
+
 #[cfg(test)]
 mod sse_openapi_tests {
     use super::*;
@@ -748,3 +1903,191 @@ mod sse_openapi_tests {
         assert!(schema.get("$ref").is_none());
     }
 }
+
+#[cfg(test)]
+mod request_validation_tests {
+    use super::*;
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use modkit::api::{Missing, OperationBuilder};
+    use tower::ServiceExt;
+
+    #[derive(serde::Deserialize, utoipa::ToSchema)]
+    #[allow(dead_code)]
+    struct NewWidget {
+        name: String,
+        size: u32,
+    }
+
+    fn app() -> Router {
+        let api = ApiIngress::default();
+        let router = OperationBuilder::<Missing, Missing, ()>::post("/widgets")
+            .query_param_typed("dry_run", false, "Validate only", "boolean")
+            .json_request::<NewWidget>(&api, "Widget to create")
+            .handler(|| async { StatusCode::CREATED })
+            .json_response(201, "Created")
+            .register(Router::new(), &api);
+        let specs: Vec<_> = api
+            .operation_specs
+            .iter()
+            .map(|e| e.value().clone())
+            .collect();
+        router.layer(from_fn_with_state(
+            validation::RequestValidationState::new(
+                Arc::new(api.schema_validator().unwrap()),
+                &specs,
+            ),
+            validation::request_validation_middleware,
+        ))
+    }
+
+    async fn post(uri: &str, body: &str) -> (StatusCode, serde_json::Value) {
+        let resp = app()
+            .oneshot(
+                Request::post(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_owned()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn valid_requests_reach_the_handler() {
+        let (status, _) = post("/widgets?dry_run=true", r#"{"name":"a","size":3}"#).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn contract_violations_return_422() {
+        let (status, problem) = post("/widgets?dry_run=maybe", r#"{"size":"big"}"#).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem["code"], "VALIDATION_ERROR");
+        let pointers: Vec<_> = problem["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["pointer"].as_str().unwrap().to_owned())
+            .collect();
+        assert!(pointers.contains(&"/name".to_string()));
+        assert!(pointers.contains(&"/size".to_string()));
+        assert!(pointers.contains(&"#/query/dry_run".to_string()));
+
+        let (status, _) = post("/widgets", "").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[derive(serde::Serialize, utoipa::ToSchema)]
+    struct Widget {
+        id: u32,
+        name: String,
+    }
+
+    async fn get_widget(body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+        let api = ApiIngress::default();
+        let router = OperationBuilder::<Missing, Missing, ()>::get("/widgets/{id}")
+            .path_param("id", "Widget id")
+            .handler(move || async move { axum::Json(body) })
+            .json_response_with_schema::<Widget>(&api, 200, "Widget")
+            .register(Router::new(), &api);
+        let specs: Vec<_> = api
+            .operation_specs
+            .iter()
+            .map(|e| e.value().clone())
+            .collect();
+        let app = router.layer(from_fn_with_state(
+            validation::ResponseValidationState::new(
+                Arc::new(api.schema_validator().unwrap()),
+                &specs,
+            ),
+            validation::response_validation_middleware,
+        ));
+        let resp = app
+            .oneshot(Request::get("/widgets/1").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn responses_are_checked_against_declared_schema() {
+        let ok = serde_json::to_value(Widget {
+            id: 1,
+            name: "a".into(),
+        })
+        .unwrap();
+        let (status, body) = get_widget(ok.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, ok);
+
+        let (status, problem) = get_widget(serde_json::json!({ "id": "1" })).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(problem["code"], "RESPONSE_VALIDATION_ERROR");
+        assert_eq!(problem["errors"].as_array().unwrap().len(), 2);
+    }
+}
+
+#[cfg(test)]
+mod websocket_tests {
+    use super::*;
+    use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+    use futures::{SinkExt, StreamExt};
+    use modkit::api::{Missing, OperationBuilder};
+
+    async fn echo(mut socket: WebSocket) {
+        while let Some(Ok(Message::Text(text))) = socket.recv().await {
+            if socket.send(Message::Text(text)).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    fn register(api: &ApiIngress) -> Router {
+        OperationBuilder::<Missing, Missing, ()>::get("/echo")
+            .summary("Echo socket")
+            .websocket(|ws: WebSocketUpgrade| async move { ws.on_upgrade(echo) })
+            .register(Router::new(), api)
+    }
+
+    #[tokio::test]
+    async fn websocket_operations_are_documented() {
+        let api = ApiIngress::default();
+        let _router = register(&api);
+
+        let v = serde_json::to_value(api.build_openapi().unwrap()).unwrap();
+        let op = v.pointer("/paths/~1echo/get").unwrap();
+        assert_eq!(op["x-websocket"], true);
+        assert_eq!(op["responses"]["101"]["description"], "Switching Protocols");
+    }
+
+    #[tokio::test]
+    async fn websocket_operations_upgrade_and_echo() {
+        let api = ApiIngress::default();
+        let app = register(&api).layer(from_fn(crate::request_id::push_req_id_to_extensions));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let (mut ws, resp) = tokio_tungstenite::connect_async(format!("ws://{addr}/echo"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::SWITCHING_PROTOCOLS);
+        ws.send(tokio_tungstenite::tungstenite::Message::text("ping"))
+            .await
+            .unwrap();
+        let reply = ws.next().await.unwrap().unwrap();
+        assert_eq!(reply.into_text().unwrap().as_str(), "ping");
+    }
+}
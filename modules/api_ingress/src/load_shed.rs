@@ -0,0 +1,210 @@
+//! Concurrency limits with load shedding.
+//!
+//! A global limit and optional per-route limits cap the number of requests in
+//! flight. When a limit is reached the request is rejected immediately with a
+//! 503 Problem and `Retry-After` instead of queueing, so slow handlers cannot
+//! pile up work and exhaust the runtime.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{header, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use modkit::api::{Problem, ProblemResponse};
+use modkit::telemetry::HttpMetrics;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Scope label of the global limit in metrics.
+const GLOBAL_SCOPE: &str = "global";
+
+/// Concurrency section of the ingress configuration.
+#[derive(Debug, Clone, Deserialize, Serialize, modkit::ConfigSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConcurrencyConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Maximum requests in flight across all routes (unlimited when unset).
+    #[serde(default)]
+    #[config(min = 1)]
+    pub max_in_flight: Option<usize>,
+    /// Per-route limits keyed by route template, e.g. `/users/{id}/export`.
+    #[serde(default)]
+    pub routes: BTreeMap<String, usize>,
+    /// `Retry-After` value sent with 503 responses.
+    #[serde(default = "default_retry_after_secs")]
+    pub retry_after_secs: u64,
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_in_flight: None,
+            routes: BTreeMap::new(),
+            retry_after_secs: default_retry_after_secs(),
+        }
+    }
+}
+
+fn default_retry_after_secs() -> u64 {
+    1
+}
+
+/// Shared state of the load shedding middleware.
+#[derive(Clone)]
+pub struct LoadShedState {
+    global: Option<Arc<Semaphore>>,
+    routes: Arc<HashMap<String, Arc<Semaphore>>>,
+    retry_after: HeaderValue,
+}
+
+impl LoadShedState {
+    pub fn new(cfg: &ConcurrencyConfig) -> anyhow::Result<Self> {
+        if cfg.max_in_flight == Some(0) || cfg.routes.values().any(|&limit| limit == 0) {
+            anyhow::bail!("concurrency limits must be greater than zero");
+        }
+        Ok(Self {
+            global: cfg.max_in_flight.map(|n| Arc::new(Semaphore::new(n))),
+            routes: Arc::new(
+                cfg.routes
+                    .iter()
+                    .map(|(route, &n)| (route.clone(), Arc::new(Semaphore::new(n))))
+                    .collect(),
+            ),
+            retry_after: HeaderValue::from(cfg.retry_after_secs),
+        })
+    }
+}
+
+/// Permit held for the duration of a request; keeps the in-flight gauge in sync.
+struct InFlight {
+    scope: String,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl InFlight {
+    fn acquire(semaphore: &Arc<Semaphore>, scope: &str) -> Option<Self> {
+        let permit = semaphore.clone().try_acquire_owned().ok()?;
+        HttpMetrics::global().add_in_flight(scope, 1);
+        Some(Self {
+            scope: scope.to_owned(),
+            _permit: permit,
+        })
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        HttpMetrics::global().add_in_flight(&self.scope, -1);
+    }
+}
+
+fn overloaded(state: &LoadShedState, scope: &str) -> Response {
+    HttpMetrics::global().record_shed(scope);
+    tracing::warn!(scope, "concurrency limit reached; shedding request");
+    let mut resp = ProblemResponse::from(
+        Problem::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Service Unavailable",
+            "Server is at capacity, retry later",
+        )
+        .with_code("OVERLOADED"),
+    )
+    .into_response();
+    resp.headers_mut()
+        .insert(header::RETRY_AFTER, state.retry_after.clone());
+    resp
+}
+
+/// Middleware enforcing the global and per-route in-flight limits.
+pub async fn load_shed_middleware(
+    State(state): State<LoadShedState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let _global = match &state.global {
+        Some(semaphore) => match InFlight::acquire(semaphore, GLOBAL_SCOPE) {
+            Some(permit) => Some(permit),
+            None => return overloaded(&state, GLOBAL_SCOPE),
+        },
+        None => None,
+    };
+
+    let route = req.extensions().get::<MatchedPath>().map(|p| p.as_str());
+    let _route = match route.and_then(|r| state.routes.get_key_value(r)) {
+        Some((route, semaphore)) => match InFlight::acquire(semaphore, route) {
+            Some(permit) => Some(permit),
+            None => return overloaded(&state, route),
+        },
+        None => None,
+    };
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn_with_state, routing::get, Router};
+    use std::time::Duration;
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    async fn call(app: Router, path: &str) -> Response {
+        app.oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn saturated_route_is_shed_with_retry_after() {
+        let release = Arc::new(Notify::new());
+        let gate = release.clone();
+        let cfg = ConcurrencyConfig {
+            enabled: true,
+            max_in_flight: Some(10),
+            routes: BTreeMap::from([("/slow".to_string(), 1)]),
+            retry_after_secs: 5,
+        };
+        let app = Router::new()
+            .route(
+                "/slow",
+                get(move || {
+                    let gate = gate.clone();
+                    async move { gate.notified().await }
+                }),
+            )
+            .route("/fast", get(|| async { "ok" }))
+            .layer(from_fn_with_state(
+                LoadShedState::new(&cfg).unwrap(),
+                load_shed_middleware,
+            ));
+
+        let blocked = tokio::spawn(call(app.clone(), "/slow"));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let shed = call(app.clone(), "/slow").await;
+        assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(shed.headers()[header::RETRY_AFTER], "5");
+        assert_eq!(call(app.clone(), "/fast").await.status(), StatusCode::OK);
+
+        release.notify_one();
+        assert_eq!(blocked.await.unwrap().status(), StatusCode::OK);
+        assert_eq!(call(app, "/fast").await.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn zero_limits_are_rejected() {
+        let cfg = ConcurrencyConfig {
+            max_in_flight: Some(0),
+            ..Default::default()
+        };
+        assert!(LoadShedState::new(&cfg).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! HTTP request metrics and the Prometheus scrape endpoint.
+
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::MatchedPath,
+    http::{header, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use modkit::telemetry::{self, HttpMetrics};
+use serde::{Deserialize, Serialize};
+
+/// Metrics section of the ingress configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// Expose the Prometheus scrape endpoint and record HTTP metrics.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path of the scrape endpoint.
+    #[serde(default = "default_path")]
+    pub path: String,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_path(),
+        }
+    }
+}
+
+fn default_path() -> String {
+    "/metrics".to_string()
+}
+
+/// Label used for requests that did not match any route (keeps cardinality bounded).
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Middleware recording request count and latency per (method, route template, status).
+pub async fn http_metrics_middleware(req: Request<Body>, next: Next) -> Response {
+    let start = Instant::now();
+    let method = req.method().clone();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_owned())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_owned());
+
+    let resp = next.run(req).await;
+
+    HttpMetrics::global().observe(
+        method.as_str(),
+        &route,
+        resp.status().as_u16(),
+        start.elapsed(),
+    );
+    resp
+}
+
+/// Prometheus scrape handler.
+pub async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        telemetry::render_prometheus(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn records_route_template_and_serves_metrics() {
+        let app = Router::new()
+            .route("/items/{id}", get(|| async { "item" }))
+            .route("/metrics", get(metrics_handler))
+            .layer(from_fn(http_metrics_middleware));
+
+        let resp = app
+            .clone()
+            .oneshot(Request::get("/items/7").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+
+        let resp = app
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains(r#"route="/items/{id}""#));
+        assert!(!text.contains("/items/7"));
+    }
+}
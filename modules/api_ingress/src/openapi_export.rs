@@ -0,0 +1,74 @@
+//! Offline OpenAPI export for client code generation.
+
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::ApiIngress;
+
+/// Serialization format of an exported OpenAPI document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpenApiFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl FromStr for OpenApiFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            other => anyhow::bail!("unknown OpenAPI format '{other}' (expected json or yaml)"),
+        }
+    }
+}
+
+impl ApiIngress {
+    /// Render the OpenAPI document of all registered operations.
+    ///
+    /// Call after the REST phase; no server needs to be running.
+    pub fn export_openapi(&self, format: OpenApiFormat) -> Result<String> {
+        let doc = self.build_openapi()?;
+        Ok(match format {
+            OpenApiFormat::Json => serde_json::to_string_pretty(&doc)?,
+            OpenApiFormat::Yaml => serde_yaml::to_string(&doc)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modkit::api::{Missing, OperationBuilder};
+
+    #[test]
+    fn format_parses_case_insensitively() {
+        assert_eq!(
+            "JSON".parse::<OpenApiFormat>().unwrap(),
+            OpenApiFormat::Json
+        );
+        assert_eq!("yml".parse::<OpenApiFormat>().unwrap(), OpenApiFormat::Yaml);
+        assert!("toml".parse::<OpenApiFormat>().is_err());
+    }
+
+    #[tokio::test]
+    async fn exports_json_and_yaml() {
+        let api = ApiIngress::default();
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/ping")
+            .operation_id("ping")
+            .handler(|| async { "pong" })
+            .text_response(200, "OK")
+            .register(axum::Router::new(), &api);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&api.export_openapi(OpenApiFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["paths"]["/ping"]["get"]["operationId"], "ping");
+
+        let yaml: serde_json::Value =
+            serde_yaml::from_str(&api.export_openapi(OpenApiFormat::Yaml).unwrap()).unwrap();
+        assert_eq!(yaml, json);
+    }
+}
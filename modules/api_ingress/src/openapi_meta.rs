@@ -0,0 +1,218 @@
+//! Document-level OpenAPI metadata: info, servers and tags.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use utoipa::openapi::{
+    external_docs::ExternalDocsBuilder,
+    info::{ContactBuilder, Info, InfoBuilder, LicenseBuilder},
+    server::{Server, ServerBuilder},
+    tag::{Tag, TagBuilder},
+};
+
+/// OpenAPI section of the ingress configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpenApiMetaConfig {
+    #[serde(default = "default_title")]
+    pub title: String,
+    #[serde(default = "default_version")]
+    pub version: String,
+    #[serde(default = "default_description")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub contact: Option<ContactConfig>,
+    #[serde(default)]
+    pub license: Option<LicenseConfig>,
+    /// Base URLs the API is reachable at.
+    #[serde(default)]
+    pub servers: Vec<ServerConfig>,
+    /// Tag descriptions; the list order is the order tags appear in the docs.
+    #[serde(default)]
+    pub tags: Vec<TagConfig>,
+}
+
+impl Default for OpenApiMetaConfig {
+    fn default() -> Self {
+        Self {
+            title: default_title(),
+            version: default_version(),
+            description: default_description(),
+            contact: None,
+            license: None,
+            servers: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+fn default_title() -> String {
+    "HyperSpot API".to_string()
+}
+
+fn default_version() -> String {
+    "0.1.0".to_string()
+}
+
+fn default_description() -> Option<String> {
+    Some("HyperSpot Server API Documentation".to_string())
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContactConfig {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LicenseConfig {
+    pub name: String,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerConfig {
+    pub url: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TagConfig {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Link to long-form documentation for the tag.
+    #[serde(default)]
+    pub external_docs: Option<ExternalDocsConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExternalDocsConfig {
+    pub url: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl OpenApiMetaConfig {
+    pub(crate) fn info(&self) -> Info {
+        InfoBuilder::new()
+            .title(&self.title)
+            .version(&self.version)
+            .description(self.description.clone())
+            .contact(self.contact.as_ref().map(|c| {
+                ContactBuilder::new()
+                    .name(c.name.clone())
+                    .url(c.url.clone())
+                    .email(c.email.clone())
+                    .build()
+            }))
+            .license(self.license.as_ref().map(|l| {
+                LicenseBuilder::new()
+                    .name(&l.name)
+                    .url(l.url.clone())
+                    .build()
+            }))
+            .build()
+    }
+
+    pub(crate) fn servers(&self) -> Option<Vec<Server>> {
+        if self.servers.is_empty() {
+            return None;
+        }
+        Some(
+            self.servers
+                .iter()
+                .map(|s| {
+                    ServerBuilder::new()
+                        .url(&s.url)
+                        .description(s.description.clone())
+                        .build()
+                })
+                .collect(),
+        )
+    }
+
+    /// Configured tags first (in config order), then any other tag used by
+    /// operations, alphabetically.
+    pub(crate) fn tags<'a>(&self, used: impl IntoIterator<Item = &'a str>) -> Option<Vec<Tag>> {
+        let mut tags: Vec<Tag> = self
+            .tags
+            .iter()
+            .map(|t| {
+                TagBuilder::new()
+                    .name(&t.name)
+                    .description(t.description.clone())
+                    .external_docs(t.external_docs.as_ref().map(|d| {
+                        ExternalDocsBuilder::new()
+                            .url(&d.url)
+                            .description(d.description.clone())
+                            .build()
+                    }))
+                    .build()
+            })
+            .collect();
+        let rest: BTreeSet<&str> = used
+            .into_iter()
+            .filter(|name| !self.tags.iter().any(|t| t.name == *name))
+            .collect();
+        tags.extend(
+            rest.into_iter()
+                .map(|name| TagBuilder::new().name(name).build()),
+        );
+        (!tags.is_empty()).then_some(tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_tags_keep_their_order() {
+        let cfg = OpenApiMetaConfig {
+            tags: vec![
+                TagConfig {
+                    name: "users".into(),
+                    description: Some("User management".into()),
+                    external_docs: Some(ExternalDocsConfig {
+                        url: "https://handbook.example.com/users".into(),
+                        description: None,
+                    }),
+                },
+                TagConfig {
+                    name: "admin".into(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let tags = cfg.tags(["zeta", "users", "alpha"]).unwrap();
+        let names: Vec<_> = tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["users", "admin", "alpha", "zeta"]);
+        assert_eq!(tags[0].description.as_deref(), Some("User management"));
+        assert_eq!(
+            tags[0].external_docs.as_ref().map(|d| d.url.as_str()),
+            Some("https://handbook.example.com/users")
+        );
+    }
+
+    #[test]
+    fn defaults_match_previous_document() {
+        let info = OpenApiMetaConfig::default().info();
+        assert_eq!(info.title, "HyperSpot API");
+        assert_eq!(info.version, "0.1.0");
+        assert!(OpenApiMetaConfig::default().servers().is_none());
+        assert!(OpenApiMetaConfig::default().tags([]).is_none());
+    }
+}
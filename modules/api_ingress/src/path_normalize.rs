@@ -0,0 +1,259 @@
+//! Request path normalization applied before routing.
+//!
+//! Trailing slashes can be kept strict (default, `/users/` is a 404), rewritten
+//! (`/users/` is routed as `/users`) or redirected with `308 Permanent Redirect`.
+//! Case-insensitive matching canonicalizes the static segments of registered
+//! operation paths, leaving path parameter values untouched.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, uri::PathAndQuery, HeaderValue, Request, StatusCode, Uri},
+    middleware::{from_fn_with_state, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use tower::Layer;
+
+/// How a trailing slash on a non-root path is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlash {
+    /// Paths must match exactly.
+    #[default]
+    Strict,
+    /// Route as if the slash were absent.
+    Rewrite,
+    /// Redirect to the path without the slash (308).
+    Redirect,
+}
+
+/// Path normalization section of the ingress configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathNormalizationConfig {
+    #[serde(default)]
+    pub trailing_slash: TrailingSlash,
+    /// Match static segments of registered operation paths ignoring ASCII case.
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+impl PathNormalizationConfig {
+    fn is_noop(&self) -> bool {
+        self.trailing_slash == TrailingSlash::Strict && !self.case_insensitive
+    }
+}
+
+#[derive(Debug)]
+enum Segment {
+    Static(String),
+    Param,
+    CatchAll,
+}
+
+#[derive(Debug)]
+struct Normalizer {
+    trailing_slash: TrailingSlash,
+    /// Route templates split into segments; only used for case-insensitive matching.
+    templates: Vec<Vec<Segment>>,
+}
+
+impl Normalizer {
+    fn new<'a>(
+        cfg: &PathNormalizationConfig,
+        templates: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let templates = if cfg.case_insensitive {
+            templates
+                .into_iter()
+                .map(|t| {
+                    t.split('/')
+                        .map(|seg| match seg.strip_prefix('{') {
+                            Some(p) if p.starts_with('*') => Segment::CatchAll,
+                            Some(_) => Segment::Param,
+                            None => Segment::Static(seg.to_string()),
+                        })
+                        .collect()
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Self {
+            trailing_slash: cfg.trailing_slash,
+            templates,
+        }
+    }
+
+    /// Rewrite static segments to the casing of the first matching template.
+    fn canonical_case(&self, path: &str) -> Option<String> {
+        let segments: Vec<&str> = path.split('/').collect();
+        self.templates.iter().find_map(|template| {
+            let mut out = Vec::with_capacity(segments.len());
+            for (i, seg) in segments.iter().enumerate() {
+                match template.get(i)? {
+                    Segment::Static(s) if s.eq_ignore_ascii_case(seg) => out.push(s.as_str()),
+                    Segment::Static(_) => return None,
+                    Segment::Param => out.push(seg),
+                    Segment::CatchAll => {
+                        out.extend(&segments[i..]);
+                        return Some(out.join("/"));
+                    }
+                }
+            }
+            (template.len() == segments.len()).then(|| out.join("/"))
+        })
+    }
+}
+
+fn with_path(uri: &Uri, path: &str) -> Option<Uri> {
+    let pq = match uri.query() {
+        Some(q) => format!("{path}?{q}"),
+        None => path.to_string(),
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(pq).ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+async fn normalize_path_middleware(
+    State(normalizer): State<Arc<Normalizer>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let original = req.uri().path();
+    let mut path = original.to_string();
+    if path.len() > 1 && path.ends_with('/') {
+        match normalizer.trailing_slash {
+            TrailingSlash::Strict => {}
+            TrailingSlash::Rewrite => path = path.trim_end_matches('/').to_string(),
+            TrailingSlash::Redirect => {
+                let trimmed = match path.trim_end_matches('/') {
+                    "" => "/",
+                    trimmed => trimmed,
+                };
+                let target = match req.uri().query() {
+                    Some(q) => format!("{trimmed}?{q}"),
+                    None => trimmed.to_string(),
+                };
+                return match HeaderValue::from_str(&target) {
+                    Ok(location) => (
+                        StatusCode::PERMANENT_REDIRECT,
+                        [(header::LOCATION, location)],
+                    )
+                        .into_response(),
+                    Err(_) => StatusCode::BAD_REQUEST.into_response(),
+                };
+            }
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    if let Some(canonical) = normalizer.canonical_case(&path) {
+        path = canonical;
+    }
+
+    if path != original {
+        if let Some(uri) = with_path(req.uri(), &path) {
+            tracing::trace!(from = %req.uri().path(), to = %path, "normalized request path");
+            *req.uri_mut() = uri;
+        }
+    }
+    next.run(req).await
+}
+
+/// Wrap `router` so requests are normalized before they are routed.
+///
+/// `templates` are the route templates eligible for case-insensitive matching.
+pub fn wrap<'a>(
+    router: Router,
+    cfg: &PathNormalizationConfig,
+    templates: impl IntoIterator<Item = &'a str>,
+) -> Router {
+    if cfg.is_noop() {
+        return router;
+    }
+    let normalizer = Arc::new(Normalizer::new(cfg, templates));
+    let normalized = from_fn_with_state(normalizer, normalize_path_middleware).layer(router);
+    Router::new().fallback_service(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Path, routing::get};
+    use tower::ServiceExt;
+
+    fn app(cfg: PathNormalizationConfig) -> Router {
+        let router = Router::new()
+            .route("/users", get(|| async { "list" }))
+            .route(
+                "/users/{id}/Profile",
+                get(|Path(id): Path<String>| async move { id }),
+            );
+        wrap(router, &cfg, ["/users", "/users/{id}/Profile"])
+    }
+
+    async fn call(app: Router, uri: &str) -> (StatusCode, String) {
+        let resp = app
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = match resp.headers().get(header::LOCATION) {
+            Some(loc) => loc.to_str().unwrap().to_owned(),
+            None => {
+                let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                String::from_utf8_lossy(&bytes).into_owned()
+            }
+        };
+        (status, body)
+    }
+
+    #[tokio::test]
+    async fn trailing_slash_modes() {
+        let strict = app(PathNormalizationConfig::default());
+        assert_eq!(call(strict, "/users/").await.0, StatusCode::NOT_FOUND);
+
+        let rewrite = app(PathNormalizationConfig {
+            trailing_slash: TrailingSlash::Rewrite,
+            ..Default::default()
+        });
+        assert_eq!(
+            call(rewrite, "/users/").await,
+            (StatusCode::OK, "list".into())
+        );
+
+        let redirect = app(PathNormalizationConfig {
+            trailing_slash: TrailingSlash::Redirect,
+            ..Default::default()
+        });
+        assert_eq!(
+            call(redirect, "/users/?page=2").await,
+            (StatusCode::PERMANENT_REDIRECT, "/users?page=2".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn case_insensitive_keeps_param_values() {
+        let app = app(PathNormalizationConfig {
+            case_insensitive: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            call(app.clone(), "/USERS").await,
+            (StatusCode::OK, "list".into())
+        );
+        assert_eq!(
+            call(app, "/Users/AbC/profile").await,
+            (StatusCode::OK, "AbC".into())
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Reverse-proxy routes.
+//!
+//! Each entry of `ApiIngressConfig.proxy` forwards everything under a path
+//! prefix to an upstream base URL through a [`TracedClient`], so legacy
+//! services can sit behind the ingress while they are migrated into modules.
+//! Request and response bodies are streamed; hop-by-hop headers are dropped.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::any,
+    Router,
+};
+use modkit::api::{Problem, ProblemResponse};
+use modkit::http::TracedClient;
+use serde::{Deserialize, Serialize};
+
+/// One proxied path prefix.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyRouteConfig {
+    /// Path prefix handled by the proxy, e.g. `/legacy/billing`.
+    pub path_prefix: String,
+    /// Upstream base URL, e.g. `http://billing.internal:8080/api`.
+    pub upstream: String,
+    /// Drop `path_prefix` before appending the remaining path to `upstream`.
+    #[serde(default = "default_strip_prefix")]
+    pub strip_prefix: bool,
+    /// Request headers to set (overriding client values).
+    #[serde(default)]
+    pub set_headers: BTreeMap<String, String>,
+    /// Request headers to remove before forwarding.
+    #[serde(default)]
+    pub remove_headers: Vec<String>,
+    /// Upstream timeout for the whole exchange.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_strip_prefix() -> bool {
+    true
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+/// Headers that describe a single connection and must not be forwarded (RFC 9110 §7.6.1).
+const HOP_BY_HOP: [HeaderName; 7] = [
+    header::CONNECTION,
+    header::PROXY_AUTHENTICATE,
+    header::PROXY_AUTHORIZATION,
+    header::TE,
+    header::TRAILER,
+    header::TRANSFER_ENCODING,
+    header::UPGRADE,
+];
+
+struct ProxyTarget {
+    prefix: String,
+    upstream: String,
+    strip_prefix: bool,
+    set_headers: Vec<(HeaderName, HeaderValue)>,
+    remove_headers: Vec<HeaderName>,
+    timeout: Duration,
+    client: TracedClient,
+}
+
+impl ProxyTarget {
+    fn from_config(cfg: &ProxyRouteConfig, client: TracedClient) -> anyhow::Result<Self> {
+        if !cfg.path_prefix.starts_with('/') {
+            anyhow::bail!(
+                "proxy path_prefix '{}' must start with '/'",
+                cfg.path_prefix
+            );
+        }
+        let upstream = reqwest::Url::parse(&cfg.upstream)
+            .map_err(|e| anyhow::anyhow!("proxy upstream '{}' is invalid: {e}", cfg.upstream))?;
+        if !matches!(upstream.scheme(), "http" | "https") {
+            anyhow::bail!("proxy upstream '{}' must be http(s)", cfg.upstream);
+        }
+        let set_headers = cfg
+            .set_headers
+            .iter()
+            .map(|(k, v)| Ok((HeaderName::try_from(k.as_str())?, HeaderValue::from_str(v)?)))
+            .collect::<anyhow::Result<_>>()?;
+        let remove_headers = cfg
+            .remove_headers
+            .iter()
+            .map(|h| HeaderName::try_from(h.as_str()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            prefix: cfg.path_prefix.trim_end_matches('/').to_string(),
+            upstream: cfg.upstream.trim_end_matches('/').to_string(),
+            strip_prefix: cfg.strip_prefix,
+            set_headers,
+            remove_headers,
+            timeout: Duration::from_secs(cfg.timeout_secs),
+            client,
+        })
+    }
+
+    fn upstream_url(&self, path: &str, query: Option<&str>) -> String {
+        let rest = if self.strip_prefix {
+            path.strip_prefix(self.prefix.as_str()).unwrap_or(path)
+        } else {
+            path
+        };
+        match query {
+            Some(q) => format!("{}{rest}?{q}", self.upstream),
+            None => format!("{}{rest}", self.upstream),
+        }
+    }
+
+    fn forward_headers(&self, incoming: &HeaderMap) -> HeaderMap {
+        let mut headers = incoming.clone();
+        for name in HOP_BY_HOP.iter().chain(&self.remove_headers) {
+            headers.remove(name);
+        }
+        if let Some(host) = headers.remove(header::HOST) {
+            headers.insert(HeaderName::from_static("x-forwarded-host"), host);
+        }
+        for (name, value) in &self.set_headers {
+            headers.insert(name.clone(), value.clone());
+        }
+        headers
+    }
+
+    async fn forward(&self, req: Request) -> Response {
+        let (parts, body) = req.into_parts();
+        let url = self.upstream_url(parts.uri.path(), parts.uri.query());
+        let builder = self
+            .client
+            .inner()
+            .request(parts.method.clone(), &url)
+            .headers(self.forward_headers(&parts.headers))
+            .timeout(self.timeout)
+            .body(reqwest::Body::wrap_stream(body.into_data_stream()));
+
+        let upstream = match self.client.send(builder).await {
+            Ok(resp) => resp,
+            Err(e) if e.is_timeout() => {
+                return gateway_problem(StatusCode::GATEWAY_TIMEOUT, "Upstream timed out", &url)
+            }
+            Err(_) => {
+                return gateway_problem(StatusCode::BAD_GATEWAY, "Upstream unavailable", &url)
+            }
+        };
+
+        let mut resp = Response::builder().status(upstream.status());
+        if let Some(headers) = resp.headers_mut() {
+            for (name, value) in upstream.headers() {
+                if !HOP_BY_HOP.contains(name) {
+                    headers.append(name.clone(), value.clone());
+                }
+            }
+        }
+        resp.body(Body::from_stream(upstream.bytes_stream()))
+            .unwrap_or_else(|_| StatusCode::BAD_GATEWAY.into_response())
+    }
+}
+
+fn gateway_problem(status: StatusCode, title: &str, url: &str) -> Response {
+    tracing::warn!(%url, status = status.as_u16(), "proxy: {title}");
+    ProblemResponse::from(
+        Problem::new(status, title, format!("Proxied request to {url} failed"))
+            .with_code("UPSTREAM_ERROR"),
+    )
+    .into_response()
+}
+
+/// Mount all configured proxy routes onto `router`.
+pub fn mount(
+    mut router: Router,
+    routes: &[ProxyRouteConfig],
+    client: TracedClient,
+) -> anyhow::Result<Router> {
+    for cfg in routes {
+        let target = Arc::new(ProxyTarget::from_config(cfg, client.clone())?);
+        tracing::info!(
+            path_prefix = %cfg.path_prefix,
+            upstream = %cfg.upstream,
+            "Mounting reverse proxy route"
+        );
+        let handler = move |req: Request| {
+            let target = target.clone();
+            async move { target.forward(req).await }
+        };
+        let prefix = cfg.path_prefix.trim_end_matches('/');
+        router = router
+            .route(
+                if prefix.is_empty() { "/" } else { prefix },
+                any(handler.clone()),
+            )
+            .route(&format!("{prefix}/{{*rest}}"), any(handler));
+    }
+    Ok(router)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::Request as HttpRequest, routing::get};
+    use tower::ServiceExt;
+
+    fn route(upstream: &str) -> ProxyRouteConfig {
+        ProxyRouteConfig {
+            path_prefix: "/legacy".into(),
+            upstream: upstream.into(),
+            strip_prefix: default_strip_prefix(),
+            set_headers: BTreeMap::from([("x-proxied-by".into(), "hyperspot".into())]),
+            remove_headers: vec!["cookie".into()],
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+
+    #[test]
+    fn builds_upstream_urls() {
+        let target =
+            ProxyTarget::from_config(&route("http://up:8080/api/"), TracedClient::default())
+                .unwrap();
+        assert_eq!(
+            target.upstream_url("/legacy/users/1", Some("a=1")),
+            "http://up:8080/api/users/1?a=1"
+        );
+        assert!(ProxyTarget::from_config(&route("ftp://up"), TracedClient::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn forwards_requests_and_rewrites_headers() {
+        let upstream = Router::new().route(
+            "/api/echo",
+            get(|headers: HeaderMap| async move {
+                let proxied = headers.get("x-proxied-by").is_some();
+                let cookie = headers.get("cookie").is_some();
+                format!("proxied={proxied} cookie={cookie}")
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, upstream).await });
+
+        let app = mount(
+            Router::new(),
+            &[route(&format!("http://{addr}/api"))],
+            TracedClient::default(),
+        )
+        .unwrap();
+        let resp = app
+            .clone()
+            .oneshot(
+                HttpRequest::get("/legacy/echo")
+                    .header("cookie", "session=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"proxied=true cookie=false");
+
+        let missing = app
+            .oneshot(
+                HttpRequest::get("/legacy/nope")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn unreachable_upstream_is_bad_gateway() {
+        let app = mount(
+            Router::new(),
+            &[route("http://127.0.0.1:1")],
+            TracedClient::default(),
+        )
+        .unwrap();
+        let resp = app
+            .oneshot(HttpRequest::get("/legacy/x").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+    }
+}
@@ -0,0 +1,173 @@
+//! Token-bucket rate limiting shared by the ingress middlewares.
+//!
+//! Besides the per-key limits of API keys, operations declared with
+//! `OperationBuilder::rate_limit` get one bucket per route.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{header, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use modkit::api::{OperationSpec, Problem, ProblemResponse};
+use parking_lot::Mutex;
+
+/// Thread-safe token bucket: `burst` tokens, refilled at `rps` per second.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub(crate) fn new(rps: u32, burst: u32) -> Self {
+        let capacity = f64::from(burst.max(1));
+        Self {
+            capacity,
+            refill_per_sec: f64::from(rps.max(1)),
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take one token, or return how long until one becomes available.
+    pub(crate) fn try_acquire(&self) -> Result<(), Duration> {
+        let mut st = self.state.lock();
+        let now = Instant::now();
+        let elapsed = now.duration_since(st.last_refill).as_secs_f64();
+        st.tokens = (st.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        st.last_refill = now;
+
+        if st.tokens >= 1.0 {
+            st.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - st.tokens) / self.refill_per_sec;
+            Err(Duration::from_secs_f64(wait))
+        }
+    }
+}
+
+/// Whole seconds for a `Retry-After` header (never zero).
+pub(crate) fn retry_after_secs(wait: Duration) -> u64 {
+    (wait.as_secs_f64().ceil() as u64).max(1)
+}
+
+/// Buckets of rate-limited routes keyed by `(method, route template)`.
+#[derive(Clone, Default)]
+pub(crate) struct RouteRateLimits {
+    routes: Arc<HashMap<(Method, String), TokenBucket>>,
+}
+
+impl RouteRateLimits {
+    pub(crate) fn from_specs<'a>(specs: impl IntoIterator<Item = &'a OperationSpec>) -> Self {
+        let routes = specs
+            .into_iter()
+            .filter_map(|spec| {
+                let limit = spec.rate_limit?;
+                let key = (spec.method.clone(), spec.path.clone());
+                Some((key, TokenBucket::new(limit.rps, limit.burst)))
+            })
+            .collect();
+        Self {
+            routes: Arc::new(routes),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+/// Middleware answering `429` once a route has used up its declared rate.
+pub(crate) async fn route_rate_limit_middleware(
+    State(state): State<RouteRateLimits>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let bucket = req.extensions().get::<MatchedPath>().and_then(|p| {
+        state
+            .routes
+            .get(&(req.method().clone(), p.as_str().to_string()))
+    });
+    if let Some(Err(wait)) = bucket.map(TokenBucket::try_acquire) {
+        let mut resp = ProblemResponse::from(Problem::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too Many Requests",
+            "Route rate limit exceeded",
+        ))
+        .into_response();
+        resp.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(retry_after_secs(wait)),
+        );
+        return resp;
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_burst_then_limits() {
+        let bucket = TokenBucket::new(1, 3);
+        assert!(bucket.try_acquire().is_ok());
+        assert!(bucket.try_acquire().is_ok());
+        assert!(bucket.try_acquire().is_ok());
+        let wait = bucket.try_acquire().unwrap_err();
+        assert!(wait <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn retry_after_rounds_up() {
+        assert_eq!(retry_after_secs(Duration::from_millis(1)), 1);
+        assert_eq!(retry_after_secs(Duration::from_secs(2)), 2);
+        assert_eq!(retry_after_secs(Duration::from_millis(2500)), 3);
+        assert_eq!(retry_after_secs(Duration::ZERO), 1);
+    }
+
+    #[tokio::test]
+    async fn declared_route_limits_are_enforced() {
+        use axum::{middleware::from_fn_with_state, routing::get, Router};
+        use modkit::api::{Missing, OperationBuilder};
+        use tower::ServiceExt;
+
+        let limited = OperationBuilder::<Missing, Missing, ()>::get("/search")
+            .rate_limit(1, 1)
+            .spec()
+            .clone();
+        let state = RouteRateLimits::from_specs([&limited]);
+        let app = Router::new()
+            .route("/search", get(|| async { "results" }))
+            .route("/health", get(|| async { "ok" }))
+            .layer(from_fn_with_state(state, route_rate_limit_middleware));
+        let call = |path: &str| {
+            app.clone()
+                .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        };
+
+        assert_eq!(call("/search").await.unwrap().status(), StatusCode::OK);
+        let resp = call("/search").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "1");
+        for _ in 0..3 {
+            assert_eq!(call("/health").await.unwrap().status(), StatusCode::OK);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! `/readyz`: readiness aggregated from module lifecycle status, module health
+//! probes and database health.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Weak};
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use modkit::lifecycle::Status;
+use modkit::{HealthStatus, ModuleRegistry};
+use serde_json::{json, Value};
+
+/// What the readiness handler inspects. The registry is held weakly because
+/// the router built from it is itself owned by a module in the registry.
+#[derive(Clone, Default)]
+pub struct ReadinessState {
+    registry: Option<Weak<ModuleRegistry>>,
+    db_manager: Option<Arc<modkit_db::DbManager>>,
+}
+
+impl ReadinessState {
+    pub fn from_ctx(ctx: &modkit::ModuleCtx) -> Self {
+        Self {
+            registry: ctx.module_registry().map(Arc::downgrade),
+            db_manager: ctx.db_manager().cloned(),
+        }
+    }
+}
+
+/// 200 when every stateful module is running, no health probe reports
+/// unhealthy and every open database answers, 503 otherwise; the body lists
+/// each module (with the uptime of running ones), probe and database.
+pub async fn readiness_check(State(state): State<ReadinessState>) -> (StatusCode, Json<Value>) {
+    let mut ready = true;
+
+    let mut modules = BTreeMap::new();
+    let mut uptime = BTreeMap::new();
+    let mut health = BTreeMap::new();
+    if let Some(registry) = state.registry.as_ref().and_then(Weak::upgrade) {
+        for (name, status) in registry.stateful_statuses() {
+            // Modules without a lifecycle cannot report progress; treat them as ready.
+            let status = status.unwrap_or(Status::Running);
+            ready &= status == Status::Running;
+            let entry = match registry.last_error(name) {
+                Some(err) if status == Status::Failed => format!("{}: {err}", status.as_str()),
+                _ => status.as_str().to_string(),
+            };
+            modules.insert(name, entry);
+        }
+        for module in registry.runtime_report() {
+            if let Some(up) = module.uptime {
+                uptime.insert(module.module, up.as_millis());
+            }
+        }
+        for (name, status) in registry.health_snapshot().await.modules {
+            // Degraded modules still serve traffic.
+            ready &= !matches!(status, HealthStatus::Unhealthy(_));
+            let entry = match status.reason() {
+                Some(reason) => format!("{}: {reason}", status.as_str()),
+                None => status.as_str().to_string(),
+            };
+            health.insert(name, entry);
+        }
+    }
+
+    let mut databases = BTreeMap::new();
+    if let Some(db) = &state.db_manager {
+        for (module, res) in db.health().await {
+            let entry = match res {
+                Ok(()) => "ok".to_string(),
+                Err(e) => {
+                    ready = false;
+                    format!("error: {e}")
+                }
+            };
+            databases.insert(module, entry);
+        }
+    }
+
+    let code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        code,
+        Json(json!({
+            "status": if ready { "ready" } else { "not_ready" },
+            "modules": modules,
+            "uptime_ms": uptime,
+            "health": health,
+            "databases": databases,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn ready_without_registry_or_db() {
+        let (code, Json(body)) = readiness_check(State(ReadinessState::default())).await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["status"], "ready");
+    }
+
+    #[tokio::test]
+    async fn not_ready_until_stateful_modules_run() {
+        // The discovered registry contains api_ingress itself, which has not been started.
+        let registry = Arc::new(ModuleRegistry::discover_and_build().unwrap());
+        let state = ReadinessState {
+            registry: Some(Arc::downgrade(&registry)),
+            db_manager: None,
+        };
+        let (code, Json(body)) = readiness_check(State(state)).await;
+        assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["modules"]["api_ingress"], "stopped");
+        assert!(body["uptime_ms"].get("api_ingress").is_none());
+    }
+}
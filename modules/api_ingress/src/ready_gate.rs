@@ -0,0 +1,163 @@
+//! Readiness gate for application routes.
+//!
+//! The ingress starts serving as soon as it is bound, which can be before the
+//! modules behind it have finished starting. Until every stateful module is
+//! `Running` the gate answers non-health routes with a 503 Problem and
+//! `Retry-After`; once they are, it stays open for the rest of the run
+//! (`/readyz` keeps reporting later changes).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use modkit::api::{Problem, ProblemResponse};
+use modkit::ModuleRegistry;
+use serde::{Deserialize, Serialize};
+
+/// Paths always served, so probes and scrapers see the process while it starts.
+const ALWAYS_OPEN: &[&str] = &["/health", "/healthz", "/readyz"];
+
+/// Ready gate section of the ingress configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReadyGateConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// `Retry-After` value sent with 503 responses.
+    #[serde(default = "default_retry_after_secs")]
+    pub retry_after_secs: u64,
+}
+
+impl Default for ReadyGateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            retry_after_secs: default_retry_after_secs(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_retry_after_secs() -> u64 {
+    1
+}
+
+/// Shared state of the ready gate middleware. The registry is held weakly
+/// for the same reason as in `ReadinessState`.
+#[derive(Clone)]
+pub struct ReadyGateState {
+    registry: Option<Weak<ModuleRegistry>>,
+    open: Arc<AtomicBool>,
+    exempt: Arc<Vec<String>>,
+    retry_after: HeaderValue,
+}
+
+impl ReadyGateState {
+    /// `exempt` adds paths served before readiness, e.g. the metrics endpoint.
+    pub fn new(
+        registry: Option<Weak<ModuleRegistry>>,
+        cfg: &ReadyGateConfig,
+        exempt: impl IntoIterator<Item = String>,
+    ) -> Self {
+        let mut paths: Vec<String> = ALWAYS_OPEN.iter().map(|p| p.to_string()).collect();
+        paths.extend(exempt);
+        Self {
+            registry,
+            open: Arc::new(AtomicBool::new(false)),
+            exempt: Arc::new(paths),
+            retry_after: HeaderValue::from(cfg.retry_after_secs),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        if self.open.load(Ordering::Relaxed) {
+            return true;
+        }
+        // Without a registry (or once it is gone) there is nothing to wait for.
+        let ready = self
+            .registry
+            .as_ref()
+            .and_then(Weak::upgrade)
+            .is_none_or(|registry| registry.all_running());
+        if ready {
+            self.open.store(true, Ordering::Relaxed);
+            tracing::info!("ready gate: all modules running; serving requests");
+        }
+        ready
+    }
+}
+
+/// Middleware answering 503 on non-health routes until all modules run.
+pub async fn ready_gate_middleware(
+    State(state): State<ReadyGateState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if state.exempt.iter().any(|p| p == req.uri().path()) || state.is_open() {
+        return next.run(req).await;
+    }
+    let mut resp = ProblemResponse::from(
+        Problem::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Service Unavailable",
+            "Server is starting, retry later",
+        )
+        .with_code("NOT_READY"),
+    )
+    .into_response();
+    resp.headers_mut()
+        .insert(header::RETRY_AFTER, state.retry_after.clone());
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn_with_state, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn call(app: Router, path: &str) -> Response {
+        app.oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn gates_routes_until_modules_run() {
+        // The discovered registry contains api_ingress itself, which has not been started.
+        let registry = Arc::new(ModuleRegistry::discover_and_build().unwrap());
+        let cfg = ReadyGateConfig {
+            retry_after_secs: 3,
+            ..Default::default()
+        };
+        let state = ReadyGateState::new(
+            Some(Arc::downgrade(&registry)),
+            &cfg,
+            ["/metrics".to_string()],
+        );
+        let app = Router::new()
+            .route("/users", get(|| async { "ok" }))
+            .route("/readyz", get(|| async { "ok" }))
+            .route("/metrics", get(|| async { "ok" }))
+            .layer(from_fn_with_state(state, ready_gate_middleware));
+
+        let resp = call(app.clone(), "/users").await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "3");
+        assert_eq!(call(app.clone(), "/readyz").await.status(), StatusCode::OK);
+        assert_eq!(call(app.clone(), "/metrics").await.status(), StatusCode::OK);
+
+        // Once the registry is gone nothing holds the gate closed.
+        drop(registry);
+        assert_eq!(call(app, "/users").await.status(), StatusCode::OK);
+    }
+}
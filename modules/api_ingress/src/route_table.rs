@@ -0,0 +1,390 @@
+//! Introspection: the `/__debug/routes`, `/__debug/clients` and
+//! `/__debug/telemetry` endpoints and the startup route table.
+
+use axum::{http::StatusCode, routing::get, Json};
+use modkit::api::{Missing, OpenApiRegistry, OperationBuilder, OperationSpec, ResponseStatus};
+use modkit::client_hub::ClientHub;
+use modkit::telemetry::{self, LogLevels, Sampler};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Path of the introspection endpoint.
+pub const DEBUG_ROUTES_PATH: &str = "/__debug/routes";
+
+/// Path of the endpoint listing the clients wired into the `ClientHub`.
+pub const DEBUG_CLIENTS_PATH: &str = "/__debug/clients";
+
+/// Path of the endpoint reading and changing log levels and trace sampling.
+pub const DEBUG_TELEMETRY_PATH: &str = "/__debug/telemetry";
+
+/// One registered operation as shown by the introspection endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteInfo {
+    pub method: String,
+    pub path: String,
+    pub operation_id: Option<String>,
+    pub module: Option<String>,
+    pub responses: Vec<RouteResponse>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteResponse {
+    pub status: ResponseStatus,
+    pub content_type: &'static str,
+    pub description: String,
+}
+
+/// Routes sorted by path, then method.
+pub fn route_table<'a>(specs: impl IntoIterator<Item = &'a OperationSpec>) -> Vec<RouteInfo> {
+    let mut routes: Vec<RouteInfo> = specs
+        .into_iter()
+        .map(|spec| RouteInfo {
+            method: spec.method.to_string(),
+            path: spec.path.clone(),
+            operation_id: spec.operation_id.clone(),
+            module: spec.module.clone(),
+            responses: spec
+                .responses
+                .iter()
+                .map(|r| RouteResponse {
+                    status: r.status,
+                    content_type: r.content_type,
+                    description: r.description.clone(),
+                })
+                .collect(),
+        })
+        .collect();
+    routes.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+    routes
+}
+
+/// Plain-text table for the startup log.
+pub fn render(routes: &[RouteInfo]) -> String {
+    let rows: Vec<[String; 5]> = routes
+        .iter()
+        .map(|r| {
+            [
+                r.method.clone(),
+                r.path.clone(),
+                r.operation_id.clone().unwrap_or_else(|| "-".into()),
+                r.module.clone().unwrap_or_else(|| "-".into()),
+                {
+                    let mut statuses: Vec<String> = Vec::new();
+                    for resp in &r.responses {
+                        let status = resp.status.to_string();
+                        // One status may be listed once per media type
+                        if !statuses.contains(&status) {
+                            statuses.push(status);
+                        }
+                    }
+                    statuses.join(",")
+                },
+            ]
+        })
+        .collect();
+    let header = ["METHOD", "PATH", "OPERATION", "MODULE", "RESPONSES"].map(String::from);
+    let mut widths = header.clone().map(|h| h.len());
+    for row in &rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.len());
+        }
+    }
+    std::iter::once(&header)
+        .chain(&rows)
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, w)| format!("{cell:<w$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Handler serving a snapshot of the route table.
+pub fn debug_routes_route(routes: Vec<RouteInfo>) -> axum::routing::MethodRouter {
+    get(move || {
+        let routes = routes.clone();
+        async move { Json(routes) }
+    })
+}
+
+/// Handler listing the clients currently registered in `hub`.
+pub fn debug_clients_route(hub: Arc<ClientHub>) -> axum::routing::MethodRouter {
+    get(move || {
+        let hub = hub.clone();
+        async move { Json(hub.list()) }
+    })
+}
+
+/// Log filter directives and trace sampler; `PUT` changes the fields present.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TelemetrySettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_directives: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampler: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampler_arg: Option<f64>,
+}
+
+fn telemetry_settings(levels: Option<&dyn LogLevels>) -> TelemetrySettings {
+    let (sampler, sampler_arg) = match telemetry::sampler().map(|s| s.to_name()) {
+        Some((name, arg)) => (Some(name), arg),
+        None => (None, None),
+    };
+    TelemetrySettings {
+        log_directives: levels.map(|l| l.directives()),
+        sampler,
+        sampler_arg,
+    }
+}
+
+fn apply_telemetry(
+    levels: Option<&dyn LogLevels>,
+    update: &TelemetrySettings,
+) -> anyhow::Result<()> {
+    // Validate everything before changing anything.
+    let sampler = match (&update.sampler, update.sampler_arg) {
+        (Some(name), arg) => Some(Sampler::from_name(name, arg)?),
+        (None, Some(_)) => anyhow::bail!("sampler_arg needs a sampler"),
+        (None, None) => None,
+    };
+    if let Some(directives) = &update.log_directives {
+        levels
+            .ok_or_else(|| anyhow::anyhow!("log levels cannot be changed in this process"))?
+            .set_directives(directives)?;
+    }
+    if let Some(sampler) = sampler {
+        telemetry::set_sampler(sampler)?;
+    }
+    Ok(())
+}
+
+/// Scope a bearer token needs to change telemetry settings.
+pub const DEBUG_TELEMETRY_SCOPE: &str = "admin:telemetry";
+
+/// Register `GET` (read) and, when `writable`, `PUT` (change) for log levels
+/// and trace sampling. `PUT` requires a bearer token with [`DEBUG_TELEMETRY_SCOPE`].
+pub fn register_debug_telemetry(
+    router: axum::Router,
+    openapi: &dyn OpenApiRegistry,
+    levels: Option<Arc<dyn LogLevels>>,
+    writable: bool,
+) -> axum::Router {
+    let read = levels.clone();
+    let router = OperationBuilder::<Missing, Missing, ()>::get(DEBUG_TELEMETRY_PATH)
+        .operation_id("debug.telemetry.get")
+        .summary("Current log levels and trace sampler")
+        .tag("debug")
+        .handler(move || {
+            let levels = read.clone();
+            async move { Json(telemetry_settings(levels.as_deref())) }
+        })
+        .json_response(200, "Telemetry settings")
+        .register(router, openapi);
+    if !writable {
+        return router;
+    }
+
+    OperationBuilder::<Missing, Missing, ()>::put(DEBUG_TELEMETRY_PATH)
+        .operation_id("debug.telemetry.update")
+        .summary("Change log levels and trace sampler")
+        .tag("debug")
+        .require_scopes([DEBUG_TELEMETRY_SCOPE])
+        .handler(move |Json(update): Json<TelemetrySettings>| {
+            let levels = levels.clone();
+            async move {
+                match apply_telemetry(levels.as_deref(), &update) {
+                    Ok(()) => {
+                        tracing::warn!(?update, "telemetry settings changed at runtime");
+                        Ok(Json(telemetry_settings(levels.as_deref())))
+                    }
+                    Err(e) => Err((StatusCode::BAD_REQUEST, format!("{e:#}"))),
+                }
+            }
+        })
+        .json_response(200, "Settings in force after the change")
+        .text_response(400, "Invalid settings")
+        .register(router, openapi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_lists_routes_sorted_with_owner() {
+        let api = crate::ApiIngress::default();
+        let scoped = modkit::api::ModuleOpenApiRegistry::new(&api, "users_info");
+        let registry: &dyn OpenApiRegistry = &scoped;
+        let _router = OperationBuilder::<Missing, Missing, ()>::post("/users")
+            .operation_id("users.create")
+            .handler(|| async { "ok" })
+            .json_response(201, "Created")
+            .json_response(400, "Bad request")
+            .register(axum::Router::new(), registry);
+        let _router = OperationBuilder::<Missing, Missing, ()>::get("/health/ready")
+            .handler(|| async { "ok" })
+            .text_response(200, "Ready")
+            .register(axum::Router::new(), &api);
+
+        let specs: Vec<_> = api
+            .operation_specs
+            .iter()
+            .map(|e| e.value().clone())
+            .collect();
+        let routes = route_table(&specs);
+        assert_eq!(routes[0].path, "/health/ready");
+        assert_eq!(routes[1].module.as_deref(), Some("users_info"));
+
+        let text = render(&routes);
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines[0].starts_with("METHOD"));
+        assert!(lines[1].contains("GET     /health/ready"));
+        assert!(lines[2].contains("users.create") && lines[2].ends_with("201,400"));
+    }
+
+    #[tokio::test]
+    async fn debug_clients_lists_hub_registrations() {
+        use tower::ServiceExt;
+
+        trait Directory: Send + Sync {}
+        struct Local;
+        impl Directory for Local {}
+
+        let hub = Arc::new(ClientHub::new());
+        hub.register::<dyn Directory>(Arc::new(Local));
+        let app = axum::Router::new().route(DEBUG_CLIENTS_PATH, debug_clients_route(hub));
+
+        let resp = app
+            .oneshot(
+                axum::http::Request::get(DEBUG_CLIENTS_PATH)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let client = &json[0];
+        assert!(client["contract"].as_str().unwrap().ends_with("Directory"));
+        assert!(client["provider"].is_null());
+        assert!(client["registered_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn debug_telemetry_reads_and_changes_log_levels() {
+        use tower::ServiceExt;
+
+        #[derive(Default)]
+        struct Levels(parking_lot::Mutex<String>);
+        impl LogLevels for Levels {
+            fn directives(&self) -> String {
+                self.0.lock().clone()
+            }
+            fn set_directives(&self, directives: &str) -> anyhow::Result<()> {
+                anyhow::ensure!(!directives.contains("loud"), "invalid level");
+                *self.0.lock() = directives.to_string();
+                Ok(())
+            }
+        }
+
+        let levels: Arc<dyn LogLevels> = Arc::new(Levels::default());
+        let api = crate::ApiIngress::default();
+        let app = register_debug_telemetry(axum::Router::new(), &api, Some(levels.clone()), true);
+        let put = |body: serde_json::Value| {
+            axum::http::Request::put(DEBUG_TELEMETRY_PATH)
+                .header("content-type", "application/json")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(put(
+                serde_json::json!({ "log_directives": "api_ingress=debug" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["log_directives"], "api_ingress=debug");
+        assert_eq!(levels.directives(), "api_ingress=debug");
+
+        // A bad sampler rejects the whole update.
+        let resp = app
+            .clone()
+            .oneshot(put(serde_json::json!({
+                "log_directives": "sqlx=off",
+                "sampler": "sometimes",
+            })))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(levels.directives(), "api_ingress=debug");
+
+        let resp = app
+            .oneshot(put(serde_json::json!({ "log_directives": "loud" })))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn debug_telemetry_update_requires_a_token() {
+        use crate::auth::{self, AuthConfig, JwtValidator, RoutePolicies};
+        use tower::ServiceExt;
+
+        let api = crate::ApiIngress::default();
+        let app = register_debug_telemetry(axum::Router::new(), &api, None, true);
+        let specs: Vec<_> = api
+            .operation_specs
+            .iter()
+            .map(|e| e.value().clone())
+            .collect();
+        let validator = JwtValidator::new(AuthConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let state = auth::AuthState::new(
+            Arc::new(validator),
+            RoutePolicies::from_specs(&specs, &[modkit::api::BEARER_AUTH_SCHEME]),
+        );
+        let app = app.layer(axum::middleware::from_fn_with_state(
+            state,
+            auth::auth_middleware,
+        ));
+
+        let resp = app
+            .clone()
+            .oneshot(
+                axum::http::Request::put(DEBUG_TELEMETRY_PATH)
+                    .header("content-type", "application/json")
+                    .body(axum::body::Body::from(r#"{"sampler":"always_on"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let resp = app
+            .oneshot(
+                axum::http::Request::get(DEBUG_TELEMETRY_PATH)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
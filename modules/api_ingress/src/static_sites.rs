@@ -0,0 +1,187 @@
+//! Static file and single-page application serving.
+//!
+//! Each entry of `ApiIngressConfig.static_sites` mounts a directory under a
+//! path prefix. With `spa: true`, unknown paths fall back to the index file
+//! so client-side routing works on deep links.
+
+use std::path::PathBuf;
+
+use axum::{
+    http::{header, HeaderValue},
+    Router,
+};
+use serde::{Deserialize, Serialize};
+use tower::{Layer, ServiceBuilder};
+use tower_http::{
+    services::{ServeDir, ServeFile},
+    set_header::SetResponseHeaderLayer,
+};
+
+/// One static site mounted by the ingress.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StaticSiteConfig {
+    /// URL prefix to mount the site under, e.g. `/dashboard`.
+    pub mount_path: String,
+    /// Directory with the files to serve.
+    pub dir: PathBuf,
+    /// Serve the index file for paths that do not match a file.
+    #[serde(default)]
+    pub spa: bool,
+    /// Index file name, relative to `dir`.
+    #[serde(default = "default_index")]
+    pub index: String,
+    /// `Cache-Control` value for served files (the SPA fallback is never cached).
+    #[serde(default)]
+    pub cache_control: Option<String>,
+}
+
+fn default_index() -> String {
+    "index.html".to_string()
+}
+
+/// Mount all configured sites onto `router`.
+pub fn mount(mut router: Router, sites: &[StaticSiteConfig]) -> anyhow::Result<Router> {
+    for site in sites {
+        let mount_path = site.mount_path.trim_end_matches('/');
+        if !site.mount_path.starts_with('/') {
+            anyhow::bail!(
+                "static site mount_path '{}' must start with '/'",
+                site.mount_path
+            );
+        }
+        if !site.dir.is_dir() {
+            anyhow::bail!(
+                "static site directory '{}' does not exist",
+                site.dir.display()
+            );
+        }
+
+        let cache_control = site
+            .cache_control
+            .as_deref()
+            .map(HeaderValue::from_str)
+            .transpose()?;
+
+        let files = ServeDir::new(&site.dir).append_index_html_on_directories(true);
+        let cache = SetResponseHeaderLayer::if_not_present(header::CACHE_CONTROL, cache_control);
+        let site_router = if site.spa {
+            let index = ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::overriding(
+                    header::CACHE_CONTROL,
+                    HeaderValue::from_static("no-cache"),
+                ))
+                .service(ServeFile::new(site.dir.join(&site.index)));
+            Router::new().fallback_service(cache.layer(files.fallback(index)))
+        } else {
+            Router::new().fallback_service(cache.layer(files))
+        };
+
+        tracing::info!(
+            mount_path = %site.mount_path,
+            dir = %site.dir.display(),
+            spa = site.spa,
+            "Mounting static site"
+        );
+        router = if mount_path.is_empty() {
+            router.merge(site_router)
+        } else {
+            router.nest(mount_path, site_router)
+        };
+    }
+    Ok(router)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, http::StatusCode, routing::get};
+    use tower::ServiceExt;
+
+    fn site_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("index.html"), "<html>app</html>").unwrap();
+        std::fs::write(dir.path().join("app.js"), "console.log(1)").unwrap();
+        dir
+    }
+
+    async fn get_path(app: Router, path: &str) -> (StatusCode, Option<String>, String) {
+        let resp = app
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let cache = resp
+            .headers()
+            .get(header::CACHE_CONTROL)
+            .map(|v| v.to_str().unwrap().to_owned());
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, cache, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[tokio::test]
+    async fn serves_files_with_spa_fallback() {
+        let dir = site_dir();
+        let sites = [StaticSiteConfig {
+            mount_path: "/dashboard".into(),
+            dir: dir.path().to_path_buf(),
+            spa: true,
+            index: default_index(),
+            cache_control: Some("public, max-age=3600".into()),
+        }];
+        let app = mount(Router::new().route("/api", get(|| async { "api" })), &sites).unwrap();
+
+        let (status, cache, body) = get_path(app.clone(), "/dashboard/app.js").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(cache.as_deref(), Some("public, max-age=3600"));
+        assert_eq!(body, "console.log(1)");
+
+        let (status, cache, body) = get_path(app.clone(), "/dashboard/users/42").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(cache.as_deref(), Some("no-cache"));
+        assert_eq!(body, "<html>app</html>");
+
+        let (status, _, body) = get_path(app, "/api").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "api");
+    }
+
+    #[tokio::test]
+    async fn missing_files_are_not_found_without_spa() {
+        let dir = site_dir();
+        let sites = [StaticSiteConfig {
+            mount_path: "/static".into(),
+            dir: dir.path().to_path_buf(),
+            spa: false,
+            index: default_index(),
+            cache_control: None,
+        }];
+        let app = mount(Router::new(), &sites).unwrap();
+        let (status, cache, _) = get_path(app, "/static/nope.js").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(cache.is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_sites() {
+        let bad_path = [StaticSiteConfig {
+            mount_path: "dashboard".into(),
+            dir: std::env::temp_dir(),
+            spa: false,
+            index: default_index(),
+            cache_control: None,
+        }];
+        assert!(mount(Router::new(), &bad_path).is_err());
+
+        let bad_dir = [StaticSiteConfig {
+            mount_path: "/dashboard".into(),
+            dir: PathBuf::from("/definitely/not/here"),
+            spa: false,
+            index: default_index(),
+            cache_control: None,
+        }];
+        assert!(mount(Router::new(), &bad_dir).is_err());
+    }
+}
@@ -0,0 +1,697 @@
+//! Contract validation against the registered OpenAPI schemas.
+//!
+//! [`SchemaValidator`] checks JSON values against the component schemas in the
+//! ingress registry (the subset of JSON Schema utoipa emits: types, `$ref`,
+//! properties/required, items, enums, composition and basic bounds).
+//! [`request_validation_middleware`] applies it to request bodies and
+//! parameters of every registered operation and answers mismatches with a
+//! 422 validation Problem. Meant for dev/staging, enabled via
+//! `ApiIngressConfig.request_validation`.
+//!
+//! [`response_validation_middleware`] is the mirror image for handlers: in
+//! debug builds it checks outgoing JSON bodies against the declared
+//! `ResponseSpec` schema and turns a mismatch into a 500, so divergence shows
+//! up in tests rather than in clients.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{header, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use modkit::api::{
+    CollectionFormat, OperationSpec, ParamLocation, ParamSpec, Problem, ProblemResponse,
+    ResponseStatus, ValidationError,
+};
+use serde_json::{Map, Value};
+
+/// Largest request body buffered for validation.
+const MAX_VALIDATED_BODY: usize = 16 * 1024 * 1024;
+
+/// Validates JSON values against component schemas (kept as JSON).
+#[derive(Debug, Default)]
+pub struct SchemaValidator {
+    components: Map<String, Value>,
+}
+
+impl SchemaValidator {
+    pub fn new(components: Map<String, Value>) -> Self {
+        Self { components }
+    }
+
+    /// Validate `value` against the named component schema.
+    pub fn validate_component(&self, name: &str, value: &Value) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        match self.components.get(name) {
+            Some(schema) => self.check(schema, value, String::new(), &mut errors),
+            None => tracing::debug!(schema = name, "validation: unknown component schema"),
+        }
+        errors
+    }
+
+    /// Validate `value` against an inline schema.
+    pub fn validate(&self, schema: &Value, value: &Value) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        self.check(schema, value, String::new(), &mut errors);
+        errors
+    }
+
+    fn resolve<'a>(&'a self, schema: &'a Value) -> Option<&'a Value> {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(r) => {
+                let name = r.strip_prefix("#/components/schemas/")?;
+                self.components.get(name)
+            }
+            None => Some(schema),
+        }
+    }
+
+    fn check(
+        &self,
+        schema: &Value,
+        value: &Value,
+        pointer: String,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let Some(schema) = self.resolve(schema) else {
+            return;
+        };
+        let fail = |errors: &mut Vec<ValidationError>, detail: String| {
+            errors.push(ValidationError {
+                detail,
+                pointer: pointer.clone(),
+            });
+        };
+
+        if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+            for s in all {
+                self.check(s, value, pointer.clone(), errors);
+            }
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+                let matched = variants
+                    .iter()
+                    .filter(|s| self.validate_at(s, value, &pointer).is_empty())
+                    .count();
+                let ok = if key == "oneOf" {
+                    matched == 1
+                } else {
+                    matched >= 1
+                };
+                if !ok {
+                    fail(errors, format!("value does not match {key} alternatives"));
+                    return;
+                }
+            }
+        }
+
+        if let Some(ty) = schema.get("type") {
+            let allowed: Vec<&str> = match ty {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            let nullable = schema.get("nullable").and_then(Value::as_bool) == Some(true);
+            if !(allowed.iter().any(|t| type_matches(t, value)) || nullable && value.is_null()) {
+                fail(
+                    errors,
+                    format!(
+                        "expected {}, got {}",
+                        allowed.join(" or "),
+                        type_name(value)
+                    ),
+                );
+                return;
+            }
+        }
+
+        if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+            if !variants.contains(value) {
+                fail(
+                    errors,
+                    format!("value is not one of {}", Value::Array(variants.clone())),
+                );
+            }
+        }
+
+        match value {
+            Value::Object(obj) => {
+                if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !obj.contains_key(name) {
+                            errors.push(ValidationError {
+                                detail: format!("missing required property '{name}'"),
+                                pointer: format!("{pointer}/{}", escape(name)),
+                            });
+                        }
+                    }
+                }
+                let props = schema.get("properties").and_then(Value::as_object);
+                let additional = schema.get("additionalProperties");
+                for (name, v) in obj {
+                    let child = format!("{pointer}/{}", escape(name));
+                    match props.and_then(|p| p.get(name)) {
+                        Some(s) => self.check(s, v, child, errors),
+                        None => match additional {
+                            Some(Value::Bool(false)) => errors.push(ValidationError {
+                                detail: format!("unknown property '{name}'"),
+                                pointer: child,
+                            }),
+                            Some(s @ Value::Object(_)) => self.check(s, v, child, errors),
+                            _ => {}
+                        },
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (i, v) in items.iter().enumerate() {
+                        self.check(item_schema, v, format!("{pointer}/{i}"), errors);
+                    }
+                }
+                if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                    if (items.len() as u64) < min {
+                        fail(errors, format!("expected at least {min} items"));
+                    }
+                }
+                if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                    if (items.len() as u64) > max {
+                        fail(errors, format!("expected at most {max} items"));
+                    }
+                }
+            }
+            Value::String(s) => {
+                let len = s.chars().count() as u64;
+                if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                    if len < min {
+                        fail(errors, format!("expected at least {min} characters"));
+                    }
+                }
+                if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                    if len > max {
+                        fail(errors, format!("expected at most {max} characters"));
+                    }
+                }
+            }
+            Value::Number(n) => {
+                let n = n.as_f64().unwrap_or_default();
+                if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                    if n < min {
+                        fail(errors, format!("must be >= {min}"));
+                    }
+                }
+                if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                    if n > max {
+                        fail(errors, format!("must be <= {max}"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn validate_at(&self, schema: &Value, value: &Value, pointer: &str) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        self.check(schema, value, pointer.to_string(), &mut errors);
+        errors
+    }
+}
+
+fn type_matches(ty: &str, value: &Value) -> bool {
+    match ty {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// JSON Pointer escaping (RFC 6901).
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// What to validate for one operation.
+#[derive(Debug, Clone)]
+struct OperationContract {
+    params: Vec<ParamSpec>,
+    body_schema: Option<String>,
+    body_required: bool,
+}
+
+/// Shared state of the request validation middleware.
+#[derive(Clone)]
+pub struct RequestValidationState {
+    validator: Arc<SchemaValidator>,
+    operations: Arc<HashMap<(Method, String), OperationContract>>,
+}
+
+impl RequestValidationState {
+    pub fn new<'a>(
+        validator: Arc<SchemaValidator>,
+        specs: impl IntoIterator<Item = &'a OperationSpec>,
+    ) -> Self {
+        let operations = specs
+            .into_iter()
+            .map(|spec| {
+                let contract = OperationContract {
+                    params: spec.params.clone(),
+                    body_schema: spec
+                        .request_body
+                        .as_ref()
+                        .filter(|rb| rb.content_type == "application/json")
+                        .and_then(|rb| rb.schema_name.clone()),
+                    body_required: spec.request_body.as_ref().is_some_and(|rb| rb.required),
+                };
+                ((spec.method.clone(), spec.path.clone()), contract)
+            })
+            .collect();
+        Self {
+            validator,
+            operations: Arc::new(operations),
+        }
+    }
+}
+
+fn check_param_type(param: &ParamSpec, raw: &[String], errors: &mut Vec<ValidationError>) {
+    let (ty, values): (&str, Vec<&str>) = match &param.items {
+        Some(items) => (
+            &items.item_type,
+            match items.collection {
+                CollectionFormat::Csv => raw.iter().flat_map(|v| v.split(',')).collect(),
+                CollectionFormat::Multi => raw.iter().map(String::as_str).collect(),
+            },
+        ),
+        None => (
+            &param.param_type,
+            raw.first().map(String::as_str).into_iter().collect(),
+        ),
+    };
+    for value in values {
+        let ok = match ty {
+            "integer" => value.parse::<i64>().is_ok(),
+            "number" => value.parse::<f64>().is_ok(),
+            "boolean" => matches!(value, "true" | "false"),
+            _ => true,
+        };
+        let detail = if !ok {
+            format!("parameter '{}' must be {ty}", param.name)
+        } else if !param.enum_values.is_empty() && !param.enum_values.iter().any(|e| e == value) {
+            format!(
+                "parameter '{}' must be one of: {}",
+                param.name,
+                param.enum_values.join(", ")
+            )
+        } else {
+            continue;
+        };
+        errors.push(ValidationError {
+            detail,
+            pointer: param_pointer(param),
+        });
+        return;
+    }
+}
+
+fn param_pointer(param: &ParamSpec) -> String {
+    let location = match param.location {
+        ParamLocation::Path => "path",
+        ParamLocation::Query => "query",
+        ParamLocation::Header => "header",
+        ParamLocation::Cookie => "cookie",
+    };
+    format!("#/{location}/{}", escape(&param.name))
+}
+
+/// Values of path parameters, matched positionally against the route template.
+fn path_params(template: &str, path: &str) -> HashMap<String, String> {
+    template
+        .split('/')
+        .zip(path.split('/'))
+        .filter_map(|(t, v)| {
+            let name = t.strip_prefix('{')?.strip_suffix('}')?;
+            Some((name.trim_start_matches('*').to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// Query values by name; repeated keys keep every value in order.
+fn query_params(query: Option<&str>) -> HashMap<String, Vec<String>> {
+    let mut params: HashMap<String, Vec<String>> = HashMap::new();
+    for (k, v) in query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|kv| !kv.is_empty())
+        .map(|kv| {
+            let (k, v) = kv.split_once('=').unwrap_or((kv, ""));
+            let decode = |s: &str| {
+                urlencoding::decode(&s.replace('+', " "))
+                    .map(|c| c.into_owned())
+                    .unwrap_or_else(|_| s.to_string())
+            };
+            (decode(k), decode(v))
+        })
+    {
+        params.entry(k).or_default().push(v);
+    }
+    params
+}
+
+fn validation_problem(errors: Vec<ValidationError>) -> Response {
+    ProblemResponse::from(
+        Problem::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Unprocessable Entity",
+            "Request does not match the API contract",
+        )
+        .with_code("VALIDATION_ERROR")
+        .with_errors(errors),
+    )
+    .into_response()
+}
+
+/// Middleware validating parameters and JSON bodies of registered operations.
+pub async fn request_validation_middleware(
+    State(state): State<RequestValidationState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(template) = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_owned())
+    else {
+        return next.run(req).await;
+    };
+    let Some(contract) = state
+        .operations
+        .get(&(req.method().clone(), template.clone()))
+    else {
+        return next.run(req).await;
+    };
+
+    let mut errors = Vec::new();
+    let path = path_params(&template, req.uri().path());
+    let query = query_params(req.uri().query());
+    for param in &contract.params {
+        let raw = match param.location {
+            ParamLocation::Path => path.get(&param.name).cloned().map(|v| vec![v]),
+            ParamLocation::Query => query.get(&param.name).cloned(),
+            ParamLocation::Header => req
+                .headers()
+                .get(param.name.as_str())
+                .and_then(|v| v.to_str().ok())
+                .map(|v| vec![v.to_owned()]),
+            ParamLocation::Cookie => None,
+        };
+        match raw {
+            Some(raw) => check_param_type(param, &raw, &mut errors),
+            None if param.required && param.location != ParamLocation::Cookie => {
+                errors.push(ValidationError {
+                    detail: format!("missing required parameter '{}'", param.name),
+                    pointer: param_pointer(param),
+                })
+            }
+            None => {}
+        }
+    }
+
+    let req = match &contract.body_schema {
+        Some(schema) => {
+            let is_json = req
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|ct| ct.starts_with("application/json"));
+            let (parts, body) = req.into_parts();
+            let bytes = match axum::body::to_bytes(body, MAX_VALIDATED_BODY).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::debug!(error = %e, "validation: failed to read request body");
+                    return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+                }
+            };
+            if bytes.is_empty() {
+                if contract.body_required {
+                    errors.push(ValidationError {
+                        detail: "request body is required".to_string(),
+                        pointer: String::new(),
+                    });
+                }
+            } else if is_json {
+                match serde_json::from_slice::<Value>(&bytes) {
+                    Ok(value) => errors.extend(state.validator.validate_component(schema, &value)),
+                    Err(e) => errors.push(ValidationError {
+                        detail: format!("invalid JSON: {e}"),
+                        pointer: String::new(),
+                    }),
+                }
+            }
+            Request::from_parts(parts, Body::from(bytes))
+        }
+        None => req,
+    };
+
+    if !errors.is_empty() {
+        tracing::debug!(route = %template, errors = errors.len(), "request failed contract validation");
+        return validation_problem(errors);
+    }
+    next.run(req).await
+}
+
+/// Shared state of the response validation middleware.
+#[derive(Clone)]
+pub struct ResponseValidationState {
+    validator: Arc<SchemaValidator>,
+    /// Declared JSON response schemas per operation, keyed by status.
+    operations: Arc<HashMap<(Method, String), HashMap<ResponseStatus, String>>>,
+}
+
+impl ResponseValidationState {
+    pub fn new<'a>(
+        validator: Arc<SchemaValidator>,
+        specs: impl IntoIterator<Item = &'a OperationSpec>,
+    ) -> Self {
+        let operations = specs
+            .into_iter()
+            .map(|spec| {
+                let schemas = spec
+                    .responses
+                    .iter()
+                    .filter(|r| r.content_type == "application/json")
+                    .filter_map(|r| Some((r.status, r.schema_name.clone()?)))
+                    .collect();
+                ((spec.method.clone(), spec.path.clone()), schemas)
+            })
+            .collect();
+        Self {
+            validator,
+            operations: Arc::new(operations),
+        }
+    }
+}
+
+/// Middleware validating JSON response bodies against their declared schema.
+pub async fn response_validation_middleware(
+    State(state): State<ResponseValidationState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| (req.method().clone(), p.as_str().to_owned()));
+    let resp = next.run(req).await;
+    let Some((method, template)) = route else {
+        return resp;
+    };
+    let Some(schema) = state
+        .operations
+        .get(&(method.clone(), template.clone()))
+        .and_then(|schemas| {
+            schemas
+                .get(&ResponseStatus::Code(resp.status().as_u16()))
+                .or_else(|| schemas.get(&ResponseStatus::Default))
+        })
+    else {
+        return resp;
+    };
+    let is_json = resp
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if !is_json {
+        return resp;
+    }
+
+    let (parts, body) = resp.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_VALIDATED_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(error = %e, route = %template, "validation: failed to read response body");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let errors = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(value) => state.validator.validate_component(schema, &value),
+        Err(e) => vec![ValidationError {
+            detail: format!("invalid JSON: {e}"),
+            pointer: String::new(),
+        }],
+    };
+    if errors.is_empty() {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+
+    for e in &errors {
+        tracing::error!(
+            %method,
+            route = %template,
+            status = parts.status.as_u16(),
+            schema = %schema,
+            pointer = %e.pointer,
+            "response does not match declared schema: {}",
+            e.detail
+        );
+    }
+    ProblemResponse::from(
+        Problem::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Response Contract Violation",
+            format!(
+                "{method} {template} returned a {} body that does not match schema '{schema}'",
+                parts.status.as_u16()
+            ),
+        )
+        .with_code("RESPONSE_VALIDATION_ERROR")
+        .with_errors(errors),
+    )
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn validator() -> SchemaValidator {
+        let components = json!({
+            "User": {
+                "type": "object",
+                "required": ["email", "age"],
+                "properties": {
+                    "email": { "type": "string", "minLength": 3 },
+                    "age": { "type": "integer", "minimum": 0 },
+                    "role": { "type": "string", "enum": ["admin", "user"] },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "manager": { "oneOf": [{ "type": "null" }, { "$ref": "#/components/schemas/User" }] }
+                },
+                "additionalProperties": false
+            }
+        });
+        SchemaValidator::new(components.as_object().unwrap().clone())
+    }
+
+    #[test]
+    fn valid_value_passes() {
+        let v = validator();
+        let user =
+            json!({ "email": "a@b.c", "age": 3, "role": "user", "tags": ["x"], "manager": null });
+        assert!(v.validate_component("User", &user).is_empty());
+    }
+
+    #[test]
+    fn errors_carry_json_pointers() {
+        let v = validator();
+        let user = json!({ "age": -1, "role": "root", "tags": [1], "extra": true });
+        let errors = v.validate_component("User", &user);
+        let pointers: Vec<_> = errors.iter().map(|e| e.pointer.as_str()).collect();
+        assert!(pointers.contains(&"/email"));
+        assert!(pointers.contains(&"/age"));
+        assert!(pointers.contains(&"/role"));
+        assert!(pointers.contains(&"/tags/0"));
+        assert!(pointers.contains(&"/extra"));
+    }
+
+    #[test]
+    fn params_are_extracted_from_template_and_query() {
+        let p = path_params("/users/{id}/posts/{post_id}", "/users/42/posts/7");
+        assert_eq!(p["id"], "42");
+        assert_eq!(p["post_id"], "7");
+        let q = query_params(Some("limit=10&name=a%20b&flag"));
+        assert_eq!(q["limit"], ["10"]);
+        assert_eq!(q["name"], ["a b"]);
+        assert_eq!(q["flag"], [""]);
+        let q = query_params(Some("tag=a&tag=b"));
+        assert_eq!(q["tag"], ["a", "b"]);
+    }
+
+    #[test]
+    fn array_and_enum_params_are_checked_per_item() {
+        let param = |items: Option<CollectionFormat>, ty: &str, values: &[&str]| ParamSpec {
+            name: "p".into(),
+            location: ParamLocation::Query,
+            required: false,
+            description: None,
+            param_type: if items.is_some() {
+                "array".into()
+            } else {
+                ty.into()
+            },
+            format: None,
+            enum_values: values.iter().map(|v| v.to_string()).collect(),
+            items: items.map(|collection| modkit::api::ArrayParam {
+                item_type: ty.into(),
+                collection,
+            }),
+        };
+        let errors = |p: &ParamSpec, raw: &[&str]| {
+            let mut errors = Vec::new();
+            let raw: Vec<String> = raw.iter().map(|v| v.to_string()).collect();
+            check_param_type(p, &raw, &mut errors);
+            errors
+        };
+
+        let sort = param(None, "string", &["asc", "desc"]);
+        assert!(errors(&sort, &["asc"]).is_empty());
+        let bad = errors(&sort, &["up"]);
+        assert_eq!(bad[0].detail, "parameter 'p' must be one of: asc, desc");
+        assert_eq!(bad[0].pointer, "#/query/p");
+
+        let status = param(
+            Some(CollectionFormat::Csv),
+            "string",
+            &["active", "archived"],
+        );
+        assert!(errors(&status, &["active,archived"]).is_empty());
+        assert_eq!(errors(&status, &["active,deleted"]).len(), 1);
+
+        let ids = param(Some(CollectionFormat::Multi), "integer", &[]);
+        assert!(errors(&ids, &["1", "2"]).is_empty());
+        assert_eq!(
+            errors(&ids, &["1", "x"])[0].detail,
+            "parameter 'p' must be integer"
+        );
+    }
+}
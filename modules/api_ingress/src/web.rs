@@ -3,6 +3,7 @@ use axum::{
     response::{Html, Json},
     routing::{get, MethodRouter},
 };
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 /// Returns a 501 Not Implemented handler for operations without implementations
@@ -26,40 +27,103 @@ pub async fn health_check() -> Json<Value> {
     }))
 }
 
-#[cfg(not(feature = "embed_elements"))]
-pub async fn serve_docs() -> Html<&'static str> {
-    // External mode: load from CDN @latest
-    Html(
-        r#"<!DOCTYPE html>
-<html>
-<head>
-  <meta charset="utf-8"/>
-  <title>API Docs</title>
-  <script src="https://unpkg.com/@stoplight/elements@latest/web-components.min.js"></script>
-  <link rel="stylesheet" href="https://unpkg.com/@stoplight/elements@latest/styles.min.css">
-</head>
-<body>
-  <elements-api apiDescriptionUrl="/openapi.json" router="hash" layout="sidebar"></elements-api>
-</body>
-</html>"#,
-    )
+/// Documentation UI rendered at `/docs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocsUi {
+    /// Stoplight Elements (embedded with `embed_elements`).
+    #[default]
+    Elements,
+    /// Swagger UI (embedded with `embed_swagger_ui`).
+    SwaggerUi,
+    /// Redoc (embedded with `embed_redoc`).
+    Redoc,
 }
 
-#[cfg(feature = "embed_elements")]
-pub async fn serve_docs() -> Html<&'static str> {
-    // Embedded mode: reference local embedded assets
-    Html(
-        r#"<!DOCTYPE html>
+impl DocsUi {
+    /// Base URL of the UI assets: the embedded route when the matching feature
+    /// is enabled, a CDN otherwise.
+    fn asset_base(self) -> &'static str {
+        match self {
+            DocsUi::Elements if cfg!(feature = "embed_elements") => "/docs/assets",
+            DocsUi::Elements => "https://unpkg.com/@stoplight/elements@latest",
+            DocsUi::SwaggerUi if cfg!(feature = "embed_swagger_ui") => "/docs/swagger-ui",
+            DocsUi::SwaggerUi => "https://unpkg.com/swagger-ui-dist@5",
+            DocsUi::Redoc if cfg!(feature = "embed_redoc") => "/docs/redoc",
+            DocsUi::Redoc => "https://cdn.redoc.ly/redoc/latest/bundles",
+        }
+    }
+
+    /// Render the HTML page for this UI.
+    pub fn page(self) -> String {
+        let base = self.asset_base();
+        let (head, body) = match self {
+            DocsUi::Elements => (
+                format!(
+                    r#"<script src="{base}/web-components.min.js"></script>
+  <link rel="stylesheet" href="{base}/styles.min.css">"#
+                ),
+                r#"<elements-api apiDescriptionUrl="/openapi.json" router="hash" layout="sidebar"></elements-api>"#
+                    .to_string(),
+            ),
+            DocsUi::SwaggerUi => (
+                format!(r#"<link rel="stylesheet" href="{base}/swagger-ui.css">"#),
+                format!(
+                    r##"<div id="swagger-ui"></div>
+  <script src="{base}/swagger-ui-bundle.js"></script>
+  <script>window.ui = SwaggerUIBundle({{ url: "/openapi.json", dom_id: "#swagger-ui" }});</script>"##
+                ),
+            ),
+            DocsUi::Redoc => (
+                String::new(),
+                format!(
+                    r#"<redoc spec-url="/openapi.json"></redoc>
+  <script src="{base}/redoc.standalone.js"></script>"#
+                ),
+            ),
+        };
+        format!(
+            r#"<!DOCTYPE html>
 <html>
 <head>
   <meta charset="utf-8"/>
   <title>API Docs</title>
-  <script src="/docs/assets/web-components.min.js"></script>
-  <link rel="stylesheet" href="/docs/assets/styles.min.css">
+  {head}
 </head>
 <body>
-  <elements-api apiDescriptionUrl="/openapi.json" router="hash" layout="sidebar"></elements-api>
+  {body}
 </body>
-</html>"#,
-    )
+</html>"#
+        )
+    }
+}
+
+/// Handler serving the pre-rendered docs page.
+pub fn serve_docs(ui: DocsUi) -> MethodRouter {
+    let page = Html(ui.page());
+    get(move || {
+        let page = page.clone();
+        async move { page }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn docs_ui_is_parsed_from_snake_case() {
+        let ui: DocsUi = serde_json::from_value(json!("swagger_ui")).unwrap();
+        assert_eq!(ui, DocsUi::SwaggerUi);
+        assert_eq!(DocsUi::default(), DocsUi::Elements);
+    }
+
+    #[test]
+    fn every_ui_points_at_the_spec() {
+        for ui in [DocsUi::Elements, DocsUi::SwaggerUi, DocsUi::Redoc] {
+            let page = ui.page();
+            assert!(page.contains("/openapi.json"), "{ui:?}");
+            assert!(page.contains(ui.asset_base()), "{ui:?}");
+        }
+    }
 }
@@ -45,8 +45,9 @@ impl Module for TestUsersModule {
     }
 }
 
+#[async_trait]
 impl RestfulModule for TestUsersModule {
-    fn register_rest(
+    async fn register_rest(
         &self,
         _ctx: &modkit::ModuleCtx,
         router: axum::Router,
@@ -142,6 +143,7 @@ async fn test_operation_builder_integration() {
         modkit::context::ModuleCtxBuilder::new(tokio_util::sync::CancellationToken::new()).build();
     let _final_router = test_module
         .register_rest(&ctx, router, &registry)
+        .await
         .expect("Failed to register routes");
 
     // Basic test that the router was created without errors
@@ -159,6 +161,7 @@ async fn test_schema_registration() {
         modkit::context::ModuleCtxBuilder::new(tokio_util::sync::CancellationToken::new()).build();
     let _final_router = test_module
         .register_rest(&ctx, router, &registry)
+        .await
         .expect("Failed to register routes");
 
     // This test would verify that schemas were registered, but since the